@@ -5,11 +5,13 @@
 //! offline strategy for deterministic tooling.
 
 use std::path::Path;
+use std::sync::Arc;
 
-use futures::stream::BoxStream;
+use futures::stream::{BoxStream, StreamExt};
 use ir::ProtocolIR;
 use serde_json::Value;
 
+use crate::subscription::{poll_events, PollOptions, SharedBackend, SubscriptionTopic};
 use crate::{ProtocolAdapterError, ProtocolAdapterResult};
 
 /// Strategy selection for the facade
@@ -77,13 +79,13 @@ impl InvocationEngine for OfflineIrStrategy {
 
 /// RPC Runtime strategy — delegates to existing RpcAdapter backends
 pub struct RpcRuntimeStrategy {
-    backend: Box<dyn crate::rpc_adapter::ProtocolBackend + Send + Sync>,
+    backend: SharedBackend,
 }
 
 impl RpcRuntimeStrategy {
     /// Construct with an existing backend
     pub fn new(backend: Box<dyn crate::rpc_adapter::ProtocolBackend + Send + Sync>) -> Self {
-        Self { backend }
+        Self { backend: Arc::from(backend) }
     }
 }
 
@@ -97,6 +99,15 @@ impl InvocationEngine for RpcRuntimeStrategy {
             .map_err(|e| ProtocolAdapterError::Message(e.to_string()))?;
         Ok(res)
     }
+
+    /// Polling subscriptions for `blocks`, `mempool` and `wallet` (see [`crate::subscription`]).
+    ///
+    /// Events are `ChainEvent`s serialized to JSON; unknown topics return `None`.
+    fn subscribe(&self, topic: &str, params: &Value) -> Option<BoxStream<'static, Value>> {
+        let topic = topic.parse::<SubscriptionTopic>().ok()?;
+        let events = poll_events(self.backend.clone(), topic, PollOptions::from_params(params));
+        Some(events.map(Value::from).boxed())
+    }
 }
 
 /// Non-RPC strategy scaffold — placeholder for future transports
@@ -152,4 +163,9 @@ impl AdapterFacade {
     pub async fn execute(&self, method: &str, params: &Value) -> ProtocolAdapterResult<Value> {
         self.engine.invoke(method, params).await
     }
+
+    /// Subscribe to a topic via the chosen strategy, if it supports streaming
+    pub fn subscribe(&self, topic: &str, params: &Value) -> Option<BoxStream<'static, Value>> {
+        self.engine.subscribe(topic, params)
+    }
 }
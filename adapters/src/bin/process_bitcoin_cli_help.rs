@@ -0,0 +1,11 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Binary entry point for the Bitcoin Core `bitcoin-cli help` dump processor.
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if let Err(e) = ethos_adapters::bitcoin_core::cli_help::run(&args) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,14 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Binary entry point for the Esplora OpenAPI to IR converter.
+//!
+//! Delegates to the library implementation so that the API module is only
+//! compiled as part of the lib (where `crate::openapi` resolves).
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if let Err(e) = ethos_adapters::esplora::api::run(&args) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
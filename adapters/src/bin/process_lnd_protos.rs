@@ -0,0 +1,14 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Binary entry point for the LND proto processor.
+//!
+//! Delegates to the library implementation so that the proto module is only
+//! compiled as part of the lib (where `crate::conversion_helpers` resolves).
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if let Err(e) = ethos_adapters::lnd::proto::run(&args) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,828 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Captured `bitcoin-cli help` text -> IR.
+//!
+//! For node builds that are only available as binaries, the RPC interface can still be
+//! recovered from the help text the node prints. A dump directory holds:
+//!
+//! - `help.txt`: the output of `bitcoin-cli help`, whose `== Category ==` headings give
+//!   each method's category;
+//! - `<method>.txt`: the output of `bitcoin-cli help <method>` for each method.
+//!
+//! `help <method>` is rendered by `RPCHelpMan::ToString`: a one-line signature, the
+//! description, then `Arguments:`, `Named Arguments:`, one `Result:` / `Result (condition):`
+//! block per result variant, and `Examples:`. The argument and result blocks are two-column
+//! layouts (`left` padded to a shared width, then `(type, flags) description`), which are read
+//! back into the `x-bitcoin-arguments` / `x-bitcoin-results` shape of the OpenRPC dump so
+//! [`super::openrpc`]'s converter builds the IR.
+//!
+//! The text loses some detail that the other Bitcoin Core sources keep:
+//!
+//! - `STR_HEX` arguments read as `string` unless their placeholder is `"hex"`;
+//! - `STR_AMOUNT` results read as `numeric`; they become `amount` when the description
+//!   mentions BTC;
+//! - `NUM_TIME` results are recognised by their `xxx` placeholder;
+//! - a default that parses as JSON is a `Default`, anything else a `DefaultHint`;
+//! - hidden arguments and hidden methods are not printed.
+//!
+//! Lines that cannot be read are collected as [`UnparsedPart`]s rather than failing the dump.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use ir::ProtocolIR;
+use path::{find_project_root, get_ir_dir, resolve_ir_output_path};
+use serde_json::Value;
+use types::version::version_ir_filename;
+
+use super::openrpc::{
+    convert_to_protocol_ir_with_version, OpenRpcDoc, OpenRpcInfo, OpenRpcMethod, OpenRpcResult,
+    RawArgument, RawResult,
+};
+use crate::{ProtocolAdapter, ProtocolAdapterError, ProtocolAdapterResult, CAP_RPC};
+
+/// File holding the `bitcoin-cli help` overview in a dump directory.
+pub const HELP_OVERVIEW_FILE: &str = "help.txt";
+
+/// Description prefix `RPCArg::ToDescriptionString` adds to `OBJ_NAMED_PARAMS` arguments.
+const NAMED_PARAMS_NOTE: &str =
+    "Options object that can be used to pass named arguments, listed below.";
+
+/// Adapter reading a directory of captured `bitcoin-cli help` output.
+#[derive(Debug, Clone)]
+pub struct CliHelpAdapter {
+    /// Version of the node the help text was captured from
+    pub version: String,
+}
+
+impl CliHelpAdapter {
+    /// Create an adapter for help text captured from a node of the given version.
+    pub fn new(version: impl Into<String>) -> Self { Self { version: version.into() } }
+}
+
+impl ProtocolAdapter for CliHelpAdapter {
+    fn name(&self) -> &'static str { "bitcoin-core-cli-help" }
+
+    fn version(&self) -> String { self.version.clone() }
+
+    fn extract_protocol_ir(&self, path: &Path) -> ProtocolAdapterResult<ProtocolIR> {
+        let dump = read_help_dir(path, &self.version)?;
+        Ok(convert_to_protocol_ir_with_version(dump.doc, Some(self.version.clone())))
+    }
+
+    fn capabilities(&self) -> Vec<&'static str> { vec![CAP_RPC] }
+}
+
+/// Part of a help dump that could not be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnparsedPart {
+    /// Method whose help text contains the part (empty for the overview)
+    pub method: String,
+    /// Section heading (e.g. `Arguments`, `Result (for verbosity = 1)`)
+    pub section: String,
+    /// The text that was not understood
+    pub text: String,
+}
+
+impl fmt::Display for UnparsedPart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.method, self.section, self.text)
+    }
+}
+
+/// Methods read from a help dump, in the OpenRPC dump's shape.
+#[derive(Debug, Clone)]
+pub struct CliHelpDump {
+    /// The methods as an OpenRPC document with `x-bitcoin-*` extensions
+    pub doc: OpenRpcDoc,
+    /// Parts of the dump that were skipped
+    pub unparsed: Vec<UnparsedPart>,
+}
+
+/// Reads a dump directory (`help.txt` plus one `<method>.txt` per method).
+pub fn read_help_dir(dir: &Path, version: &str) -> ProtocolAdapterResult<CliHelpDump> {
+    let overview = std::fs::read_to_string(dir.join(HELP_OVERVIEW_FILE)).ok();
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.extension().is_some_and(|ext| ext == "txt")
+                && p.file_name().is_some_and(|name| name != HELP_OVERVIEW_FILE)
+        })
+        .collect();
+    paths.sort();
+    if paths.is_empty() {
+        return Err(ProtocolAdapterError::Message(format!(
+            "No `help <method>` dumps (*.txt) found in {}",
+            dir.display()
+        )));
+    }
+    let mut commands = Vec::with_capacity(paths.len());
+    for path in paths {
+        commands.push(std::fs::read_to_string(&path)?);
+    }
+    Ok(parse_help_dump(overview.as_deref(), &commands, version))
+}
+
+/// Builds the methods from the `help` overview (if captured) and `help <method>` texts.
+pub fn parse_help_dump(overview: Option<&str>, commands: &[String], version: &str) -> CliHelpDump {
+    let mut unparsed = Vec::new();
+    let categories = overview.map(parse_overview).unwrap_or_default();
+
+    let mut methods: Vec<OpenRpcMethod> = Vec::new();
+    for text in commands {
+        let Some(mut method) = parse_command_help(text, &mut unparsed) else { continue };
+        if methods.iter().any(|m| m.name == method.name) {
+            continue;
+        }
+        match categories.iter().find(|(name, _)| *name == method.name) {
+            Some((_, category)) => method.x_bitcoin_category = category.clone(),
+            None if overview.is_some() => unparsed.push(UnparsedPart {
+                method: method.name.clone(),
+                section: "help".to_string(),
+                text: "method is not listed in the help overview".to_string(),
+            }),
+            None => {}
+        }
+        methods.push(method);
+    }
+    for (name, _) in &categories {
+        if !methods.iter().any(|m| &m.name == name) {
+            unparsed.push(UnparsedPart {
+                method: name.clone(),
+                section: "help".to_string(),
+                text: "no `help <method>` dump".to_string(),
+            });
+        }
+    }
+
+    let doc = OpenRpcDoc {
+        open_rpc: "1.3.2".to_string(),
+        info: OpenRpcInfo {
+            title: Some("Bitcoin Core JSON-RPC".to_string()),
+            version: Some(version.to_string()),
+            description: Some("Reconstructed from bitcoin-cli help output.".to_string()),
+            x_bitcoin_version_full: None,
+            x_bitcoin_version_major: None,
+            x_bitcoin_version_minor: None,
+            x_bitcoin_version_build: None,
+            x_bitcoin_timestamp_ms: None,
+        },
+        methods,
+    };
+    CliHelpDump { doc, unparsed }
+}
+
+/// `(method, category)` pairs of the `help` overview, in listing order.
+pub fn parse_overview(text: &str) -> Vec<(String, String)> {
+    let mut category = String::new();
+    let mut methods = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if let Some(heading) = line.strip_prefix("== ").and_then(|l| l.strip_suffix(" ==")) {
+            category = heading.to_lowercase();
+        } else if let Some(name) = line.split_whitespace().next() {
+            methods.push((name.to_string(), category.clone()));
+        }
+    }
+    methods
+}
+
+/// Reads one `help <method>` text.
+fn parse_command_help(text: &str, unparsed: &mut Vec<UnparsedPart>) -> Option<OpenRpcMethod> {
+    let lines: Vec<&str> = text.lines().collect();
+    let name = lines.first()?.split_whitespace().next()?.to_string();
+    if name == "help:" || lines.len() < 2 {
+        unparsed.push(UnparsedPart {
+            method: String::new(),
+            section: "help".to_string(),
+            text: lines.first().copied().unwrap_or_default().to_string(),
+        });
+        return None;
+    }
+
+    // Headings are preceded by an empty line; the description runs up to the first one
+    let headings: Vec<usize> = (1..lines.len())
+        .filter(|&i| lines[i - 1].trim().is_empty() && section_heading(lines[i]).is_some())
+        .collect();
+    let description_end = headings.first().copied().unwrap_or(lines.len());
+    let description = lines[1..description_end].join("\n").trim().to_string();
+
+    let mut arguments: Vec<RawArgument> = Vec::new();
+    let mut results = Vec::new();
+    let mut examples = None;
+    for (n, &start) in headings.iter().enumerate() {
+        let end = headings.get(n + 1).copied().unwrap_or(lines.len());
+        let heading = lines[start].trim().trim_end_matches(':');
+        let body = &lines[start + 1..end];
+        let mut report = |text: String| {
+            unparsed.push(UnparsedPart { method: name.clone(), section: heading.to_string(), text })
+        };
+        match section_heading(lines[start]) {
+            Some(Heading::Arguments) => arguments = parse_arguments(body, &mut report),
+            Some(Heading::NamedArguments) => {
+                let named = parse_arguments(body, &mut report);
+                match arguments
+                    .iter_mut()
+                    .rev()
+                    .find(|a| a.r#type == "object" && a.inner.is_empty())
+                {
+                    Some(options) => options.inner = named,
+                    None => report("no options argument for the named arguments".to_string()),
+                }
+            }
+            Some(Heading::Result(condition)) =>
+                if let Some(result) = parse_result(body, condition, &mut report) {
+                    results.push(result);
+                },
+            Some(Heading::Examples) => {
+                let text = body.join("\n").trim_end().to_string();
+                examples = (!text.is_empty()).then(|| text + "\n");
+            }
+            None => {}
+        }
+    }
+
+    Some(OpenRpcMethod {
+        x_bitcoin_argument_names: arguments.iter().map(|a| a.names.join("|")).collect(),
+        name,
+        description,
+        params: Vec::new(),
+        result: Some(OpenRpcResult {
+            name: Some("result".to_string()),
+            schema: None,
+            x_bitcoin_results: results,
+        }),
+        x_bitcoin_category: String::new(),
+        x_bitcoin_examples: examples,
+        x_bitcoin_arguments: arguments,
+    })
+}
+
+enum Heading {
+    Arguments,
+    NamedArguments,
+    Result(String),
+    Examples,
+}
+
+fn section_heading(line: &str) -> Option<Heading> {
+    match line {
+        "Arguments:" => Some(Heading::Arguments),
+        "Named Arguments:" => Some(Heading::NamedArguments),
+        "Result:" => Some(Heading::Result(String::new())),
+        "Examples:" => Some(Heading::Examples),
+        _ => line
+            .strip_prefix("Result (")
+            .and_then(|l| l.strip_suffix("):"))
+            .map(|condition| Heading::Result(condition.to_string())),
+    }
+}
+
+/// One row of a two-column help block.
+#[derive(Debug)]
+struct Row {
+    /// Indentation of the left column
+    indent: usize,
+    /// Left column without indentation or trailing separator comma
+    left: String,
+    /// Right column, with continuation lines joined by `\n`
+    right: String,
+}
+
+/// Splits a two-column block into rows.
+///
+/// `Sections::ToString` pads every left column to the block's widest entry plus four spaces
+/// and indents continuation lines of the right column to the same width.
+fn rows(lines: &[&str]) -> Vec<Row> {
+    let column = lines.iter().find_map(|line| right_column(line));
+    let mut rows: Vec<Row> = Vec::new();
+    for line in lines {
+        let indent = line.len() - line.trim_start().len();
+        if line.trim().is_empty() {
+            if let Some(last) = rows.last_mut() {
+                last.right.push('\n');
+            }
+            continue;
+        }
+        if let (Some(column), Some(last)) = (column, rows.last_mut()) {
+            if indent >= column {
+                if !last.right.is_empty() && !last.right.ends_with('\n') {
+                    last.right.push('\n');
+                }
+                last.right.push_str(line.trim());
+                continue;
+            }
+        }
+        let (left, right) = match column.and_then(|c| line.get(..c).zip(line.get(c..))) {
+            Some((left, right)) => (left, right),
+            None => (*line, ""),
+        };
+        let left = left.trim();
+        rows.push(Row {
+            indent,
+            left: left.strip_suffix(',').unwrap_or(left).to_string(),
+            right: right.trim().to_string(),
+        });
+    }
+    for row in &mut rows {
+        row.right = row.right.trim_end().to_string();
+    }
+    rows
+}
+
+/// Start of the right column: the first text after a run of four or more spaces.
+fn right_column(line: &str) -> Option<usize> {
+    let content = line.len() - line.trim_start().len();
+    let gap = line[content..].find("    ")? + content;
+    let start = line[gap..].find(|c: char| c != ' ')? + gap;
+    Some(start)
+}
+
+/// `(type, flags) description` of a right column.
+struct Annotation {
+    type_name: String,
+    required: bool,
+    optional: bool,
+    default: Option<String>,
+    description: String,
+}
+
+fn parse_annotation(right: &str) -> Option<Annotation> {
+    let inner = right.strip_prefix('(')?;
+    let mut depth = 1usize;
+    let close = inner.char_indices().find_map(|(i, c)| {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => {}
+        }
+        (depth == 0).then_some(i)
+    })?;
+    let (type_name, flags) = match inner[..close].split_once(", ") {
+        Some((type_name, flags)) => (type_name, flags),
+        None => (&inner[..close], ""),
+    };
+    Some(Annotation {
+        type_name: type_name.to_string(),
+        required: flags.starts_with("required"),
+        optional: flags.starts_with("optional"),
+        default: flags.strip_prefix("optional, default=").map(str::to_string),
+        description: inner[close + 1..].trim().to_string(),
+    })
+}
+
+/// Argument type of a help type name (`RPCArg::ToDescriptionString`).
+fn argument_type(type_name: &str, placeholder: &str) -> Option<&'static str> {
+    Some(match type_name {
+        "string" if placeholder.trim_matches('"') == "hex" => "hex",
+        "string" => "string",
+        "numeric" => "number",
+        "numeric or string" => "amount",
+        "numeric or array" => "range",
+        "boolean" => "boolean",
+        "json object" => "object",
+        "json array" => "array",
+        _ => return None,
+    })
+}
+
+/// Result type of a help type name and placeholder (`RPCResult::ToSections`).
+fn result_type(type_name: &str, placeholder: &str, description: &str) -> Option<&'static str> {
+    Some(match type_name {
+        "json object" | "empty JSON object" => "object",
+        "json array" => "array",
+        "string" if placeholder == "\"hex\"" => "hex",
+        "string" => "string",
+        "numeric" if placeholder == "xxx" => "timestamp",
+        "numeric" if description.contains("BTC") => "amount",
+        "numeric" => "number",
+        "boolean" => "boolean",
+        "json null" => "none",
+        _ => return None,
+    })
+}
+
+/// `"key": value` / `"key" : value` -> (key, value); anything else has no key.
+fn split_key(left: &str) -> (Option<&str>, &str) {
+    if let Some(rest) = left.strip_prefix('"') {
+        if let Some((key, value)) = rest.split_once('"') {
+            let value = value.trim_start();
+            if let Some(value) = value.strip_prefix(':') {
+                return (Some(key), value.trim());
+            }
+        }
+    }
+    (None, left)
+}
+
+/// Reads an `Arguments:` or `Named Arguments:` block.
+fn parse_arguments(lines: &[&str], report: &mut impl FnMut(String)) -> Vec<RawArgument> {
+    let mut arguments = Vec::new();
+    // Open arguments; the bottom entry is the current top-level argument
+    let mut stack: Vec<RawArgument> = Vec::new();
+    let mut root_opened = false;
+
+    let close = |stack: &mut Vec<RawArgument>| {
+        if stack.len() > 1 {
+            if let (Some(child), Some(parent)) = (stack.pop(), stack.last_mut()) {
+                parent.inner.push(child);
+            }
+        }
+    };
+
+    for row in rows(lines) {
+        if row.indent == 0 {
+            while stack.len() > 1 {
+                close(&mut stack);
+            }
+            arguments.extend(stack.pop());
+            root_opened = false;
+            let name = match row.left.split_once(". ") {
+                Some((n, name)) if n.chars().all(|c| c.is_ascii_digit()) => name,
+                _ => row.left.as_str(),
+            };
+            match new_argument(name, "", &row.right) {
+                Some(argument) => stack.push(argument),
+                None => report(format!("{}    {}", row.left, row.right)),
+            }
+            continue;
+        }
+        if stack.is_empty() {
+            report(row.left);
+            continue;
+        }
+
+        let left = row.left.as_str();
+        if left.starts_with('}') || left.starts_with(']') {
+            close(&mut stack);
+            continue;
+        }
+        if left == "..." {
+            continue;
+        }
+        let (key, value) = split_key(left);
+        if value == "{" || value == "[" {
+            if row.right.is_empty() && stack.len() == 1 && !root_opened {
+                // The top-level argument's own brackets
+                root_opened = true;
+                continue;
+            }
+            match new_argument(key.unwrap_or_default(), value, &row.right) {
+                Some(argument) => stack.push(argument),
+                None => report(format!("{}    {}", row.left, row.right)),
+            }
+            continue;
+        }
+        let name = key.unwrap_or_else(|| value.trim_matches('"'));
+        match (new_argument(name, value, &row.right), stack.last_mut()) {
+            (Some(argument), Some(parent)) => parent.inner.push(argument),
+            _ => report(format!("{}    {}", row.left, row.right)),
+        }
+    }
+    while stack.len() > 1 {
+        close(&mut stack);
+    }
+    arguments.extend(stack.pop());
+    arguments
+}
+
+fn new_argument(names: &str, placeholder: &str, right: &str) -> Option<RawArgument> {
+    let annotation = parse_annotation(right)?;
+    let r#type = argument_type(&annotation.type_name, placeholder)?;
+    let (default, default_hint) = match annotation.default {
+        Some(text) => match serde_json::from_str::<Value>(&text) {
+            Ok(value) => (Some(value), None),
+            Err(_) => (None, Some(text)),
+        },
+        None => (None, None),
+    };
+    let description = annotation.description;
+    let description =
+        description.strip_prefix(NAMED_PARAMS_NOTE).map(str::trim_start).unwrap_or(&description);
+    Some(RawArgument {
+        names: names.split('|').map(str::to_string).collect(),
+        description: description.to_string(),
+        oneline_description: String::new(),
+        also_positional: false,
+        type_str: Vec::new(),
+        required: annotation.required,
+        default,
+        default_hint,
+        hidden: false,
+        r#type: r#type.to_string(),
+        inner: Vec::new(),
+    })
+}
+
+/// Reads a `Result:` block.
+fn parse_result(
+    lines: &[&str],
+    condition: String,
+    report: &mut impl FnMut(String),
+) -> Option<RawResult> {
+    let mut stack: Vec<RawResult> = Vec::new();
+    let mut root = None;
+
+    let push =
+        |stack: &mut Vec<RawResult>, root: &mut Option<RawResult>, result: RawResult| match stack
+            .last_mut()
+        {
+            Some(parent) => parent.inner.push(result),
+            None => *root = Some(result),
+        };
+
+    for row in rows(lines) {
+        let left = row.left.as_str();
+        if left.starts_with('}') || left.starts_with(']') {
+            if let Some(done) = stack.pop() {
+                push(&mut stack, &mut root, done);
+            }
+            continue;
+        }
+        if left == "..." {
+            // Continuation marker of OBJ_DYN / ARR, or an ELISION entry with a description
+            if !row.right.is_empty() {
+                let elision = RawResult {
+                    r#type: "elision".to_string(),
+                    optional: false,
+                    description: row.right.clone(),
+                    skip_type_check: false,
+                    key_name: String::new(),
+                    condition: String::new(),
+                    inner: Vec::new(),
+                };
+                push(&mut stack, &mut root, elision);
+            }
+            continue;
+        }
+
+        let (key, value) = split_key(left);
+        let annotation = parse_annotation(&row.right);
+        let r#type =
+            annotation.as_ref().and_then(|a| result_type(&a.type_name, value, &a.description));
+        let (Some(annotation), Some(r#type)) = (annotation, r#type) else {
+            report(format!("{}    {}", row.left, row.right));
+            continue;
+        };
+        let result = RawResult {
+            r#type: r#type.to_string(),
+            optional: annotation.optional,
+            description: annotation.description,
+            skip_type_check: false,
+            key_name: key.unwrap_or_default().to_string(),
+            condition: if stack.is_empty() { condition.clone() } else { String::new() },
+            inner: Vec::new(),
+        };
+        if value == "{" || value == "[" {
+            stack.push(result);
+        } else {
+            push(&mut stack, &mut root, result);
+        }
+    }
+    while let Some(done) = stack.pop() {
+        push(&mut stack, &mut root, done);
+    }
+    root
+}
+
+/// Entry point for the `process_bitcoin_cli_help` binary.
+///
+/// Usage: process_bitcoin_cli_help <help_dir> <version> [output_file]
+pub fn run(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    if args.len() < 3 || args.iter().skip(1).any(|a| a == "-h" || a == "--help") {
+        eprintln!("Usage:");
+        eprintln!("  {} <help_dir> <version> [output_file]   # Build IR from help dumps", args[0]);
+        eprintln!();
+        eprintln!("<help_dir> holds `bitcoin-cli help` as {} and", HELP_OVERVIEW_FILE);
+        eprintln!("`bitcoin-cli help <method>` as <method>.txt.");
+        std::process::exit(1);
+    }
+
+    let version = args[2].trim_start_matches('v').to_string();
+    let dump = read_help_dir(Path::new(&args[1]), &version)?;
+    for part in &dump.unparsed {
+        eprintln!("unparsed: {}", part);
+    }
+
+    let project_root = find_project_root()?;
+    let output_file = match args.get(3) {
+        Some(output) => PathBuf::from(output),
+        None => get_ir_dir()?.join(version_ir_filename(&version, "bitcoin")),
+    };
+    let output_resolved = resolve_ir_output_path(&project_root, &output_file);
+    let method_count = dump.doc.methods.len();
+    let protocol_ir = convert_to_protocol_ir_with_version(dump.doc, Some(version.clone()));
+    protocol_ir.to_file(&output_resolved)?;
+
+    println!("✓ Converted help text to IR: {}", output_resolved.display());
+    println!("  Version: {}", version);
+    println!("  Methods: {} ({} unparsed parts)", method_count, dump.unparsed.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::bitcoin_core::openrpc::convert_to_protocol_ir_with_version_map;
+
+    const OVERVIEW: &str = "== Blockchain ==\ngetblock \"blockhash\" ( verbosity )\n\n== Rawtransactions ==\ncreaterawtransaction [{\"txid\":\"hex\",\"vout\":n},...] [{\"address\":amount,...},{\"data\":\"hex\"},...] ( locktime replaceable )\ngetrawtransaction \"txid\" ( verbose \"blockhash\" )\n";
+
+    const GETBLOCK: &str = r#"getblock "blockhash" ( verbosity )
+
+If verbosity is 0, returns a string that is serialized, hex-encoded data for block 'hash'.
+If verbosity is 1, returns an Object with information about block <hash>.
+
+Arguments:
+1. blockhash    (string, required) The block hash
+2. verbosity    (numeric, optional, default=1) 0 for hex-encoded data, 1 for a JSON object, 2 for JSON object with transaction data,
+                and 3 for JSON object with transaction data including prevout information for inputs
+
+Result (for verbosity = 0):
+"hex"    (string) A string that is serialized, hex-encoded data for block 'hash'
+
+Result (for verbosity = 1):
+{                                 (json object)
+  "hash" : "hex",                 (string) the block hash (same as provided)
+  "time" : xxx,                   (numeric) The block time expressed in UNIX epoch time
+  "fees" : {                      (json object, optional) fees by address
+    "address" : n,                (numeric) fee in BTC
+    ...
+  },
+  "tx" : [                        (json array) The transaction ids
+    "hex",                        (string) The transaction id
+    ...
+  ],
+  "nextblockhash" : "hex"         (string, optional) The hash of the next block (if available)
+}
+
+Result (for verbosity = 2):
+{                  (json object)
+  ...,             Same output as verbosity = 1
+  "tx" : [         (json array)
+    {              (json object)
+      ...,         The transactions in the format of the getrawtransaction RPC
+      "fee" : n    (numeric) The transaction fee in BTC
+    },
+    ...
+  ],
+  "weird" : ???    something unexpected
+}
+
+Examples:
+> bitcoin-cli getblock "00000000c937983704a73af28acdec37b049d214adbda81d7e2a3dd146f6ed09"
+> curl --user myusername --data-binary '{"jsonrpc": "2.0", "id": "curltest", "method": "getblock", "params": ["00000000c937983704a73af28acdec37b049d214adbda81d7e2a3dd146f6ed09"]}' -H 'content-type: application/json' http://127.0.0.1:8332/
+"#;
+
+    const CREATERAWTRANSACTION: &str = r#"createrawtransaction [{"txid":"hex","vout":n},...] [{"address":amount,...},{"data":"hex"},...] ( locktime replaceable )
+
+Create a transaction spending the given inputs and creating new outputs.
+
+Arguments:
+1. inputs                      (json array, required) The inputs
+     [
+       {                       (json object)
+         "txid": "hex",        (string, required) The transaction id
+         "vout": n,            (numeric, required) The output number
+         "sequence": n,        (numeric, optional, default=depends on the value of the 'replaceable' and 'locktime' arguments) The sequence number
+       },
+       ...
+     ]
+2. outputs                     (json array, required) The outputs specified as key-value pairs.
+     [
+       {                       (json object)
+         "address": amount,    (numeric or string, required) A key-value pair.
+         ...
+       },
+       ...
+     ]
+3. locktime                    (numeric, optional, default=0) Raw locktime. Non-0 value also locktime-activates inputs
+4. replaceable                 (boolean, optional, default=true) Marks this transaction as BIP125-replaceable.
+
+Result:
+"hex"    (string) hex string of the transaction
+
+Examples:
+> bitcoin-cli createrawtransaction "[{\"txid\":\"myid\",\"vout\":0}]" "[{\"address\":0.01}]"
+"#;
+
+    fn dump() -> CliHelpDump {
+        parse_help_dump(
+            Some(OVERVIEW),
+            &[GETBLOCK.to_string(), CREATERAWTRANSACTION.to_string()],
+            "27.0.0",
+        )
+    }
+
+    #[test]
+    fn arguments_are_read_with_types_optionality_and_defaults() {
+        let dump = dump();
+        let method = &dump.doc.methods[1];
+        assert_eq!(method.name, "createrawtransaction");
+        assert_eq!(method.x_bitcoin_category, "rawtransactions");
+        let args = &method.x_bitcoin_arguments;
+        assert_eq!(
+            method.x_bitcoin_argument_names,
+            ["inputs", "outputs", "locktime", "replaceable"]
+        );
+        assert_eq!(args[0].r#type, "array");
+        assert!(args[0].required);
+        let input = &args[0].inner[0];
+        assert_eq!(input.r#type, "object");
+        let fields: Vec<(&str, &str, bool)> = input
+            .inner
+            .iter()
+            .map(|a| (a.names[0].as_str(), a.r#type.as_str(), a.required))
+            .collect();
+        assert_eq!(
+            fields,
+            [("txid", "hex", true), ("vout", "number", true), ("sequence", "number", false)]
+        );
+        assert_eq!(
+            input.inner[2].default_hint.as_deref(),
+            Some("depends on the value of the 'replaceable' and 'locktime' arguments")
+        );
+        assert_eq!(args[1].inner[0].inner[0].r#type, "amount");
+        assert_eq!(args[2].default, Some(serde_json::json!(0)));
+        assert_eq!(args[3].default, Some(serde_json::json!(true)));
+
+        let getblock = &dump.doc.methods[0];
+        assert_eq!(
+            getblock.x_bitcoin_arguments[1].description,
+            "0 for hex-encoded data, 1 for a JSON object, 2 for JSON object with transaction data,\n\
+             and 3 for JSON object with transaction data including prevout information for inputs"
+        );
+    }
+
+    #[test]
+    fn result_skeletons_become_result_trees() {
+        let dump = dump();
+        let method = &dump.doc.methods[0];
+        assert!(method.description.starts_with("If verbosity is 0"));
+        let results = &method.result.as_ref().expect("result").x_bitcoin_results;
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].r#type, "hex");
+        assert_eq!(results[0].condition, "for verbosity = 0");
+
+        let verbose = &results[1];
+        assert_eq!(verbose.r#type, "object");
+        let keys: Vec<(&str, &str)> =
+            verbose.inner.iter().map(|r| (r.key_name.as_str(), r.r#type.as_str())).collect();
+        assert_eq!(
+            keys,
+            [
+                ("hash", "hex"),
+                ("time", "timestamp"),
+                ("fees", "object"),
+                ("tx", "array"),
+                ("nextblockhash", "hex")
+            ]
+        );
+        assert!(verbose.inner[2].optional);
+        assert_eq!(verbose.inner[2].inner[0].key_name, "address");
+        assert_eq!(verbose.inner[2].inner[0].r#type, "amount");
+        assert!(verbose.inner[4].optional);
+
+        let full = &results[2];
+        assert_eq!(full.inner[0].r#type, "elision");
+        assert_eq!(full.inner[0].description, "Same output as verbosity = 1");
+        assert_eq!(full.inner[1].inner[0].inner[1].key_name, "fee");
+
+        assert_eq!(
+            dump.unparsed,
+            [
+                UnparsedPart {
+                    method: "getblock".to_string(),
+                    section: "Result (for verbosity = 2)".to_string(),
+                    text: "\"weird\" : ???    something unexpected".to_string(),
+                },
+                UnparsedPart {
+                    method: "getrawtransaction".to_string(),
+                    section: "help".to_string(),
+                    text: "no `help <method>` dump".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn help_dump_converts_to_protocol_ir_with_examples() {
+        let dump = dump();
+        let ir = convert_to_protocol_ir_with_version_map(
+            dump.doc,
+            Some("27.0.0".to_string()),
+            &HashMap::new(),
+        );
+        let rpcs = ir.get_rpc_methods();
+        let getblock = rpcs.iter().find(|r| r.name == "getblock").expect("getblock");
+        let params: Vec<(&str, bool, Option<&str>)> = getblock
+            .params
+            .iter()
+            .map(|p| (p.name.as_str(), p.required, p.default_value.as_deref()))
+            .collect();
+        assert_eq!(params, [("blockhash", true, None), ("verbosity", false, Some("1"))]);
+        let examples = getblock.examples.as_ref().expect("examples");
+        assert!(examples[0].starts_with("> bitcoin-cli getblock \"00000000c9"));
+        assert!(examples[0].contains("\"method\": \"getblock\""));
+    }
+}
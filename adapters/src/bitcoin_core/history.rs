@@ -0,0 +1,506 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Version history from a directory of per-release OpenRPC documents.
+//!
+//! Each document (one per release, e.g. v25 … v30) is converted on its own, then methods,
+//! params and result fields are aligned across releases by name. An item's `version_added`
+//! is the first release it appears in and its `version_removed` the first release after the
+//! last one it appears in. Params and fields only carry versions that differ from their
+//! parent's, matching how [`item_visible_for_version`](super::openrpc::item_visible_for_version)
+//! treats `None` (visible whenever the parent is). Every item is described by the latest
+//! release that has it; items dropped in later releases keep their last definition.
+//!
+//! A removal and an addition in the same parent and the same release whose shapes and
+//! descriptions match are reported as a [`Rename`] candidate.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+use ir::{FieldDef, ParamDef, ProtocolDef, ProtocolIR, ProtocolModule, RpcDef, TypeDef};
+use serde::Serialize;
+
+use super::openrpc::{
+    convert_to_protocol_ir_with_version_map, extract_version_from_openrpc, load_openrpc_doc,
+    normalize_version_added_for_storage, parse_version_for_ordering, replace_rpc_definitions,
+    OpenRpcDoc,
+};
+use crate::conversion_helpers::sort_definitions_by_name;
+
+/// Minimum shape similarity for a rename candidate
+const RENAME_SHAPE_THRESHOLD: f64 = 0.8;
+/// Minimum combined shape and description similarity for a rename candidate
+const RENAME_SCORE_THRESHOLD: f64 = 0.75;
+
+/// One release's converted IR
+#[derive(Debug, Clone)]
+pub struct Release {
+    /// Release version from the document's `info`
+    pub version: String,
+    /// IR converted from the release's document
+    pub ir: ProtocolIR,
+}
+
+impl Release {
+    /// Converts one release's OpenRPC document.
+    pub fn from_doc(doc: OpenRpcDoc) -> Result<Self, String> {
+        let version = extract_version_from_openrpc(&doc)?;
+        let ir =
+            convert_to_protocol_ir_with_version_map(doc, Some(version.clone()), &HashMap::new());
+        Ok(Self { version, ir })
+    }
+}
+
+/// Reads every `*.json` OpenRPC document in `dir`, ordered by release version.
+pub fn read_release_dir(dir: &Path) -> Result<Vec<Release>, Box<dyn std::error::Error>> {
+    let mut paths: Vec<_> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+
+    let mut releases = Vec::with_capacity(paths.len());
+    for path in paths {
+        let release = Release::from_doc(load_openrpc_doc(&path)?)
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        releases.push(release);
+    }
+    releases.sort_by_key(|r| parse_version_for_ordering(&r.version));
+    for pair in releases.windows(2) {
+        if normalize_version_added_for_storage(&pair[0].version)
+            == normalize_version_added_for_storage(&pair[1].version)
+        {
+            return Err(format!(
+                "releases {} and {} map to the same version",
+                pair[0].version, pair[1].version
+            )
+            .into());
+        }
+    }
+    Ok(releases)
+}
+
+/// A removal and addition in the same release that look like one item renamed
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Rename {
+    /// Where the item lives: "method", "<method> params" or "<method> result<path>"
+    pub scope: String,
+    /// Name before the release
+    pub from: String,
+    /// Name from the release on
+    pub to: String,
+    /// Release the rename happened in
+    pub version: String,
+}
+
+/// Methods, params and fields of all releases with their version ranges
+#[derive(Debug, Clone)]
+pub struct VersionHistory {
+    /// Release versions, oldest first, as stored in the IR
+    pub releases: Vec<String>,
+    /// `rpc` module with every method of every release
+    pub ir: ProtocolIR,
+    /// Rename candidates, for review
+    pub renames: Vec<Rename>,
+}
+
+/// Builds the version history of `releases` (ordered oldest first).
+///
+/// Methods already present in the oldest release have no observed `version_added`; they
+/// take it from `baseline` (method name -> version added, e.g. the canonical IR's) when
+/// that is earlier, and the oldest release's version otherwise.
+pub fn build_history(releases: &[Release], baseline: &HashMap<String, String>) -> VersionHistory {
+    let mut builder = HistoryBuilder {
+        versions: releases
+            .iter()
+            .map(|r| normalize_version_added_for_storage(&r.version))
+            .collect(),
+        renames: Vec::new(),
+    };
+    let lists: Vec<Option<Vec<&RpcDef>>> =
+        releases.iter().map(|r| Some(r.ir.get_rpc_methods())).collect();
+    let all = Window { first: 0, last: releases.len().saturating_sub(1) };
+
+    let methods = align(&lists, |rpc| rpc.name.clone());
+    builder.detect_renames(&methods, all, "method", method_shape);
+
+    let mut definitions: Vec<ProtocolDef> = methods
+        .iter()
+        .map(|method| {
+            let mut rpc = builder.merge_method(method, all);
+            if method.window.first == 0 {
+                if let Some(earlier) = baseline.get(&rpc.name).filter(|v| {
+                    parse_version_for_ordering(v) < parse_version_for_ordering(&builder.versions[0])
+                }) {
+                    rpc.version_added = Some(earlier.clone());
+                }
+            }
+            ProtocolDef::RpcMethod(rpc)
+        })
+        .collect();
+    sort_definitions_by_name(&mut definitions);
+
+    let module = ProtocolModule::new("rpc".to_string(), "Bitcoin RPC API".to_string(), definitions);
+    VersionHistory {
+        releases: builder.versions,
+        ir: ProtocolIR::new(vec![module]),
+        renames: builder.renames,
+    }
+}
+
+/// Replaces the canonical IR's methods with the history's and writes it back.
+pub fn update_canonical_ir_from_history(
+    canonical_path: &Path,
+    history: &VersionHistory,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut canonical_ir = ProtocolIR::from_file(canonical_path)?;
+    let definitions =
+        history.ir.modules().iter().flat_map(|m| m.definitions().iter().cloned()).collect();
+    replace_rpc_definitions(&mut canonical_ir, definitions);
+    canonical_ir.to_file(canonical_path)?;
+    Ok(())
+}
+
+/// First and last release index an item appears in
+#[derive(Debug, Clone, Copy)]
+struct Window {
+    first: usize,
+    last: usize,
+}
+
+/// One item aligned across releases
+struct Aligned<'a, T> {
+    key: String,
+    /// The item in each release (`None` where absent)
+    presence: Vec<Option<&'a T>>,
+    window: Window,
+}
+
+impl<'a, T> Aligned<'a, T> {
+    /// Definition from the latest release that has the item
+    fn latest(&self) -> &'a T {
+        self.presence[self.window.last].expect("window.last indexes a present item")
+    }
+}
+
+/// Aligns per-release item lists by key. Items come in the latest release's order, then
+/// items dropped earlier in the order of the last release that had them.
+fn align<'a, T>(lists: &[Option<Vec<&'a T>>], key: impl Fn(&T) -> String) -> Vec<Aligned<'a, T>> {
+    let mut aligned: Vec<Aligned<'a, T>> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for (release, list) in lists.iter().enumerate().rev() {
+        for item in list.iter().flatten() {
+            let key = key(item);
+            let slot = *index.entry(key.clone()).or_insert_with(|| {
+                aligned.push(Aligned {
+                    key,
+                    presence: vec![None; lists.len()],
+                    window: Window { first: release, last: release },
+                });
+                aligned.len() - 1
+            });
+            let entry = &mut aligned[slot];
+            if entry.presence[release].is_none() {
+                entry.presence[release] = Some(*item);
+                entry.window.first = release;
+            }
+        }
+    }
+    aligned
+}
+
+struct HistoryBuilder {
+    versions: Vec<String>,
+    renames: Vec<Rename>,
+}
+
+impl HistoryBuilder {
+    /// `version_added`/`version_removed` of an item relative to its parent
+    fn bounds(&self, item: Window, parent: Window) -> (Option<String>, Option<String>) {
+        let added = (item.first > parent.first).then(|| self.versions[item.first].clone());
+        let removed = (item.last < parent.last).then(|| self.versions[item.last + 1].clone());
+        (added, removed)
+    }
+
+    fn merge_method(&mut self, method: &Aligned<RpcDef>, all: Window) -> RpcDef {
+        let mut rpc = method.latest().clone();
+        let window = method.window;
+        rpc.version_added = Some(self.versions[window.first].clone());
+        rpc.version_removed = self.bounds(window, all).1;
+
+        let param_lists: Vec<Option<Vec<&ParamDef>>> =
+            method.presence.iter().map(|m| m.map(|m| m.params.iter().collect())).collect();
+        let params = align(&param_lists, |p| p.name.clone());
+        self.detect_renames(&params, window, &format!("{} params", rpc.name), param_shape);
+        rpc.params = params
+            .iter()
+            .map(|param| {
+                let mut merged = param.latest().clone();
+                (merged.version_added, merged.version_removed) = self.bounds(param.window, window);
+                let types: Vec<_> =
+                    param.presence.iter().map(|p| p.map(|p| &p.param_type)).collect();
+                merged.param_type =
+                    self.merge_type(&types, param.window, &format!("{} {}", rpc.name, param.key));
+                merged
+            })
+            .collect();
+
+        let results: Vec<Option<&TypeDef>> =
+            method.presence.iter().map(|m| m.and_then(|m| m.result.as_ref())).collect();
+        if results.iter().any(Option::is_some) {
+            rpc.result = Some(self.merge_type(&results, window, &format!("{} result", rpc.name)));
+        }
+        rpc
+    }
+
+    /// Merges a type's fields across the releases in `window`.
+    fn merge_type(&mut self, types: &[Option<&TypeDef>], window: Window, scope: &str) -> TypeDef {
+        let latest = types[..=window.last]
+            .iter()
+            .rev()
+            .flatten()
+            .next()
+            .or_else(|| types.iter().flatten().next())
+            .expect("merge_type is called with at least one present type");
+        let mut merged = (*latest).clone();
+        if merged.fields.is_none() {
+            return merged;
+        }
+
+        let field_lists: Vec<Option<Vec<&FieldDef>>> = types
+            .iter()
+            .map(|t| t.and_then(|t| t.fields.as_ref()).map(|f| f.iter().collect()))
+            .collect();
+        let fields = align(&field_lists, |f| f.key.as_ident());
+        self.detect_renames(&fields, window, scope, field_shape);
+        merged.fields = Some(
+            fields
+                .iter()
+                .map(|field| {
+                    let mut def = field.latest().clone();
+                    (def.version_added, def.version_removed) = self.bounds(field.window, window);
+                    let types: Vec<_> =
+                        field.presence.iter().map(|f| f.map(|f| &f.field_type)).collect();
+                    def.field_type =
+                        self.merge_type(&types, field.window, &format!("{}/{}", scope, field.key));
+                    def
+                })
+                .collect(),
+        );
+        merged
+    }
+
+    /// Pairs items removed right before a release with items added in it when their shapes
+    /// and descriptions match, best match first.
+    fn detect_renames<T>(
+        &mut self,
+        items: &[Aligned<T>],
+        parent: Window,
+        scope: &str,
+        shape: impl Fn(&T) -> Shape,
+    ) {
+        let mut candidates = Vec::new();
+        for removed in items.iter().filter(|i| i.window.last < parent.last) {
+            for added in items.iter().filter(|i| i.window.first == removed.window.last + 1) {
+                let (a, b) = (
+                    shape(removed.latest()),
+                    shape(
+                        added.presence[added.window.first]
+                            .expect("window.first indexes a present item"),
+                    ),
+                );
+                let layout = jaccard(&a.leaves, &b.leaves);
+                let text = if a.words.is_empty() && b.words.is_empty() {
+                    1.0
+                } else {
+                    jaccard(&a.words, &b.words)
+                };
+                let score = (layout + text) / 2.0;
+                if layout >= RENAME_SHAPE_THRESHOLD && score >= RENAME_SCORE_THRESHOLD {
+                    candidates.push((score, removed, added));
+                }
+            }
+        }
+        candidates.sort_by(|a, b| {
+            b.0.total_cmp(&a.0).then_with(|| (&a.1.key, &a.2.key).cmp(&(&b.1.key, &b.2.key)))
+        });
+
+        let mut paired: BTreeSet<&str> = BTreeSet::new();
+        for (_, removed, added) in candidates {
+            if paired.contains(removed.key.as_str()) || paired.contains(added.key.as_str()) {
+                continue;
+            }
+            paired.insert(&removed.key);
+            paired.insert(&added.key);
+            self.renames.push(Rename {
+                scope: scope.to_string(),
+                from: removed.key.clone(),
+                to: added.key.clone(),
+                version: self.versions[added.window.first].clone(),
+            });
+        }
+    }
+}
+
+/// What rename detection compares: typed leaf paths and description words
+struct Shape {
+    leaves: BTreeSet<String>,
+    words: BTreeSet<String>,
+}
+
+fn method_shape(rpc: &RpcDef) -> Shape {
+    let mut leaves = BTreeSet::new();
+    for param in &rpc.params {
+        type_leaves(&param.param_type, &format!("param/{}", param.name), &mut leaves);
+    }
+    if let Some(result) = &rpc.result {
+        type_leaves(result, "result", &mut leaves);
+    }
+    Shape { leaves, words: words(&rpc.description) }
+}
+
+fn param_shape(param: &ParamDef) -> Shape {
+    let mut leaves = BTreeSet::new();
+    type_leaves(&param.param_type, "", &mut leaves);
+    Shape { leaves, words: words(&param.description) }
+}
+
+fn field_shape(field: &FieldDef) -> Shape {
+    let mut leaves = BTreeSet::new();
+    type_leaves(&field.field_type, "", &mut leaves);
+    Shape { leaves, words: words(&field.description) }
+}
+
+fn type_leaves(ty: &TypeDef, prefix: &str, out: &mut BTreeSet<String>) {
+    let kind = ty.protocol_type.clone().unwrap_or_else(|| format!("{:?}", ty.kind));
+    out.insert(format!("{}:{}", prefix, kind));
+    for field in ty.fields.iter().flatten() {
+        type_leaves(&field.field_type, &format!("{}/{}", prefix, field.key.as_ident()), out);
+    }
+}
+
+fn words(text: &str) -> BTreeSet<String> {
+    text.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| w.len() > 2)
+        .map(str::to_ascii_lowercase)
+        .collect()
+}
+
+fn jaccard(a: &BTreeSet<String>, b: &BTreeSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 1.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(version: &str, methods: serde_json::Value) -> OpenRpcDoc {
+        serde_json::from_value(serde_json::json!({
+            "openrpc": "1.3.2",
+            "info": { "version": version },
+            "methods": methods,
+        }))
+        .expect("valid document")
+    }
+
+    fn method(name: &str, arguments: &[&str], fields: &[(&str, &str)]) -> serde_json::Value {
+        serde_json::json!({
+            "name": name,
+            "description": format!("{} description", name),
+            "x-bitcoin-category": "blockchain",
+            "x-bitcoin-arguments": arguments.iter().map(|a| serde_json::json!({
+                "names": [a], "description": format!("The {} to use", a),
+                "required": false, "type": "number",
+            })).collect::<Vec<_>>(),
+            "result": { "x-bitcoin-results": [{
+                "type": "object", "description": "", "key_name": "",
+                "inner": fields.iter().map(|(key, description)| serde_json::json!({
+                    "type": "number", "description": description, "key_name": key,
+                })).collect::<Vec<_>>(),
+            }]},
+        })
+    }
+
+    fn releases() -> Vec<Release> {
+        [
+            doc(
+                "28.0.0",
+                serde_json::json!([
+                    method("getinfo", &[], &[("blocks", "Block height")]),
+                    method("getchaininfo", &["verbose"], &[("headers", "Number of headers")]),
+                ]),
+            ),
+            doc(
+                "29.0.0",
+                serde_json::json!([method(
+                    "getchaininfo",
+                    &["verbose", "extra"],
+                    &[("headers", "Number of headers"), ("size", "Size on disk")]
+                )]),
+            ),
+            doc(
+                "30.0.0",
+                serde_json::json!([method(
+                    "getchaininfo",
+                    &["verbose"],
+                    &[("header_count", "Number of headers"), ("size", "Size on disk")]
+                )]),
+            ),
+        ]
+        .into_iter()
+        .map(|doc| Release::from_doc(doc).expect("versioned document"))
+        .collect()
+    }
+
+    #[test]
+    fn computes_version_ranges_at_every_level() {
+        let baseline = HashMap::from([("getchaininfo".to_string(), "0.9".to_string())]);
+        let history = build_history(&releases(), &baseline);
+        assert_eq!(history.releases, vec!["28", "29", "30"]);
+
+        let methods = history.ir.get_rpc_methods();
+        let getinfo = methods.iter().find(|m| m.name == "getinfo").expect("kept");
+        assert_eq!(getinfo.version_added.as_deref(), Some("28"));
+        assert_eq!(getinfo.version_removed.as_deref(), Some("29"));
+
+        let chain = methods.iter().find(|m| m.name == "getchaininfo").expect("kept");
+        assert_eq!(chain.version_added.as_deref(), Some("0.9"));
+        assert_eq!(chain.version_removed, None);
+        let extra = chain.params.iter().find(|p| p.name == "extra").expect("kept");
+        assert_eq!(
+            (extra.version_added.as_deref(), extra.version_removed.as_deref()),
+            (Some("29"), Some("30"))
+        );
+
+        let fields = chain.result.as_ref().and_then(|r| r.fields.as_ref()).expect("fields");
+        let versions: Vec<(String, Option<&str>, Option<&str>)> = fields
+            .iter()
+            .map(|f| (f.key.as_ident(), f.version_added.as_deref(), f.version_removed.as_deref()))
+            .collect();
+        assert_eq!(
+            versions,
+            vec![
+                ("header_count".to_string(), Some("30"), None),
+                ("size".to_string(), Some("29"), None),
+                ("headers".to_string(), None, Some("30")),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_renames_with_matching_shape() {
+        let history = build_history(&releases(), &HashMap::new());
+        assert_eq!(
+            history.renames,
+            vec![Rename {
+                scope: "getchaininfo result".to_string(),
+                from: "headers".to_string(),
+                to: "header_count".to_string(),
+                version: "30".to_string(),
+            }]
+        );
+    }
+}
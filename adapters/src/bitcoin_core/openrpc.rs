@@ -6,18 +6,17 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use ir::{
-    FieldDef, FieldKey, ParamDef, ProtocolDef, ProtocolIR, ProtocolModule, RpcDef, TypeDef,
-    TypeKind,
+    Deprecation, FieldDef, FieldKey, ParamDef, ProtocolDef, ProtocolIR, ProtocolModule, RpcDef,
+    TypeDef, TypeKind,
 };
 use normalization::bitcoin_canonical_from_adapter_method;
-use path::{
-    canonical_bitcoin_ir_path, find_project_root, get_ir_dir, resolve_ir_output_path,
-    version_ir_filename,
-};
+use path::{canonical_bitcoin_ir_path, find_project_root, get_ir_dir, resolve_ir_output_path};
 use semantics::method_categorization;
 use serde::Deserialize;
-use types::ProtocolVersion;
+use types::version::version_ir_filename;
+use types::{ProtocolVersion, VersionRange};
 
+use super::{history, rest, zmq};
 use crate::conversion_helpers::{determine_requires_private_keys, sort_definitions_by_name};
 
 /// RPCs whose wire result is a top-level JSON array.
@@ -374,42 +373,98 @@ fn build_array_of_objects_wrapper(inner_fields: Vec<FieldDef>) -> Vec<FieldDef>
         default_value: None,
         version_added: None,
         version_removed: None,
+        deprecated: None,
     }]
 }
 
-/// Strips the build/metadata suffix from a version string (e.g. "30.99.0-705399b1d57a" -> "30.99.0").
-fn strip_version_suffix(version: &str) -> String {
-    let trimmed = version.trim_start_matches('v').trim();
-    let end = trimmed.find(|c: char| c == '-' || c == '+').unwrap_or(trimmed.len());
-    trimmed[..end].to_string()
+/// Returns true if the version carries a build suffix (e.g. "30.99.0-705399b1d57a-dirty" or
+/// "+meta"), as opposed to a release candidate or a Knots-style `.knotsYYYYMMDD` suffix.
+fn has_build_suffix(version: &ProtocolVersion) -> bool {
+    version.suffix.as_deref().is_some_and(|suffix| {
+        version.version_string.trim().strip_suffix(suffix).is_some_and(|v| v.ends_with(['-', '+']))
+    })
 }
 
-/// Returns true if the version string contains a build/metadata suffix (e.g. "-dirty", "-rc1", "+meta").
-fn has_version_suffix(version: &str) -> bool {
-    let trimmed = version.trim_start_matches('v').trim();
-    trimmed.chars().any(|c| c == '-' || c == '+')
+/// Returns the versions in which an item with the given version_added and version_removed is
+/// available: from version_added (inclusive) to version_removed (exclusive). `None` when
+/// version_added cannot be parsed; an unparseable version_removed is ignored.
+pub fn item_version_range(
+    version_added: Option<&str>,
+    version_removed: Option<&str>,
+) -> Option<VersionRange> {
+    let added = match version_added {
+        Some(v) => Some(ProtocolVersion::from_string(v.trim()).ok()?),
+        None => None,
+    };
+    let removed = version_removed.and_then(|v| ProtocolVersion::from_string(v.trim()).ok());
+    Some(VersionRange::between(added, removed))
 }
 
 /// Returns true if a param/field with the given version_added and version_removed is visible
 /// when generating for target_version. Used by codegen to filter params and result fields.
 /// `None` means visible (no version metadata / assumed available in all versions).
+///
+/// Versions compare in full, so an item added in 29.1 is hidden from 29.0 and a release
+/// candidate orders before its release. Unreleased versions (30.99.x, or stored as the next
+/// major) order after every 30.x release.
 pub fn item_visible_for_version(
     version_added: Option<&str>,
     version_removed: Option<&str>,
     target_version: &str,
 ) -> bool {
-    let target_major = parse_version_for_ordering(target_version).major();
-    if let Some(added) = version_added {
-        if effective_major_for_comparison(added) > target_major {
-            return false;
-        }
-    }
-    if let Some(removed) = version_removed {
-        if effective_major_for_comparison(removed) <= target_major {
-            return false;
-        }
-    }
-    true
+    let target = parse_version_for_ordering(target_version);
+    item_version_range(version_added, version_removed).is_some_and(|range| range.contains(&target))
+}
+
+/// Extracts deprecation info from a Bitcoin Core help description.
+///
+/// Core marks deprecated arguments, results and RPCs with an uppercase `DEPRECATED` (e.g.
+/// "(DEPRECATED, returned only if config option -deprecatedrpc=startingheight is passed)").
+/// The `-deprecatedrpc=` option, a "deprecated since/in vNN" version and a "use `X` instead"
+/// replacement are picked up when the description names them.
+pub fn deprecation_from_description(description: &str) -> Option<Deprecation> {
+    let marker = description.find("DEPRECATED")?;
+    let word = |text: &str| -> Option<String> {
+        let word: String =
+            text.chars().take_while(|c| c.is_ascii_alphanumeric() || *c == '_').collect();
+        (!word.is_empty()).then_some(word)
+    };
+
+    let flag = description.split_once("-deprecatedrpc=").and_then(|(_, rest)| word(rest));
+
+    let after_marker = description[marker + "DEPRECATED".len()..].trim_start();
+    let since = ["since ", "in "].iter().find_map(|prefix| {
+        let version: String = after_marker
+            .strip_prefix(prefix)?
+            .trim_start_matches('v')
+            .chars()
+            .take_while(|c| c.is_ascii_digit() || *c == '.')
+            .collect();
+        let version = version.trim_end_matches('.');
+        (!version.is_empty()).then(|| version.to_string())
+    });
+
+    let lower = description.to_lowercase();
+    let replacement = lower.match_indices("use ").find_map(|(index, _)| {
+        let rest = &description[index + "use ".len()..];
+        let rest = rest.strip_prefix("the ").unwrap_or(rest);
+        let (name, rest) = match rest.strip_prefix('`') {
+            Some(quoted) => {
+                let (name, rest) = quoted.split_once('`')?;
+                (name.to_string(), rest)
+            }
+            None => {
+                let name = word(rest)?;
+                let rest = &rest[name.len()..];
+                (name, rest)
+            }
+        };
+        let rest = rest.trim_start();
+        let rest = rest.strip_prefix("RPC").unwrap_or(rest).trim_start();
+        rest.starts_with("instead").then_some(name)
+    });
+
+    Some(Deprecation { since, replacement, flag })
 }
 
 /// Filters a `TypeDef`'s fields (and nested types) by version_added/version_removed for the
@@ -457,40 +512,32 @@ pub fn filter_params_for_version(params: &[ir::ParamDef], target: &str) -> Vec<i
         .collect()
 }
 
-/// Computes the effective major version for inclusion comparison. We only use the major version: when building 30.2.8,
-/// include methods whose version_added is in major 30 or earlier. Unreleased (30.99.x or with a build suffix such as "-dirty")
-/// is treated as next major (31) so it is excluded when targeting 30. Supports "30" (major-only) and "30.2.8".
-pub fn effective_major_for_comparison(version: &str) -> u32 {
-    let stripped = strip_version_suffix(version);
-    if let Ok(pv) = ProtocolVersion::from_string(&stripped) {
-        let unreleased = pv.minor == 99 || has_version_suffix(version);
-        return if unreleased { pv.major.saturating_add(1) } else { pv.major };
-    }
-    stripped.trim().parse::<u32>().unwrap_or(u32::MAX)
-}
-
-/// Normalizes version_added for storage in IR: one or two numbers (e.g. 17, 28, 30, or 0.17).
+/// Normalizes version_added for storage in IR: the shortest form of the release (e.g. 17 -> "0.17",
+/// "28.0" -> "28", "29.1" -> "29.1", "30.0rc1" -> "30rc1"; Knots-style suffixes are kept).
 /// Unreleased (30.99.x or with a build suffix such as "-dirty") becomes the next major (31).
 pub(super) fn normalize_version_added_for_storage(version: &str) -> String {
-    let stripped = strip_version_suffix(version);
-    if let Ok(pv) = ProtocolVersion::from_string(&stripped) {
-        let unreleased = pv.minor == 99 || has_version_suffix(version);
-        if unreleased {
-            return format!("{}", pv.major.saturating_add(1));
-        }
-        if pv.major == 0 {
-            return format!("0.{}", pv.minor);
-        }
-        return format!("{}", pv.major);
+    let Ok(pv) = ProtocolVersion::from_string(version.trim()) else {
+        return version.trim().to_string();
+    };
+    if pv.minor == 99 || has_build_suffix(&pv) {
+        return format!("{}", pv.major.saturating_add(1));
     }
-    stripped.trim().to_string()
+    let numbers = if pv.patch != 0 {
+        format!("{}.{}.{}", pv.major, pv.minor, pv.patch)
+    } else if pv.major == 0 || pv.minor != 0 {
+        format!("{}.{}", pv.major, pv.minor)
+    } else {
+        format!("{}", pv.major)
+    };
+    let rc = pv.release_candidate.map(|rc| format!("rc{}", rc)).unwrap_or_default();
+    let suffix = pv.suffix.map(|suffix| format!(".{}", suffix)).unwrap_or_default();
+    format!("{}{}{}", numbers, rc, suffix)
 }
 
-/// Parses a version string for comparison. Uses the shared `ProtocolVersion` (major.minor.patch).
-/// Only used for target versions (releases); unparseable values are treated as 0.0.0.
-fn parse_version_for_ordering(version: &str) -> ProtocolVersion {
-    let normalized = strip_version_suffix(version);
-    ProtocolVersion::from_string(&normalized).unwrap_or_default()
+/// Parses a version string for comparison with the shared `ProtocolVersion` ordering
+/// (major.minor.patch, release candidate, suffix). Unparseable values are treated as 0.0.0.
+pub(super) fn parse_version_for_ordering(version: &str) -> ProtocolVersion {
+    ProtocolVersion::from_string(version.trim()).unwrap_or_default()
 }
 
 /// Extracts the version string from an OpenRPC document.
@@ -521,16 +568,16 @@ pub fn load_ir_and_version_map_from_path(
     let ir = ProtocolIR::from_file(ir_file_path)?;
     let mut method_to_version: HashMap<String, String> = HashMap::new();
 
-    // Extract version_added from each RPC method in the canonical IR (keep earlier by major)
+    // Extract version_added from each RPC method in the canonical IR (keep the earlier one)
     for rpc in ir.get_rpc_methods() {
         if let Some(ref version_added) = rpc.version_added {
             let normalized = normalize_version_added_for_storage(version_added);
             method_to_version
                 .entry(rpc.name.clone())
                 .and_modify(|existing_version| {
-                    let existing_major = effective_major_for_comparison(existing_version);
-                    let new_major = effective_major_for_comparison(version_added);
-                    if new_major < existing_major {
+                    if parse_version_for_ordering(version_added)
+                        < parse_version_for_ordering(existing_version)
+                    {
                         *existing_version = normalized.clone();
                     }
                 })
@@ -626,36 +673,46 @@ fn merge_version_from_existing_rpc(our: &mut ir::RpcDef, existing: &ir::RpcDef)
 
 /// Extracts version-specific IR from canonical IR.
 ///
-/// Filters the canonical IR to only include methods available in the target version.
-/// Comparison uses major version only: building 30.2.8 includes methods present in major 30.
-/// Methods with `version_added = None` (unreleased) are excluded. Unreleased (e.g. 30.99-)
-/// is treated as next major (31) so excluded when targeting 30.
+/// Filters the canonical IR to only include methods available in the target version, comparing
+/// full versions (see [`item_visible_for_version`]): building 29.0 excludes methods added in
+/// 29.1. Methods with `version_added = None` (unreleased) are excluded. Unreleased (e.g. 30.99-)
+/// orders after every 30.x release, so it is excluded when targeting 30.
+///
+/// RPC definitions are collected into the `rpc` module; other modules (e.g. `rest`, `zmq`) are
+/// kept under their own names with their endpoints and notifications filtered the same way.
 pub fn extract_version_ir(canonical_ir: ProtocolIR, target_version: &str) -> ProtocolIR {
-    let target_major = parse_version_for_ordering(target_version).major();
     let mut definitions = Vec::new();
+    let mut other_modules = Vec::new();
 
     for module in canonical_ir.modules() {
+        if module.name() != "rpc" {
+            let mut module_definitions: Vec<ProtocolDef> = module
+                .definitions()
+                .iter()
+                .filter_map(|def| filter_module_definition_for_version(def, target_version))
+                .collect();
+            sort_definitions_by_name(&mut module_definitions);
+            other_modules.push(ProtocolModule::new(
+                module.name().to_string(),
+                module.description().to_string(),
+                module_definitions,
+            ));
+            continue;
+        }
+
         for def in module.definitions() {
             match def {
                 ProtocolDef::RpcMethod(rpc) => {
-                    // Include only if the method was added in the target major or earlier.
-                    let was_available = if let Some(ref v) = rpc.version_added {
-                        effective_major_for_comparison(v) <= target_major
-                    } else {
-                        false
-                    };
-
-                    // Exclude methods that were removed on or before the target major.
-                    // Use `effective_major_for_comparison` so unreleased removals (e.g. 30.99 or with
-                    // build suffixes) are treated as the next major and remain available when
-                    // targeting the current major.
-                    let not_removed = if let Some(ref v) = rpc.version_removed {
-                        effective_major_for_comparison(v) > target_major
-                    } else {
-                        true
-                    };
-
-                    if was_available && not_removed {
+                    // Include only if the method was added in the target version or earlier and
+                    // not removed on or before it; methods without version_added are unreleased.
+                    let available = rpc.version_added.is_some()
+                        && item_visible_for_version(
+                            rpc.version_added.as_deref(),
+                            rpc.version_removed.as_deref(),
+                            target_version,
+                        );
+
+                    if available {
                         let mut rpc_for_version = rpc.clone();
                         rpc_for_version.params =
                             filter_params_for_version(&rpc_for_version.params, target_version);
@@ -674,15 +731,42 @@ pub fn extract_version_ir(canonical_ir: ProtocolIR, target_version: &str) -> Pro
     // Sort definitions by method name for deterministic output.
     sort_definitions_by_name(&mut definitions);
 
-    ProtocolIR::new(vec![ProtocolModule::new(
-        "rpc".to_string(),
-        "Bitcoin RPC API".to_string(),
-        definitions,
-    )])
+    let mut modules =
+        vec![ProtocolModule::new("rpc".to_string(), "Bitcoin RPC API".to_string(), definitions)];
+    modules.extend(other_modules);
+    ProtocolIR::new(modules)
+}
+
+/// Filters one definition of a non-RPC module (REST endpoints, notifications) for the target
+/// version; other definitions are kept as-is.
+fn filter_module_definition_for_version(
+    def: &ProtocolDef,
+    target_version: &str,
+) -> Option<ProtocolDef> {
+    let endpoint = match def {
+        ProtocolDef::RestEndpoint(endpoint) => endpoint,
+        ProtocolDef::Message(message) => {
+            return item_visible_for_version(message.version.as_deref(), None, target_version)
+                .then(|| def.clone());
+        }
+        other => return Some(other.clone()),
+    };
+    if !item_visible_for_version(
+        endpoint.version_added.as_deref(),
+        endpoint.version_removed.as_deref(),
+        target_version,
+    ) {
+        return None;
+    }
+    let mut endpoint = endpoint.clone();
+    endpoint.params = filter_params_for_version(&endpoint.params, target_version);
+    endpoint.query_params = filter_params_for_version(&endpoint.query_params, target_version);
+    endpoint.result = endpoint.result.map(|r| filter_type_def_for_version(&r, target_version));
+    Some(ProtocolDef::RestEndpoint(endpoint))
 }
 
 /// Loads and parses an OpenRPC document from a file.
-fn load_openrpc_doc(path: &PathBuf) -> Result<OpenRpcDoc, Box<dyn std::error::Error>> {
+pub(super) fn load_openrpc_doc(path: &Path) -> Result<OpenRpcDoc, Box<dyn std::error::Error>> {
     let content = std::fs::read_to_string(path)?;
     let doc: OpenRpcDoc = serde_json::from_str(&content)?;
     Ok(doc)
@@ -722,6 +806,7 @@ fn convert_argument_to_type_def(raw: &RawArgument) -> TypeDef {
             default_value: inner.default_value(),
             version_added: None,
             version_removed: None,
+            deprecated: deprecation_from_description(&inner.description),
         });
 
         type_def.fields = Some(if raw.r#type == "array" {
@@ -771,6 +856,7 @@ fn convert_result(raw: &RawResult, parent_key: Option<&str>, method_name: Option
                     default_value: inner.default_value(),
                     version_added: None,
                     version_removed: None,
+                    deprecated: deprecation_from_description(&inner.description),
                 }
             })
             .collect();
@@ -892,6 +978,7 @@ fn merge_results_to_object(results: &[RawResult], method_name: &str) -> TypeDef
                     default_value: None,
                     version_added: None,
                     version_removed: None,
+                    deprecated: deprecation_from_description(&inner.description),
                 });
             }
         } else {
@@ -935,6 +1022,7 @@ fn merge_results_to_object(results: &[RawResult], method_name: &str) -> TypeDef
                 default_value: None,
                 version_added: None,
                 version_removed: None,
+                deprecated: deprecation_from_description(&result.description),
             });
         }
     }
@@ -951,15 +1039,17 @@ fn merge_results_to_object(results: &[RawResult], method_name: &str) -> TypeDef
 /// Converts a raw argument to a `ParamDef`.
 fn convert_argument(raw: RawArgument) -> ParamDef {
     let param_name = raw.names.first().cloned().unwrap_or_default();
+    let deprecated = deprecation_from_description(&raw.description);
 
     ParamDef {
         name: param_name.clone(),
         param_type: convert_argument_to_type_def(&raw),
         required: raw.required,
         description: raw.description,
-        default_value: raw.default.map(|v| v.to_string()).or_else(|| raw.default_hint),
+        default_value: raw.default.map(|v| v.to_string()).or(raw.default_hint),
         version_added: None,
         version_removed: None,
+        deprecated,
     }
 }
 
@@ -1021,6 +1111,7 @@ fn convert_openrpc_method(method: OpenRpcMethod, version_added: Option<String>)
                 default_value: None,
                 version_added: None,
                 version_removed: None,
+                deprecated: None,
             }]),
             protocol_type: Some("array".to_string()),
             ..Default::default()
@@ -1063,6 +1154,8 @@ fn convert_openrpc_method(method: OpenRpcMethod, version_added: Option<String>)
         .map(|s| vec![s.clone()])
         .unwrap_or_default();
 
+    let deprecated = deprecation_from_description(&method.description);
+
     RpcDef {
         name: method.name,
         description: method.description,
@@ -1073,66 +1166,14 @@ fn convert_openrpc_method(method: OpenRpcMethod, version_added: Option<String>)
         requires_private_keys,
         version_added,
         version_removed: None,
+        deprecated,
         examples: if examples.is_empty() { None } else { Some(examples) },
         hidden: if method.x_bitcoin_category.to_lowercase() == "hidden" {
             Some(true)
         } else {
             None
         },
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn top_level_array_methods_use_array_typedef_with_element_type() {
-        let raw_elem = RawResult {
-            r#type: "string".to_string(),
-            optional: false,
-            description: "the derived addresses".to_string(),
-            skip_type_check: false,
-            key_name: String::new(),
-            condition: String::new(),
-            inner: Vec::new(),
-        };
-
-        let raw_array = RawResult {
-            r#type: "array".to_string(),
-            optional: false,
-            description: "list of derived addresses".to_string(),
-            skip_type_check: false,
-            key_name: String::new(),
-            condition: String::new(),
-            inner: vec![raw_elem],
-        };
-
-        let method = OpenRpcMethod {
-            name: "deriveaddresses".to_string(),
-            description: String::new(),
-            params: Vec::new(),
-            result: Some(OpenRpcResult {
-                name: Some("result".to_string()),
-                schema: None,
-                x_bitcoin_results: vec![raw_array],
-            }),
-            x_bitcoin_category: "wallet".to_string(),
-            x_bitcoin_examples: None,
-            x_bitcoin_argument_names: Vec::new(),
-            x_bitcoin_arguments: Vec::new(),
-        };
-
-        let rpc = convert_openrpc_method(method, Some("30".to_string()));
-        let result_ty = rpc.result.expect("result type should be present");
-
-        assert_eq!(result_ty.kind, TypeKind::Array);
-
-        let elem_ty = result_ty
-            .array_element_type()
-            .expect("array element type should be discoverable via helper");
-
-        assert_eq!(elem_ty.protocol_type.as_deref(), Some("string"));
+        http: None,
     }
 }
 
@@ -1211,18 +1252,36 @@ pub fn update_canonical_ir_from_openrpc(
         }
     }
 
-    if let Some(rpc_module) = existing_ir.modules_mut().iter_mut().find(|m| m.name() == "rpc") {
-        *rpc_module.definitions_mut() = new_defs;
+    replace_rpc_definitions(&mut existing_ir, new_defs);
+    existing_ir.to_file(canonical_path)?;
+    Ok(())
+}
+
+/// Replaces the definitions of the canonical IR's `rpc` module and refreshes the REST and ZMQ
+/// modules, which OpenRPC does not describe, from the adapter's listings.
+pub(super) fn replace_rpc_definitions(
+    canonical_ir: &mut ProtocolIR,
+    definitions: Vec<ProtocolDef>,
+) {
+    if let Some(rpc_module) = canonical_ir.modules_mut().iter_mut().find(|m| m.name() == "rpc") {
+        *rpc_module.definitions_mut() = definitions;
     } else {
-        existing_ir.modules_mut().push(ProtocolModule::new(
+        canonical_ir.modules_mut().push(ProtocolModule::new(
             "rpc".to_string(),
             "Bitcoin RPC API".to_string(),
-            new_defs,
+            definitions,
         ));
     }
 
-    existing_ir.to_file(canonical_path)?;
-    Ok(())
+    // The REST and ZMQ interfaces are not described by OpenRPC; refresh them from the
+    // adapter's listings.
+    let rest_module = rest::rest_module(&canonical_ir.get_rpc_methods());
+    for listed_module in [rest_module, zmq::zmq_module()] {
+        match canonical_ir.modules_mut().iter_mut().find(|m| m.name() == listed_module.name()) {
+            Some(module) => *module = listed_module,
+            None => canonical_ir.modules_mut().push(listed_module),
+        }
+    }
 }
 
 /// Runs the `process_bitcoin_openrpc` binary entry point.
@@ -1230,6 +1289,7 @@ pub fn update_canonical_ir_from_openrpc(
 /// Usage patterns:
 /// 1. Convert OpenRPC to IR: process_bitcoin_openrpc <openrpc_file> [output_file]
 /// 2. Extract version-specific IR: process_bitcoin_openrpc <version> [output_file]
+/// 3. Build version history: process_bitcoin_openrpc --history <openrpc_dir> [output_file]
 ///
 /// Library entry point for the `process_bitcoin_openrpc` binary.
 pub fn run(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
@@ -1240,16 +1300,57 @@ pub fn run(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
             args[0]
         );
         eprintln!("  {} <version> [output_file]                         # Extract version-specific IR from canonical IR", args[0]);
+        eprintln!("  {} --history <openrpc_dir> [output_file]           # Build version history from per-release documents", args[0]);
         eprintln!();
         eprintln!("Examples:");
         eprintln!("  {} openrpc.json output.ir.json", args[0]);
         eprintln!("  {} 30.2", args[0]);
         eprintln!("  {} 30.2 v30_2_0_bitcoin.ir.json", args[0]);
+        eprintln!("  {} --history openrpc/", args[0]);
         std::process::exit(1);
     }
 
     let first_arg = &args[1];
 
+    if first_arg == "--history" {
+        // Mode 3: Build version history from a directory of per-release documents
+        let dir = args.get(2).ok_or("--history requires a directory of OpenRPC documents")?;
+        let releases = history::read_release_dir(Path::new(dir))?;
+        if releases.is_empty() {
+            return Err(format!("no OpenRPC documents in {}", dir).into());
+        }
+
+        let project_root = find_project_root()?;
+        let canonical_ir_path = canonical_bitcoin_ir_path(&project_root);
+        let output_resolved = match args.get(3) {
+            Some(output) => resolve_ir_output_path(&project_root, &PathBuf::from(output)),
+            None => canonical_ir_path.clone(),
+        };
+        let writing_canonical = output_resolved == canonical_ir_path;
+
+        let baseline = if writing_canonical {
+            load_method_version_map_from_path(&canonical_ir_path)?
+        } else {
+            HashMap::new()
+        };
+        let version_history = history::build_history(&releases, &baseline);
+        if writing_canonical {
+            history::update_canonical_ir_from_history(&output_resolved, &version_history)?;
+        } else {
+            version_history.ir.to_file(&output_resolved)?;
+        }
+
+        println!("✓ Built version history: {}", output_resolved.display());
+        println!("  Releases: {}", version_history.releases.join(", "));
+        for rename in &version_history.renames {
+            println!(
+                "  Possible rename in {} ({}): {} -> {}",
+                rename.version, rename.scope, rename.from, rename.to
+            );
+        }
+        return Ok(());
+    }
+
     // Check if first argument is a version string (contains only digits and dots, or starts with 'v')
     let is_version =
         first_arg.trim_start_matches('v').chars().all(|c| c.is_ascii_digit() || c == '.');
@@ -1301,3 +1402,117 @@ pub fn run(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn top_level_array_methods_use_array_typedef_with_element_type() {
+        let raw_elem = RawResult {
+            r#type: "string".to_string(),
+            optional: false,
+            description: "the derived addresses".to_string(),
+            skip_type_check: false,
+            key_name: String::new(),
+            condition: String::new(),
+            inner: Vec::new(),
+        };
+
+        let raw_array = RawResult {
+            r#type: "array".to_string(),
+            optional: false,
+            description: "list of derived addresses".to_string(),
+            skip_type_check: false,
+            key_name: String::new(),
+            condition: String::new(),
+            inner: vec![raw_elem],
+        };
+
+        let method = OpenRpcMethod {
+            name: "deriveaddresses".to_string(),
+            description: String::new(),
+            params: Vec::new(),
+            result: Some(OpenRpcResult {
+                name: Some("result".to_string()),
+                schema: None,
+                x_bitcoin_results: vec![raw_array],
+            }),
+            x_bitcoin_category: "wallet".to_string(),
+            x_bitcoin_examples: None,
+            x_bitcoin_argument_names: Vec::new(),
+            x_bitcoin_arguments: Vec::new(),
+        };
+
+        let rpc = convert_openrpc_method(method, Some("30".to_string()));
+        let result_ty = rpc.result.expect("result type should be present");
+
+        assert_eq!(result_ty.kind, TypeKind::Array);
+
+        let elem_ty = result_ty
+            .array_element_type()
+            .expect("array element type should be discoverable via helper");
+
+        assert_eq!(elem_ty.protocol_type.as_deref(), Some("string"));
+    }
+
+    #[test]
+    fn deprecation_is_extracted_from_descriptions() {
+        let flagged = deprecation_from_description(
+            "(DEPRECATED, returned only if config option -deprecatedrpc=startingheight is passed) \
+             The starting height (block) of the peer",
+        )
+        .expect("deprecated");
+        assert_eq!(flagged.flag.as_deref(), Some("startingheight"));
+        assert_eq!(flagged.since, None);
+
+        let replaced =
+            deprecation_from_description("DEPRECATED since v28. Use `getblockchaininfo` instead.")
+                .expect("deprecated");
+        assert_eq!(replaced.since.as_deref(), Some("28"));
+        assert_eq!(replaced.replacement.as_deref(), Some("getblockchaininfo"));
+        assert_eq!(replaced.flag, None);
+
+        assert_eq!(
+            deprecation_from_description(
+                "any network and blockchain warnings (run with `-deprecatedrpc=warnings` to \
+                 return the latest warning as a single string)"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn visibility_compares_full_versions() {
+        assert!(!item_visible_for_version(Some("29.1"), None, "29.0"));
+        assert!(item_visible_for_version(Some("29.1"), None, "29.1.2"));
+        assert!(!item_visible_for_version(Some("30"), None, "30.0rc1"));
+        assert!(item_visible_for_version(Some("30rc1"), None, "30.0rc2"));
+        assert!(!item_visible_for_version(Some("30.99.0-705399b1d57a-dirty"), None, "30.2"));
+        assert!(item_visible_for_version(Some("28"), Some("30.1"), "30.0"));
+        assert!(!item_visible_for_version(Some("28"), Some("30.1"), "30.1"));
+        assert!(!item_visible_for_version(Some("not a version"), None, "30.0"));
+
+        let string = ir::test_utils::primitive_type("string", None);
+        let mut added_in_minor = ir::test_utils::param("fee_rate", string.clone(), false);
+        added_in_minor.version_added = Some("29.1".to_string());
+        let params = vec![ir::test_utils::param("txid", string, true), added_in_minor];
+        let names = |target: &str| -> Vec<String> {
+            filter_params_for_version(&params, target).into_iter().map(|p| p.name).collect()
+        };
+        assert_eq!(names("29.0"), vec!["txid"]);
+        assert_eq!(names("v29.1"), vec!["txid", "fee_rate"]);
+    }
+
+    #[test]
+    fn version_added_keeps_minor_releases() {
+        assert_eq!(normalize_version_added_for_storage("0.17.0"), "0.17");
+        assert_eq!(normalize_version_added_for_storage("28.0"), "28");
+        assert_eq!(normalize_version_added_for_storage("v29.1"), "29.1");
+        assert_eq!(normalize_version_added_for_storage("29.0.1"), "29.0.1");
+        assert_eq!(normalize_version_added_for_storage("30.0rc1"), "30rc1");
+        assert_eq!(normalize_version_added_for_storage("29.1.knots20250903"), "29.1.knots20250903");
+        assert_eq!(normalize_version_added_for_storage("30.99.0"), "31");
+        assert_eq!(normalize_version_added_for_storage("30.2.0-705399b1d57a-dirty"), "31");
+    }
+}
@@ -0,0 +1,458 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Bitcoin Core REST interface (`-rest`) described as a `rest` ProtocolModule.
+//!
+//! The REST interface has no machine-readable schema, so the endpoints are listed here
+//! (see `doc/REST-interface.md` in Bitcoin Core). JSON responses that mirror an RPC result
+//! reference that RPC through `shared_result` so generated clients reuse the same types.
+//! `/rest/block` has no RPC counterpart with the same layout; its result is assembled from the
+//! `getblock` and `getrawtransaction` results.
+
+use ir::{
+    FieldDef, FieldKey, ParamDef, ProtocolDef, ProtocolModule, RestEndpointDef, RestFormat,
+    RestResultContainer, RestSharedResult, RpcDef, TypeDef, TypeKind,
+};
+
+/// Name of the module holding the REST endpoints in the canonical IR.
+pub const REST_MODULE_NAME: &str = "rest";
+
+/// Encodings offered by endpoints that serve raw chain data.
+const ALL_FORMATS: [RestFormat; 3] = [RestFormat::Bin, RestFormat::Hex, RestFormat::Json];
+
+/// Builds the `rest` module describing Bitcoin Core's REST endpoints.
+///
+/// `rpc_methods` are the RPC definitions the endpoint results are derived from.
+pub fn rest_module(rpc_methods: &[&RpcDef]) -> ProtocolModule {
+    let definitions =
+        rest_endpoints(rpc_methods).into_iter().map(ProtocolDef::RestEndpoint).collect();
+    ProtocolModule::new(
+        REST_MODULE_NAME.to_string(),
+        "Bitcoin Core REST API".to_string(),
+        definitions,
+    )
+}
+
+/// All REST endpoints, sorted by name.
+///
+/// `block` has a JSON result only when `rpc_methods` include `getblock` and
+/// `getrawtransaction`.
+pub fn rest_endpoints(rpc_methods: &[&RpcDef]) -> Vec<RestEndpointDef> {
+    let result_of = |name: &str| {
+        rpc_methods.iter().find(|rpc| rpc.name == name).and_then(|rpc| rpc.result.as_ref())
+    };
+    let mut block = EndpointBuilder::new(endpoint(
+        "block",
+        "Block with full transaction details (getblock verbosity 2 layout).",
+        "/rest/block/{blockhash}",
+        vec![path_param("blockhash", "hex", "The block hash")],
+        &ALL_FORMATS,
+        "0.17",
+    ));
+    if let (Some(getblock), Some(getrawtransaction)) =
+        (result_of("getblock"), result_of("getrawtransaction"))
+    {
+        block = block.result(block_result(getblock, getrawtransaction));
+    }
+    let mut endpoints = vec![
+        block.build(),
+        EndpointBuilder::new(endpoint(
+            "block_notxdetails",
+            "Block with transaction ids only.",
+            "/rest/block/notxdetails/{blockhash}",
+            vec![path_param("blockhash", "hex", "The block hash")],
+            &ALL_FORMATS,
+            "0.17",
+        ))
+        .shared("getblock", RestResultContainer::Single)
+        .build(),
+        EndpointBuilder::new(endpoint(
+            "blockhashbyheight",
+            "Hash of the block at the given height in the active chain.",
+            "/rest/blockhashbyheight/{height}",
+            vec![path_param("height", "number", "The height index")],
+            &ALL_FORMATS,
+            "0.18",
+        ))
+        .result(object(
+            "RestBlockHashByHeightResponse",
+            vec![field("blockhash", primitive("hex", "The block hash"), true)],
+        ))
+        .build(),
+        EndpointBuilder::new(endpoint(
+            "chaininfo",
+            "Blockchain state (same layout as getblockchaininfo).",
+            "/rest/chaininfo",
+            vec![],
+            &[RestFormat::Json],
+            "0.17",
+        ))
+        .shared("getblockchaininfo", RestResultContainer::Single)
+        .build(),
+        EndpointBuilder::new(endpoint(
+            "getutxos",
+            "Unspent outputs for the given outpoints, optionally including the mempool.",
+            "/rest/getutxos/{checkmempool}/{outpoints}",
+            vec![
+                ParamDef {
+                    required: false,
+                    default_value: Some("false".to_string()),
+                    ..path_param("checkmempool", "boolean", "Also consider mempool transactions")
+                },
+                ParamDef {
+                    param_type: array_of(primitive("outpoint", "An outpoint as <txid>-<n>")),
+                    ..path_param("outpoints", "array", "The outpoints to look up")
+                },
+            ],
+            &ALL_FORMATS,
+            "0.17",
+        ))
+        .result(object(
+            "RestGetUtxosResponse",
+            vec![
+                field("chainHeight", primitive("number", "The height of the chain tip"), true),
+                field("chaintipHash", primitive("hex", "The hash of the chain tip"), true),
+                field(
+                    "bitmap",
+                    primitive("string", "One character per requested outpoint, 1 if unspent"),
+                    true,
+                ),
+                field(
+                    "utxos",
+                    array_of(object(
+                        "RestUtxo",
+                        vec![
+                            field("height", primitive("number", "Height of the output"), true),
+                            field("value", primitive("amount", "Value in BTC"), true),
+                            field(
+                                "scriptPubKey",
+                                object(
+                                    "RestUtxoScriptPubKey",
+                                    vec![
+                                        field("asm", primitive("string", "Disassembly"), true),
+                                        field(
+                                            "desc",
+                                            primitive("string", "Inferred descriptor"),
+                                            true,
+                                        ),
+                                        field("hex", primitive("hex", "Serialized script"), true),
+                                        field("address", primitive("string", "The address"), false),
+                                        field("type", primitive("string", "The script type"), true),
+                                    ],
+                                ),
+                                true,
+                            ),
+                        ],
+                    )),
+                    true,
+                ),
+            ],
+        ))
+        .build(),
+        EndpointBuilder::new(endpoint(
+            "headers",
+            "Block headers starting at the given block hash.",
+            "/rest/headers/{blockhash}",
+            vec![path_param("blockhash", "hex", "The hash of the first header")],
+            &ALL_FORMATS,
+            "24",
+        ))
+        .query(vec![ParamDef {
+            required: false,
+            default_value: Some("5".to_string()),
+            ..path_param("count", "number", "Number of headers to return")
+        }])
+        .shared("getblockheader", RestResultContainer::Array)
+        .build(),
+        EndpointBuilder::new(endpoint(
+            "mempool_contents",
+            "Verbose mempool contents keyed by txid.",
+            "/rest/mempool/contents",
+            vec![],
+            &[RestFormat::Json],
+            "0.17",
+        ))
+        .shared("getmempoolentry", RestResultContainer::Map)
+        .build(),
+        EndpointBuilder::new(endpoint(
+            "mempool_info",
+            "Mempool state (same layout as getmempoolinfo).",
+            "/rest/mempool/info",
+            vec![],
+            &[RestFormat::Json],
+            "0.17",
+        ))
+        .shared("getmempoolinfo", RestResultContainer::Single)
+        .build(),
+        EndpointBuilder::new(endpoint(
+            "tx",
+            "Transaction by id; requires -txindex for transactions outside the mempool.",
+            "/rest/tx/{txid}",
+            vec![path_param("txid", "hex", "The transaction id")],
+            &ALL_FORMATS,
+            "0.17",
+        ))
+        .shared("getrawtransaction", RestResultContainer::Single)
+        .build(),
+    ];
+    endpoints.sort_by(|a, b| a.name.cmp(&b.name));
+    endpoints
+}
+
+/// Fields of the verbose `getrawtransaction` result that describe the transaction's block or
+/// the raw encoding, and so do not appear in a block's transactions
+const STANDALONE_TX_FIELDS: [&str; 6] =
+    ["data", "in_active_chain", "blockhash", "confirmations", "blocktime", "time"];
+
+/// `/rest/block` result: the `getblock` result with `tx` holding the transactions in the layout
+/// of the verbose `getrawtransaction` result
+fn block_result(getblock: &TypeDef, getrawtransaction: &TypeDef) -> TypeDef {
+    let mut block = named_layout(getblock, "RestBlock");
+    let mut tx = named_layout(getrawtransaction, "RestBlockTx");
+    if let Some(fields) = tx.fields.as_mut() {
+        fields.retain(|f| f.key.json_key().is_some_and(|key| !STANDALONE_TX_FIELDS.contains(&key)));
+    }
+    if let Some(field) = block.fields.iter_mut().flatten().find(|f| f.key.json_key() == Some("tx"))
+    {
+        field.description = "The transactions".to_string();
+        field.field_type = TypeDef { description: field.description.clone(), ..array_of(tx) };
+    }
+    block.description = "Block with full transaction details.".to_string();
+    block
+}
+
+/// Copy of an RPC result with each object type named after its path from `name` (e.g.
+/// `RestBlockTxVin`), array-of-object wrappers turned into arrays, and the fields of other
+/// verbosity layouts (`elision` placeholders and `<key>_<n>` variants) left out
+fn named_layout(type_def: &TypeDef, name: &str) -> TypeDef {
+    let Some(fields) = type_def.fields.as_ref().filter(|fields| !fields.is_empty()) else {
+        return type_def.clone();
+    };
+    // `build_array_of_objects_wrapper`: an "array" object whose one field is the element
+    if type_def.kind == TypeKind::Array
+        || (type_def.protocol_type.as_deref() == Some("array") && fields.len() == 1)
+    {
+        let mut element = type_def.array_element_type().unwrap_or(&fields[0].field_type);
+        // The element object may itself hold the item under a single positional `field_0`
+        while let Some([only]) = element.fields.as_deref() {
+            if only.field_type.kind != TypeKind::Object
+                || !only.key.json_key().is_some_and(|key| key.starts_with("field_"))
+            {
+                break;
+            }
+            element = &only.field_type;
+        }
+        return TypeDef {
+            description: type_def.description.clone(),
+            ..array_of(named_layout(element, name))
+        };
+    }
+    let keys: Vec<&str> = fields.iter().filter_map(|f| f.key.json_key()).collect();
+    let is_variant = |key: &str| {
+        key.rsplit_once('_').is_some_and(|(base, n)| {
+            keys.contains(&base) && !n.is_empty() && n.chars().all(|c| c.is_ascii_digit())
+        })
+    };
+    let fields = fields
+        .iter()
+        .filter(|f| f.field_type.protocol_type.as_deref() != Some("elision"))
+        .filter(|f| f.key.json_key().is_some_and(|key| !is_variant(key)))
+        .map(|f| {
+            let key = f.key.json_key().unwrap_or_default();
+            FieldDef {
+                field_type: named_layout(&f.field_type, &format!("{}{}", name, pascal_case(key))),
+                ..f.clone()
+            }
+        })
+        .collect();
+    TypeDef { name: name.to_string(), fields: Some(fields), ..type_def.clone() }
+}
+
+/// `scriptPubKey` → `ScriptPubKey`, `txinwitness` → `Txinwitness`
+fn pascal_case(key: &str) -> String {
+    key.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Small builder for the optional parts of an endpoint.
+struct EndpointBuilder {
+    endpoint: RestEndpointDef,
+}
+
+impl EndpointBuilder {
+    fn new(endpoint: RestEndpointDef) -> Self { Self { endpoint } }
+
+    fn shared(mut self, method: &str, container: RestResultContainer) -> Self {
+        self.endpoint.shared_result =
+            Some(RestSharedResult { method: method.to_string(), container });
+        self
+    }
+
+    fn result(mut self, result: TypeDef) -> Self {
+        self.endpoint.result = Some(result);
+        self
+    }
+
+    fn query(mut self, query_params: Vec<ParamDef>) -> Self {
+        self.endpoint.query_params = query_params;
+        self
+    }
+
+    fn build(self) -> RestEndpointDef { self.endpoint }
+}
+
+fn endpoint(
+    name: &str,
+    description: &str,
+    path: &str,
+    params: Vec<ParamDef>,
+    formats: &[RestFormat],
+    version_added: &str,
+) -> RestEndpointDef {
+    RestEndpointDef {
+        name: name.to_string(),
+        description: description.to_string(),
+        path: path.to_string(),
+        params,
+        query_params: Vec::new(),
+        formats: formats.to_vec(),
+        bare_path: false,
+        result: None,
+        shared_result: None,
+        version_added: Some(version_added.to_string()),
+        version_removed: None,
+    }
+}
+
+fn path_param(name: &str, protocol_type: &str, description: &str) -> ParamDef {
+    ParamDef {
+        name: name.to_string(),
+        param_type: primitive(protocol_type, description),
+        required: true,
+        description: description.to_string(),
+        default_value: None,
+        version_added: None,
+        version_removed: None,
+        deprecated: None,
+    }
+}
+
+fn primitive(protocol_type: &str, description: &str) -> TypeDef {
+    TypeDef {
+        name: protocol_type.to_string(),
+        description: description.to_string(),
+        kind: TypeKind::Primitive,
+        protocol_type: Some(protocol_type.to_string()),
+        ..TypeDef::default()
+    }
+}
+
+fn object(name: &str, fields: Vec<FieldDef>) -> TypeDef {
+    TypeDef {
+        name: name.to_string(),
+        kind: TypeKind::Object,
+        fields: Some(fields),
+        protocol_type: Some("object".to_string()),
+        ..TypeDef::default()
+    }
+}
+
+fn array_of(element: TypeDef) -> TypeDef {
+    TypeDef {
+        name: "array".to_string(),
+        kind: TypeKind::Array,
+        fields: Some(vec![FieldDef {
+            key: FieldKey::Anonymous(0),
+            description: element.description.clone(),
+            field_type: element,
+            required: true,
+            default_value: None,
+            version_added: None,
+            version_removed: None,
+            deprecated: None,
+        }]),
+        protocol_type: Some("array".to_string()),
+        ..TypeDef::default()
+    }
+}
+
+fn field(key: &str, field_type: TypeDef, required: bool) -> FieldDef {
+    FieldDef {
+        key: FieldKey::Named(key.to_string()),
+        description: field_type.description.clone(),
+        field_type,
+        required,
+        default_value: None,
+        version_added: None,
+        version_removed: None,
+        deprecated: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ir::ProtocolIR;
+
+    use super::*;
+
+    fn canonical_ir() -> ProtocolIR {
+        let root = path::find_project_root().expect("project root");
+        ProtocolIR::from_file(&root.join("resources/ir/bitcoin.ir.json")).expect("canonical IR")
+    }
+
+    #[test]
+    fn path_placeholders_match_params() {
+        for endpoint in rest_endpoints(&[]) {
+            let names: Vec<&str> = endpoint.params.iter().map(|p| p.name.as_str()).collect();
+            assert_eq!(endpoint.path_placeholders(), names, "endpoint {}", endpoint.name);
+        }
+    }
+
+    #[test]
+    fn json_endpoints_describe_their_response() {
+        let ir = canonical_ir();
+        for endpoint in rest_endpoints(&ir.get_rpc_methods()) {
+            assert!(
+                endpoint.result.is_some() || endpoint.shared_result.is_some(),
+                "endpoint {} has no JSON response type",
+                endpoint.name
+            );
+        }
+    }
+
+    #[test]
+    fn block_result_types_transactions() {
+        let ir = canonical_ir();
+        let endpoints = rest_endpoints(&ir.get_rpc_methods());
+        let block = endpoints.iter().find(|e| e.name == "block").expect("block endpoint");
+        let result = block.result.as_ref().expect("block result");
+        let field = |ty: &TypeDef, key: &str| {
+            ty.fields
+                .iter()
+                .flatten()
+                .find(|f| f.key.json_key() == Some(key))
+                .map(|f| f.field_type.clone())
+        };
+
+        assert_eq!(result.name, "RestBlock");
+        assert!(field(result, "merkleroot").is_some());
+        assert!(field(result, "tx_1").is_none());
+        let tx = field(result, "tx").expect("tx field");
+        let element = tx.array_element_type().expect("tx is an array");
+        assert_eq!(element.name, "RestBlockTx");
+        assert!(field(element, "txid").is_some());
+        assert!(field(element, "fee").is_some());
+        assert!(field(element, "data").is_none());
+        let vin = field(element, "vin").expect("vin field");
+        let input = vin.array_element_type().expect("vin is an array");
+        assert_eq!(input.name, "RestBlockTxVin");
+        assert!(field(input, "scriptSig").is_some());
+    }
+}
@@ -0,0 +1,1655 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Bitcoin Core `RPCHelpMan` definitions read from a source checkout -> IR.
+//!
+//! Every Bitcoin Core RPC documents itself with an `RPCHelpMan{name, description, args,
+//! results, examples, handler}` value built in C++ (`src/rpc/*.cpp`, `src/wallet/rpc/*.cpp`).
+//! The OpenRPC document handled by [`super::openrpc`] is rendered from those values by an
+//! out-of-tree patch, so it lags Core master; reading the sources directly lets us generate
+//! clients for unreleased branches.
+//!
+//! The sources are read with a small expression parser rather than a C++ front end:
+//!
+//! - top-level functions and variables of every `.cpp`/`.h` file in the RPC directories are
+//!   indexed, so helpers such as `ScriptPubKeyDoc()`, constants such as
+//!   `HELP_REQUIRING_PASSPHRASE` and the `Cat(...)`, `strprintf(...)` and `HelpExample*(...)`
+//!   utilities resolve while the definitions are evaluated;
+//! - the RPCs are the functions registered in `CRPCCommand` tables (`{"category", &fn}`),
+//!   which also supply each method's category;
+//! - each `RPCArg` / `RPCResult` tree is translated into the `x-bitcoin-arguments` /
+//!   `x-bitcoin-results` shape of the OpenRPC dump, and [`super::openrpc`]'s converter builds
+//!   the IR, so both sources produce the same IR for the same Core version.
+//!
+//! As in the OpenRPC dump, `Type::OBJ_DYN` results are objects whose single inner entry
+//! describes every dynamically keyed value, `Type::NUM_TIME` is a `timestamp` and
+//! `Type::ELISION` entries are kept as `elision`. Result helpers that cannot be resolved
+//! become `elision` entries and unresolved argument helpers are dropped; both are reported
+//! as warnings.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use ir::ProtocolIR;
+use path::{canonical_bitcoin_ir_path, find_project_root, get_ir_dir, resolve_ir_output_path};
+use serde_json::Value;
+use types::version::version_ir_filename;
+
+use super::openrpc::{
+    convert_to_protocol_ir_with_version, update_canonical_ir_from_openrpc, OpenRpcDoc, OpenRpcInfo,
+    OpenRpcMethod, OpenRpcResult, RawArgument, RawResult,
+};
+use crate::{ProtocolAdapter, ProtocolAdapterError, ProtocolAdapterResult, CAP_RPC};
+
+/// Directories of a Bitcoin Core checkout holding `RPCHelpMan` definitions.
+pub const RPC_SOURCE_DIRS: [&str; 2] = ["src/rpc", "src/wallet/rpc"];
+
+/// Constants defined outside the RPC directories (`src/policy/feerate.h`).
+const BUILTIN_CONSTANTS: [(&str, &str); 2] = [("CURRENCY_UNIT", "BTC"), ("CURRENCY_ATOM", "sat")];
+
+/// Types whose brace-initializers are interpreted as RPC documentation, never as helper calls.
+const DOC_CONSTRUCTORS: [&str; 6] =
+    ["RPCHelpMan", "RPCArg", "RPCArgOptions", "RPCResult", "RPCResults", "RPCExamples"];
+
+/// Nesting limit for helper resolution (guards against recursive helpers).
+const MAX_RESOLVE_DEPTH: usize = 64;
+
+/// Adapter reading `RPCHelpMan` definitions from a Bitcoin Core source checkout.
+#[derive(Debug, Clone, Default)]
+pub struct RpcHelpManAdapter {
+    /// Version reported by the adapter; defaults to the checkout's `CLIENT_VERSION_*`.
+    pub version: Option<String>,
+}
+
+impl RpcHelpManAdapter {
+    /// Create an adapter that reports the checkout's own version.
+    pub fn new() -> Self { Self::default() }
+}
+
+impl ProtocolAdapter for RpcHelpManAdapter {
+    fn name(&self) -> &'static str { "bitcoin-core-rpchelpman" }
+
+    fn version(&self) -> String { self.version.clone().unwrap_or_else(|| "latest".to_string()) }
+
+    fn extract_protocol_ir(&self, path: &Path) -> ProtocolAdapterResult<ProtocolIR> {
+        let source = read_source_tree(path)?;
+        let version = self.version.clone().unwrap_or_else(|| source.version.clone());
+        Ok(convert_to_protocol_ir_with_version(source.doc, Some(version)))
+    }
+
+    fn capabilities(&self) -> Vec<&'static str> { vec![CAP_RPC] }
+}
+
+/// RPC definitions read from Bitcoin Core sources, in the OpenRPC dump's shape.
+#[derive(Debug, Clone)]
+pub struct SourceRpcs {
+    /// Version of the checkout (`major.minor.build`; master is `NN.99.0`)
+    pub version: String,
+    /// The methods as an OpenRPC document with `x-bitcoin-*` extensions
+    pub doc: OpenRpcDoc,
+    /// Definitions that could not be fully resolved
+    pub warnings: Vec<String>,
+}
+
+/// Reads the RPC definitions of the Bitcoin Core checkout at `root`.
+pub fn read_source_tree(root: &Path) -> ProtocolAdapterResult<SourceRpcs> {
+    let version = source_version(root)?;
+    let mut files = Vec::new();
+    for dir in RPC_SOURCE_DIRS {
+        let dir = root.join(dir);
+        if !dir.is_dir() {
+            continue;
+        }
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(&dir)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|ext| ext == "cpp" || ext == "h"))
+            .collect();
+        paths.sort();
+        for path in paths {
+            files.push(std::fs::read_to_string(&path)?);
+        }
+    }
+    if files.is_empty() {
+        return Err(ProtocolAdapterError::Message(format!(
+            "No RPC sources found under {} (expected {})",
+            root.display(),
+            RPC_SOURCE_DIRS.join(", ")
+        )));
+    }
+    Ok(parse_sources(&files, &version))
+}
+
+/// Extracts the RPCs defined in the given C++ sources.
+pub fn parse_sources(sources: &[String], version: &str) -> SourceRpcs {
+    let mut index = SourceIndex::default();
+    for source in sources {
+        index.scan(&tokenize(source));
+    }
+
+    let mut interpreter = Interpreter { index: &index, warnings: Vec::new() };
+    let mut methods: Vec<OpenRpcMethod> = Vec::new();
+    for (category, function) in &index.commands {
+        let Some(def) = index.function(function, 0) else {
+            interpreter.warnings.push(format!("{}: RPC function not found", function));
+            continue;
+        };
+        let Some(helpman) = def.returns.as_ref().map(|e| interpreter.call(def, Vec::new(), e, 0))
+        else {
+            interpreter.warnings.push(format!("{}: no RPCHelpMan returned", function));
+            continue;
+        };
+        match interpreter.method(&helpman, category) {
+            Some(method) if !methods.iter().any(|m| m.name == method.name) => methods.push(method),
+            Some(_) => {}
+            None => interpreter.warnings.push(format!("{}: unrecognized RPCHelpMan", function)),
+        }
+    }
+
+    let warnings = interpreter.warnings;
+    let doc = OpenRpcDoc {
+        open_rpc: "1.3.2".to_string(),
+        info: OpenRpcInfo {
+            title: Some("Bitcoin Core JSON-RPC".to_string()),
+            version: Some(version.to_string()),
+            description: Some("Extracted from Bitcoin Core RPCHelpMan sources.".to_string()),
+            x_bitcoin_version_full: None,
+            x_bitcoin_version_major: None,
+            x_bitcoin_version_minor: None,
+            x_bitcoin_version_build: None,
+            x_bitcoin_timestamp_ms: None,
+        },
+        methods,
+    };
+    SourceRpcs { version: version.to_string(), doc, warnings }
+}
+
+/// Reads the `CLIENT_VERSION_*` of a checkout from `CMakeLists.txt` (v29+) or `configure.ac`.
+pub fn source_version(root: &Path) -> ProtocolAdapterResult<String> {
+    let mut parts: HashMap<String, String> = HashMap::new();
+    for file in ["CMakeLists.txt", "configure.ac"] {
+        let Ok(content) = std::fs::read_to_string(root.join(file)) else { continue };
+        for line in content.lines() {
+            // set(CLIENT_VERSION_MAJOR 30) / define(_CLIENT_VERSION_MAJOR, 27)
+            let line = line.trim();
+            let Some(rest) = line
+                .strip_prefix("set(CLIENT_VERSION_")
+                .or(line.strip_prefix("define(_CLIENT_VERSION_"))
+            else {
+                continue;
+            };
+            let rest = rest.trim_end_matches(')');
+            let mut it = rest.splitn(2, |c: char| c == ',' || c.is_whitespace());
+            if let (Some(key), Some(value)) = (it.next(), it.next()) {
+                parts.entry(key.to_string()).or_insert_with(|| value.trim().to_string());
+            }
+        }
+    }
+    match (parts.get("MAJOR"), parts.get("MINOR")) {
+        (Some(major), Some(minor)) => Ok(format!(
+            "{}.{}.{}",
+            major,
+            minor,
+            parts.get("BUILD").map(String::as_str).unwrap_or("0")
+        )),
+        _ => Err(ProtocolAdapterError::Message(format!(
+            "No CLIENT_VERSION_MAJOR/MINOR in {}/CMakeLists.txt or configure.ac",
+            root.display()
+        ))),
+    }
+}
+
+// ---------------------------------------------------------------------------------------------
+// Tokens
+// ---------------------------------------------------------------------------------------------
+
+/// C++ token; `::`-qualified names are a single identifier.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Str(String),
+    Num(String),
+    Ident(String),
+    Sym(char),
+}
+
+impl Token {
+    fn is_sym(&self, c: char) -> bool { matches!(self, Token::Sym(s) if *s == c) }
+
+    fn ident(&self) -> Option<&str> {
+        match self {
+            Token::Ident(name) => Some(name),
+            _ => None,
+        }
+    }
+}
+
+/// Tokenizes C++ source, dropping comments and preprocessor lines.
+fn tokenize(source: &str) -> Vec<Token> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    let mut line_start = true;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\n' {
+            line_start = true;
+            i += 1;
+            continue;
+        }
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        if c == '#' && line_start {
+            // Preprocessor directive, including `\` continuations
+            while i < chars.len() && chars[i] != '\n' {
+                if chars[i] == '\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            continue;
+        }
+        line_start = false;
+        if c == '/' && chars.get(i + 1) == Some(&'/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i + 1 < chars.len() && !(chars[i] == '*' && chars[i + 1] == '/') {
+                i += 1;
+            }
+            i += 2;
+        } else if c == '"' {
+            let (text, end) = read_string(&chars, i + 1);
+            tokens.push(Token::Str(text));
+            i = end;
+        } else if c == '\'' {
+            let start = i;
+            i += 1;
+            while i < chars.len() && chars[i] != '\'' {
+                if chars[i] == '\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            i += 1;
+            tokens.push(Token::Num(chars[start..i.min(chars.len())].iter().collect()));
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_ascii_alphanumeric() || matches!(chars[i], '.' | '\''))
+            {
+                i += 1;
+            }
+            tokens.push(Token::Num(chars[start..i].iter().collect()));
+        } else if c.is_alphabetic() || c == '_' || (c == ':' && chars.get(i + 1) == Some(&':')) {
+            let start = i;
+            loop {
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                let qualified = chars.get(i) == Some(&':')
+                    && chars.get(i + 1) == Some(&':')
+                    && chars.get(i + 2).is_some_and(|n| n.is_alphabetic() || *n == '_');
+                if !qualified {
+                    break;
+                }
+                i += 2;
+            }
+            let word: String = chars[start..i].iter().collect();
+            match (word.as_str(), chars.get(i)) {
+                ("R", Some('"')) => {
+                    let (text, end) = read_raw_string(&chars, i + 1);
+                    tokens.push(Token::Str(text));
+                    i = end;
+                }
+                ("u8" | "L" | "u" | "U", Some('"')) => {
+                    let (text, end) = read_string(&chars, i + 1);
+                    tokens.push(Token::Str(text));
+                    i = end;
+                }
+                _ => tokens.push(Token::Ident(word.trim_start_matches("::").to_string())),
+            }
+        } else {
+            tokens.push(Token::Sym(c));
+            i += 1;
+        }
+    }
+    tokens
+}
+
+/// Reads a string literal body starting after the opening quote; returns the text and the
+/// index after the closing quote.
+fn read_string(chars: &[char], mut i: usize) -> (String, usize) {
+    let mut text = String::new();
+    while i < chars.len() && chars[i] != '"' {
+        if chars[i] == '\\' && i + 1 < chars.len() {
+            i += 1;
+            match chars[i] {
+                'n' => text.push('\n'),
+                't' => text.push('\t'),
+                'r' => text.push('\r'),
+                '0' => text.push('\0'),
+                other => text.push(other),
+            }
+        } else {
+            text.push(chars[i]);
+        }
+        i += 1;
+    }
+    (text, i + 1)
+}
+
+/// Reads a raw string literal (`R"delim(...)delim"`) starting after the opening quote.
+fn read_raw_string(chars: &[char], i: usize) -> (String, usize) {
+    let Some(open) = chars[i..].iter().position(|c| *c == '(') else {
+        return (String::new(), chars.len());
+    };
+    let delimiter: String = chars[i..i + open].iter().collect();
+    let terminator: Vec<char> = format!("){}\"", delimiter).chars().collect();
+    let body_start = i + open + 1;
+    let mut end = body_start;
+    while end + terminator.len() <= chars.len()
+        && chars[end..end + terminator.len()] != terminator[..]
+    {
+        end += 1;
+    }
+    let body_end = end.min(chars.len());
+    (chars[body_start..body_end].iter().collect(), (end + terminator.len()).min(chars.len()))
+}
+
+/// Index just past the bracket group opening at `open` (`(`, `[` or `{`).
+fn skip_group(tokens: &[Token], open: usize) -> usize {
+    let mut depth = 0usize;
+    let mut i = open;
+    while i < tokens.len() {
+        match tokens[i] {
+            Token::Sym('(' | '[' | '{') => depth += 1,
+            Token::Sym(')' | ']' | '}') => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    return i + 1;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    tokens.len()
+}
+
+/// Last `::` segment of a qualified name.
+fn last_segment(name: &str) -> &str { name.rsplit("::").next().unwrap_or(name) }
+
+// ---------------------------------------------------------------------------------------------
+// Expressions
+// ---------------------------------------------------------------------------------------------
+
+/// The subset of C++ expressions used to build RPC documentation.
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Str(String),
+    Num(String),
+    Bool(bool),
+    /// Identifier or qualified name (`RPCArg::Type::STR`, `CURRENCY_UNIT`)
+    Path(String),
+    /// `f(args)`
+    Call(String, Vec<Expr>),
+    /// `T{items}` or `{items}`
+    Init(Option<String>, Vec<Expr>),
+    /// `.field = value` inside an initializer
+    Designated(String, Box<Expr>),
+    /// `a + b`
+    Concat(Box<Expr>, Box<Expr>),
+    /// `c ? a : b`
+    Cond(Box<Expr>, Box<Expr>, Box<Expr>),
+    /// `!e`
+    Not(Box<Expr>),
+    /// `e[i]`
+    Index(Box<Expr>, Box<Expr>),
+    /// Anything else (lambdas, arithmetic, member calls)
+    Opaque,
+}
+
+struct Parser<'t> {
+    tokens: &'t [Token],
+    pos: usize,
+}
+
+impl<'t> Parser<'t> {
+    fn new(tokens: &'t [Token]) -> Self { Self { tokens, pos: 0 } }
+
+    fn peek(&self) -> Option<&'t Token> { self.tokens.get(self.pos) }
+
+    fn peek_sym(&self, c: char) -> bool { self.peek().is_some_and(|t| t.is_sym(c)) }
+
+    fn eat_sym(&mut self, c: char) -> bool {
+        let found = self.peek_sym(c);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expr(&mut self) -> Expr {
+        let cond = self.binary();
+        if !self.eat_sym('?') {
+            return cond;
+        }
+        let then = self.expr();
+        self.eat_sym(':');
+        let otherwise = self.expr();
+        Expr::Cond(Box::new(cond), Box::new(then), Box::new(otherwise))
+    }
+
+    fn binary(&mut self) -> Expr {
+        let mut lhs = self.unary();
+        loop {
+            let Some(Token::Sym(op)) = self.peek() else { return lhs };
+            if *op == '+' {
+                self.pos += 1;
+                let rhs = self.unary();
+                lhs = Expr::Concat(Box::new(lhs), Box::new(rhs));
+            } else if "-*/%|&^<>=!".contains(*op) {
+                while self
+                    .peek()
+                    .is_some_and(|t| matches!(t, Token::Sym(c) if "-*/%|&^<>=!".contains(*c)))
+                {
+                    self.pos += 1;
+                }
+                self.unary();
+                lhs = Expr::Opaque;
+            } else {
+                return lhs;
+            }
+        }
+    }
+
+    fn unary(&mut self) -> Expr {
+        if self.eat_sym('!') {
+            return Expr::Not(Box::new(self.unary()));
+        }
+        if self.eat_sym('-') {
+            return match self.unary() {
+                Expr::Num(n) => Expr::Num(format!("-{}", n)),
+                _ => Expr::Opaque,
+            };
+        }
+        if self.eat_sym('&') || self.eat_sym('*') {
+            return self.unary();
+        }
+        let base = self.primary();
+        self.postfix(base)
+    }
+
+    fn postfix(&mut self, mut base: Expr) -> Expr {
+        loop {
+            if self.peek_sym('[') {
+                self.pos += 1;
+                let index = self.expr();
+                self.eat_sym(']');
+                base = Expr::Index(Box::new(base), Box::new(index));
+            } else if self.peek_sym('.')
+                || (self.peek_sym('-')
+                    && self.tokens.get(self.pos + 1).is_some_and(|t| t.is_sym('>')))
+            {
+                // Member access / call: the value is not modelled
+                self.pos += if self.peek_sym('.') { 1 } else { 2 };
+                if self.peek().and_then(Token::ident).is_some() {
+                    self.pos += 1;
+                }
+                if self.peek_sym('(') {
+                    self.pos = skip_group(self.tokens, self.pos);
+                }
+                base = Expr::Opaque;
+            } else {
+                return base;
+            }
+        }
+    }
+
+    fn primary(&mut self) -> Expr {
+        let Some(token) = self.peek() else { return Expr::Opaque };
+        match token {
+            Token::Str(_) => {
+                let mut text = String::new();
+                while let Some(Token::Str(s)) = self.peek() {
+                    text.push_str(s);
+                    self.pos += 1;
+                }
+                Expr::Str(text)
+            }
+            Token::Num(n) => {
+                self.pos += 1;
+                Expr::Num(n.clone())
+            }
+            Token::Ident(name) => {
+                self.pos += 1;
+                match name.as_str() {
+                    "true" => return Expr::Bool(true),
+                    "false" => return Expr::Bool(false),
+                    _ => {}
+                }
+                self.skip_template_args();
+                if self.eat_sym('(') {
+                    Expr::Call(name.clone(), self.list(')'))
+                } else if self.eat_sym('{') {
+                    Expr::Init(Some(name.clone()), self.list('}'))
+                } else {
+                    Expr::Path(name.clone())
+                }
+            }
+            Token::Sym('{') => {
+                self.pos += 1;
+                Expr::Init(None, self.list('}'))
+            }
+            Token::Sym('(') => {
+                self.pos += 1;
+                let inner = self.expr();
+                self.eat_sym(')');
+                inner
+            }
+            Token::Sym('[') => {
+                // Lambda: [captures](params) specifiers -> ret { body }
+                self.pos = skip_group(self.tokens, self.pos);
+                if self.peek_sym('(') {
+                    self.pos = skip_group(self.tokens, self.pos);
+                }
+                while self
+                    .peek()
+                    .is_some_and(|t| !t.is_sym('{') && !t.is_sym(',') && !t.is_sym('}'))
+                {
+                    self.pos += 1;
+                }
+                if self.peek_sym('{') {
+                    self.pos = skip_group(self.tokens, self.pos);
+                }
+                Expr::Opaque
+            }
+            Token::Sym('.') => {
+                self.pos += 1;
+                let Some(field) = self.peek().and_then(Token::ident) else { return Expr::Opaque };
+                self.pos += 1;
+                self.eat_sym('=');
+                Expr::Designated(field.to_string(), Box::new(self.expr()))
+            }
+            Token::Sym(_) => {
+                self.pos += 1;
+                Expr::Opaque
+            }
+        }
+    }
+
+    /// Comma-separated items up to and including `close`.
+    fn list(&mut self, close: char) -> Vec<Expr> {
+        let mut items = Vec::new();
+        while let Some(token) = self.peek() {
+            if token.is_sym(close) {
+                self.pos += 1;
+                break;
+            }
+            let start = self.pos;
+            let item = self.expr();
+            if self.pos == start {
+                // Stray token (e.g. an unmatched closer): drop it
+                self.pos += 1;
+                continue;
+            }
+            items.push(item);
+            self.eat_sym(',');
+        }
+        items
+    }
+
+    /// Skips `<...>` after an identifier when it closes like a template argument list.
+    fn skip_template_args(&mut self) {
+        if !self.peek_sym('<') {
+            return;
+        }
+        let mut depth = 0usize;
+        let mut i = self.pos;
+        while let Some(token) = self.tokens.get(i) {
+            match token {
+                Token::Sym('<') => depth += 1,
+                Token::Sym('>') => {
+                    depth -= 1;
+                    if depth == 0 {
+                        self.pos = i + 1;
+                        return;
+                    }
+                }
+                Token::Sym(';' | '{' | '}' | ')' | '?') => return,
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+}
+
+fn parse_expr(tokens: &[Token]) -> Expr { Parser::new(tokens).expr() }
+
+// ---------------------------------------------------------------------------------------------
+// Source index
+// ---------------------------------------------------------------------------------------------
+
+/// A top-level function: its parameters, simple local variables and final `return`.
+#[derive(Debug, Clone)]
+struct FunctionDef {
+    params: Vec<String>,
+    locals: Vec<(String, Expr)>,
+    returns: Option<Expr>,
+}
+
+/// Functions, variables and command tables of the scanned sources.
+#[derive(Debug, Default)]
+struct SourceIndex {
+    functions: HashMap<String, Vec<FunctionDef>>,
+    globals: HashMap<String, Expr>,
+    /// `(category, function)` pairs of the `CRPCCommand` tables, in source order
+    commands: Vec<(String, String)>,
+}
+
+impl SourceIndex {
+    fn function(&self, name: &str, arity: usize) -> Option<&FunctionDef> {
+        let overloads = self.functions.get(name)?;
+        overloads
+            .iter()
+            .find(|f| f.params.len() == arity)
+            .or_else(|| overloads.iter().find(|f| f.params.len() > arity))
+    }
+
+    fn scan(&mut self, tokens: &[Token]) {
+        self.scan_command_tables(tokens);
+
+        let mut i = 0;
+        let mut start = 0;
+        while i < tokens.len() {
+            match &tokens[i] {
+                Token::Ident(word) if word == "namespace" && i == start => {
+                    // Namespace blocks are transparent; their closing brace is skipped below
+                    while i < tokens.len() && !tokens[i].is_sym('{') && !tokens[i].is_sym(';') {
+                        i += 1;
+                    }
+                    i += 1;
+                    start = i;
+                }
+                Token::Sym('}') => {
+                    i += 1;
+                    start = i;
+                }
+                Token::Sym(';') => {
+                    self.declaration(&tokens[start..i]);
+                    i += 1;
+                    start = i;
+                }
+                Token::Sym('{') if i > start && is_function_body(&tokens[start..i]) => {
+                    let end = skip_group(tokens, i);
+                    self.function_definition(&tokens[start..i], &tokens[i + 1..end - 1]);
+                    i = end;
+                    start = i;
+                }
+                Token::Sym('{' | '(' | '[') => i = skip_group(tokens, i),
+                _ => i += 1,
+            }
+        }
+    }
+
+    /// `{"category", &function}` entries (and pre-0.21 `{"category", "name", &function, ...}`).
+    fn scan_command_tables(&mut self, tokens: &[Token]) {
+        for (i, window) in tokens.windows(4).enumerate() {
+            let [Token::Sym('{'), Token::Str(category), Token::Sym(','), next] = window else {
+                continue;
+            };
+            let mut j = i + 3;
+            if matches!(next, Token::Str(_)) && tokens.get(j + 1).is_some_and(|t| t.is_sym(',')) {
+                j += 2;
+            }
+            if let (Some(Token::Sym('&')), Some(Token::Ident(function))) =
+                (tokens.get(j), tokens.get(j + 1))
+            {
+                self.commands.push((category.clone(), last_segment(function).to_string()));
+            }
+        }
+    }
+
+    fn function_definition(&mut self, header: &[Token], body: &[Token]) {
+        let Some(open) = header.iter().position(|t| t.is_sym('(')) else { return };
+        let Some(Token::Ident(name)) = open.checked_sub(1).and_then(|p| header.get(p)) else {
+            return;
+        };
+        // Member functions and constructors (e.g. `RPCHelpMan::RPCHelpMan`) are not helpers
+        if name.contains("::") {
+            return;
+        }
+        let close = skip_group(header, open);
+        let params = split_top_level(&header[open + 1..close.saturating_sub(1)], ',')
+            .into_iter()
+            .filter_map(param_name)
+            .collect();
+        let (locals, returns) = function_body(body);
+        self.functions.entry(name.clone()).or_default().push(FunctionDef {
+            params,
+            locals,
+            returns,
+        });
+    }
+
+    fn declaration(&mut self, tokens: &[Token]) {
+        if let Some((name, value)) = variable_declaration(tokens) {
+            self.globals.insert(name, value);
+        }
+    }
+}
+
+/// Whether the tokens before a `{` are a function header (`... name(params) [const] {`).
+fn is_function_body(header: &[Token]) -> bool {
+    let mut end = header.len();
+    while end > 0
+        && matches!(&header[end - 1], Token::Ident(w) if matches!(w.as_str(), "const" | "override" | "noexcept" | "final"))
+    {
+        end -= 1;
+    }
+    // `x = f(...) {` is not a definition; `=` inside the parameter list is a default argument
+    end > 0 && header[end - 1].is_sym(')') && split_top_level(header, '=').len() == 1
+}
+
+/// Splits at `sep` outside any bracket or template group.
+fn split_top_level(tokens: &[Token], sep: char) -> Vec<&[Token]> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Sym('(' | '[' | '{' | '<') => depth += 1,
+            Token::Sym(')' | ']' | '}' | '>') => depth -= 1,
+            Token::Sym(c) if *c == sep && depth == 0 => {
+                parts.push(&tokens[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if start < tokens.len() {
+        parts.push(&tokens[start..]);
+    }
+    parts
+}
+
+/// Name of a parameter declaration (`const std::string& name = ""` -> `name`).
+fn param_name(tokens: &[Token]) -> Option<String> {
+    let end = tokens.iter().position(|t| t.is_sym('=')).unwrap_or(tokens.len());
+    let declarator = &tokens[..end];
+    if declarator.len() < 2 {
+        return None;
+    }
+    declarator.iter().rev().find_map(Token::ident).map(str::to_string)
+}
+
+/// `type name = value` or `type name{value}` (with optional `[N]`), as `(name, value)`.
+fn variable_declaration(tokens: &[Token]) -> Option<(String, Expr)> {
+    if tokens.len() < 2 {
+        return None;
+    }
+    if let Some(Token::Ident(first)) = tokens.first() {
+        if matches!(
+            first.as_str(),
+            "class"
+                | "struct"
+                | "enum"
+                | "union"
+                | "using"
+                | "typedef"
+                | "template"
+                | "friend"
+                | "return"
+                | "if"
+                | "for"
+                | "while"
+                | "switch"
+                | "else"
+                | "do"
+                | "case"
+                | "throw"
+        ) {
+            return None;
+        }
+    }
+
+    let mut depth = 0i32;
+    let mut eq = None;
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Sym('(' | '[' | '{') => depth += 1,
+            Token::Sym(')' | ']' | '}') => depth -= 1,
+            Token::Sym('=') if depth == 0 => {
+                let compound = tokens.get(i + 1).is_some_and(|t| t.is_sym('='))
+                    || i.checked_sub(1)
+                        .and_then(|p| tokens.get(p))
+                        .is_some_and(|t| matches!(t, Token::Sym(c) if "=!<>+-*/|&".contains(*c)));
+                if !compound {
+                    eq = Some(i);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let (name_end, value) = match eq {
+        Some(i) => (i, parse_expr(&tokens[i + 1..])),
+        None => {
+            // Brace initializer: the declaration ends with `name{...}`
+            if !tokens.last()?.is_sym('}') {
+                return None;
+            }
+            let open = (0..tokens.len())
+                .find(|&i| tokens[i].is_sym('{') && skip_group(tokens, i) == tokens.len())?;
+            (open, parse_expr(&tokens[open..]))
+        }
+    };
+
+    let mut name_at = name_end.checked_sub(1)?;
+    if tokens[name_at].is_sym(']') {
+        // `NAME[2]`: step back over the array bound
+        name_at = (0..name_at).rev().find(|&i| tokens[i].is_sym('['))?.checked_sub(1)?;
+    }
+    let name = tokens[name_at].ident()?;
+    if name_at == 0 {
+        return None;
+    }
+    let type_token = &tokens[name_at - 1];
+    if !(type_token.ident().is_some()
+        || type_token.is_sym('>')
+        || type_token.is_sym('&')
+        || type_token.is_sym('*'))
+    {
+        return None;
+    }
+    Some((last_segment(name).to_string(), value))
+}
+
+/// Local variables and the last top-level `return` of a function body.
+fn function_body(body: &[Token]) -> (Vec<(String, Expr)>, Option<Expr>) {
+    let mut locals = Vec::new();
+    let mut returns = None;
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (i, token) in body.iter().enumerate() {
+        match token {
+            Token::Sym('(' | '[' | '{') => depth += 1,
+            Token::Sym(')' | ']' | '}') => {
+                depth -= 1;
+                // A statement block (`if (...) {...}`) ends a statement without a `;`
+                if depth == 0
+                    && token.is_sym('}')
+                    && !body.get(i + 1).is_some_and(|t| t.is_sym(';'))
+                {
+                    let open =
+                        (start..i).find(|&o| skip_group(body, o) == i + 1 && body[o].is_sym('{'));
+                    let is_block = open.is_some_and(|o| {
+                        o == start
+                            || body[o - 1].is_sym(')')
+                            || matches!(body[o - 1].ident(), Some("else" | "do" | "try"))
+                    });
+                    if is_block {
+                        start = i + 1;
+                    }
+                }
+            }
+            Token::Sym(';') if depth == 0 => {
+                let statement = &body[start..i];
+                if let Some(Token::Ident(word)) = statement.first() {
+                    if word == "return" {
+                        returns = Some(parse_expr(&statement[1..]));
+                    }
+                }
+                if let Some(local) = variable_declaration(statement) {
+                    locals.push(local);
+                }
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    (locals, returns)
+}
+
+// ---------------------------------------------------------------------------------------------
+// Evaluation
+// ---------------------------------------------------------------------------------------------
+
+struct Interpreter<'a> {
+    index: &'a SourceIndex,
+    warnings: Vec<String>,
+}
+
+type Env = HashMap<String, Expr>;
+
+impl Interpreter<'_> {
+    /// Evaluates a function's `returns` with `args` bound to its parameters.
+    fn call(&mut self, def: &FunctionDef, args: Vec<Expr>, returns: &Expr, depth: usize) -> Expr {
+        let mut env: Env = def.params.iter().cloned().zip(args).collect();
+        for (name, value) in &def.locals {
+            let value = self.resolve(value, &env, depth + 1);
+            env.insert(name.clone(), value);
+        }
+        self.resolve(returns, &env, depth + 1)
+    }
+
+    /// Resolves identifiers, helper calls and string operations.
+    fn resolve(&mut self, expr: &Expr, env: &Env, depth: usize) -> Expr {
+        if depth > MAX_RESOLVE_DEPTH {
+            return Expr::Opaque;
+        }
+        match expr {
+            Expr::Path(name) => {
+                if let Some(value) = env.get(name) {
+                    return value.clone();
+                }
+                let short = last_segment(name);
+                if let Some((_, value)) = BUILTIN_CONSTANTS.iter().find(|(n, _)| *n == short) {
+                    return Expr::Str(value.to_string());
+                }
+                match self.index.globals.get(short) {
+                    Some(value) => self.resolve(value, &Env::new(), depth + 1),
+                    None => expr.clone(),
+                }
+            }
+            Expr::Call(name, args) => {
+                let args: Vec<Expr> =
+                    args.iter().map(|a| self.resolve(a, env, depth + 1)).collect();
+                let short = last_segment(name);
+                if DOC_CONSTRUCTORS.contains(&short) {
+                    return Expr::Init(Some(name.clone()), args);
+                }
+                if let Some(value) = builtin_call(short, &args) {
+                    return value;
+                }
+                let index = self.index;
+                match index.function(short, args.len()) {
+                    Some(def) => match &def.returns {
+                        Some(returns) => self.call(def, args, returns, depth + 1),
+                        None => Expr::Opaque,
+                    },
+                    None => Expr::Call(name.clone(), args),
+                }
+            }
+            Expr::Init(name, items) => {
+                let items: Vec<Expr> =
+                    items.iter().map(|i| self.resolve(i, env, depth + 1)).collect();
+                match (name.as_deref(), items.as_slice()) {
+                    (Some("std::string"), [single]) => Expr::Str(text_lossy(single)),
+                    _ => Expr::Init(name.clone(), items),
+                }
+            }
+            Expr::Concat(lhs, rhs) => {
+                let lhs = self.resolve(lhs, env, depth + 1);
+                let rhs = self.resolve(rhs, env, depth + 1);
+                if is_list(&lhs) || is_list(&rhs) {
+                    return Expr::Opaque;
+                }
+                Expr::Str(text_lossy(&lhs) + &text_lossy(&rhs))
+            }
+            Expr::Cond(cond, then, otherwise) => match self.resolve(cond, env, depth + 1) {
+                Expr::Bool(true) => self.resolve(then, env, depth + 1),
+                Expr::Bool(false) => self.resolve(otherwise, env, depth + 1),
+                _ => Expr::Opaque,
+            },
+            Expr::Not(inner) => match self.resolve(inner, env, depth + 1) {
+                Expr::Bool(b) => Expr::Bool(!b),
+                _ => Expr::Opaque,
+            },
+            Expr::Index(base, index) => {
+                let base = self.resolve(base, env, depth + 1);
+                let index = self.resolve(index, env, depth + 1);
+                match (base, index) {
+                    (Expr::Init(_, items), Expr::Num(n)) => n
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|n| items.get(n).cloned())
+                        .unwrap_or(Expr::Opaque),
+                    _ => Expr::Opaque,
+                }
+            }
+            Expr::Designated(field, value) =>
+                Expr::Designated(field.clone(), Box::new(self.resolve(value, env, depth + 1))),
+            Expr::Str(_) | Expr::Num(_) | Expr::Bool(_) | Expr::Opaque => expr.clone(),
+        }
+    }
+
+    /// Converts a resolved `RPCHelpMan{...}` into an OpenRPC method.
+    fn method(&mut self, helpman: &Expr, category: &str) -> Option<OpenRpcMethod> {
+        let items = constructor_items(helpman, "RPCHelpMan")?;
+        let positional: Vec<&Expr> = positional(items);
+        let name = text(positional.first()?)?;
+        let description = positional.get(1).map(|e| text_lossy(e)).unwrap_or_default();
+
+        let mut arguments = Vec::new();
+        if let Some(args) = positional.get(2) {
+            for arg in list_items(args).unwrap_or_default() {
+                self.argument(&name, arg, &mut arguments);
+            }
+        }
+
+        let mut results = Vec::new();
+        if let Some(result) = positional.get(3) {
+            self.results(&name, result, &mut results);
+        }
+
+        let examples = positional
+            .get(4)
+            .and_then(|e| constructor_items(e, "RPCExamples"))
+            .and_then(|items| items.first())
+            .map(text_lossy)
+            .filter(|e| !e.is_empty());
+
+        Some(OpenRpcMethod {
+            x_bitcoin_argument_names: arguments
+                .iter()
+                .map(|a: &RawArgument| a.names.join("|"))
+                .collect(),
+            name,
+            description: description.trim().to_string(),
+            params: Vec::new(),
+            result: Some(OpenRpcResult {
+                name: Some("result".to_string()),
+                schema: None,
+                x_bitcoin_results: results,
+            }),
+            x_bitcoin_category: category.to_string(),
+            x_bitcoin_examples: examples,
+            x_bitcoin_arguments: arguments,
+        })
+    }
+
+    /// Appends the `RPCArg` (or list of them) in `expr` to `out`.
+    fn argument(&mut self, method: &str, expr: &Expr, out: &mut Vec<RawArgument>) {
+        let Some(items) = constructor_items(expr, "RPCArg").filter(|items| is_arg(items)) else {
+            match list_items(expr) {
+                Some(list) => list.iter().for_each(|e| self.argument(method, e, out)),
+                None => self.warnings.push(format!(
+                    "{}: unresolved argument {}",
+                    method,
+                    describe(expr)
+                )),
+            }
+            return;
+        };
+        let positional = positional(items);
+        let names = text(positional[0]).unwrap_or_default();
+        let Some(r#type) = positional.get(1).and_then(|t| path_of(t)).and_then(arg_type) else {
+            self.warnings.push(format!("{}: argument '{}' has an unknown type", method, names));
+            return;
+        };
+
+        let mut arg = RawArgument {
+            names: names.split('|').map(str::to_string).collect(),
+            description: positional.get(3).map(|e| text_lossy(e)).unwrap_or_default(),
+            oneline_description: String::new(),
+            also_positional: false,
+            type_str: Vec::new(),
+            required: false,
+            default: None,
+            default_hint: None,
+            hidden: false,
+            r#type: r#type.to_string(),
+            inner: Vec::new(),
+        };
+        if let Some(fallback) = positional.get(2) {
+            apply_fallback(&mut arg, fallback);
+        }
+        for extra in positional.iter().skip(4) {
+            if let Some(options) = constructor_items(extra, "RPCArgOptions") {
+                apply_arg_options(&mut arg, options);
+            } else if let Expr::Str(oneline) = extra {
+                arg.oneline_description = oneline.clone();
+            } else if let Some(list) = list_items(extra) {
+                if !list.is_empty() && list.iter().all(|e| matches!(e, Expr::Str(_))) {
+                    arg.type_str = list.iter().map(text_lossy).collect();
+                } else {
+                    let mut inner = Vec::new();
+                    list.iter().for_each(|e| self.argument(method, e, &mut inner));
+                    arg.inner = inner;
+                }
+            }
+        }
+        out.push(arg);
+    }
+
+    /// Appends the `RPCResult`s in `expr` (a result, `RPCResults{...}` or a list) to `out`.
+    fn results(&mut self, method: &str, expr: &Expr, out: &mut Vec<RawResult>) {
+        if let Some(items) = constructor_items(expr, "RPCResult").filter(|items| is_result(items)) {
+            let positional = positional(items);
+            let mut k = 0;
+            let condition = match (positional.first(), positional.get(1).and_then(|e| path_of(e))) {
+                (Some(Expr::Str(condition)), Some(_)) => {
+                    k = 1;
+                    condition.clone()
+                }
+                _ => String::new(),
+            };
+            let type_name = positional.get(k).and_then(|e| path_of(e)).unwrap_or_default();
+            let r#type = result_type(type_name).unwrap_or_else(|| {
+                self.warnings.push(format!("{}: unknown result type {}", method, type_name));
+                "any"
+            });
+            let key_name = positional.get(k + 1).map(|e| text_lossy(e)).unwrap_or_default();
+            let mut rest = positional.iter().skip(k + 2).peekable();
+            let optional = match rest.peek() {
+                Some(Expr::Bool(optional)) => {
+                    rest.next();
+                    *optional
+                }
+                _ => false,
+            };
+            let description = rest.next().map(|e| text_lossy(e)).unwrap_or_default();
+            let mut inner = Vec::new();
+            let mut skip_type_check = false;
+            for extra in rest {
+                match extra {
+                    Expr::Bool(skip) => skip_type_check = *skip,
+                    other if list_items(other).is_some() => self.results(method, other, &mut inner),
+                    _ => {}
+                }
+            }
+            out.push(RawResult {
+                r#type: r#type.to_string(),
+                optional,
+                description,
+                skip_type_check,
+                key_name,
+                condition,
+                inner,
+            });
+            return;
+        }
+
+        match list_items(expr) {
+            Some(list) => list.iter().for_each(|e| self.results(method, e, out)),
+            None => {
+                let what = describe(expr);
+                self.warnings.push(format!("{}: unresolved result {}", method, what));
+                out.push(RawResult {
+                    r#type: "elision".to_string(),
+                    optional: false,
+                    description: format!("Unresolved {}", what),
+                    skip_type_check: false,
+                    key_name: String::new(),
+                    condition: String::new(),
+                    inner: Vec::new(),
+                });
+            }
+        }
+    }
+}
+
+/// Helpers evaluated natively: list concatenation, formatting and example rendering.
+fn builtin_call(name: &str, args: &[Expr]) -> Option<Expr> {
+    let example_args = || args.get(1).map(text_lossy).unwrap_or_default();
+    let method = || args.first().map(text_lossy).unwrap_or_default();
+    match name {
+        "Cat" => {
+            let mut items = Vec::new();
+            for arg in args {
+                match list_items(arg) {
+                    Some(list) => items.extend(list.iter().cloned()),
+                    None => items.push(arg.clone()),
+                }
+            }
+            Some(Expr::Init(None, items))
+        }
+        "strprintf" | "format" => Some(Expr::Str(printf(args))),
+        "ToString" | "to_string" | "string" =>
+            Some(Expr::Str(args.first().map(text_lossy).unwrap_or_default())),
+        "HelpExampleCli" =>
+            Some(Expr::Str(format!("> bitcoin-cli {} {}\n", method(), example_args()))),
+        "HelpExampleRpc" => Some(Expr::Str(format!(
+            "> curl --user myusername --data-binary '{{\"jsonrpc\": \"2.0\", \"id\": \"curltest\", \
+             \"method\": \"{}\", \"params\": [{}]}}' -H 'content-type: application/json' \
+             http://127.0.0.1:8332/\n",
+            method(),
+            example_args()
+        ))),
+        "HelpExampleCliNamed" => {
+            let named: Vec<String> = named_example_args(args.get(1))
+                .into_iter()
+                .map(|(key, value)| match value {
+                    Value::String(s) => format!("{}={}", key, s),
+                    other => format!("{}={}", key, other),
+                })
+                .collect();
+            Some(Expr::Str(format!("> bitcoin-cli -named {} {}\n", method(), named.join(" "))))
+        }
+        "HelpExampleRpcNamed" => {
+            let params: serde_json::Map<String, Value> =
+                named_example_args(args.get(1)).into_iter().collect();
+            Some(Expr::Str(format!(
+                "> curl --user myusername --data-binary '{{\"jsonrpc\": \"2.0\", \"id\": \"curltest\", \
+                 \"method\": \"{}\", \"params\": {}}}' -H 'content-type: application/json' \
+                 http://127.0.0.1:8332/\n",
+                method(),
+                Value::Object(params)
+            )))
+        }
+        _ => None,
+    }
+}
+
+/// `{{"key", value}, ...}` arguments of the `HelpExample*Named` helpers.
+fn named_example_args(list: Option<&Expr>) -> Vec<(String, Value)> {
+    list.and_then(list_items)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|pair| match list_items(pair)? {
+            [key, value] => Some((text_lossy(key), json_value(value).unwrap_or(Value::Null))),
+            _ => None,
+        })
+        .collect()
+}
+
+/// `strprintf` with every conversion rendered as text.
+fn printf(args: &[Expr]) -> String {
+    let format = args.first().map(text_lossy).unwrap_or_default();
+    let mut values = args.iter().skip(1).map(text_lossy);
+    let mut out = String::new();
+    let mut chars = format.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+        if chars.peek() == Some(&'%') {
+            chars.next();
+            out.push('%');
+            continue;
+        }
+        // Flags, width, precision and length modifiers up to the conversion letter
+        while let Some(&spec) = chars.peek() {
+            chars.next();
+            if spec.is_ascii_alphabetic() && !matches!(spec, 'l' | 'h' | 'z' | 'j' | 't' | 'L') {
+                break;
+            }
+        }
+        out.push_str(&values.next().unwrap_or_default());
+    }
+    out
+}
+
+/// Text of a resolved string-like expression.
+fn text(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Str(s) | Expr::Num(s) => Some(s.clone()),
+        Expr::Bool(b) => Some(b.to_string()),
+        Expr::Init(_, items) if items.len() == 1 => text(&items[0]),
+        _ => None,
+    }
+}
+
+/// Text of an expression, rendering unresolved names as themselves and anything else as "".
+fn text_lossy(expr: &Expr) -> String {
+    match expr {
+        Expr::Path(name) => last_segment(name).to_string(),
+        other => text(other).unwrap_or_default(),
+    }
+}
+
+/// JSON value of an `RPCArg::Default{...}` or named example argument.
+fn json_value(expr: &Expr) -> Option<Value> {
+    match expr {
+        Expr::Str(s) => Some(Value::String(s.clone())),
+        Expr::Bool(b) => Some(Value::Bool(*b)),
+        Expr::Num(n) => serde_json::from_str(n.trim_end_matches(['u', 'U', 'l', 'L'])).ok(),
+        Expr::Path(name) => match last_segment(name) {
+            "VARR" => Some(Value::Array(Vec::new())),
+            "VOBJ" => Some(Value::Object(serde_json::Map::new())),
+            "VNULL" => Some(Value::Null),
+            _ => None,
+        },
+        Expr::Init(Some(name), items) | Expr::Call(name, items)
+            if last_segment(name) == "UniValue" && items.len() == 1 =>
+            json_value(&items[0]),
+        _ => None,
+    }
+}
+
+fn is_list(expr: &Expr) -> bool { list_items(expr).is_some() }
+
+/// Items of a braced list (`{...}`, `std::vector<T>{...}` or `RPCResults{...}`).
+fn list_items(expr: &Expr) -> Option<&[Expr]> {
+    match expr {
+        Expr::Init(None, items) => Some(items),
+        Expr::Init(Some(name), items)
+            if name.contains("vector") || last_segment(name) == "RPCResults" =>
+            Some(items),
+        _ => None,
+    }
+}
+
+/// Items of `Name{...}` / `Name(...)`, or of an untyped `{...}`.
+fn constructor_items<'e>(expr: &'e Expr, name: &str) -> Option<&'e [Expr]> {
+    match expr {
+        Expr::Init(None, items) => Some(items),
+        Expr::Init(Some(n), items) | Expr::Call(n, items) if last_segment(n) == name => Some(items),
+        _ => None,
+    }
+}
+
+fn positional(items: &[Expr]) -> Vec<&Expr> {
+    items.iter().filter(|e| !matches!(e, Expr::Designated(..))).collect()
+}
+
+fn path_of(expr: &Expr) -> Option<&str> {
+    match expr {
+        Expr::Path(name) => Some(name),
+        _ => None,
+    }
+}
+
+/// Whether initializer items look like an `RPCArg` (`{"name", RPCArg::Type::X, ...}`).
+fn is_arg(items: &[Expr]) -> bool {
+    matches!(items, [Expr::Str(_), Expr::Path(t), ..] if arg_type(t).is_some())
+}
+
+/// Whether initializer items look like an `RPCResult` (optionally led by a condition).
+fn is_result(items: &[Expr]) -> bool {
+    match items {
+        [Expr::Path(t), ..] => result_type(t).is_some(),
+        [Expr::Str(_), Expr::Path(t), ..] => result_type(t).is_some(),
+        _ => false,
+    }
+}
+
+/// `RPCArg::Type` -> OpenRPC `x-bitcoin-arguments` type.
+fn arg_type(path: &str) -> Option<&'static str> {
+    if !path.contains("Type::") {
+        return None;
+    }
+    Some(match last_segment(path) {
+        "STR" => "string",
+        "STR_HEX" => "hex",
+        "NUM" => "number",
+        "AMOUNT" => "amount",
+        "BOOL" => "boolean",
+        "OBJ" | "OBJ_NAMED_PARAMS" | "OBJ_USER_KEYS" => "object",
+        "ARR" => "array",
+        "RANGE" => "range",
+        _ => return None,
+    })
+}
+
+/// `RPCResult::Type` -> OpenRPC `x-bitcoin-results` type.
+fn result_type(path: &str) -> Option<&'static str> {
+    if !path.contains("Type::") {
+        return None;
+    }
+    Some(match last_segment(path) {
+        "OBJ" | "OBJ_DYN" => "object",
+        "ARR" | "ARR_FIXED" => "array",
+        "STR" => "string",
+        "STR_HEX" => "hex",
+        "STR_AMOUNT" => "amount",
+        "NUM" => "number",
+        "NUM_TIME" => "timestamp",
+        "BOOL" => "boolean",
+        "NONE" => "none",
+        "ANY" => "any",
+        "ELISION" => "elision",
+        _ => return None,
+    })
+}
+
+/// Applies an `RPCArg` fallback: `Optional::NO`/`OMITTED`, `Default{v}` or `DefaultHint{"..."}`.
+fn apply_fallback(arg: &mut RawArgument, fallback: &Expr) {
+    match fallback {
+        Expr::Path(path) => arg.required = last_segment(path) == "NO",
+        Expr::Init(Some(name), items) | Expr::Call(name, items) => {
+            let value = items.first();
+            match last_segment(name) {
+                "DefaultHint" => arg.default_hint = value.map(text_lossy),
+                "Default" => match value.and_then(json_value) {
+                    Some(default) => arg.default = Some(default),
+                    None => arg.default_hint = value.map(text_lossy).filter(|h| !h.is_empty()),
+                },
+                _ => {}
+            }
+        }
+        // Pre-0.21 definitions: `/* opt */ bool` or a default string
+        Expr::Bool(optional) => arg.required = !optional,
+        Expr::Str(hint) if !hint.is_empty() => arg.default_hint = Some(hint.clone()),
+        _ => {}
+    }
+}
+
+/// Applies `RPCArgOptions{.hidden = true, .oneline_description = "...", ...}`.
+fn apply_arg_options(arg: &mut RawArgument, options: &[Expr]) {
+    for option in options {
+        let Expr::Designated(field, value) = option else { continue };
+        match (field.as_str(), value.as_ref()) {
+            ("hidden", Expr::Bool(b)) => arg.hidden = *b,
+            ("also_positional", Expr::Bool(b)) => arg.also_positional = *b,
+            ("oneline_description", value) => arg.oneline_description = text_lossy(value),
+            ("type_str", value) =>
+                arg.type_str =
+                    list_items(value).unwrap_or_default().iter().map(text_lossy).collect(),
+            _ => {}
+        }
+    }
+}
+
+/// Short rendering of an unresolved expression for warnings.
+fn describe(expr: &Expr) -> String {
+    match expr {
+        Expr::Call(name, _) => format!("{}(...)", name),
+        Expr::Path(name) => name.clone(),
+        _ => "expression".to_string(),
+    }
+}
+
+/// Entry point for the `process_bitcoin_rpchelpman` binary.
+///
+/// Usage: process_bitcoin_rpchelpman <bitcoin_src_dir> [output_file]
+///
+/// Writing to the canonical IR path merges the methods like an OpenRPC update does.
+pub fn run(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    if args.len() < 2 || args.iter().skip(1).any(|a| a == "-h" || a == "--help") {
+        eprintln!("Usage:");
+        eprintln!(
+            "  {} <bitcoin_src_dir> [output_file]   # Extract IR from RPCHelpMan sources",
+            args[0]
+        );
+        eprintln!();
+        eprintln!("Examples:");
+        eprintln!("  {} ../bitcoin", args[0]);
+        eprintln!("  {} ../bitcoin resources/ir/bitcoin.ir.json", args[0]);
+        std::process::exit(1);
+    }
+
+    let source = read_source_tree(Path::new(&args[1]))?;
+    for warning in &source.warnings {
+        eprintln!("warning: {}", warning);
+    }
+
+    let project_root = find_project_root()?;
+    let output_file = match args.get(2) {
+        Some(output) => PathBuf::from(output),
+        None => get_ir_dir()?.join(version_ir_filename(&source.version, "bitcoin")),
+    };
+    let output_resolved = resolve_ir_output_path(&project_root, &output_file);
+    let method_count = source.doc.methods.len();
+
+    if output_resolved == canonical_bitcoin_ir_path(&project_root) {
+        update_canonical_ir_from_openrpc(&output_resolved, source.doc, &source.version)?;
+    } else {
+        let protocol_ir =
+            convert_to_protocol_ir_with_version(source.doc, Some(source.version.clone()));
+        protocol_ir.to_file(&output_resolved)?;
+    }
+
+    println!("✓ Extracted RPCHelpMan definitions to IR: {}", output_resolved.display());
+    println!("  Version: {}", source.version);
+    println!("  Methods: {} ({} warnings)", method_count, source.warnings.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitcoin_core::openrpc::convert_to_protocol_ir_with_version_map;
+
+    const BLOCKCHAIN_CPP: &str = r#"
+// Copyright (c) The Bitcoin Core developers
+#include <rpc/util.h>
+
+namespace {
+const std::string EXAMPLE_BLOCKHASH = "00000000c937983704a73af28acdec37b049d214adbda81d7e2a3dd146f6ed09";
+} // namespace
+
+static std::vector<RPCResult> BlockHeaderResults(bool include_hash = true)
+{
+    return {
+        {RPCResult::Type::STR_HEX, "hash", "the block hash (same as provided)"},
+        {RPCResult::Type::NUM, "confirmations", "The number of confirmations, or -1 if the block is not on the main chain"},
+        {RPCResult::Type::NUM_TIME, "time", "The block time expressed in " + UNIX_EPOCH_TIME},
+    };
+}
+
+static RPCHelpMan getblock()
+{
+    return RPCHelpMan{
+        "getblock",
+        "If verbosity is 0, returns a string that is serialized, hex-encoded data for block 'hash'.\n",
+        {
+            {"blockhash", RPCArg::Type::STR_HEX, RPCArg::Optional::NO, "The block hash"},
+            {"verbosity|verbose", RPCArg::Type::NUM, RPCArg::Default{1}, "0 for hex-encoded data, 1 for a JSON object",
+             RPCArgOptions{.skip_type_check = true}},
+            {"fee_rate", RPCArg::Type::AMOUNT, RPCArg::DefaultHint{"not set, falls back to wallet fee estimation"},
+             strprintf("Specify a fee rate in %s/vB.", CURRENCY_ATOM)},
+        },
+        {
+            RPCResult{"for verbosity = 0",
+                RPCResult::Type::STR_HEX, "", "A string that is serialized, hex-encoded data for block 'hash'"},
+            RPCResult{"for verbosity = 1",
+                RPCResult::Type::OBJ, "", "",
+                Cat<std::vector<RPCResult>>(BlockHeaderResults(), {
+                    {RPCResult::Type::OBJ_DYN, "fees", /*optional=*/true, "fees by address",
+                    {
+                        {RPCResult::Type::STR_AMOUNT, "address", strprintf("fee in %s", CURRENCY_UNIT)},
+                    }},
+                    {RPCResult::Type::ARR, "tx", "The transaction ids",
+                        {{RPCResult::Type::STR_HEX, "", "The transaction id"}}},
+                    {RPCResult::Type::ELISION, "", "Same output as verbosity = 1"},
+                    UnknownHelper(),
+                })},
+        },
+        RPCExamples{
+            HelpExampleCli("getblock", "\"" + EXAMPLE_BLOCKHASH + "\"")
+    + HelpExampleRpc("getblock", "\"" + EXAMPLE_BLOCKHASH + "\"")
+        },
+        [&](const RPCHelpMan& self, const JSONRPCRequest& request) -> UniValue
+{
+    return NullUniValue;
+},
+    };
+}
+
+void RegisterBlockchainRPCCommands(CRPCTable& t)
+{
+    static const CRPCCommand commands[]{
+        {"blockchain", &getblock},
+    };
+    for (const auto& c : commands) {
+        t.appendCommand(c.name, &c);
+    }
+}
+"#;
+
+    const UTIL_H: &str = r#"
+extern const std::string UNIX_EPOCH_TIME;
+const std::string UNIX_EPOCH_TIME = "UNIX epoch time";
+"#;
+
+    fn parse() -> SourceRpcs {
+        parse_sources(&[UTIL_H.to_string(), BLOCKCHAIN_CPP.to_string()], "30.99.0")
+    }
+
+    #[test]
+    fn rpchelpman_arguments_and_results_become_openrpc_extensions() {
+        let source = parse();
+        assert_eq!(source.doc.methods.len(), 1);
+        let method = &source.doc.methods[0];
+        assert_eq!(method.name, "getblock");
+        assert_eq!(method.x_bitcoin_category, "blockchain");
+        assert_eq!(method.x_bitcoin_argument_names, ["blockhash", "verbosity|verbose", "fee_rate"]);
+
+        let args = &method.x_bitcoin_arguments;
+        assert!(args[0].required);
+        assert_eq!(args[0].r#type, "hex");
+        assert_eq!(args[1].names, ["verbosity", "verbose"]);
+        assert_eq!(args[1].default, Some(serde_json::json!(1)));
+        assert!(!args[1].required);
+        assert_eq!(
+            args[2].default_hint.as_deref(),
+            Some("not set, falls back to wallet fee estimation")
+        );
+        assert_eq!(args[2].description, "Specify a fee rate in sat/vB.");
+
+        let results = &method.result.as_ref().expect("result").x_bitcoin_results;
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].condition, "for verbosity = 0");
+        let verbose = &results[1].inner;
+        let keys: Vec<&str> = verbose.iter().map(|r| r.key_name.as_str()).collect();
+        assert_eq!(keys, ["hash", "confirmations", "time", "fees", "tx", "", ""]);
+        assert_eq!(verbose[2].r#type, "timestamp");
+        assert_eq!(verbose[2].description, "The block time expressed in UNIX epoch time");
+        assert_eq!(verbose[3].r#type, "object");
+        assert!(verbose[3].optional);
+        assert_eq!(verbose[3].inner[0].description, "fee in BTC");
+        assert_eq!(verbose[5].r#type, "elision");
+        assert_eq!(verbose[6].r#type, "elision");
+        assert_eq!(source.warnings, ["getblock: unresolved result UnknownHelper(...)"]);
+
+        let examples = method.x_bitcoin_examples.as_deref().expect("examples");
+        assert!(examples.starts_with(
+            "> bitcoin-cli getblock \"00000000c937983704a73af28acdec37b049d214adbda81d7e2a3dd146f6ed09\"\n"
+        ));
+        assert!(examples.contains("\"method\": \"getblock\""));
+    }
+
+    #[test]
+    fn source_rpcs_convert_to_protocol_ir() {
+        let source = parse();
+        let ir = convert_to_protocol_ir_with_version_map(
+            source.doc,
+            Some(source.version),
+            &HashMap::new(),
+        );
+        let rpcs = ir.get_rpc_methods();
+        assert_eq!(rpcs.len(), 1);
+        let params: Vec<(&str, bool)> =
+            rpcs[0].params.iter().map(|p| (p.name.as_str(), p.required)).collect();
+        assert_eq!(params, [("blockhash", true), ("verbosity", false), ("fee_rate", false)]);
+        assert_eq!(rpcs[0].category, "blockchain");
+    }
+
+    #[test]
+    fn source_version_reads_cmake_and_configure_ac() {
+        let dir = std::env::temp_dir().join(format!("ethos-rpchelpman-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("temp dir");
+
+        std::fs::write(
+            dir.join("configure.ac"),
+            "define(_CLIENT_VERSION_MAJOR, 27)\ndefine(_CLIENT_VERSION_MINOR, 99)\ndefine(_CLIENT_VERSION_BUILD, 0)\n",
+        )
+        .expect("write configure.ac");
+        assert_eq!(source_version(&dir).expect("version"), "27.99.0");
+
+        std::fs::write(
+            dir.join("CMakeLists.txt"),
+            "set(CLIENT_VERSION_MAJOR 30)\nset(CLIENT_VERSION_MINOR 99)\nset(CLIENT_VERSION_BUILD 0)\n",
+        )
+        .expect("write CMakeLists.txt");
+        assert_eq!(source_version(&dir).expect("version"), "30.99.0");
+
+        std::fs::remove_dir_all(&dir).expect("cleanup");
+    }
+}
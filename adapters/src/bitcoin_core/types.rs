@@ -795,6 +795,12 @@ const CATEGORY_RULES: &[CategoryRule] = &[
         field_name: Some("nblocks"),
         category: BitcoinCoreRpcType::SignedInteger,
     },
+    // Electrum balances: negative when mempool transactions spend confirmed coins
+    CategoryRule {
+        rpc_type: RpcJsonType::Number,
+        field_name: Some("unconfirmed"),
+        category: BitcoinCoreRpcType::SignedInteger,
+    },
     // Hex types
     CategoryRule {
         rpc_type: RpcJsonType::Hex,
@@ -0,0 +1,155 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Bitcoin Core ZMQ publishers described as notifications in a `zmq` ProtocolModule.
+//!
+//! Every ZMQ message is a three-part multipart message: the topic, the payload and a
+//! 4-byte little-endian per-topic sequence number (see `doc/zmq.md` in Bitcoin Core).
+//! The payload layout is given by the message fields, read in order; the field
+//! `protocol_type` names the wire encoding:
+//!
+//! - `hash256`, `blockhash`, `txid`: 32 bytes in display (reversed) byte order
+//! - `block`, `transaction`: consensus-encoded, consuming the rest of the payload
+//! - `u64le`: 8-byte little-endian integer
+//! - enums: one ASCII byte matching a variant value
+//!
+//! Optional fields are only present when bytes remain in the payload.
+
+use ir::{
+    FieldDef, FieldKey, MessageDef, MessageType, ProtocolDef, ProtocolModule, TypeDef, TypeKind,
+    VariantDef,
+};
+
+/// Name of the module holding the ZMQ notifications in the canonical IR.
+pub const ZMQ_MODULE_NAME: &str = "zmq";
+
+/// Builds the `zmq` module describing Bitcoin Core's ZMQ notifications.
+pub fn zmq_module() -> ProtocolModule {
+    let definitions = zmq_notifications().into_iter().map(ProtocolDef::Message).collect();
+    ProtocolModule::new(
+        ZMQ_MODULE_NAME.to_string(),
+        "Bitcoin Core ZMQ notifications".to_string(),
+        definitions,
+    )
+}
+
+/// All ZMQ notifications, sorted by name.
+pub fn zmq_notifications() -> Vec<MessageDef> {
+    let mut notifications = vec![
+        notification(
+            "hashblock",
+            "Hash of each block connected to the active chain.",
+            vec![field("blockhash", "blockhash", "The block hash", true)],
+            "0.17",
+        ),
+        notification(
+            "hashtx",
+            "Id of each transaction added to the mempool or confirmed in a connected block.",
+            vec![field("txid", "txid", "The transaction id", true)],
+            "0.17",
+        ),
+        notification(
+            "rawblock",
+            "Serialized block for each block connected to the active chain.",
+            vec![field("block", "block", "The consensus-encoded block", true)],
+            "0.17",
+        ),
+        notification(
+            "rawtx",
+            "Serialized transaction for each transaction added to the mempool or confirmed.",
+            vec![field("tx", "transaction", "The consensus-encoded transaction", true)],
+            "0.17",
+        ),
+        notification(
+            "sequence",
+            "Block connect/disconnect and mempool add/remove events with mempool sequence numbers.",
+            vec![
+                field("hash", "hash256", "Block hash for C/D, transaction id for A/R", true),
+                FieldDef {
+                    field_type: sequence_label(),
+                    ..field("label", "char", "The kind of event", true)
+                },
+                field(
+                    "mempool_sequence",
+                    "u64le",
+                    "Mempool sequence number; only present for A/R events",
+                    false,
+                ),
+            ],
+            "0.21",
+        ),
+    ];
+    notifications.sort_by(|a, b| a.name.cmp(&b.name));
+    notifications
+}
+
+fn notification(
+    topic: &str,
+    description: &str,
+    fields: Vec<FieldDef>,
+    version_added: &str,
+) -> MessageDef {
+    MessageDef {
+        name: topic.to_string(),
+        description: description.to_string(),
+        fields,
+        message_type: MessageType::Notification,
+        version: Some(version_added.to_string()),
+        topic: Some(topic.to_string()),
+        sequenced: true,
+    }
+}
+
+fn field(key: &str, protocol_type: &str, description: &str, required: bool) -> FieldDef {
+    FieldDef {
+        key: FieldKey::Named(key.to_string()),
+        field_type: TypeDef {
+            name: protocol_type.to_string(),
+            description: description.to_string(),
+            kind: TypeKind::Primitive,
+            protocol_type: Some(protocol_type.to_string()),
+            ..TypeDef::default()
+        },
+        required,
+        description: description.to_string(),
+        default_value: None,
+        version_added: None,
+        version_removed: None,
+        deprecated: None,
+    }
+}
+
+fn sequence_label() -> TypeDef {
+    let variant = |name: &str, value: &str, description: &str| VariantDef {
+        name: name.to_string(),
+        description: description.to_string(),
+        value: Some(value.to_string()),
+        associated_data: None,
+    };
+    TypeDef {
+        name: "SequenceLabel".to_string(),
+        description: "The kind of event".to_string(),
+        kind: TypeKind::Enum,
+        variants: Some(vec![
+            variant("BlockConnected", "C", "Block connected to the active chain"),
+            variant("BlockDisconnected", "D", "Block disconnected from the active chain"),
+            variant("TransactionAdded", "A", "Transaction added to the mempool"),
+            variant("TransactionRemoved", "R", "Transaction removed from the mempool"),
+        ]),
+        protocol_type: Some("char".to_string()),
+        ..TypeDef::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_notification_has_a_topic_and_sequence() {
+        for message in zmq_notifications() {
+            assert_eq!(message.topic.as_deref(), Some(message.name.as_str()));
+            assert!(message.sequenced, "{} has no sequence number", message.name);
+            assert!(!message.fields.is_empty(), "{} has no payload", message.name);
+        }
+    }
+}
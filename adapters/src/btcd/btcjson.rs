@@ -0,0 +1,522 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! btcd `btcjson` command/result definitions -> IR.
+//!
+//! btcd has no machine-readable RPC schema; its interface lives in Go structs in the
+//! `btcjson` package (`*Cmd` structs registered with `MustRegisterCmd`, and `*Result`
+//! structs returned by the RPC server). `resources/adapters/btcd/btcjson.json` is a
+//! checked-in transcription of those definitions: every command lists its positional
+//! fields with their Go types, and every result struct lists its fields with their
+//! `json` tags.
+//!
+//! Go types map onto the IR as follows:
+//!
+//! - `string` -> `string`, `bool` -> `boolean`, integer and float kinds -> `number`,
+//!   `interface{}` -> `any`
+//! - `*T` -> `T`, but optional (an optional command argument, or a result field that may
+//!   be absent); a `jsonrpcdefault` tag becomes the parameter's default value
+//! - `[]T` -> `TypeKind::Array` with one `field_0` element prototype
+//! - `map[string]T` -> `any` (dynamic keys have no fixed shape in the IR)
+//! - named result structs -> `TypeKind::Object`, keyed by their `json` tag names;
+//!   `omitempty` fields are optional
+//! - named string/integer types (e.g. `AddNodeSubCmd`) -> their underlying type, with the
+//!   accepted values appended to the description
+//!
+//! Like btcjson's own help generator, parameter names are the lowercased Go field names.
+//! Commands flagged `websocket_only`, `notification` or `wallet_only` are not served by
+//! btcd's HTTP JSON-RPC endpoint and are skipped.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use ir::{
+    FieldDef, FieldKey, ParamDef, ProtocolDef, ProtocolIR, ProtocolModule, RpcDef, TypeDef,
+    TypeKind,
+};
+use path::{find_project_root, resolve_ir_output_path};
+use semantics::method_categorization;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::conversion_helpers::{determine_requires_private_keys, sort_definitions_by_name};
+use crate::{ProtocolAdapter, ProtocolAdapterError, ProtocolAdapterResult, CAP_RPC};
+
+/// Workspace-relative path of the checked-in btcjson schema fixture.
+pub const BTCJSON_FIXTURE: &str = "resources/adapters/btcd/btcjson.json";
+
+/// Adapter for btcd's btcjson schema fixture.
+#[derive(Debug, Clone, Default)]
+pub struct BtcdAdapter {
+    /// Version reported by the adapter; defaults to the fixture's `version`.
+    pub version: Option<String>,
+}
+
+impl BtcdAdapter {
+    /// Create an adapter that reports the fixture's own version.
+    pub fn new() -> Self { Self::default() }
+}
+
+impl ProtocolAdapter for BtcdAdapter {
+    fn name(&self) -> &'static str { "btcd" }
+
+    fn version(&self) -> String { self.version.clone().unwrap_or_else(|| "latest".to_string()) }
+
+    fn extract_protocol_ir(&self, path: &Path) -> ProtocolAdapterResult<ProtocolIR> {
+        let content = std::fs::read_to_string(path)?;
+        let document: Value = serde_json::from_str(&content)?;
+        convert_document(&document)
+    }
+
+    fn capabilities(&self) -> Vec<&'static str> { vec![CAP_RPC] }
+}
+
+#[derive(Debug, Deserialize)]
+struct BtcJsonSchema {
+    version: String,
+    #[serde(default)]
+    named_types: BTreeMap<String, NamedType>,
+    #[serde(default)]
+    structs: BTreeMap<String, StructDef>,
+    commands: Vec<CommandDef>,
+}
+
+/// A Go named type over a builtin (`type AddNodeSubCmd string`).
+#[derive(Debug, Deserialize)]
+struct NamedType {
+    #[serde(rename = "type")]
+    go_type: String,
+    #[serde(default)]
+    values: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StructDef {
+    fields: Vec<StructField>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StructField {
+    json: String,
+    #[serde(rename = "type")]
+    go_type: String,
+    #[serde(default)]
+    help: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommandDef {
+    method: String,
+    category: String,
+    #[serde(default)]
+    flags: Vec<UsageFlag>,
+    #[serde(default)]
+    help: String,
+    #[serde(default)]
+    fields: Vec<CommandField>,
+    result: Option<String>,
+    #[serde(default)]
+    result_help: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommandField {
+    name: String,
+    #[serde(rename = "type")]
+    go_type: String,
+    default: Option<String>,
+    #[serde(default)]
+    help: String,
+}
+
+/// btcjson `UsageFlag`s that take a command off the HTTP JSON-RPC surface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum UsageFlag {
+    WalletOnly,
+    WebsocketOnly,
+    Notification,
+}
+
+/// A parsed Go type expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum GoType {
+    Pointer(Box<GoType>),
+    Slice(Box<GoType>),
+    Map(Box<GoType>),
+    Named(String),
+}
+
+impl GoType {
+    fn parse(expr: &str) -> ProtocolAdapterResult<Self> {
+        let expr = expr.trim();
+        if let Some(inner) = expr.strip_prefix('*') {
+            return Ok(GoType::Pointer(Box::new(Self::parse(inner)?)));
+        }
+        if let Some(inner) = expr.strip_prefix("[]") {
+            return Ok(GoType::Slice(Box::new(Self::parse(inner)?)));
+        }
+        if let Some(rest) = expr.strip_prefix("map[") {
+            let (key, value) = rest.split_once(']').ok_or_else(|| {
+                ProtocolAdapterError::Message(format!("Malformed Go map type '{}'", expr))
+            })?;
+            if key != "string" {
+                return Err(ProtocolAdapterError::Message(format!(
+                    "Unsupported Go map key type '{}' in '{}'",
+                    key, expr
+                )));
+            }
+            return Ok(GoType::Map(Box::new(Self::parse(value)?)));
+        }
+        if expr.is_empty() {
+            return Err(ProtocolAdapterError::Message("Empty Go type".to_string()));
+        }
+        Ok(GoType::Named(expr.to_string()))
+    }
+
+    fn is_pointer(&self) -> bool { matches!(self, GoType::Pointer(_)) }
+}
+
+/// Maps a Go builtin type onto the IR's protocol type vocabulary.
+fn builtin_protocol_type(name: &str) -> Option<&'static str> {
+    match name {
+        "string" => Some("string"),
+        "bool" => Some("boolean"),
+        "int" | "int8" | "int16" | "int32" | "int64" | "uint" | "uint8" | "uint16" | "uint32"
+        | "uint64" | "float32" | "float64" => Some("number"),
+        "interface{}" | "json.RawMessage" => Some("any"),
+        _ => None,
+    }
+}
+
+fn primitive(protocol_type: &str, description: String) -> TypeDef {
+    TypeDef {
+        name: protocol_type.to_string(),
+        description,
+        kind: TypeKind::Primitive,
+        protocol_type: Some(protocol_type.to_string()),
+        ..TypeDef::default()
+    }
+}
+
+/// Converts a btcjson schema document (see [`BTCJSON_FIXTURE`]) into a `ProtocolIR` with a
+/// single `rpc` module.
+pub fn convert_document(document: &Value) -> ProtocolAdapterResult<ProtocolIR> {
+    let schema: BtcJsonSchema = serde_json::from_value(document.clone())?;
+    let mut converter = TypeConverter { schema: &schema, stack: Vec::new() };
+
+    let mut definitions = Vec::new();
+    for command in &schema.commands {
+        if !command.flags.is_empty() {
+            continue;
+        }
+        definitions.push(ProtocolDef::RpcMethod(converter.convert_command(command)?));
+    }
+
+    sort_definitions_by_name(&mut definitions);
+
+    let module = ProtocolModule::new(
+        "rpc".to_string(),
+        format!("btcd JSON-RPC API ({})", schema.version),
+        definitions,
+    );
+    Ok(ProtocolIR::new(vec![module]))
+}
+
+/// Workspace-relative path of the btcd IR generated from [`BTCJSON_FIXTURE`].
+pub const BTCD_IR_FILE: &str = "resources/ir/btcd.ir.json";
+
+/// Entry point for the `process_btcd_btcjson` binary: converts a btcjson fixture
+/// (default [`BTCJSON_FIXTURE`]) into IR (default [`BTCD_IR_FILE`]).
+pub fn run(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    if args.iter().skip(1).any(|a| a == "-h" || a == "--help") {
+        eprintln!("Usage:");
+        eprintln!("  {} [btcjson_file] [output_file]   # Convert btcjson fixture to IR", args[0]);
+        eprintln!();
+        eprintln!("Defaults: {} -> {}", BTCJSON_FIXTURE, BTCD_IR_FILE);
+        std::process::exit(1);
+    }
+
+    let project_root = find_project_root()?;
+    let input = args.get(1).map(PathBuf::from).unwrap_or_else(|| PathBuf::from(BTCJSON_FIXTURE));
+    let output = args.get(2).map(PathBuf::from).unwrap_or_else(|| PathBuf::from(BTCD_IR_FILE));
+    let input = resolve_ir_output_path(&project_root, &input);
+    let output = resolve_ir_output_path(&project_root, &output);
+
+    let protocol_ir = BtcdAdapter::new().extract_protocol_ir(&input)?;
+    protocol_ir.to_file(&output)?;
+
+    println!("✓ Converted btcjson to IR: {}", output.display());
+    println!("  Methods: {}", protocol_ir.get_rpc_methods().len());
+    Ok(())
+}
+
+/// Resolves Go types against the schema's named types and structs.
+struct TypeConverter<'a> {
+    schema: &'a BtcJsonSchema,
+    /// Structs currently being expanded; a repeat is a recursive type.
+    stack: Vec<&'a str>,
+}
+
+impl<'a> TypeConverter<'a> {
+    fn convert_command(&mut self, command: &'a CommandDef) -> ProtocolAdapterResult<RpcDef> {
+        let mut params = Vec::new();
+        for field in &command.fields {
+            let go_type = GoType::parse(&field.go_type)?;
+            params.push(ParamDef {
+                name: field.name.to_lowercase(),
+                param_type: self.convert(&go_type, &field.help)?,
+                required: !go_type.is_pointer(),
+                description: field.help.clone(),
+                default_value: field.default.clone(),
+                version_added: None,
+                version_removed: None,
+                deprecated: None,
+            });
+        }
+
+        let result = match &command.result {
+            Some(go_type) => self.convert(&GoType::parse(go_type)?, &command.result_help)?,
+            None => primitive("none", String::new()),
+        };
+
+        let category = command.category.to_lowercase();
+        Ok(RpcDef {
+            name: command.method.clone(),
+            description: command.help.clone(),
+            params,
+            result: Some(result),
+            access_level: method_categorization::access_level_for(&category, &command.method),
+            requires_private_keys: determine_requires_private_keys(&category, &command.method),
+            category,
+            hidden: None,
+            version_added: None,
+            version_removed: None,
+            deprecated: None,
+            examples: None,
+            http: None,
+        })
+    }
+
+    fn convert(&mut self, go_type: &GoType, description: &str) -> ProtocolAdapterResult<TypeDef> {
+        match go_type {
+            GoType::Pointer(inner) => self.convert(inner, description),
+            GoType::Slice(inner) => {
+                let element = self.convert(inner, "")?;
+                Ok(TypeDef {
+                    name: "array".to_string(),
+                    description: description.to_string(),
+                    kind: TypeKind::Array,
+                    fields: Some(vec![FieldDef {
+                        key: FieldKey::Named("field_0".to_string()),
+                        field_type: element,
+                        required: true,
+                        description: String::new(),
+                        default_value: None,
+                        version_added: None,
+                        version_removed: None,
+                        deprecated: None,
+                    }]),
+                    protocol_type: Some("array".to_string()),
+                    ..TypeDef::default()
+                })
+            }
+            GoType::Map(_) => Ok(primitive("any", description.to_string())),
+            GoType::Named(name) => self.convert_named(name, description),
+        }
+    }
+
+    fn convert_named(&mut self, name: &str, description: &str) -> ProtocolAdapterResult<TypeDef> {
+        if let Some(protocol_type) = builtin_protocol_type(name) {
+            return Ok(primitive(protocol_type, description.to_string()));
+        }
+
+        if let Some(named) = self.schema.named_types.get(name) {
+            let mut type_def = self.convert(&GoType::parse(&named.go_type)?, description)?;
+            if !named.values.is_empty() {
+                let values = named.values.join(", ");
+                type_def.description = if description.is_empty() {
+                    format!("One of: {}", values)
+                } else {
+                    format!("{} (one of: {})", description, values)
+                };
+            }
+            return Ok(type_def);
+        }
+
+        let (struct_name, struct_def) =
+            self.schema.structs.get_key_value(name).ok_or_else(|| {
+                ProtocolAdapterError::Message(format!("Unknown btcjson type '{}'", name))
+            })?;
+
+        if self.stack.contains(&struct_name.as_str()) {
+            return Ok(TypeDef {
+                name: struct_name.clone(),
+                description: description.to_string(),
+                kind: TypeKind::Alias,
+                base_type: Some(struct_name.clone()),
+                protocol_type: Some("object".to_string()),
+                ..TypeDef::default()
+            });
+        }
+
+        self.stack.push(struct_name);
+        let mut fields = Vec::new();
+        for field in &struct_def.fields {
+            let mut tag = field.json.split(',');
+            let key = tag.next().unwrap_or_default();
+            if key == "-" {
+                continue;
+            }
+            let omitempty = tag.any(|option| option == "omitempty");
+            let go_type = GoType::parse(&field.go_type)?;
+            fields.push(FieldDef {
+                key: FieldKey::Named(key.to_string()),
+                field_type: self.convert(&go_type, &field.help)?,
+                required: !omitempty && !go_type.is_pointer(),
+                description: field.help.clone(),
+                default_value: None,
+                version_added: None,
+                version_removed: None,
+                deprecated: None,
+            });
+        }
+        self.stack.pop();
+
+        Ok(TypeDef {
+            name: struct_name.clone(),
+            description: description.to_string(),
+            kind: TypeKind::Object,
+            fields: Some(fields),
+            protocol_type: Some("object".to_string()),
+            ..TypeDef::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn rpc<'a>(ir: &'a ProtocolIR, name: &str) -> &'a RpcDef {
+        ir.get_rpc_methods()
+            .into_iter()
+            .find(|m| m.name == name)
+            .unwrap_or_else(|| panic!("method {} not found", name))
+    }
+
+    #[test]
+    fn converts_commands_and_result_structs() {
+        let document = json!({
+            "source": "github.com/btcsuite/btcd/btcjson",
+            "version": "v0.24.2",
+            "named_types": {
+                "NodeSubCmd": { "type": "string", "values": ["connect", "remove"] }
+            },
+            "structs": {
+                "Peer": {
+                    "fields": [
+                        { "name": "ID", "json": "id", "type": "int32", "help": "Peer id" },
+                        { "name": "AddrLocal", "json": "addrlocal,omitempty", "type": "string" },
+                        { "name": "Inbound", "json": "inbound", "type": "*bool" },
+                        { "name": "Ignored", "json": "-", "type": "string" }
+                    ]
+                }
+            },
+            "commands": [
+                {
+                    "method": "getpeerinfo",
+                    "cmd": "GetPeerInfoCmd",
+                    "category": "network",
+                    "help": "Returns peers.",
+                    "fields": [],
+                    "result": "[]Peer"
+                },
+                {
+                    "method": "node",
+                    "cmd": "NodeCmd",
+                    "category": "network",
+                    "fields": [
+                        { "name": "SubCmd", "type": "NodeSubCmd", "help": "What to do" },
+                        { "name": "ConnectSubCmd", "type": "*string", "default": "temp" }
+                    ],
+                    "result": null
+                },
+                {
+                    "method": "notifyblocks",
+                    "cmd": "NotifyBlocksCmd",
+                    "category": "network",
+                    "flags": ["websocket_only"],
+                    "fields": [],
+                    "result": null
+                }
+            ]
+        });
+
+        let ir = convert_document(&document).expect("convert");
+        assert_eq!(ir.get_rpc_methods().len(), 2, "websocket-only commands are skipped");
+
+        let peers = rpc(&ir, "getpeerinfo").result.as_ref().expect("result");
+        assert!(matches!(peers.kind, TypeKind::Array));
+        let peer = &peers.fields.as_ref().expect("element")[0].field_type;
+        assert_eq!(peer.name, "Peer");
+        let fields = peer.fields.as_ref().expect("fields");
+        assert_eq!(fields.len(), 3);
+        assert_eq!(fields[0].key, FieldKey::Named("id".to_string()));
+        assert!(fields[0].required);
+        assert_eq!(fields[0].field_type.protocol_type.as_deref(), Some("number"));
+        assert!(!fields[1].required, "omitempty fields are optional");
+        assert!(!fields[2].required, "pointer fields are optional");
+        assert_eq!(fields[2].field_type.protocol_type.as_deref(), Some("boolean"));
+
+        let node = rpc(&ir, "node");
+        assert_eq!(node.params[0].name, "subcmd");
+        assert!(node.params[0].required);
+        assert_eq!(node.params[0].param_type.protocol_type.as_deref(), Some("string"));
+        assert_eq!(node.params[0].description, "What to do");
+        assert!(node.params[0].param_type.description.contains("connect, remove"));
+        assert_eq!(node.params[1].name, "connectsubcmd");
+        assert!(!node.params[1].required);
+        assert_eq!(node.params[1].default_value.as_deref(), Some("temp"));
+        assert_eq!(node.result.as_ref().and_then(|r| r.protocol_type.as_deref()), Some("none"));
+    }
+
+    #[test]
+    fn rejects_unknown_types() {
+        let document = json!({
+            "source": "github.com/btcsuite/btcd/btcjson",
+            "version": "v0.24.2",
+            "commands": [
+                { "method": "getfoo", "cmd": "GetFooCmd", "category": "util", "result": "FooResult" }
+            ]
+        });
+        let err = convert_document(&document).expect_err("unknown result type");
+        assert!(err.to_string().contains("FooResult"));
+
+        assert!(GoType::parse("map[int]string").is_err());
+        assert_eq!(
+            GoType::parse("*[]string").expect("parse"),
+            GoType::Pointer(Box::new(GoType::Slice(Box::new(GoType::Named("string".into())))))
+        );
+    }
+
+    #[test]
+    fn converts_btcjson_fixture() {
+        let root = path::find_project_root().expect("project root");
+        let ir = BtcdAdapter::new()
+            .extract_protocol_ir(&root.join(BTCJSON_FIXTURE))
+            .expect("fixture converts");
+        let methods = ir.get_rpc_methods();
+        assert!(methods.len() > 40);
+        assert!(methods.iter().all(|m| m.name != "session" && m.name != "blockconnected"));
+
+        let info = rpc(&ir, "getblockchaininfo").result.as_ref().expect("result");
+        assert!(info
+            .fields
+            .as_ref()
+            .expect("fields")
+            .iter()
+            .any(|f| f.key == FieldKey::Named("bestblockhash".to_string())));
+    }
+}
@@ -2,7 +2,15 @@
 
 //! Shared utilities for converting protocol schemas into ProtocolIR.
 
+use std::fmt;
+
 use ir::ProtocolDef;
+use serde::de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde_json::{Map, Value};
+
+/// Key added next to `properties` by [`parse_schema_document`], listing the property names
+/// in document order.
+pub const PROPERTY_ORDER_KEY: &str = "x-property-order";
 
 /// Wallet RPC methods that require private key access
 const WALLET_METHODS_REQUIRING_PRIVATE_KEYS: &[&str] = &[
@@ -34,21 +42,150 @@ pub fn determine_requires_private_keys(category: &str, method_name: &str) -> boo
     WALLET_METHODS_REQUIRING_PRIVATE_KEYS.contains(&name.as_str())
 }
 
-/// Sorts protocol definitions: Type defs first (by type name), then RpcMethod defs (by method name).
+/// Sorts protocol definitions: Type defs first (by type name), then RpcMethod defs (by method name),
+/// then RestEndpoint defs (by endpoint name), then Message defs (by message name).
 ///
 /// This ensures consistent ordering and keeps canonical type definitions before method definitions.
-pub fn sort_definitions_by_name(definitions: &mut Vec<ProtocolDef>) {
+pub fn sort_definitions_by_name(definitions: &mut [ProtocolDef]) {
     definitions.sort_by(|a, b| {
         let (ord_a, name_a) = match a {
             ProtocolDef::Type(ref ty) => (0u8, ty.name.as_str()),
             ProtocolDef::RpcMethod(ref rpc) => (1, rpc.name.as_str()),
-            _ => (2, ""),
+            ProtocolDef::RestEndpoint(ref endpoint) => (2, endpoint.name.as_str()),
+            ProtocolDef::Message(ref message) => (3, message.name.as_str()),
+            _ => (4, ""),
         };
         let (ord_b, name_b) = match b {
             ProtocolDef::Type(ref ty) => (0, ty.name.as_str()),
             ProtocolDef::RpcMethod(ref rpc) => (1, rpc.name.as_str()),
-            _ => (2, ""),
+            ProtocolDef::RestEndpoint(ref endpoint) => (2, endpoint.name.as_str()),
+            ProtocolDef::Message(ref message) => (3, message.name.as_str()),
+            _ => (4, ""),
         };
         (ord_a, name_a).cmp(&(ord_b, name_b))
     });
 }
+
+/// Parses a JSON Schema based document (OpenRPC, OpenAPI, Core Lightning schemas).
+///
+/// `serde_json::Value` does not keep object key order, but the order of `properties` is
+/// meaningful (positional parameters, struct field order), so it is recorded in a
+/// [`PROPERTY_ORDER_KEY`] array next to each `properties` object.
+pub fn parse_schema_document(content: &str) -> serde_json::Result<Value> {
+    let ordered: OrderedValue = serde_json::from_str(content)?;
+    Ok(ordered.into_value())
+}
+
+/// The `properties` of an object schema in the order recorded by [`parse_schema_document`].
+///
+/// Schemas built some other way have no recorded order; their required properties come
+/// first, in `required` order, followed by the others.
+pub fn properties_in_order(schema: &Value) -> Vec<(&str, &Value)> {
+    let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    let names: Vec<&str> = match schema.get(PROPERTY_ORDER_KEY).and_then(Value::as_array) {
+        Some(order) => order.iter().filter_map(Value::as_str).collect(),
+        None => {
+            let required: Vec<&str> = schema
+                .get("required")
+                .and_then(Value::as_array)
+                .map(|names| names.iter().filter_map(Value::as_str).collect())
+                .unwrap_or_default();
+            let optional = properties.keys().map(String::as_str).filter(|n| !required.contains(n));
+            required.iter().copied().chain(optional).collect()
+        }
+    };
+    names
+        .into_iter()
+        .filter_map(|name| properties.get_key_value(name))
+        .map(|(name, property)| (name.as_str(), property))
+        .collect()
+}
+
+/// JSON value whose objects keep their keys in document order
+enum OrderedValue {
+    Object(Vec<(String, OrderedValue)>),
+    Array(Vec<OrderedValue>),
+    Scalar(Value),
+}
+
+impl OrderedValue {
+    fn into_value(self) -> Value {
+        match self {
+            OrderedValue::Object(entries) => {
+                let order = entries.iter().find_map(|(key, value)| match value {
+                    OrderedValue::Object(properties) if key == "properties" => Some(
+                        properties.iter().map(|(name, _)| Value::from(name.as_str())).collect(),
+                    ),
+                    _ => None,
+                });
+                let mut map: Map<String, Value> =
+                    entries.into_iter().map(|(key, value)| (key, value.into_value())).collect();
+                if let Some(order) = order {
+                    map.insert(PROPERTY_ORDER_KEY.to_string(), Value::Array(order));
+                }
+                Value::Object(map)
+            }
+            OrderedValue::Array(items) =>
+                Value::Array(items.into_iter().map(OrderedValue::into_value).collect()),
+            OrderedValue::Scalar(value) => value,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for OrderedValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(OrderedValueVisitor)
+    }
+}
+
+struct OrderedValueVisitor;
+
+impl<'de> Visitor<'de> for OrderedValueVisitor {
+    type Value = OrderedValue;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str("a JSON value") }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(OrderedValue::Scalar(Value::from(v)))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(OrderedValue::Scalar(Value::from(v)))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(OrderedValue::Scalar(Value::from(v)))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E> {
+        Ok(OrderedValue::Scalar(Value::from(v)))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(OrderedValue::Scalar(Value::from(v)))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E> {
+        Ok(OrderedValue::Scalar(Value::from(v)))
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> { Ok(OrderedValue::Scalar(Value::Null)) }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut items = Vec::new();
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(OrderedValue::Array(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut entries = Vec::new();
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+        Ok(OrderedValue::Object(entries))
+    }
+}
@@ -0,0 +1,568 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Core Lightning `doc/schemas` JSON schemas -> IR.
+//!
+//! Core Lightning documents every JSON-RPC command in a JSON schema file under
+//! `doc/schemas/` (the same files its man pages, `cln-rpc` and `cln-grpc` are generated
+//! from). Each file names its command in `rpc` and carries a `request` and a `response`
+//! object schema. `resources/adapters/core_lightning/schemas/` holds checked-in copies.
+//!
+//! CLN's schema dialect maps onto the IR as follows:
+//!
+//! - `string`, and the string-encoded domain types (`pubkey`, `hex`, `hash`, `secret`,
+//!   `txid`, `short_channel_id`, `outpoint`, `feerate`, ...) -> `string`
+//! - `integer`, `number`, the sized integers (`u64`, `u32`, `u16`, `u8`), `port`, and
+//!   the amounts (`msat`, `sat`) -> `number`; `boolean` -> `boolean`
+//! - amounts that also accept a keyword (`msat_or_any`, `sat_or_all`, ...) -> `string`
+//! - `object` -> `TypeKind::Object` named after the command and field, the way `cln-rpc`
+//!   names its structs (e.g. `ListpeersPeers`); `required` decides `FieldDef::required`
+//! - `array` -> `TypeKind::Array` with one `field_0` element prototype
+//! - `enum` -> its base type, with the accepted values appended to the description
+//! - `added` -> `version_added`; the removal release of `deprecated: [from, until]` ->
+//!   `version_removed` and the deprecation release -> `deprecated`
+//! - properties that only appear in an `allOf` / `if` / `then` branch become optional
+//!   fields
+//!
+//! Request properties become positional parameters in schema order, which is the order
+//! `lightningd` accepts them in, so schema files are read with
+//! [`parse_schema_document`], which records that order. Schemas carry no command category,
+//! so commands are grouped the way `lightning-cli help` does (see [`category_for`]).
+
+use std::path::{Path, PathBuf};
+
+use ir::{
+    Deprecation, FieldDef, FieldKey, ParamDef, ProtocolDef, ProtocolIR, ProtocolModule, RpcDef,
+    TypeDef, TypeKind,
+};
+use path::{find_project_root, resolve_ir_output_path};
+use semantics::method_categorization;
+use serde_json::Value;
+
+use crate::conversion_helpers::{
+    determine_requires_private_keys, parse_schema_document, properties_in_order,
+    sort_definitions_by_name,
+};
+use crate::{ProtocolAdapter, ProtocolAdapterError, ProtocolAdapterResult, CAP_RPC};
+
+/// Workspace-relative directory of the checked-in Core Lightning schema fixtures.
+pub const CLN_SCHEMA_DIR: &str = "resources/adapters/core_lightning/schemas";
+
+/// Workspace-relative path of the Lightning IR generated from [`CLN_SCHEMA_DIR`].
+pub const LIGHTNING_IR_FILE: &str = "resources/ir/lightning.ir.json";
+
+/// Adapter for Core Lightning's `doc/schemas` JSON schemas.
+#[derive(Debug, Clone, Default)]
+pub struct CoreLightningAdapter {
+    /// Version reported by the adapter.
+    pub version: Option<String>,
+}
+
+impl CoreLightningAdapter {
+    /// Create an adapter with no explicit version.
+    pub fn new() -> Self { Self::default() }
+}
+
+impl ProtocolAdapter for CoreLightningAdapter {
+    fn name(&self) -> &'static str { "core_lightning" }
+
+    fn version(&self) -> String { self.version.clone().unwrap_or_else(|| "latest".to_string()) }
+
+    /// Reads a single schema file, or every `*.json` schema in a directory.
+    fn extract_protocol_ir(&self, path: &Path) -> ProtocolAdapterResult<ProtocolIR> {
+        let files = if path.is_dir() {
+            let mut files = Vec::new();
+            for entry in std::fs::read_dir(path)? {
+                let file = entry?.path();
+                if file.extension().is_some_and(|ext| ext == "json") {
+                    files.push(file);
+                }
+            }
+            files
+        } else {
+            vec![path.to_path_buf()]
+        };
+
+        let mut documents = Vec::new();
+        for file in files {
+            let content = std::fs::read_to_string(&file)?;
+            documents.push(parse_schema_document(&content)?);
+        }
+        convert_documents(&documents)
+    }
+
+    fn capabilities(&self) -> Vec<&'static str> { vec![CAP_RPC] }
+}
+
+/// Returns `true` if `value` looks like a CLN command schema (has `rpc`, `request` and
+/// `response`). Other files in `doc/schemas` (notifications, hooks, the draft meta-schema)
+/// do not.
+pub fn is_command_schema(value: &Value) -> bool {
+    value.get("rpc").is_some_and(Value::is_string)
+        && value.get("request").is_some()
+        && value.get("response").is_some()
+}
+
+/// Converts CLN command schemas into a `ProtocolIR` with a single `rpc` module.
+/// Documents that are not command schemas are skipped.
+pub fn convert_documents(documents: &[Value]) -> ProtocolAdapterResult<ProtocolIR> {
+    let mut definitions = Vec::new();
+    for document in documents.iter().filter(|d| is_command_schema(d)) {
+        definitions.push(ProtocolDef::RpcMethod(convert_command(document)?));
+    }
+
+    sort_definitions_by_name(&mut definitions);
+
+    let module = ProtocolModule::new(
+        "rpc".to_string(),
+        "Core Lightning JSON-RPC API".to_string(),
+        definitions,
+    );
+    Ok(ProtocolIR::new(vec![module]))
+}
+
+/// Entry point for the `process_core_lightning_schemas` binary: converts a schema
+/// directory (default [`CLN_SCHEMA_DIR`]) into IR (default [`LIGHTNING_IR_FILE`]).
+pub fn run(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    if args.iter().skip(1).any(|a| a == "-h" || a == "--help") {
+        eprintln!("Usage:");
+        eprintln!("  {} [schema_dir] [output_file]   # Convert CLN schemas to IR", args[0]);
+        eprintln!();
+        eprintln!("Defaults: {} -> {}", CLN_SCHEMA_DIR, LIGHTNING_IR_FILE);
+        std::process::exit(1);
+    }
+
+    let project_root = find_project_root()?;
+    let input = args.get(1).map(PathBuf::from).unwrap_or_else(|| PathBuf::from(CLN_SCHEMA_DIR));
+    let output = args.get(2).map(PathBuf::from).unwrap_or_else(|| PathBuf::from(LIGHTNING_IR_FILE));
+    let input = resolve_ir_output_path(&project_root, &input);
+    let output = resolve_ir_output_path(&project_root, &output);
+
+    let protocol_ir = CoreLightningAdapter::new().extract_protocol_ir(&input)?;
+    protocol_ir.to_file(&output)?;
+
+    println!("✓ Converted Core Lightning schemas to IR: {}", output.display());
+    println!("  Methods: {}", protocol_ir.get_rpc_methods().len());
+    Ok(())
+}
+
+/// Groups a command the way `lightning-cli help` does, using the category names the rest
+/// of the pipeline understands. Commands not listed fall back to `util`.
+pub fn category_for(rpc: &str) -> &'static str {
+    match rpc {
+        "getinfo" | "stop" | "help" | "getlog" | "check" | "plugin" => "control",
+        "connect" | "disconnect" | "listpeers" | "listnodes" | "ping" | "sendcustommsg" =>
+            "network",
+        "fundchannel" | "close" | "listpeerchannels" | "listchannels" | "listclosedchannels"
+        | "setchannel" | "multifundchannel" | "openchannel_init" => "channel",
+        "invoice" | "listinvoices" | "delinvoice" | "waitinvoice" | "waitanyinvoice" => "invoice",
+        "pay" | "xpay" | "keysend" | "sendpay" | "waitsendpay" | "listpays" | "listsendpays"
+        | "decodepay" | "decode" => "payment",
+        "newaddr" | "listfunds" | "withdraw" | "fundpsbt" | "utxopsbt" | "txprepare" | "txsend"
+        | "txdiscard" | "feerates" | "reserveinputs" | "unreserveinputs" => "wallet",
+        _ => "util",
+    }
+}
+
+fn str_field<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value.get(key).and_then(Value::as_str)
+}
+
+/// CLN descriptions are arrays of markdown lines (older schemas use a plain string).
+fn description_of(value: &Value) -> String {
+    match value.get("description") {
+        Some(Value::Array(lines)) =>
+            lines.iter().filter_map(Value::as_str).collect::<Vec<_>>().join(" "),
+        Some(Value::String(text)) => text.clone(),
+        _ => String::new(),
+    }
+}
+
+/// Maps a CLN schema type onto the IR's protocol type vocabulary.
+fn map_cln_type(cln_type: &str) -> ProtocolAdapterResult<&'static str> {
+    match cln_type {
+        "string"
+        | "pubkey"
+        | "hex"
+        | "hash"
+        | "secret"
+        | "txid"
+        | "short_channel_id"
+        | "short_channel_id_dir"
+        | "signature"
+        | "bip340sig"
+        | "point32"
+        | "outpoint"
+        | "utxo"
+        | "outputdesc"
+        | "feerate"
+        | "currency"
+        | "msat_or_any"
+        | "msat_or_all"
+        | "sat_or_all" => Ok("string"),
+        "integer" | "number" | "u64" | "u32" | "u16" | "u8" | "port" | "msat" | "sat" =>
+            Ok("number"),
+        "boolean" => Ok("boolean"),
+        other => Err(ProtocolAdapterError::Message(format!(
+            "Unsupported Core Lightning schema type '{}'",
+            other
+        ))),
+    }
+}
+
+/// `cln-rpc`-style type name segment: first letter upper-cased, separators dropped.
+fn type_name_segment(name: &str) -> String {
+    name.split(['_', '-'])
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+fn version_added(schema: &Value) -> Option<String> { str_field(schema, "added").map(String::from) }
+
+/// `deprecated: ["v24.11", "v25.12"]` lists the deprecation and removal releases.
+fn version_removed(schema: &Value) -> Option<String> {
+    schema.get("deprecated").and_then(Value::as_array)?.get(1)?.as_str().map(String::from)
+}
+
+/// The deprecation release of `deprecated: ["v24.11", "v25.12"]`.
+fn deprecation(schema: &Value) -> Option<Deprecation> {
+    let since = schema.get("deprecated").and_then(Value::as_array)?.first()?.as_str();
+    Some(Deprecation { since: since.map(String::from), ..Deprecation::default() })
+}
+
+fn default_of(schema: &Value) -> Option<String> {
+    match schema.get("default")? {
+        Value::String(text) => Some(text.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+fn convert_command(document: &Value) -> ProtocolAdapterResult<RpcDef> {
+    let rpc = str_field(document, "rpc").unwrap_or_default();
+    let request = &document["request"];
+    let required = required_names(request);
+
+    let mut params = Vec::new();
+    for (name, schema) in properties_in_order(request) {
+        params.push(ParamDef {
+            name: name.to_string(),
+            param_type: convert_schema(schema, &type_name_segment(rpc), name)?,
+            required: required.contains(&name),
+            description: description_of(schema),
+            default_value: default_of(schema),
+            version_added: version_added(schema),
+            version_removed: version_removed(schema),
+            deprecated: deprecation(schema),
+        });
+    }
+
+    let response = &document["response"];
+    let has_fields = object_properties(response).next().is_some();
+    let result = if has_fields {
+        let mut result = convert_object(response, &type_name_segment(rpc))?;
+        result.name = "object".to_string();
+        result
+    } else {
+        TypeDef {
+            name: "none".to_string(),
+            kind: TypeKind::Primitive,
+            protocol_type: Some("none".to_string()),
+            ..TypeDef::default()
+        }
+    };
+
+    let category = category_for(rpc).to_string();
+    Ok(RpcDef {
+        name: rpc.to_string(),
+        description: description_of(document),
+        params,
+        result: Some(result),
+        access_level: method_categorization::access_level_for(&category, rpc),
+        requires_private_keys: determine_requires_private_keys(&category, rpc),
+        category,
+        hidden: None,
+        version_added: version_added(document),
+        version_removed: version_removed(document),
+        deprecated: deprecation(document),
+        examples: None,
+        http: None,
+    })
+}
+
+fn required_names(schema: &Value) -> Vec<&str> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+/// Top-level properties followed by properties introduced only in `allOf` branches
+/// (`then` / `else`), which are always optional. Branches re-list known properties as
+/// `{}` to allow them; those are skipped.
+fn object_properties(schema: &Value) -> impl Iterator<Item = (&str, &Value, bool)> {
+    let base = schema.get("properties").and_then(Value::as_object);
+    let required = required_names(schema);
+    let top = properties_in_order(schema)
+        .into_iter()
+        .map(move |(name, prop)| (name, prop, required.contains(&name)));
+
+    let branches = schema
+        .get("allOf")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .flat_map(|branch| ["then", "else"].into_iter().filter_map(move |k| branch.get(k)))
+        .flat_map(properties_in_order)
+        .filter(move |(name, prop)| {
+            !base.is_some_and(|b| b.contains_key(*name))
+                && prop.as_object().is_some_and(|p| !p.is_empty())
+        })
+        .map(|(name, prop)| (name, prop, false));
+
+    top.chain(branches)
+}
+
+/// Converts a property schema; `owner` is the type-name prefix of the enclosing object.
+fn convert_schema(schema: &Value, owner: &str, field: &str) -> ProtocolAdapterResult<TypeDef> {
+    let description = description_of(schema);
+    let cln_type = str_field(schema, "type").unwrap_or("string");
+
+    let mut type_def = match cln_type {
+        "object" => {
+            let name = format!("{}{}", owner, type_name_segment(field));
+            if object_properties(schema).next().is_none() {
+                // Free-form maps (`additionalProperties` only) have no fixed shape
+                TypeDef {
+                    name: "any".to_string(),
+                    kind: TypeKind::Primitive,
+                    protocol_type: Some("any".to_string()),
+                    ..TypeDef::default()
+                }
+            } else {
+                convert_object(schema, &name)?
+            }
+        }
+        "array" => {
+            let items = schema.get("items").ok_or_else(|| {
+                ProtocolAdapterError::Message(format!("Array '{}' has no items schema", field))
+            })?;
+            TypeDef {
+                name: "array".to_string(),
+                kind: TypeKind::Array,
+                fields: Some(vec![FieldDef {
+                    key: FieldKey::Named("field_0".to_string()),
+                    field_type: convert_schema(items, owner, field)?,
+                    required: true,
+                    description: String::new(),
+                    default_value: None,
+                    version_added: None,
+                    version_removed: None,
+                    deprecated: None,
+                }]),
+                protocol_type: Some("array".to_string()),
+                ..TypeDef::default()
+            }
+        }
+        other => {
+            let protocol_type = map_cln_type(other)?;
+            TypeDef {
+                name: protocol_type.to_string(),
+                kind: TypeKind::Primitive,
+                protocol_type: Some(protocol_type.to_string()),
+                ..TypeDef::default()
+            }
+        }
+    };
+
+    type_def.description = match schema.get("enum").and_then(Value::as_array) {
+        Some(values) => {
+            let values = values
+                .iter()
+                .map(|v| v.as_str().map(String::from).unwrap_or_else(|| v.to_string()))
+                .collect::<Vec<_>>()
+                .join(", ");
+            if description.is_empty() {
+                format!("One of: {}", values)
+            } else {
+                format!("{} (one of: {})", description, values)
+            }
+        }
+        None => description,
+    };
+    Ok(type_def)
+}
+
+fn convert_object(schema: &Value, name: &str) -> ProtocolAdapterResult<TypeDef> {
+    let mut fields = Vec::new();
+    for (key, prop, required) in object_properties(schema) {
+        fields.push(FieldDef {
+            key: FieldKey::Named(key.to_string()),
+            field_type: convert_schema(prop, name, key)?,
+            required,
+            description: description_of(prop),
+            default_value: None,
+            version_added: version_added(prop),
+            version_removed: version_removed(prop),
+            deprecated: deprecation(prop),
+        });
+    }
+
+    Ok(TypeDef {
+        name: name.to_string(),
+        description: description_of(schema),
+        kind: TypeKind::Object,
+        fields: Some(fields),
+        protocol_type: Some("object".to_string()),
+        ..TypeDef::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn rpc<'a>(ir: &'a ProtocolIR, name: &str) -> &'a RpcDef {
+        ir.get_rpc_methods()
+            .into_iter()
+            .find(|m| m.name == name)
+            .unwrap_or_else(|| panic!("method {} not found", name))
+    }
+
+    #[test]
+    fn converts_request_and_response_schemas() {
+        let document = parse_schema_document(
+            r#"{
+            "rpc": "listpeers",
+            "title": "Command returning data on connected lightning nodes",
+            "description": ["Returns peers.", "Second line."],
+            "request": {
+                "required": [],
+                "properties": {
+                    "id": { "type": "pubkey", "description": ["Peer to show."] },
+                    "level": { "type": "string", "enum": ["io", "debug"], "description": ["Log level."] }
+                }
+            },
+            "response": {
+                "required": ["peers"],
+                "properties": {
+                    "peers": {
+                        "type": "array",
+                        "description": ["The list of peers."],
+                        "items": {
+                            "type": "object",
+                            "required": ["id"],
+                            "properties": {
+                                "id": { "type": "pubkey", "description": ["The peer id."] },
+                                "num_channels": { "type": "u32", "added": "v23.02", "description": ["Channels."] },
+                                "netaddr": { "type": "array", "items": { "type": "string" } }
+                            }
+                        }
+                    }
+                }
+            }
+        }"#,
+        )
+        .expect("schema parses");
+
+        let ir = convert_documents(&[document, json!({ "$schema": "draft" })]).expect("convert");
+        assert_eq!(ir.get_rpc_methods().len(), 1, "non-command schemas are skipped");
+
+        let listpeers = rpc(&ir, "listpeers");
+        assert_eq!(listpeers.category, "network");
+        assert_eq!(listpeers.description, "Returns peers. Second line.");
+        assert_eq!(listpeers.params[0].name, "id");
+        assert!(!listpeers.params[0].required);
+        assert_eq!(listpeers.params[0].param_type.protocol_type.as_deref(), Some("string"));
+        assert!(listpeers.params[1].param_type.description.contains("one of: io, debug"));
+
+        let result = listpeers.result.as_ref().expect("result");
+        assert_eq!(result.name, "object");
+        let peers = &result.fields.as_ref().expect("fields")[0];
+        assert!(peers.required);
+        assert!(matches!(peers.field_type.kind, TypeKind::Array));
+        let peer = &peers.field_type.fields.as_ref().expect("element")[0].field_type;
+        assert_eq!(peer.name, "ListpeersPeers");
+        let peer_fields = peer.fields.as_ref().expect("peer fields");
+        assert!(peer_fields[0].required);
+        assert!(!peer_fields[1].required);
+        assert_eq!(peer_fields[1].field_type.protocol_type.as_deref(), Some("number"));
+        assert_eq!(peer_fields[1].version_added.as_deref(), Some("v23.02"));
+    }
+
+    #[test]
+    fn conditional_properties_are_optional() {
+        let document = json!({
+            "rpc": "close",
+            "request": { "required": ["id"], "properties": { "id": { "type": "string" } } },
+            "response": {
+                "required": ["type"],
+                "properties": {
+                    "type": { "type": "string", "enum": ["mutual", "unilateral", "unopened"] },
+                    "txid": { "type": "txid", "deprecated": ["v24.11", "v25.12"] }
+                },
+                "allOf": [{
+                    "if": { "properties": { "type": { "enum": ["mutual"] } } },
+                    "then": {
+                        "required": ["txs"],
+                        "properties": {
+                            "type": {},
+                            "txs": { "type": "array", "items": { "type": "hex" } }
+                        }
+                    }
+                }]
+            }
+        });
+
+        let ir = convert_documents(&[document]).expect("convert");
+        let close = rpc(&ir, "close");
+        assert!(close.params[0].required);
+        let fields = close.result.as_ref().and_then(|r| r.fields.as_ref()).expect("fields");
+        let keys: Vec<_> = fields.iter().map(|f| f.key.as_ident()).collect();
+        assert_eq!(keys, vec!["type", "txid", "txs"]);
+        assert_eq!(fields[1].version_removed.as_deref(), Some("v25.12"));
+        assert_eq!(fields[1].deprecated.as_ref().and_then(|d| d.since.as_deref()), Some("v24.11"));
+        assert!(!fields[2].required);
+    }
+
+    #[test]
+    fn rejects_unknown_types_and_handles_empty_responses() {
+        let unknown = json!({
+            "rpc": "getfoo",
+            "request": { "properties": { "foo": { "type": "frobnicator" } } },
+            "response": { "properties": {} }
+        });
+        let err = convert_documents(&[unknown]).expect_err("unknown type");
+        assert!(err.to_string().contains("frobnicator"));
+
+        let disconnect = json!({
+            "rpc": "disconnect",
+            "request": { "required": ["id"], "properties": { "id": { "type": "pubkey" } } },
+            "response": { "properties": {} }
+        });
+        let ir = convert_documents(&[disconnect]).expect("convert");
+        let result = rpc(&ir, "disconnect").result.as_ref().expect("result");
+        assert_eq!(result.protocol_type.as_deref(), Some("none"));
+    }
+
+    #[test]
+    fn converts_checked_in_schemas() {
+        let root = path::find_project_root().expect("project root");
+        let ir = CoreLightningAdapter::new()
+            .extract_protocol_ir(&root.join(CLN_SCHEMA_DIR))
+            .expect("fixtures convert");
+        let methods = ir.get_rpc_methods();
+        assert!(methods.len() >= 14);
+
+        let fundchannel = rpc(&ir, "fundchannel");
+        let params: Vec<_> = fundchannel.params.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(&params[..3], &["id", "amount", "feerate"], "schema order is positional order");
+    }
+}
@@ -0,0 +1,561 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Electrum server protocol (1.4) described as IR.
+//!
+//! Electrum servers (ElectrumX, electrs, Fulcrum) speak JSON-RPC 2.0 over a TCP (or TLS)
+//! stream, one JSON object per line. The protocol has no machine-readable schema; the
+//! methods below follow the ElectrumX protocol documentation (`protocol-methods.rst`) for
+//! the subset electrs implements:
+//!
+//! - `server.*`: version negotiation, banner, features, peers, keep-alive
+//! - `blockchain.block.*` / `blockchain.headers.subscribe`: block headers and the tip
+//! - `blockchain.scripthash.*`: balance, history, mempool and UTXOs of a script hash
+//!   (the reversed SHA-256 of an output script, hex-encoded)
+//! - `blockchain.transaction.*`: broadcast, raw transactions and merkle proofs
+//! - fee estimates and the mempool fee histogram
+//!
+//! Parameters are positional, in the documented order. Amounts are integer satoshis,
+//! except the fee estimates, which are BTC per kilobyte.
+//!
+//! The two `*.subscribe` methods return the current state and make the server push a
+//! notification whenever it changes: a request object with the subscribed method's name and
+//! no `id`. Those pushes are described as `MessageDef`s in a separate `notifications`
+//! module, with the method name as `topic` and the push `params` as positional fields.
+
+use std::path::{Path, PathBuf};
+
+use ir::{
+    FieldDef, FieldKey, MessageDef, MessageType, ParamDef, ProtocolDef, ProtocolIR, ProtocolModule,
+    RpcDef, TypeDef, TypeKind,
+};
+use path::{find_project_root, resolve_ir_output_path};
+use semantics::method_categorization;
+
+use crate::conversion_helpers::{determine_requires_private_keys, sort_definitions_by_name};
+use crate::{ProtocolAdapter, ProtocolAdapterResult, CAP_RPC};
+
+/// Workspace-relative path of the Electrum IR.
+pub const ELECTRUM_IR_FILE: &str = "resources/ir/electrum.ir.json";
+
+/// Name of the module holding the server push notifications in the Electrum IR.
+pub const NOTIFICATIONS_MODULE_NAME: &str = "notifications";
+
+/// Protocol version the method descriptions follow.
+pub const ELECTRUM_PROTOCOL_VERSION: &str = "1.4";
+
+/// Adapter for the Electrum server protocol.
+///
+/// The protocol is described in code rather than read from a schema file, so
+/// [`ProtocolAdapter::extract_protocol_ir`] ignores its path argument.
+#[derive(Debug, Clone, Default)]
+pub struct ElectrumAdapter {
+    /// Version reported by the adapter.
+    pub version: Option<String>,
+}
+
+impl ElectrumAdapter {
+    /// Create an adapter with no explicit version.
+    pub fn new() -> Self { Self::default() }
+}
+
+impl ProtocolAdapter for ElectrumAdapter {
+    fn name(&self) -> &'static str { "electrum" }
+
+    fn version(&self) -> String {
+        self.version.clone().unwrap_or_else(|| ELECTRUM_PROTOCOL_VERSION.to_string())
+    }
+
+    fn extract_protocol_ir(&self, _path: &Path) -> ProtocolAdapterResult<ProtocolIR> {
+        Ok(electrum_ir())
+    }
+
+    fn capabilities(&self) -> Vec<&'static str> { vec![CAP_RPC] }
+}
+
+/// The Electrum protocol as IR: an `rpc` module with the methods and a
+/// [`NOTIFICATIONS_MODULE_NAME`] module with the subscription pushes.
+pub fn electrum_ir() -> ProtocolIR {
+    let mut definitions: Vec<ProtocolDef> =
+        electrum_methods().into_iter().map(ProtocolDef::RpcMethod).collect();
+    sort_definitions_by_name(&mut definitions);
+    let rpc = ProtocolModule::new(
+        "rpc".to_string(),
+        format!("Electrum server protocol {} methods", ELECTRUM_PROTOCOL_VERSION),
+        definitions,
+    );
+    let notifications = ProtocolModule::new(
+        NOTIFICATIONS_MODULE_NAME.to_string(),
+        "Notifications pushed for Electrum subscriptions".to_string(),
+        electrum_notifications().into_iter().map(ProtocolDef::Message).collect(),
+    );
+    ProtocolIR::new(vec![rpc, notifications])
+}
+
+/// Entry point for the `process_electrum_protocol` binary: writes the Electrum IR to
+/// [`ELECTRUM_IR_FILE`] or the given path.
+pub fn run(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    if args.iter().skip(1).any(|a| a == "-h" || a == "--help") {
+        eprintln!("Usage:");
+        eprintln!("  {} [output_file]   # Write the Electrum protocol IR", args[0]);
+        eprintln!();
+        eprintln!("Default: {}", ELECTRUM_IR_FILE);
+        std::process::exit(1);
+    }
+
+    let project_root = find_project_root()?;
+    let output = args.get(1).map(PathBuf::from).unwrap_or_else(|| PathBuf::from(ELECTRUM_IR_FILE));
+    let output = resolve_ir_output_path(&project_root, &output);
+
+    let protocol_ir = electrum_ir();
+    protocol_ir.to_file(&output)?;
+
+    println!("✓ Wrote Electrum protocol IR: {}", output.display());
+    println!("  Methods: {}", protocol_ir.get_rpc_methods().len());
+    println!("  Notifications: {}", protocol_ir.get_notifications().len());
+    Ok(())
+}
+
+/// All Electrum methods.
+pub fn electrum_methods() -> Vec<RpcDef> {
+    vec![
+        // server.*
+        method(
+            "server.version",
+            "control",
+            "Identify the client and negotiate the protocol version. Must be the first request on a connection; the server may close connections that send it twice.",
+            vec![
+                param("client_name", string("Name and version of the client software"), false),
+                param(
+                    "protocol_version",
+                    string("Protocol version (or a [min, max] range) the client supports"),
+                    false,
+                ),
+            ],
+            array(string("Server software version, then the negotiated protocol version")),
+        ),
+        method(
+            "server.banner",
+            "control",
+            "Return a banner to be shown in the client.",
+            vec![],
+            string("The server banner text"),
+        ),
+        method(
+            "server.donation_address",
+            "control",
+            "Return a server donation address.",
+            vec![],
+            string("A donation address, or an empty string"),
+        ),
+        method(
+            "server.features",
+            "control",
+            "Return a list of features and services supported by the server.",
+            vec![],
+            object(vec![
+                field("genesis_hash", string("Hash of the chain's genesis block"), true),
+                field("hash_function", string("Hash function of script hashes, e.g. sha256"), true),
+                field("server_version", string("Server software version"), true),
+                field("protocol_min", string("Lowest supported protocol version"), true),
+                field("protocol_max", string("Highest supported protocol version"), true),
+                field("pruning", any("History pruning limit, or null"), false),
+                field("hosts", any("Hosts and ports the server is reachable on"), false),
+            ]),
+        ),
+        method(
+            "server.ping",
+            "control",
+            "Ping the server to keep the connection alive.",
+            vec![],
+            none(),
+        ),
+        method(
+            "server.peers.subscribe",
+            "network",
+            "Return the list of peer servers this server knows about, as [ip, host, features] triples.",
+            vec![],
+            any("Array of [ip address, host name, [feature strings]]"),
+        ),
+        // blockchain.block.* / headers
+        method(
+            "blockchain.block.header",
+            "blockchain",
+            "Return the block header at the given height. With a checkpoint height, also return a merkle proof of the header against the checkpoint.",
+            vec![
+                param("height", number("Height of the block"), true),
+                param("cp_height", number("Checkpoint height, or 0 for no proof"), false),
+            ],
+            any("Hex-encoded header, or an object with `header`, `branch` and `root` when cp_height is non-zero"),
+        ),
+        method(
+            "blockchain.block.headers",
+            "blockchain",
+            "Return a concatenated chunk of block headers from the main chain.",
+            vec![
+                param("start_height", number("Height of the first header"), true),
+                param("count", number("Number of headers requested"), true),
+                param("cp_height", number("Checkpoint height, or 0 for no proof"), false),
+            ],
+            object(vec![
+                field("count", number("Number of headers returned"), true),
+                field("hex", string("The headers, hex-encoded and concatenated"), true),
+                field("max", number("Maximum number of headers the server returns in one request"), true),
+                field("root", string("Merkle root of the checkpoint (cp_height only)"), false),
+                field("branch", array(string("Merkle branch hash")), false),
+            ]),
+        ),
+        method(
+            "blockchain.headers.subscribe",
+            "blockchain",
+            "Subscribe to receive block headers when a new block is found. Returns the current tip.",
+            vec![],
+            header_notification_type(),
+        ),
+        method(
+            "blockchain.estimatefee",
+            "blockchain",
+            "Return the estimated fee rate for a transaction to be confirmed within the given number of blocks.",
+            vec![param("number", number("Confirmation target in blocks"), true)],
+            float("Fee rate in BTC per kilobyte, or -1 if the server has no estimate"),
+        ),
+        method(
+            "blockchain.relayfee",
+            "blockchain",
+            "Return the minimum fee rate a low-priority transaction must pay to be relayed.",
+            vec![],
+            float("Fee rate in BTC per kilobyte"),
+        ),
+        // blockchain.scripthash.*
+        method(
+            "blockchain.scripthash.get_balance",
+            "blockchain",
+            "Return the confirmed and unconfirmed balances of a script hash.",
+            vec![scripthash_param()],
+            object(vec![
+                field("confirmed", number("Confirmed balance in satoshis"), true),
+                field(
+                    "unconfirmed",
+                    number("Unconfirmed balance in satoshis; negative when mempool transactions spend confirmed coins"),
+                    true,
+                ),
+            ]),
+        ),
+        method(
+            "blockchain.scripthash.get_history",
+            "blockchain",
+            "Return the confirmed and unconfirmed history of a script hash, confirmed transactions first in blockchain order.",
+            vec![scripthash_param()],
+            array(history_entry("ScripthashHistoryEntry", false)),
+        ),
+        method(
+            "blockchain.scripthash.get_mempool",
+            "blockchain",
+            "Return the unconfirmed transactions of a script hash.",
+            vec![scripthash_param()],
+            array(history_entry("ScripthashMempoolEntry", true)),
+        ),
+        method(
+            "blockchain.scripthash.listunspent",
+            "blockchain",
+            "Return an ordered list of UTXOs sent to a script hash.",
+            vec![scripthash_param()],
+            array(named(
+                "ScripthashUnspentEntry",
+                object(vec![
+                    field("tx_hash", string("Id of the transaction holding the output"), true),
+                    field("tx_pos", number("Index of the output in the transaction"), true),
+                    field("height", number("Confirmation height, or 0 if unconfirmed"), true),
+                    field("value", number("Output value in satoshis"), true),
+                ]),
+            )),
+        ),
+        method(
+            "blockchain.scripthash.subscribe",
+            "blockchain",
+            "Subscribe to a script hash. Returns its current status; a notification is pushed whenever the status changes.",
+            vec![scripthash_param()],
+            any("Status hash of the script hash's history, or null if it has no history"),
+        ),
+        method(
+            "blockchain.scripthash.unsubscribe",
+            "blockchain",
+            "Unsubscribe from a script hash, preventing future notifications if its status changes.",
+            vec![scripthash_param()],
+            boolean("True if the script hash was subscribed"),
+        ),
+        // blockchain.transaction.*
+        method(
+            "blockchain.transaction.broadcast",
+            "rawtransactions",
+            "Broadcast a transaction to the network.",
+            vec![param("raw_tx", string("The raw transaction, hex-encoded"), true)],
+            string("The transaction id"),
+        ),
+        method(
+            "blockchain.transaction.get",
+            "rawtransactions",
+            "Return a raw transaction.",
+            vec![
+                param("tx_hash", string("The transaction id"), true),
+                param("verbose", boolean("Return a decoded transaction object instead of hex"), false),
+            ],
+            any("Hex-encoded transaction, or the decoded transaction when verbose"),
+        ),
+        method(
+            "blockchain.transaction.get_merkle",
+            "rawtransactions",
+            "Return the merkle branch of a confirmed transaction given its id and height.",
+            vec![
+                param("tx_hash", string("The transaction id"), true),
+                param("height", number("Height of the block containing the transaction"), true),
+            ],
+            object(vec![
+                field("block_height", number("Height of the block"), true),
+                field("merkle", array(string("Merkle branch hash")), true),
+                field("pos", number("Index of the transaction in the block"), true),
+            ]),
+        ),
+        method(
+            "blockchain.transaction.id_from_pos",
+            "rawtransactions",
+            "Return a transaction id and optionally a merkle proof, given a block height and a position in the block.",
+            vec![
+                param("height", number("Height of the block"), true),
+                param("tx_pos", number("Index of the transaction in the block"), true),
+                param("merkle", boolean("Also return the merkle branch"), false),
+            ],
+            any("The transaction id, or an object with `tx_hash` and `merkle` when merkle is true"),
+        ),
+        // mempool.*
+        method(
+            "mempool.get_fee_histogram",
+            "mempool",
+            "Return a histogram of the fee rates paid by transactions in the memory pool, as [fee rate, vsize] pairs in descending fee rate order.",
+            vec![],
+            array(any("[fee rate in sat/vbyte, virtual size of transactions paying at least that rate]")),
+        ),
+    ]
+}
+
+/// Notifications pushed for `blockchain.headers.subscribe` and
+/// `blockchain.scripthash.subscribe`, sorted by name.
+pub fn electrum_notifications() -> Vec<MessageDef> {
+    vec![
+        notification(
+            "blockchain.headers.subscribe",
+            "Pushed when the server's chain tip changes.",
+            vec![field("header", header_notification_type(), true)],
+        ),
+        notification(
+            "blockchain.scripthash.subscribe",
+            "Pushed when the status of a subscribed script hash changes.",
+            vec![
+                field("scripthash", string("The script hash"), true),
+                field("status", any("The new status hash, or null if the history is empty"), true),
+            ],
+        ),
+    ]
+}
+
+fn method(
+    name: &str,
+    category: &str,
+    description: &str,
+    params: Vec<ParamDef>,
+    result: TypeDef,
+) -> RpcDef {
+    let result = if matches!(result.kind, TypeKind::Object) {
+        TypeDef { name: "object".to_string(), ..result }
+    } else {
+        result
+    };
+    RpcDef {
+        name: name.to_string(),
+        description: description.to_string(),
+        params,
+        result: Some(result),
+        category: category.to_string(),
+        access_level: method_categorization::access_level_for(category, name),
+        requires_private_keys: determine_requires_private_keys(category, name),
+        version_added: None,
+        version_removed: None,
+        deprecated: None,
+        examples: None,
+        hidden: None,
+        http: None,
+    }
+}
+
+fn notification(topic: &str, description: &str, fields: Vec<FieldDef>) -> MessageDef {
+    MessageDef {
+        name: topic.to_string(),
+        description: description.to_string(),
+        fields,
+        message_type: MessageType::Notification,
+        version: Some(ELECTRUM_PROTOCOL_VERSION.to_string()),
+        topic: Some(topic.to_string()),
+        sequenced: false,
+    }
+}
+
+fn header_notification_type() -> TypeDef {
+    object(vec![
+        field("height", number("Height of the chain tip"), true),
+        field("hex", string("The tip's block header, hex-encoded"), true),
+    ])
+}
+
+/// `get_history` and `get_mempool` entries; only mempool entries always carry a fee.
+fn history_entry(name: &str, mempool_only: bool) -> TypeDef {
+    named(
+        name,
+        object(vec![
+        field("tx_hash", string("The transaction id"), true),
+        // -1 marks a mempool transaction with unconfirmed inputs, so this is not unsigned
+        field(
+            "height",
+            any("Confirmation height; 0 for mempool transactions, -1 if an input is unconfirmed"),
+            true,
+        ),
+        field("fee", number("Transaction fee in satoshis, for mempool transactions"), mempool_only),
+    ]),
+    )
+}
+
+fn scripthash_param() -> ParamDef {
+    param("scripthash", string("The script hash, hex-encoded"), true)
+}
+
+fn primitive(protocol_type: &str, description: &str) -> TypeDef {
+    TypeDef {
+        name: protocol_type.to_string(),
+        description: description.to_string(),
+        kind: TypeKind::Primitive,
+        protocol_type: Some(protocol_type.to_string()),
+        ..TypeDef::default()
+    }
+}
+
+fn string(description: &str) -> TypeDef { primitive("string", description) }
+
+fn number(description: &str) -> TypeDef { primitive("number", description) }
+
+fn boolean(description: &str) -> TypeDef { primitive("boolean", description) }
+
+fn any(description: &str) -> TypeDef { primitive("any", description) }
+
+fn none() -> TypeDef { primitive("none", "") }
+
+/// A fractional number. Top-level primitive results are typed by name, so this one carries
+/// its Rust type.
+fn float(description: &str) -> TypeDef {
+    TypeDef { name: "f64".to_string(), ..number(description) }
+}
+
+/// Array elements that are objects need a type name to become their own struct.
+fn named(name: &str, type_def: TypeDef) -> TypeDef {
+    TypeDef { name: name.to_string(), ..type_def }
+}
+
+fn object(fields: Vec<FieldDef>) -> TypeDef {
+    TypeDef {
+        name: "object".to_string(),
+        kind: TypeKind::Object,
+        fields: Some(fields),
+        protocol_type: Some("object".to_string()),
+        ..TypeDef::default()
+    }
+}
+
+fn array(element: TypeDef) -> TypeDef {
+    TypeDef {
+        name: "array".to_string(),
+        kind: TypeKind::Array,
+        fields: Some(vec![field("field_0", element, true)]),
+        protocol_type: Some("array".to_string()),
+        ..TypeDef::default()
+    }
+}
+
+fn field(name: &str, field_type: TypeDef, required: bool) -> FieldDef {
+    FieldDef {
+        key: FieldKey::Named(name.to_string()),
+        description: field_type.description.clone(),
+        field_type,
+        required,
+        default_value: None,
+        version_added: None,
+        version_removed: None,
+        deprecated: None,
+    }
+}
+
+fn param(name: &str, param_type: TypeDef, required: bool) -> ParamDef {
+    ParamDef {
+        name: name.to_string(),
+        description: param_type.description.clone(),
+        param_type,
+        required,
+        default_value: None,
+        version_added: None,
+        version_removed: None,
+        deprecated: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describes_methods_and_notifications() {
+        let ir = electrum_ir();
+        let names: Vec<_> = ir.get_rpc_methods().iter().map(|m| m.name.clone()).collect();
+        for expected in [
+            "server.version",
+            "blockchain.headers.subscribe",
+            "blockchain.scripthash.get_balance",
+            "blockchain.scripthash.subscribe",
+            "blockchain.transaction.broadcast",
+        ] {
+            assert!(names.contains(&expected.to_string()), "missing {}", expected);
+        }
+        let mut sorted = names.clone();
+        sorted.sort();
+        assert_eq!(names, sorted);
+
+        let notifications = ir.get_notifications();
+        assert_eq!(notifications.len(), 2);
+        assert!(ir.get_module(NOTIFICATIONS_MODULE_NAME).is_some());
+        // Every push has a method to subscribe with
+        for notification in notifications {
+            assert!(names.contains(&notification.name));
+            assert_eq!(notification.topic.as_deref(), Some(notification.name.as_str()));
+            assert!(!notification.sequenced);
+        }
+    }
+
+    #[test]
+    fn types_results_and_params() {
+        let methods = electrum_methods();
+        let get = |name: &str| methods.iter().find(|m| m.name == name).expect("method");
+
+        let balance = get("blockchain.scripthash.get_balance");
+        assert_eq!(balance.category, "blockchain");
+        assert_eq!(balance.params.len(), 1);
+        assert!(balance.params[0].required);
+        let result = balance.result.as_ref().expect("result");
+        assert_eq!(result.name, "object");
+        assert_eq!(result.fields.as_ref().map(Vec::len), Some(2));
+
+        let version = get("server.version");
+        assert!(version.params.iter().all(|p| !p.required));
+        assert!(matches!(version.result.as_ref().map(|r| &r.kind), Some(TypeKind::Array)));
+
+        let ping = get("server.ping");
+        assert_eq!(ping.result.as_ref().and_then(|r| r.protocol_type.as_deref()), Some("none"));
+
+        let broadcast = get("blockchain.transaction.broadcast");
+        assert_eq!(broadcast.category, "rawtransactions");
+    }
+}
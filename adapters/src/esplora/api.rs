@@ -0,0 +1,75 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Esplora HTTP API (checked-in OpenAPI description -> IR).
+//!
+//! Esplora publishes its API as prose (`API.md` in Blockstream's electrs fork), so
+//! `resources/adapters/esplora/openapi.json` transcribes the read-only endpoints into
+//! OpenAPI 3 for [`crate::openapi`]. Hashes and txids are `$ref`s to the `BlockHash` /
+//! `Txid` schemas, and satoshi values carry `x-protocol-type: satoshis`, so generated
+//! clients type them with the same `bitcoin` crate types as the Bitcoin Core client.
+
+use std::path::PathBuf;
+
+use path::{find_project_root, resolve_ir_output_path};
+
+use crate::openapi::OpenApiAdapter;
+use crate::ProtocolAdapter;
+
+/// Default location of the Esplora OpenAPI description, relative to the project root.
+pub const ESPLORA_OPENAPI_FILE: &str = "resources/adapters/esplora/openapi.json";
+
+/// Default output location of the Esplora IR, relative to the project root.
+pub const ESPLORA_IR_FILE: &str = "resources/ir/esplora.ir.json";
+
+/// Entry point for the `process_esplora_openapi` binary: converts an OpenAPI description
+/// (default [`ESPLORA_OPENAPI_FILE`]) into IR (default [`ESPLORA_IR_FILE`]).
+pub fn run(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    if args.iter().skip(1).any(|a| a == "-h" || a == "--help") {
+        eprintln!("Usage:");
+        eprintln!("  {} [openapi_file] [output_file]   # Convert the Esplora API to IR", args[0]);
+        eprintln!();
+        eprintln!("Defaults: {} -> {}", ESPLORA_OPENAPI_FILE, ESPLORA_IR_FILE);
+        std::process::exit(1);
+    }
+
+    let project_root = find_project_root()?;
+    let input =
+        args.get(1).map(PathBuf::from).unwrap_or_else(|| PathBuf::from(ESPLORA_OPENAPI_FILE));
+    let output = args.get(2).map(PathBuf::from).unwrap_or_else(|| PathBuf::from(ESPLORA_IR_FILE));
+    let input = resolve_ir_output_path(&project_root, &input);
+    let output = resolve_ir_output_path(&project_root, &output);
+
+    let protocol_ir = OpenApiAdapter::new().extract_protocol_ir(&input)?;
+    protocol_ir.to_file(&output)?;
+
+    println!("✓ Converted Esplora OpenAPI description to IR: {}", output.display());
+    println!("  Endpoints: {}", protocol_ir.get_rest_endpoints().len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use ir::RestFormat;
+
+    use super::*;
+
+    #[test]
+    fn converts_checked_in_description() {
+        let root = find_project_root().expect("project root");
+        let ir = OpenApiAdapter::new()
+            .extract_protocol_ir(&root.join(ESPLORA_OPENAPI_FILE))
+            .expect("converts");
+        let endpoints = ir.get_rest_endpoints();
+        let find = |name: &str| {
+            endpoints.iter().find(|e| e.name == name).unwrap_or_else(|| panic!("{} missing", name))
+        };
+
+        assert!(endpoints.iter().all(|e| e.bare_path));
+        assert_eq!(find("tx_status").path, "/tx/{txid}/status");
+        assert_eq!(find("address_utxo").params[0].name, "address");
+        assert_eq!(find("blocks_tip_hash").formats, vec![RestFormat::Text]);
+        assert_eq!(find("block_raw").formats, vec![RestFormat::Bin]);
+        let utxo = find("address_utxo").result.as_ref().and_then(|r| r.array_element_type());
+        assert_eq!(utxo.map(|t| t.name.as_str()), Some("Utxo"));
+    }
+}
@@ -16,17 +16,61 @@ use ir::ProtocolIR;
 
 /// Bitcoin Core type definitions and utilities
 pub mod bitcoin_core {
+    /// Captured `bitcoin-cli help` output (help text dumps -> IR)
+    pub mod cli_help;
+    /// Version history across per-release OpenRPC documents (directory of documents -> IR)
+    pub mod history;
     /// Bitcoin Core OpenRPC converter and version filtering (openrpc.json / getopenrpcinfo -> IR)
     pub mod openrpc;
+    /// Bitcoin Core REST interface endpoints (`/rest/...`) as a `rest` ProtocolModule
+    pub mod rest;
+    /// Bitcoin Core `RPCHelpMan` definitions (C++ source checkout -> IR)
+    pub mod rpchelpman;
     /// Bitcoin Core type definitions and utilities
     pub mod types;
+    /// Bitcoin Core ZMQ publishers (`-zmqpub...`) as notifications in a `zmq` ProtocolModule
+    pub mod zmq;
+}
+
+/// btcd (btcsuite) support
+pub mod btcd {
+    /// btcd btcjson command/result definitions (checked-in schema fixture -> IR)
+    pub mod btcjson;
+}
+
+/// Core Lightning support
+pub mod core_lightning {
+    /// Core Lightning `doc/schemas` command schemas (checked-in fixtures -> IR)
+    pub mod schema;
+}
+
+/// LND support
+pub mod lnd {
+    /// LND `lnrpc` protobuf definitions with `google.api.http` REST bindings (checked-in fixtures -> IR)
+    pub mod proto;
+}
+
+/// Electrum server protocol support
+pub mod electrum {
+    /// Electrum protocol 1.4 methods and subscription notifications (built-in description -> IR)
+    pub mod protocol;
+}
+
+/// Esplora HTTP API support
+pub mod esplora {
+    /// Esplora REST endpoints (checked-in OpenAPI description -> IR)
+    pub mod api;
 }
 
 pub mod adapter_facade;
 pub mod conversion_helpers;
 pub mod normalization_registry;
+pub mod openapi;
+pub mod openrpc;
+pub mod openrpc_export;
 pub mod protocol_adapter;
 pub mod rpc_adapter;
+pub mod subscription;
 
 // Re-export the main ProtocolAdapter types for convenience
 pub use adapter_facade::*;
@@ -0,0 +1,1144 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! LND `lnrpc` protobuf definitions -> IR.
+//!
+//! LND describes its API as proto3 services (`lnrpc/lightning.proto` plus the subserver
+//! protos). Its REST interface is a grpc-gateway: each unary RPC is bound to an HTTP route
+//! by a `google.api.http` option, and messages travel as proto3 JSON with the proto field
+//! names. `resources/adapters/lnd/` holds a checked-in copy with the bindings inlined.
+//!
+//! The definitions map onto the IR as follows:
+//!
+//! - each unary `rpc` with a `google.api.http` binding -> `RpcDef` with `http` set; the
+//!   request message's fields become parameters in declaration order, required only when
+//!   the route binds them in its path
+//! - streaming RPCs and RPCs without a binding are skipped: the gateway serves streams as
+//!   newline-delimited JSON, which does not fit request/response
+//! - the response message -> the `object` result; a message without fields -> `any`
+//! - every `message` / `enum` -> a `ProtocolDef::Type`, nested ones named `OuterInner`
+//! - scalars follow the proto3 JSON mapping: `bool` -> `boolean`; the 32-bit integers,
+//!   `float` and `double` -> `number`; `string`, `bytes` (base64) and the 64-bit integers
+//!   (decimal strings) -> `string`
+//! - enums -> `string` (the gateway writes value names), with the names in the description
+//! - `repeated` -> `TypeKind::Array`; `map<K, V>` -> `any`
+//! - response fields are required except message-typed, `optional` and `oneof` fields,
+//!   which LND's marshaler (`EmitUnpopulated`) may leave null or out
+//!
+//! The parser covers the proto3 subset LND uses: `syntax`, `package`, `import`, `option`,
+//! nested `message` / `enum`, `oneof`, `map<K, V>`, field options, `reserved`, and
+//! `service` blocks. Comments directly above a definition become its description.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use ir::{
+    Deprecation, FieldDef, FieldKey, HttpBinding, HttpMethod, ParamDef, ProtocolDef, ProtocolIR,
+    ProtocolModule, RpcDef, TypeDef, TypeKind, VariantDef,
+};
+use path::{find_project_root, resolve_ir_output_path};
+use semantics::method_categorization;
+
+use crate::conversion_helpers::{determine_requires_private_keys, sort_definitions_by_name};
+use crate::{ProtocolAdapter, ProtocolAdapterError, ProtocolAdapterResult, CAP_RPC};
+
+/// Workspace-relative directory of the checked-in LND proto fixtures.
+pub const LND_PROTO_DIR: &str = "resources/adapters/lnd";
+
+/// Workspace-relative path of the LND IR generated from [`LND_PROTO_DIR`].
+pub const LND_IR_FILE: &str = "resources/ir/lnd.ir.json";
+
+/// Adapter for LND's `.proto` service definitions.
+#[derive(Debug, Clone, Default)]
+pub struct LndAdapter {
+    /// Version reported by the adapter.
+    pub version: Option<String>,
+}
+
+impl LndAdapter {
+    /// Create an adapter with no explicit version.
+    pub fn new() -> Self { Self::default() }
+}
+
+impl ProtocolAdapter for LndAdapter {
+    fn name(&self) -> &'static str { "lnd" }
+
+    fn version(&self) -> String { self.version.clone().unwrap_or_else(|| "latest".to_string()) }
+
+    /// Reads a single `.proto` file, or every `*.proto` file in a directory.
+    fn extract_protocol_ir(&self, path: &Path) -> ProtocolAdapterResult<ProtocolIR> {
+        let files = if path.is_dir() {
+            let mut files = Vec::new();
+            for entry in std::fs::read_dir(path)? {
+                let file = entry?.path();
+                if file.extension().is_some_and(|ext| ext == "proto") {
+                    files.push(file);
+                }
+            }
+            files.sort();
+            files
+        } else {
+            vec![path.to_path_buf()]
+        };
+
+        let mut sources = Vec::new();
+        for file in files {
+            sources.push(std::fs::read_to_string(&file)?);
+        }
+        convert_sources(&sources)
+    }
+
+    fn capabilities(&self) -> Vec<&'static str> { vec![CAP_RPC] }
+}
+
+/// Parses proto sources and converts them into a `ProtocolIR` with a single `rpc` module.
+///
+/// Messages and enums of all sources share one namespace, so cross-file references resolve.
+pub fn convert_sources(sources: &[String]) -> ProtocolAdapterResult<ProtocolIR> {
+    let mut schema = ProtoSchema::default();
+    for source in sources {
+        Parser::new(source)?.parse_file(&mut schema)?;
+    }
+
+    let mut definitions = Vec::new();
+    for message in schema.messages.values() {
+        definitions.push(ProtocolDef::Type(schema.message_type(message, &mut Vec::new())?));
+    }
+    for proto_enum in schema.enums.values() {
+        definitions.push(ProtocolDef::Type(enum_type(proto_enum)));
+    }
+    for rpc in &schema.rpcs {
+        if let Some(method) = schema.convert_rpc(rpc)? {
+            definitions.push(ProtocolDef::RpcMethod(method));
+        }
+    }
+
+    sort_definitions_by_name(&mut definitions);
+
+    let module = ProtocolModule::new(
+        "rpc".to_string(),
+        "LND Lightning REST API (grpc-gateway)".to_string(),
+        definitions,
+    );
+    Ok(ProtocolIR::new(vec![module]))
+}
+
+/// Entry point for the `process_lnd_protos` binary: converts a proto directory (default
+/// [`LND_PROTO_DIR`]) into IR (default [`LND_IR_FILE`]).
+pub fn run(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    if args.iter().skip(1).any(|a| a == "-h" || a == "--help") {
+        eprintln!("Usage:");
+        eprintln!("  {} [proto_dir_or_file] [output_file]   # Convert LND protos to IR", args[0]);
+        eprintln!();
+        eprintln!("Defaults: {} -> {}", LND_PROTO_DIR, LND_IR_FILE);
+        std::process::exit(1);
+    }
+
+    let project_root = find_project_root()?;
+    let input = args.get(1).map(PathBuf::from).unwrap_or_else(|| PathBuf::from(LND_PROTO_DIR));
+    let output = args.get(2).map(PathBuf::from).unwrap_or_else(|| PathBuf::from(LND_IR_FILE));
+    let input = resolve_ir_output_path(&project_root, &input);
+    let output = resolve_ir_output_path(&project_root, &output);
+
+    let protocol_ir = LndAdapter::new().extract_protocol_ir(&input)?;
+    protocol_ir.to_file(&output)?;
+
+    println!("✓ Converted LND protos to IR: {}", output.display());
+    println!("  Methods: {}", protocol_ir.get_rpc_methods().len());
+    Ok(())
+}
+
+/// Groups an RPC the way `lncli help` does, using the category names the rest of the
+/// pipeline understands. RPCs not listed fall back to `util`.
+pub fn category_for(rpc: &str) -> &'static str {
+    match rpc {
+        "GetInfo" | "GetRecoveryInfo" | "StopDaemon" | "DebugLevel" => "control",
+        "ConnectPeer" | "DisconnectPeer" | "ListPeers" | "GetNodeInfo" | "DescribeGraph"
+        | "GetNetworkInfo" | "GetChanInfo" => "network",
+        "ListChannels"
+        | "OpenChannelSync"
+        | "CloseChannel"
+        | "ChannelBalance"
+        | "PendingChannels"
+        | "ClosedChannels"
+        | "UpdateChannelPolicy" => "channel",
+        "AddInvoice" | "ListInvoices" | "LookupInvoice" => "invoice",
+        "SendPaymentSync" | "SendToRouteSync" | "DecodePayReq" | "ListPayments" | "QueryRoutes" =>
+            "payment",
+        "WalletBalance" | "SendCoins" | "SendMany" | "NewAddress" | "ListUnspent"
+        | "GetTransactions" | "EstimateFee" => "wallet",
+        _ => "util",
+    }
+}
+
+fn proto_error(message: impl Into<String>) -> ProtocolAdapterError {
+    ProtocolAdapterError::Message(message.into())
+}
+
+// --- Parsed schema -------------------------------------------------------------------
+
+#[derive(Debug, Default)]
+struct ProtoSchema {
+    /// Messages by package-relative qualified name (`Invoice`, `Invoice.Htlc`)
+    messages: BTreeMap<String, ProtoMessage>,
+    /// Enums by package-relative qualified name (`Invoice.InvoiceState`)
+    enums: BTreeMap<String, ProtoEnum>,
+    /// Service RPCs in declaration order
+    rpcs: Vec<ProtoRpc>,
+}
+
+#[derive(Debug)]
+struct ProtoMessage {
+    name: String,
+    description: String,
+    fields: Vec<ProtoField>,
+}
+
+#[derive(Debug)]
+struct ProtoField {
+    name: String,
+    type_name: String,
+    repeated: bool,
+    /// proto3 `optional` or a `oneof` member
+    optional: bool,
+    map: bool,
+    deprecated: bool,
+    description: String,
+}
+
+#[derive(Debug)]
+struct ProtoEnum {
+    name: String,
+    description: String,
+    values: Vec<(String, String)>,
+}
+
+#[derive(Debug)]
+struct ProtoRpc {
+    name: String,
+    description: String,
+    request: String,
+    response: String,
+    streaming: bool,
+    http: Option<HttpBinding>,
+}
+
+/// What a field's type name resolves to.
+enum Resolved<'a> {
+    Scalar(&'static str),
+    Message(&'a ProtoMessage),
+    Enum(&'a ProtoEnum),
+}
+
+/// proto3 JSON mapping of the scalar types onto the IR's protocol types.
+fn scalar_protocol_type(type_name: &str) -> Option<&'static str> {
+    match type_name {
+        "bool" => Some("boolean"),
+        "int32" | "uint32" | "sint32" | "fixed32" | "sfixed32" | "float" | "double" =>
+            Some("number"),
+        "string" | "bytes" | "int64" | "uint64" | "sint64" | "fixed64" | "sfixed64" =>
+            Some("string"),
+        _ => None,
+    }
+}
+
+/// Description of a scalar whose JSON form differs from its proto type.
+fn scalar_note(type_name: &str) -> &'static str {
+    match type_name {
+        "bytes" => "base64-encoded bytes",
+        "int64" | "uint64" | "sint64" | "fixed64" | "sfixed64" =>
+            "64-bit integer encoded as a decimal string",
+        _ => "",
+    }
+}
+
+/// IR type name of a qualified proto name: `Invoice.Htlc` -> `InvoiceHtlc`.
+fn ir_type_name(qualified: &str) -> String { qualified.replace('.', "") }
+
+fn primitive(protocol_type: &str, description: &str) -> TypeDef {
+    TypeDef {
+        name: protocol_type.to_string(),
+        description: description.to_string(),
+        kind: TypeKind::Primitive,
+        protocol_type: Some(protocol_type.to_string()),
+        ..TypeDef::default()
+    }
+}
+
+fn enum_type(proto_enum: &ProtoEnum) -> TypeDef {
+    let variants = proto_enum
+        .values
+        .iter()
+        .map(|(name, value)| VariantDef {
+            name: name.clone(),
+            description: String::new(),
+            value: Some(value.clone()),
+            associated_data: None,
+        })
+        .collect();
+    TypeDef {
+        name: ir_type_name(&proto_enum.name),
+        description: proto_enum.description.clone(),
+        kind: TypeKind::Enum,
+        variants: Some(variants),
+        protocol_type: Some("string".to_string()),
+        ..TypeDef::default()
+    }
+}
+
+/// Path parameters of a route template: `{chan_point.output_index}` binds `chan_point`.
+fn path_fields(path: &str) -> Vec<&str> {
+    path.split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split('}').next())
+        .map(|binding| binding.split(['.', '=']).next().unwrap_or(binding))
+        .collect()
+}
+
+impl ProtoSchema {
+    /// Resolves `type_name` as referenced from inside `scope` (a qualified message name),
+    /// searching the innermost scope outward like protoc does. A package prefix
+    /// (`lnrpc.Invoice`, `.lnrpc.Invoice`) is dropped.
+    fn resolve(&self, type_name: &str, scope: &str) -> ProtocolAdapterResult<Resolved<'_>> {
+        if let Some(protocol_type) = scalar_protocol_type(type_name) {
+            return Ok(Resolved::Scalar(protocol_type));
+        }
+
+        let name = type_name.trim_start_matches('.');
+        let mut candidates = Vec::new();
+        let mut prefix = scope;
+        loop {
+            candidates.push(if prefix.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}.{}", prefix, name)
+            });
+            match prefix.rfind('.') {
+                Some(dot) => prefix = &prefix[..dot],
+                None if !prefix.is_empty() => prefix = "",
+                None => break,
+            }
+        }
+        if let Some((_, unqualified)) = name.split_once('.') {
+            candidates.push(unqualified.to_string());
+        }
+
+        for candidate in &candidates {
+            if let Some(message) = self.messages.get(candidate) {
+                return Ok(Resolved::Message(message));
+            }
+            if let Some(proto_enum) = self.enums.get(candidate) {
+                return Ok(Resolved::Enum(proto_enum));
+            }
+        }
+        Err(proto_error(format!(
+            "Unknown proto type '{}' referenced from '{}'",
+            type_name,
+            if scope.is_empty() { "<top level>" } else { scope }
+        )))
+    }
+
+    /// Object type for a message. `stack` holds the messages being expanded; a recursive
+    /// reference becomes `any` instead of an infinitely deep type.
+    fn message_type(
+        &self,
+        message: &ProtoMessage,
+        stack: &mut Vec<String>,
+    ) -> ProtocolAdapterResult<TypeDef> {
+        if message.fields.is_empty() || stack.contains(&message.name) {
+            return Ok(primitive("any", &message.description));
+        }
+
+        stack.push(message.name.clone());
+        let mut fields = Vec::new();
+        for field in &message.fields {
+            let field_type = self.field_type(field, &message.name, stack)?;
+            let required = !field.optional && !matches!(field_type.kind, TypeKind::Object);
+            fields.push(FieldDef {
+                key: FieldKey::Named(field.name.clone()),
+                field_type,
+                required,
+                description: field_description(field),
+                default_value: None,
+                version_added: None,
+                version_removed: None,
+                deprecated: field.deprecated.then(Deprecation::default),
+            });
+        }
+        stack.pop();
+
+        Ok(TypeDef {
+            name: ir_type_name(&message.name),
+            description: message.description.clone(),
+            kind: TypeKind::Object,
+            fields: Some(fields),
+            protocol_type: Some("object".to_string()),
+            ..TypeDef::default()
+        })
+    }
+
+    fn field_type(
+        &self,
+        field: &ProtoField,
+        scope: &str,
+        stack: &mut Vec<String>,
+    ) -> ProtocolAdapterResult<TypeDef> {
+        let element = if field.map {
+            primitive("any", "map keyed by the proto map key")
+        } else {
+            match self.resolve(&field.type_name, scope)? {
+                Resolved::Scalar(protocol_type) =>
+                    primitive(protocol_type, scalar_note(&field.type_name)),
+                Resolved::Enum(proto_enum) => {
+                    let names = proto_enum
+                        .values
+                        .iter()
+                        .map(|(name, _)| name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    primitive("string", &format!("One of: {}", names))
+                }
+                Resolved::Message(message) => self.message_type(message, stack)?,
+            }
+        };
+
+        if !field.repeated || field.map {
+            return Ok(element);
+        }
+        Ok(TypeDef {
+            name: "array".to_string(),
+            kind: TypeKind::Array,
+            fields: Some(vec![FieldDef {
+                key: FieldKey::Named("field_0".to_string()),
+                field_type: element,
+                required: true,
+                description: String::new(),
+                default_value: None,
+                version_added: None,
+                version_removed: None,
+                deprecated: None,
+            }]),
+            protocol_type: Some("array".to_string()),
+            ..TypeDef::default()
+        })
+    }
+
+    fn convert_rpc(&self, rpc: &ProtoRpc) -> ProtocolAdapterResult<Option<RpcDef>> {
+        let Some(http) = rpc.http.clone().filter(|_| !rpc.streaming) else {
+            return Ok(None);
+        };
+
+        let request = match self.resolve(&rpc.request, "")? {
+            Resolved::Message(message) => message,
+            _ => return Err(proto_error(format!("RPC {} request is not a message", rpc.name))),
+        };
+        let bound = path_fields(&http.path);
+        let mut params = Vec::new();
+        for field in &request.fields {
+            params.push(ParamDef {
+                name: field.name.clone(),
+                param_type: self.field_type(field, &request.name, &mut Vec::new())?,
+                required: bound.contains(&field.name.as_str()),
+                description: field_description(field),
+                default_value: None,
+                version_added: None,
+                version_removed: None,
+                deprecated: field.deprecated.then(Deprecation::default),
+            });
+        }
+        if let Some(unbound) = bound.iter().find(|name| !params.iter().any(|p| &p.name == *name)) {
+            return Err(proto_error(format!(
+                "RPC {} binds '{}' in its path, but {} has no such field",
+                rpc.name, unbound, request.name
+            )));
+        }
+
+        let mut result = match self.resolve(&rpc.response, "")? {
+            Resolved::Message(message) => self.message_type(message, &mut Vec::new())?,
+            _ => return Err(proto_error(format!("RPC {} response is not a message", rpc.name))),
+        };
+        if matches!(result.kind, TypeKind::Object) {
+            result.name = "object".to_string();
+        }
+
+        let category = category_for(&rpc.name).to_string();
+        Ok(Some(RpcDef {
+            name: rpc.name.clone(),
+            description: rpc.description.clone(),
+            params,
+            result: Some(result),
+            access_level: method_categorization::access_level_for(&category, &rpc.name),
+            requires_private_keys: determine_requires_private_keys(&category, &rpc.name),
+            category,
+            hidden: None,
+            version_added: None,
+            version_removed: None,
+            deprecated: None,
+            examples: None,
+            http: Some(http),
+        }))
+    }
+}
+
+fn field_description(field: &ProtoField) -> String {
+    if field.deprecated {
+        format!("Deprecated. {}", field.description).trim_end().to_string()
+    } else {
+        field.description.clone()
+    }
+}
+
+// --- Lexer ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    /// Identifiers, keywords, numbers and dotted names
+    Word(String),
+    /// String literal (unescaped)
+    Str(String),
+    /// Single punctuation character
+    Sym(char),
+}
+
+#[derive(Debug)]
+struct Token {
+    tok: Tok,
+    line: usize,
+    /// Comment block directly above the token
+    doc: Option<String>,
+}
+
+/// Turns a raw `//` or `/* */` comment into description text. `lncli:` lines name the CLI
+/// command and are dropped.
+fn comment_text(raw: &str) -> String {
+    let body = raw
+        .strip_prefix("/*")
+        .map(|b| b.strip_suffix("*/").unwrap_or(b))
+        .or_else(|| raw.strip_prefix("//"))
+        .unwrap_or(raw);
+    body.lines()
+        .map(|line| line.trim().trim_start_matches('*').trim())
+        .filter(|line| !line.is_empty() && !line.starts_with("lncli:"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn tokenize(source: &str) -> ProtocolAdapterResult<Vec<Token>> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens: Vec<Token> = Vec::new();
+    // Comment text with the line it ends on; consecutive comment lines are joined
+    let mut pending: Option<(String, usize)> = None;
+    let mut line = 1;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\n' {
+            line += 1;
+            i += 1;
+        } else if c.is_whitespace() {
+            i += 1;
+        } else if c == '/' && matches!(chars.get(i + 1), Some('/') | Some('*')) {
+            let start_line = line;
+            let start = i;
+            if chars[i + 1] == '/' {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            } else {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    if chars[i] == '\n' {
+                        line += 1;
+                    }
+                    i += 1;
+                }
+                i = (i + 2).min(chars.len());
+            }
+            // Trailing comments on a definition's own line document nothing that follows
+            if tokens.last().is_some_and(|t| t.line == start_line) {
+                continue;
+            }
+            let text = comment_text(&chars[start..i].iter().collect::<String>());
+            pending = match pending.take() {
+                Some((previous, end)) if end + 1 >= start_line && !previous.is_empty() =>
+                    Some((format!("{} {}", previous, text).trim().to_string(), line)),
+                _ => Some((text, line)),
+            };
+        } else {
+            let token_line = line;
+            let tok = if c == '"' || c == '\'' {
+                let quote = c;
+                let mut value = String::new();
+                i += 1;
+                while i < chars.len() && chars[i] != quote {
+                    if chars[i] == '\\' && i + 1 < chars.len() {
+                        i += 1;
+                    }
+                    value.push(chars[i]);
+                    i += 1;
+                }
+                if i >= chars.len() {
+                    return Err(proto_error(format!("Unterminated string on line {}", token_line)));
+                }
+                i += 1;
+                Tok::Str(value)
+            } else if c.is_alphanumeric() || c == '_' || c == '.' {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || matches!(chars[i], '_' | '.'))
+                {
+                    i += 1;
+                }
+                Tok::Word(chars[start..i].iter().collect())
+            } else {
+                i += 1;
+                Tok::Sym(c)
+            };
+            let doc = pending
+                .take()
+                .filter(|(text, end)| end + 1 >= token_line && !text.is_empty())
+                .map(|(text, _)| text);
+            tokens.push(Token { tok, line: token_line, doc });
+        }
+    }
+    Ok(tokens)
+}
+
+// --- Parser --------------------------------------------------------------------------
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn new(source: &str) -> ProtocolAdapterResult<Self> {
+        Ok(Self { tokens: tokenize(source)?, pos: 0 })
+    }
+
+    fn peek(&self) -> Option<&Tok> { self.tokens.get(self.pos).map(|t| &t.tok) }
+
+    fn at_word(&self, word: &str) -> bool { matches!(self.peek(), Some(Tok::Word(w)) if w == word) }
+
+    fn at_sym(&self, sym: char) -> bool { self.peek() == Some(&Tok::Sym(sym)) }
+
+    /// Doc comment attached to the current token.
+    fn doc(&self) -> String {
+        self.tokens.get(self.pos).and_then(|t| t.doc.clone()).unwrap_or_default()
+    }
+
+    fn error(&self, expected: &str) -> ProtocolAdapterError {
+        match self.tokens.get(self.pos) {
+            Some(token) => proto_error(format!(
+                "Expected {} on line {}, found {:?}",
+                expected, token.line, token.tok
+            )),
+            None => proto_error(format!("Expected {}, found end of file", expected)),
+        }
+    }
+
+    fn next(&mut self) -> Option<Tok> {
+        let tok = self.tokens.get(self.pos).map(|t| t.tok.clone());
+        self.pos += 1;
+        tok
+    }
+
+    fn word(&mut self) -> ProtocolAdapterResult<String> {
+        match self.peek() {
+            Some(Tok::Word(w)) => {
+                let w = w.clone();
+                self.pos += 1;
+                Ok(w)
+            }
+            _ => Err(self.error("an identifier")),
+        }
+    }
+
+    fn expect_sym(&mut self, sym: char) -> ProtocolAdapterResult<()> {
+        if self.at_sym(sym) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("'{}'", sym)))
+        }
+    }
+
+    /// Skips to the end of the current statement: the next `;` outside braces, or the
+    /// closing brace of a block statement.
+    fn skip_statement(&mut self) -> ProtocolAdapterResult<()> {
+        let mut depth = 0usize;
+        loop {
+            match self.next() {
+                Some(Tok::Sym('{')) => depth += 1,
+                Some(Tok::Sym('}')) => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0 && !self.at_sym(';') {
+                        return Ok(());
+                    }
+                }
+                Some(Tok::Sym(';')) if depth == 0 => return Ok(()),
+                Some(_) => {}
+                None => return Err(proto_error("Unexpected end of file in statement")),
+            }
+        }
+    }
+
+    fn parse_file(&mut self, schema: &mut ProtoSchema) -> ProtocolAdapterResult<()> {
+        while let Some(tok) = self.peek() {
+            match tok {
+                Tok::Word(w) if w == "message" => self.parse_message(schema, "")?,
+                Tok::Word(w) if w == "enum" => self.parse_enum(schema, "")?,
+                Tok::Word(w) if w == "service" => self.parse_service(schema)?,
+                Tok::Sym(';') => self.pos += 1,
+                _ => self.skip_statement()?,
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_message(
+        &mut self,
+        schema: &mut ProtoSchema,
+        scope: &str,
+    ) -> ProtocolAdapterResult<()> {
+        let description = self.doc();
+        self.pos += 1; // `message`
+        let name = self.word()?;
+        let qualified = if scope.is_empty() { name } else { format!("{}.{}", scope, name) };
+        self.expect_sym('{')?;
+
+        let mut fields = Vec::new();
+        loop {
+            match self.peek() {
+                Some(Tok::Sym('}')) => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(Tok::Sym(';')) => self.pos += 1,
+                Some(Tok::Word(w)) => match w.as_str() {
+                    "message" => self.parse_message(schema, &qualified)?,
+                    "enum" => self.parse_enum(schema, &qualified)?,
+                    "option" | "reserved" | "extensions" => self.skip_statement()?,
+                    "oneof" => {
+                        self.pos += 1;
+                        self.word()?;
+                        self.expect_sym('{')?;
+                        while !self.at_sym('}') {
+                            if self.at_word("option") {
+                                self.skip_statement()?;
+                            } else {
+                                let mut field = self.parse_field()?;
+                                field.optional = true;
+                                fields.push(field);
+                            }
+                        }
+                        self.pos += 1;
+                    }
+                    _ => fields.push(self.parse_field()?),
+                },
+                _ => return Err(self.error("a field, nested definition or '}'")),
+            }
+        }
+
+        schema
+            .messages
+            .insert(qualified.clone(), ProtoMessage { name: qualified, description, fields });
+        Ok(())
+    }
+
+    fn parse_field(&mut self) -> ProtocolAdapterResult<ProtoField> {
+        let description = self.doc();
+        let mut field = ProtoField {
+            name: String::new(),
+            type_name: String::new(),
+            repeated: false,
+            optional: false,
+            map: false,
+            deprecated: false,
+            description,
+        };
+
+        match self.peek() {
+            Some(Tok::Word(w)) if w == "repeated" => {
+                field.repeated = true;
+                self.pos += 1;
+            }
+            Some(Tok::Word(w)) if w == "optional" => {
+                field.optional = true;
+                self.pos += 1;
+            }
+            _ => {}
+        }
+
+        if self.at_word("map")
+            && self.tokens.get(self.pos + 1).map(|t| &t.tok) == Some(&Tok::Sym('<'))
+        {
+            self.pos += 2;
+            self.word()?;
+            self.expect_sym(',')?;
+            field.type_name = self.word()?;
+            self.expect_sym('>')?;
+            field.map = true;
+        } else {
+            field.type_name = self.word()?;
+        }
+        field.name = self.word()?;
+        self.expect_sym('=')?;
+        self.word()?;
+
+        if self.at_sym('[') {
+            self.pos += 1;
+            while !self.at_sym(']') {
+                let key = match self.next() {
+                    Some(Tok::Word(w)) => w,
+                    Some(Tok::Sym('(')) => {
+                        let name = self.word()?;
+                        self.expect_sym(')')?;
+                        name
+                    }
+                    _ => return Err(self.error("a field option")),
+                };
+                self.expect_sym('=')?;
+                let value = self.next();
+                if key == "deprecated" && value == Some(Tok::Word("true".to_string())) {
+                    field.deprecated = true;
+                }
+                if self.at_sym(',') {
+                    self.pos += 1;
+                }
+            }
+            self.pos += 1;
+        }
+        self.expect_sym(';')?;
+        Ok(field)
+    }
+
+    fn parse_enum(&mut self, schema: &mut ProtoSchema, scope: &str) -> ProtocolAdapterResult<()> {
+        let description = self.doc();
+        self.pos += 1; // `enum`
+        let name = self.word()?;
+        let qualified = if scope.is_empty() { name } else { format!("{}.{}", scope, name) };
+        self.expect_sym('{')?;
+
+        let mut values = Vec::new();
+        loop {
+            match self.peek() {
+                Some(Tok::Sym('}')) => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(Tok::Sym(';')) => self.pos += 1,
+                Some(Tok::Word(w)) if w == "option" || w == "reserved" => self.skip_statement()?,
+                Some(Tok::Word(_)) => {
+                    let value_name = self.word()?;
+                    self.expect_sym('=')?;
+                    let negative = self.at_sym('-');
+                    if negative {
+                        self.pos += 1;
+                    }
+                    let number = self.word()?;
+                    values
+                        .push((value_name, if negative { format!("-{}", number) } else { number }));
+                    if self.at_sym('[') {
+                        while !self.at_sym(']') {
+                            self.pos += 1;
+                        }
+                        self.pos += 1;
+                    }
+                    self.expect_sym(';')?;
+                }
+                _ => return Err(self.error("an enum value or '}'")),
+            }
+        }
+
+        schema.enums.insert(qualified.clone(), ProtoEnum { name: qualified, description, values });
+        Ok(())
+    }
+
+    fn parse_service(&mut self, schema: &mut ProtoSchema) -> ProtocolAdapterResult<()> {
+        self.pos += 1; // `service`
+        self.word()?;
+        self.expect_sym('{')?;
+        loop {
+            match self.peek() {
+                Some(Tok::Sym('}')) => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                Some(Tok::Sym(';')) => self.pos += 1,
+                Some(Tok::Word(w)) if w == "rpc" => {
+                    let rpc = self.parse_rpc()?;
+                    schema.rpcs.push(rpc);
+                }
+                Some(_) => self.skip_statement()?,
+                None => return Err(self.error("'}'")),
+            }
+        }
+    }
+
+    /// `(stream? Type)`; returns the type and whether it is streamed.
+    fn parse_rpc_type(&mut self) -> ProtocolAdapterResult<(String, bool)> {
+        self.expect_sym('(')?;
+        let streaming = self.at_word("stream");
+        if streaming {
+            self.pos += 1;
+        }
+        let type_name = self.word()?;
+        self.expect_sym(')')?;
+        Ok((type_name, streaming))
+    }
+
+    fn parse_rpc(&mut self) -> ProtocolAdapterResult<ProtoRpc> {
+        let description = self.doc();
+        self.pos += 1; // `rpc`
+        let name = self.word()?;
+        let (request, client_streaming) = self.parse_rpc_type()?;
+        if self.word()? != "returns" {
+            return Err(proto_error(format!("Expected 'returns' in rpc {}", name)));
+        }
+        let (response, server_streaming) = self.parse_rpc_type()?;
+
+        let mut http = None;
+        if self.at_sym('{') {
+            self.pos += 1;
+            while !self.at_sym('}') {
+                if self.at_word("option") && self.is_http_option() {
+                    http = Some(self.parse_http_option(&name)?);
+                } else {
+                    self.skip_statement()?;
+                }
+            }
+            self.pos += 1;
+        } else {
+            self.expect_sym(';')?;
+        }
+
+        Ok(ProtoRpc {
+            name,
+            description,
+            request,
+            response,
+            streaming: client_streaming || server_streaming,
+            http,
+        })
+    }
+
+    /// Whether the statement at the cursor is `option (google.api.http) = ...`.
+    fn is_http_option(&self) -> bool {
+        let ahead = |offset: usize| self.tokens.get(self.pos + offset).map(|t| &t.tok);
+        ahead(1) == Some(&Tok::Sym('('))
+            && ahead(2) == Some(&Tok::Word("google.api.http".to_string()))
+            && ahead(3) == Some(&Tok::Sym(')'))
+    }
+
+    /// Parses `option (google.api.http) = { get: "/v1/..." body: "*" };`. Nested
+    /// `additional_bindings` blocks are skipped; the primary binding is what REST clients use.
+    fn parse_http_option(&mut self, rpc: &str) -> ProtocolAdapterResult<HttpBinding> {
+        self.pos += 4; // option ( google.api.http )
+        self.expect_sym('=')?;
+        self.expect_sym('{')?;
+
+        let mut route = None;
+        let mut body = None;
+        while !self.at_sym('}') {
+            let key = self.word()?;
+            if self.at_sym('{') {
+                self.skip_statement()?;
+                continue;
+            }
+            self.expect_sym(':')?;
+            let value = match self.next() {
+                Some(Tok::Str(value)) => value,
+                _ =>
+                    return Err(proto_error(format!(
+                        "Expected a string for '{}' in rpc {}",
+                        key, rpc
+                    ))),
+            };
+            let method = match key.as_str() {
+                "get" => Some(HttpMethod::Get),
+                "post" => Some(HttpMethod::Post),
+                "put" => Some(HttpMethod::Put),
+                "patch" => Some(HttpMethod::Patch),
+                "delete" => Some(HttpMethod::Delete),
+                _ => None,
+            };
+            match (method, key.as_str()) {
+                (Some(method), _) => route = Some((method, value)),
+                (None, "body") => body = Some(value),
+                _ => {}
+            }
+            if self.at_sym(',') || self.at_sym(';') {
+                self.pos += 1;
+            }
+        }
+        self.pos += 1;
+        if self.at_sym(';') {
+            self.pos += 1;
+        }
+
+        let (method, path) = route
+            .ok_or_else(|| proto_error(format!("google.api.http on rpc {} has no route", rpc)))?;
+        Ok(HttpBinding { method, path, body })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"
+syntax = "proto3";
+package lnrpc;
+import "google/api/annotations.proto";
+
+service Lightning {
+    /* lncli: `getnodeinfo`
+    GetNodeInfo returns the latest advertised information for a node.
+    */
+    rpc GetNodeInfo (NodeInfoRequest) returns (NodeInfo) {
+        option (google.api.http) = {
+            get: "/v1/graph/node/{pub_key}"
+        };
+    }
+
+    // Streams invoice updates.
+    rpc SubscribeInvoices (NodeInfoRequest) returns (stream NodeInfo) {
+        option (google.api.http) = { get: "/v1/invoices/subscribe" };
+    }
+
+    rpc NoRoute (NodeInfoRequest) returns (NodeInfo);
+}
+
+message NodeInfoRequest {
+    // The 33-byte hex-encoded compressed public key of the target node
+    string pub_key = 1;
+
+    // If true, will include all known channels associated with the node.
+    bool include_channels = 2;
+}
+
+message NodeInfo {
+    // The node itself
+    LightningNode node = 1;
+    uint32 num_channels = 2;
+    int64 total_capacity = 3 [jstype = JS_STRING];
+    repeated Address addresses = 4;
+    map<string, Feature> features = 5;
+    oneof funding {
+        bytes funding_txid_bytes = 6;
+        string funding_txid_str = 7 [deprecated = true];
+    }
+    State state = 8;
+
+    message Address {
+        string addr = 1;
+    }
+
+    enum State {
+        UNKNOWN = 0;
+        ACTIVE = 1;
+    }
+}
+
+message LightningNode {
+    string alias = 1;
+}
+
+message Feature {
+    string name = 1;
+}
+"#;
+
+    fn rpc<'a>(ir: &'a ProtocolIR, name: &str) -> &'a RpcDef {
+        ir.get_rpc_methods()
+            .into_iter()
+            .find(|m| m.name == name)
+            .unwrap_or_else(|| panic!("method {} not found", name))
+    }
+
+    #[test]
+    fn converts_bound_unary_rpcs() {
+        let ir = convert_sources(&[SAMPLE.to_string()]).expect("convert");
+        let names: Vec<_> = ir.get_rpc_methods().iter().map(|m| m.name.clone()).collect();
+        assert_eq!(names, vec!["GetNodeInfo"], "streaming and unbound RPCs are skipped");
+
+        let method = rpc(&ir, "GetNodeInfo");
+        assert_eq!(method.category, "network");
+        assert_eq!(
+            method.description,
+            "GetNodeInfo returns the latest advertised information for a node."
+        );
+        let http = method.http.as_ref().expect("http binding");
+        assert_eq!(http.method, HttpMethod::Get);
+        assert_eq!(http.path, "/v1/graph/node/{pub_key}");
+        assert_eq!(http.body, None);
+
+        assert_eq!(method.params[0].name, "pub_key");
+        assert!(method.params[0].required, "path parameters are required");
+        assert!(!method.params[1].required);
+        assert_eq!(method.params[1].param_type.protocol_type.as_deref(), Some("boolean"));
+        assert!(method.params[1].description.starts_with("If true"));
+    }
+
+    #[test]
+    fn maps_proto3_json_types() {
+        let ir = convert_sources(&[SAMPLE.to_string()]).expect("convert");
+        let result = rpc(&ir, "GetNodeInfo").result.as_ref().expect("result");
+        assert_eq!(result.name, "object");
+        let fields = result.fields.as_ref().expect("fields");
+        let field = |name: &str| {
+            fields.iter().find(|f| f.key.as_ident() == name).unwrap_or_else(|| panic!("{}", name))
+        };
+
+        let node = field("node");
+        assert_eq!(node.field_type.name, "LightningNode");
+        assert!(!node.required, "message fields may be null");
+        assert_eq!(field("num_channels").field_type.protocol_type.as_deref(), Some("number"));
+        let capacity = field("total_capacity");
+        assert_eq!(capacity.field_type.protocol_type.as_deref(), Some("string"));
+        assert!(capacity.required);
+
+        let addresses = &field("addresses").field_type;
+        assert!(matches!(addresses.kind, TypeKind::Array));
+        let element = &addresses.fields.as_ref().expect("element")[0].field_type;
+        assert_eq!(element.name, "NodeInfoAddress", "nested messages resolve in scope");
+
+        assert_eq!(field("features").field_type.protocol_type.as_deref(), Some("any"));
+        let txid = field("funding_txid_str");
+        assert!(!txid.required, "oneof members are optional");
+        assert!(txid.description.starts_with("Deprecated."));
+        assert!(field("state").field_type.description.contains("UNKNOWN, ACTIVE"));
+
+        let types: Vec<_> = ir.get_type_definitions().iter().map(|t| t.name.clone()).collect();
+        assert!(types.contains(&"NodeInfoState".to_string()));
+        assert!(types.contains(&"NodeInfo".to_string()));
+    }
+
+    #[test]
+    fn rejects_unknown_types_and_unbound_path_fields() {
+        let unknown = "message A { Missing b = 1; }\nservice S { rpc X (A) returns (A) { option (google.api.http) = { get: \"/x\" }; } }";
+        let err = convert_sources(&[unknown.to_string()]).expect_err("unknown type");
+        assert!(err.to_string().contains("Missing"));
+
+        let unbound = "message A { string b = 1; }\nservice S { rpc X (A) returns (A) { option (google.api.http) = { delete: \"/x/{c}\" }; } }";
+        let err = convert_sources(&[unbound.to_string()]).expect_err("unbound path field");
+        assert!(err.to_string().contains("'c'"));
+    }
+
+    #[test]
+    fn converts_checked_in_protos() {
+        let root = path::find_project_root().expect("project root");
+        let ir = LndAdapter::new()
+            .extract_protocol_ir(&root.join(LND_PROTO_DIR))
+            .expect("fixtures convert");
+        assert!(ir.get_rpc_methods().len() >= 17);
+        assert!(ir.get_rpc_methods().iter().all(|m| m.http.is_some()));
+
+        let close = ir.get_rpc_methods().into_iter().find(|m| m.name == "CloseChannel");
+        assert!(close.is_none(), "CloseChannel streams its updates");
+
+        let add_invoice = rpc(&ir, "AddInvoice");
+        assert_eq!(add_invoice.http.as_ref().and_then(|h| h.body.as_deref()), Some("*"));
+    }
+}
@@ -94,6 +94,34 @@ pub struct NormalizationMetadata {
 pub enum AdapterKind {
     /// Bitcoin Core adapter
     BitcoinCore,
+    /// btcd adapter
+    Btcd,
+    /// Core Lightning adapter
+    CoreLightning,
+    /// LND adapter
+    Lnd,
+    /// electrs adapter
+    Electrs,
+    /// Esplora adapter
+    Esplora,
+    /// Bitcoin Knots adapter
+    BitcoinKnots,
+}
+
+impl From<types::Implementation> for AdapterKind {
+    fn from(implementation: types::Implementation) -> Self {
+        match implementation {
+            types::Implementation::BitcoinCore => AdapterKind::BitcoinCore,
+            types::Implementation::Btcd => AdapterKind::Btcd,
+            types::Implementation::CoreLightning => AdapterKind::CoreLightning,
+            types::Implementation::Lnd => AdapterKind::Lnd,
+            types::Implementation::Electrs => AdapterKind::Electrs,
+            types::Implementation::Esplora => AdapterKind::Esplora,
+            types::Implementation::BitcoinKnots => AdapterKind::BitcoinKnots,
+            types::Implementation::Registered(_) =>
+                AdapterKind::from(implementation.builtin_base()),
+        }
+    }
 }
 
 /// Registry for normalization rules
@@ -128,7 +156,10 @@ impl NormalizationRegistry {
     /// Construct a registry for a specific adapter type using a conventional preset name
     pub fn for_adapter(adapter: AdapterKind) -> Result<Self, NormalizationError> {
         let preset = match adapter {
-            AdapterKind::BitcoinCore => "bitcoin",
+            AdapterKind::BitcoinCore | AdapterKind::Btcd | AdapterKind::BitcoinKnots => "bitcoin",
+            AdapterKind::CoreLightning | AdapterKind::Lnd => "lightning",
+            AdapterKind::Electrs => "electrum",
+            AdapterKind::Esplora => "esplora",
         };
         Self::from_preset(preset)
     }
@@ -210,9 +241,15 @@ impl NormalizationRegistry {
     }
 
     /// Translate a canonical method name to adapter-specific method name
+    ///
+    /// Bitcoin Knots maps only the methods it adds and falls back to Bitcoin Core's mappings.
     pub fn to_adapter_method(&self, adapter: AdapterKind, canonical: &str) -> String {
-        if let Some(adapter_mappings) = self.method_mappings.get(&adapter) {
-            if let Some(mapped) = adapter_mappings.get(canonical) {
+        let base = match adapter {
+            AdapterKind::BitcoinKnots => Some(AdapterKind::BitcoinCore),
+            _ => None,
+        };
+        for kind in std::iter::once(adapter).chain(base) {
+            if let Some(mapped) = self.method_mappings.get(&kind).and_then(|m| m.get(canonical)) {
                 return mapped.clone();
             }
         }
@@ -400,4 +437,109 @@ mod tests {
         assert!(normalized.get("amount").is_some());
         assert!(metadata.dropped_fields.contains(&"timestamp".to_string()));
     }
+
+    #[test]
+    fn test_btcd_shares_canonical_names_with_core() {
+        assert_eq!(AdapterKind::from(types::Implementation::Btcd), AdapterKind::Btcd);
+
+        for rpc in ["getblockchaininfo", "getpeerinfo", "getrawtransaction"] {
+            assert_eq!(
+                normalization::canonical_from_adapter_method("btcd", rpc, None),
+                normalization::canonical_from_adapter_method("bitcoin_core", rpc, None)
+            );
+        }
+        assert_eq!(
+            normalization::canonical_from_adapter_method("btcd", "getbestblock", None).as_deref(),
+            Ok("GetBestBlock")
+        );
+    }
+
+    #[test]
+    fn test_knots_falls_back_to_core_mappings() {
+        assert_eq!(
+            AdapterKind::from(types::Implementation::BitcoinKnots),
+            AdapterKind::BitcoinKnots
+        );
+        assert_eq!(
+            normalization::canonical_from_adapter_method("bitcoin_knots", "getpeerinfo", None)
+                .as_deref(),
+            Ok("GetPeerInfo")
+        );
+        assert_eq!(
+            normalization::canonical_from_adapter_method("bitcoin_knots", "getgeneralinfo", None)
+                .as_deref(),
+            Ok("GetGeneralInfo")
+        );
+        assert!(normalization::canonical_from_adapter_method(
+            "bitcoin_core",
+            "getgeneralinfo",
+            None
+        )
+        .is_err());
+
+        let mut registry = NormalizationRegistry::default();
+        registry.add_method_mapping(AdapterKind::BitcoinCore, "GetPeerInfo", "getpeerinfo");
+        registry.add_method_mapping(AdapterKind::BitcoinKnots, "GetGeneralInfo", "getgeneralinfo");
+        assert_eq!(
+            registry.to_adapter_method(AdapterKind::BitcoinKnots, "GetPeerInfo"),
+            "getpeerinfo"
+        );
+        assert_eq!(
+            registry.to_adapter_method(AdapterKind::BitcoinKnots, "GetGeneralInfo"),
+            "getgeneralinfo"
+        );
+        assert_eq!(
+            registry.to_adapter_method(AdapterKind::BitcoinCore, "GetGeneralInfo"),
+            "GetGeneralInfo"
+        );
+    }
+
+    #[test]
+    fn test_core_lightning_uses_lightning_preset() {
+        assert_eq!(
+            AdapterKind::from(types::Implementation::CoreLightning),
+            AdapterKind::CoreLightning
+        );
+        assert_eq!(
+            normalization::canonical_from_adapter_method(
+                "core_lightning",
+                "listpeerchannels",
+                None
+            )
+            .as_deref(),
+            Ok("ListPeerChannels")
+        );
+        assert!(normalization::canonical_from_adapter_method(
+            "core_lightning",
+            "getblockchaininfo",
+            None
+        )
+        .is_err());
+        assert_eq!(
+            normalization::canonical_from_adapter_method("lnd", "SendPaymentSync", None).as_deref(),
+            Ok("Pay")
+        );
+    }
+
+    #[test]
+    fn test_electrs_uses_electrum_preset() {
+        assert_eq!(AdapterKind::from(types::Implementation::Electrs), AdapterKind::Electrs);
+        assert_eq!(
+            normalization::canonical_from_adapter_method(
+                "electrs",
+                "blockchain.scripthash.get_balance",
+                None
+            )
+            .as_deref(),
+            Ok("ScripthashGetBalance")
+        );
+        // Every method the adapter describes has a canonical name
+        for method in crate::electrum::protocol::electrum_methods() {
+            assert!(
+                normalization::canonical_from_adapter_method("electrs", &method.name, None).is_ok(),
+                "unmapped {}",
+                method.name
+            );
+        }
+    }
 }
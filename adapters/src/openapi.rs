@@ -0,0 +1,507 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Generic OpenAPI 3.x adapter (REST API descriptions -> a `rest` ProtocolModule).
+//!
+//! Every `GET` operation becomes a [`RestEndpointDef`] serving its single format at the
+//! bare path (`bare_path`), as REST APIs described by OpenAPI do:
+//!
+//! - path templates keep their `{name}` placeholders; Express-style `:name` segments
+//!   (as written in Esplora's documentation) are accepted too
+//! - `in: path` parameters fill the placeholders (in template order) and `in: query`
+//!   parameters become query parameters; header and cookie parameters are ignored
+//! - the first `2xx` response picks the format from its media type (`application/json` ->
+//!   `Json`, `text/plain` -> `Text`, `application/octet-stream` -> `Bin`), and its schema
+//!   becomes the endpoint result
+//! - endpoints are named by `operationId` in snake case, else by the literal segments of the
+//!   path (`/tx/{txid}/status` -> `tx_status`)
+//!
+//! Schemas go through the same JSON Schema conversion as [`crate::openrpc`], including its
+//! `x-` extensions; `x-protocol-type: satoshis` marks integer satoshi amounts. Inline object
+//! schemas are named after the endpoint and field they appear in, since generated clients
+//! emit one struct per object type. Operations other than `GET` are skipped: the REST IR
+//! describes read-only endpoints.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use ir::{
+    FieldKey, ParamDef, ProtocolDef, ProtocolIR, ProtocolModule, RestEndpointDef, RestFormat,
+    TypeDef, TypeKind,
+};
+use serde_json::Value;
+
+use crate::bitcoin_core::rest::REST_MODULE_NAME;
+use crate::conversion_helpers::{parse_schema_document, sort_definitions_by_name};
+use crate::openrpc::{
+    default_of, description_of, primitive, schema_ref, str_field, SchemaConverter,
+};
+use crate::{ProtocolAdapter, ProtocolAdapterError, ProtocolAdapterResult, CAP_REST};
+
+/// Response media types the REST IR can describe, in order of preference.
+const MEDIA_TYPES: [(&str, RestFormat); 3] = [
+    ("application/json", RestFormat::Json),
+    ("text/plain", RestFormat::Text),
+    ("application/octet-stream", RestFormat::Bin),
+];
+
+/// Adapter for OpenAPI 3.x documents.
+#[derive(Debug, Clone, Default)]
+pub struct OpenApiAdapter {
+    /// Version reported by the adapter; defaults to the document's `info.version`.
+    pub version: Option<String>,
+}
+
+impl OpenApiAdapter {
+    /// Create an adapter that reports the document's own `info.version`.
+    pub fn new() -> Self { Self::default() }
+
+    /// Create an adapter reporting a fixed version.
+    pub fn with_version(version: impl Into<String>) -> Self {
+        Self { version: Some(version.into()) }
+    }
+}
+
+impl ProtocolAdapter for OpenApiAdapter {
+    fn name(&self) -> &'static str { "openapi" }
+
+    fn version(&self) -> String { self.version.clone().unwrap_or_else(|| "latest".to_string()) }
+
+    fn extract_protocol_ir(&self, path: &Path) -> ProtocolAdapterResult<ProtocolIR> {
+        let content = std::fs::read_to_string(path)?;
+        convert_document(&parse_schema_document(&content)?)
+    }
+
+    fn capabilities(&self) -> Vec<&'static str> { vec![CAP_REST] }
+}
+
+/// Returns true when `value` looks like an OpenAPI document (has a top-level `openapi` string).
+pub fn is_openapi_document(value: &Value) -> bool {
+    value.get("openapi").and_then(Value::as_str).is_some()
+}
+
+/// Converts an OpenAPI 3.x document into a `ProtocolIR` with a single `rest` module.
+///
+/// `components/schemas` become named `ProtocolDef::Type` definitions; every `GET` operation
+/// becomes a `ProtocolDef::RestEndpoint` with its `$ref`s resolved inline.
+pub fn convert_document(document: &Value) -> ProtocolAdapterResult<ProtocolIR> {
+    let spec_version = document.get("openapi").and_then(Value::as_str).ok_or_else(|| {
+        ProtocolAdapterError::Message("Not an OpenAPI document: missing 'openapi'".to_string())
+    })?;
+    if !spec_version.starts_with("3.") {
+        return Err(ProtocolAdapterError::Message(format!(
+            "Unsupported OpenAPI version '{}': only 3.x is supported",
+            spec_version
+        )));
+    }
+
+    let mut converter = SchemaConverter::new(document);
+    let mut definitions = Vec::new();
+
+    if let Some(schemas) = document.pointer("/components/schemas").and_then(Value::as_object) {
+        for (name, schema) in schemas {
+            converter.stack.push(schema_ref(name));
+            let mut type_def = converter.convert(schema)?;
+            converter.stack.pop();
+            type_def.name = name.clone();
+            definitions.push(ProtocolDef::Type(type_def));
+        }
+    }
+
+    let paths = document.get("paths").and_then(Value::as_object).ok_or_else(|| {
+        ProtocolAdapterError::Message("OpenAPI document has no 'paths' object".to_string())
+    })?;
+    let mut names = HashSet::new();
+    for (path, item) in paths {
+        let item = converter.deref(item)?;
+        let Some(operation) = item.get("get") else { continue };
+        let endpoint = convert_operation(&mut converter, path, item, operation)?;
+        if !names.insert(endpoint.name.clone()) {
+            return Err(ProtocolAdapterError::Message(format!(
+                "Duplicate endpoint name '{}' (from {}); set a distinct operationId",
+                endpoint.name, path
+            )));
+        }
+        definitions.push(ProtocolDef::RestEndpoint(endpoint));
+    }
+
+    sort_definitions_by_name(&mut definitions);
+
+    let title = document.pointer("/info/title").and_then(Value::as_str).unwrap_or("OpenAPI");
+    let module = ProtocolModule::new(REST_MODULE_NAME.to_string(), title.to_string(), definitions);
+    match document.pointer("/info/version").and_then(Value::as_str) {
+        Some(version) => Ok(ProtocolIR::new_with_version(version.to_string(), vec![module])),
+        None => Ok(ProtocolIR::new(vec![module])),
+    }
+}
+
+fn convert_operation<'a>(
+    converter: &mut SchemaConverter<'a>,
+    raw_path: &str,
+    item: &'a Value,
+    operation: &'a Value,
+) -> ProtocolAdapterResult<RestEndpointDef> {
+    let path = normalize_path(raw_path);
+    let name = match str_field(operation, "operationId") {
+        Some(operation_id) => snake_case(operation_id),
+        None => name_from_path(&path),
+    };
+
+    // Operation-level parameters override path-level ones with the same name and location
+    let mut path_params = Vec::new();
+    let mut query_params = Vec::new();
+    let mut seen = HashSet::new();
+    let declared = [operation, item]
+        .into_iter()
+        .flat_map(|scope| scope.get("parameters").and_then(Value::as_array).into_iter().flatten());
+    for parameter in declared {
+        let parameter = converter.deref(parameter)?;
+        let location = str_field(parameter, "in").unwrap_or_default();
+        let param = convert_parameter(converter, parameter)?;
+        if !seen.insert((location.to_string(), param.name.clone())) {
+            continue;
+        }
+        match location {
+            "path" => path_params.push(param),
+            "query" => query_params.push(param),
+            _ => {}
+        }
+    }
+
+    let mut params = Vec::new();
+    for placeholder in placeholders(&path) {
+        let index = path_params.iter().position(|p| p.name == placeholder).ok_or_else(|| {
+            ProtocolAdapterError::Message(format!(
+                "Path parameter '{}' of {} is not declared",
+                placeholder, raw_path
+            ))
+        })?;
+        let mut param = path_params.remove(index);
+        param.required = true;
+        params.push(param);
+    }
+
+    let (format, mut result) = convert_response(converter, raw_path, operation)?;
+    if let Some(result) = result.as_mut() {
+        name_inline_objects(result, &format!("{}Response", pascal_case(&name)));
+    }
+
+    Ok(RestEndpointDef {
+        name,
+        description: description_of(operation),
+        path,
+        params,
+        query_params,
+        formats: vec![format],
+        bare_path: true,
+        result,
+        shared_result: None,
+        version_added: str_field(operation, "x-version-added").map(str::to_string),
+        version_removed: str_field(operation, "x-version-removed").map(str::to_string),
+    })
+}
+
+fn convert_parameter<'a>(
+    converter: &mut SchemaConverter<'a>,
+    parameter: &'a Value,
+) -> ProtocolAdapterResult<ParamDef> {
+    let name = str_field(parameter, "name")
+        .ok_or_else(|| ProtocolAdapterError::Message("Parameter without a name".to_string()))?
+        .to_string();
+    let param_type = match parameter.get("schema") {
+        Some(schema) => converter.convert(schema)?,
+        None => primitive("string", String::new()),
+    };
+    Ok(ParamDef {
+        name,
+        param_type,
+        required: parameter.get("required").and_then(Value::as_bool).unwrap_or(false),
+        description: description_of(parameter),
+        default_value: default_of(parameter),
+        version_added: str_field(parameter, "x-version-added").map(str::to_string),
+        version_removed: str_field(parameter, "x-version-removed").map(str::to_string),
+        deprecated: None,
+    })
+}
+
+/// Format and result type of the first `2xx` response.
+///
+/// A response without content is described as JSON of unknown shape.
+fn convert_response<'a>(
+    converter: &mut SchemaConverter<'a>,
+    raw_path: &str,
+    operation: &'a Value,
+) -> ProtocolAdapterResult<(RestFormat, Option<TypeDef>)> {
+    let responses = operation.get("responses").and_then(Value::as_object);
+    let success = responses
+        .into_iter()
+        .flatten()
+        .filter(|(status, _)| status.starts_with('2'))
+        .min_by(|a, b| a.0.cmp(b.0))
+        .map(|(_, response)| response);
+    let Some(response) = success else {
+        return Err(ProtocolAdapterError::Message(format!("GET {} has no 2xx response", raw_path)));
+    };
+    let response = converter.deref(response)?;
+    let Some(content) = response.get("content").and_then(Value::as_object) else {
+        return Ok((RestFormat::Json, None));
+    };
+
+    let media_type = |wanted: &str| {
+        content.iter().find(|(key, _)| key.split(';').next().map(str::trim) == Some(wanted))
+    };
+    let (format, media) = MEDIA_TYPES
+        .iter()
+        .find_map(|(wanted, format)| media_type(wanted).map(|(_, media)| (*format, media)))
+        .ok_or_else(|| {
+            let offered = content.keys().cloned().collect::<Vec<_>>().join(", ");
+            ProtocolAdapterError::Message(format!(
+                "GET {} has no supported response media type (offered: {})",
+                raw_path, offered
+            ))
+        })?;
+
+    let result = match (format, media.get("schema")) {
+        (RestFormat::Bin, _) | (_, None) => None,
+        (_, Some(schema)) => {
+            let mut type_def = converter.convert(schema)?;
+            if type_def.description.is_empty() {
+                type_def.description = description_of(response);
+            }
+            Some(type_def)
+        }
+    };
+    Ok((format, result))
+}
+
+/// Rewrites Express-style `:name` segments as `{name}` placeholders.
+fn normalize_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| match segment.strip_prefix(':') {
+            Some(name) => format!("{{{}}}", name),
+            None => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn placeholders(path: &str) -> Vec<&str> {
+    path.split('/')
+        .filter_map(|segment| segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')))
+        .collect()
+}
+
+/// Endpoint name from the literal path segments (`/blocks/tip/hash` -> `blocks_tip_hash`).
+fn name_from_path(path: &str) -> String {
+    let name = path
+        .split('/')
+        .filter(|segment| !segment.is_empty() && !segment.starts_with('{'))
+        .map(snake_case)
+        .collect::<Vec<_>>()
+        .join("_");
+    if name.is_empty() {
+        "root".to_string()
+    } else {
+        name
+    }
+}
+
+/// `getTxStatus` / `merkle-proof` -> `get_tx_status` / `merkle_proof`.
+fn snake_case(name: &str) -> String {
+    let mut out = String::new();
+    let mut previous_lower = false;
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            if previous_lower {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+            previous_lower = false;
+        } else if c.is_ascii_alphanumeric() {
+            out.push(c);
+            previous_lower = true;
+        } else {
+            if !out.is_empty() && !out.ends_with('_') {
+                out.push('_');
+            }
+            previous_lower = false;
+        }
+    }
+    out.trim_end_matches('_').to_string()
+}
+
+/// `tx_status` -> `TxStatus`.
+fn pascal_case(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Names inline object schemas (converted as `object`) after the place they appear:
+/// `name` for the type itself, `{name}{Field}` for its fields and `{name}Item` for array
+/// elements.
+fn name_inline_objects(type_def: &mut TypeDef, name: &str) {
+    if type_def.kind == TypeKind::Object && type_def.name == "object" {
+        type_def.name = name.to_string();
+    }
+    let parent =
+        if type_def.kind == TypeKind::Object { type_def.name.clone() } else { name.to_string() };
+    for field in type_def.fields.iter_mut().flatten() {
+        let child = match &field.key {
+            FieldKey::Named(key) => format!("{}{}", parent, pascal_case(key)),
+            FieldKey::Anonymous(_) => format!("{}Item", parent),
+        };
+        name_inline_objects(&mut field.field_type, &child);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn endpoint<'a>(ir: &'a ProtocolIR, name: &str) -> &'a RestEndpointDef {
+        ir.get_rest_endpoints().into_iter().find(|e| e.name == name).expect("endpoint present")
+    }
+
+    fn document(paths: Value) -> Value {
+        json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Test API", "version": "1.2.3" },
+            "paths": paths,
+            "components": {
+                "schemas": {
+                    "Txid": { "type": "string" },
+                    "TxStatus": {
+                        "type": "object",
+                        "required": ["confirmed"],
+                        "properties": {
+                            "confirmed": { "type": "boolean" },
+                            "block_height": { "type": "integer" }
+                        }
+                    }
+                },
+                "parameters": {
+                    "txid": {
+                        "name": "txid",
+                        "in": "path",
+                        "required": true,
+                        "schema": { "$ref": "#/components/schemas/Txid" }
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn converts_paths_params_and_responses() {
+        let doc = document(json!({
+            "/tx/:txid/status": {
+                "parameters": [{ "$ref": "#/components/parameters/txid" }],
+                "get": {
+                    "summary": "Transaction confirmation status",
+                    "responses": {
+                        "200": {
+                            "description": "OK",
+                            "content": {
+                                "application/json": {
+                                    "schema": { "$ref": "#/components/schemas/TxStatus" }
+                                }
+                            }
+                        }
+                    }
+                },
+                "post": { "responses": { "200": { "description": "ignored" } } }
+            },
+            "/blocks/tip/hash": {
+                "get": {
+                    "operationId": "getTipHash",
+                    "responses": {
+                        "200": {
+                            "description": "OK",
+                            "content": { "text/plain; charset=utf-8": { "schema": { "type": "string" } } }
+                        }
+                    }
+                }
+            },
+            "/address/{address}/txs": {
+                "get": {
+                    "parameters": [
+                        { "name": "address", "in": "path", "required": true, "schema": { "type": "string" } },
+                        { "name": "limit", "in": "query", "schema": { "type": "integer" } },
+                        { "name": "X-Trace", "in": "header", "schema": { "type": "string" } }
+                    ],
+                    "responses": {
+                        "200": {
+                            "description": "OK",
+                            "content": {
+                                "application/json": {
+                                    "schema": {
+                                        "type": "array",
+                                        "items": {
+                                            "type": "object",
+                                            "properties": { "txid": { "$ref": "#/components/schemas/Txid" } }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }));
+
+        let ir = convert_document(&doc).expect("converts");
+        assert_eq!(ir.version(), "1.2.3");
+        assert_eq!(ir.modules()[0].name(), REST_MODULE_NAME);
+        assert_eq!(ir.get_rest_endpoints().len(), 3);
+
+        let status = endpoint(&ir, "tx_status");
+        assert_eq!(status.path, "/tx/{txid}/status");
+        assert!(status.bare_path);
+        assert_eq!(status.formats, vec![RestFormat::Json]);
+        assert_eq!(status.description, "Transaction confirmation status");
+        assert_eq!(status.params[0].name, "txid");
+        assert_eq!(status.params[0].param_type.canonical_name.as_deref(), Some("Txid"));
+        let result = status.result.as_ref().expect("result");
+        assert_eq!(result.name, "TxStatus");
+        assert_eq!(result.fields.as_ref().map(Vec::len), Some(2));
+
+        let tip = endpoint(&ir, "get_tip_hash");
+        assert_eq!(tip.formats, vec![RestFormat::Text]);
+        assert_eq!(tip.result.as_ref().map(|r| r.name.as_str()), Some("string"));
+
+        let txs = endpoint(&ir, "address_txs");
+        assert_eq!(txs.params.len(), 1);
+        assert_eq!(txs.query_params.len(), 1);
+        assert!(!txs.query_params[0].required);
+        let element = txs.result.as_ref().and_then(TypeDef::array_element_type).expect("element");
+        assert_eq!(element.name, "AddressTxsResponseItem");
+    }
+
+    #[test]
+    fn rejects_undeclared_path_params_and_duplicate_names() {
+        let ok = json!({ "200": { "description": "OK" } });
+        let undeclared = document(json!({ "/block/{hash}": { "get": { "responses": ok } } }));
+        assert!(convert_document(&undeclared).is_err());
+
+        let duplicate = document(json!({
+            "/blocks": { "get": { "responses": ok } },
+            "/blocks/{start_height}": { "get": {
+                "parameters": [{ "name": "start_height", "in": "path", "schema": { "type": "integer" } }],
+                "responses": ok
+            } }
+        }));
+        assert!(convert_document(&duplicate).is_err());
+
+        assert!(convert_document(&json!({ "openapi": "2.0", "paths": {} })).is_err());
+        assert!(!is_openapi_document(&json!({ "swagger": "2.0" })));
+    }
+}
@@ -0,0 +1,794 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Generic OpenRPC 1.x adapter (spec-compliant documents -> IR).
+//!
+//! Unlike [`crate::bitcoin_core::openrpc`], which reads Bitcoin Core's own dialect
+//! (`x-bitcoin-arguments` / `x-bitcoin-results`), this adapter only relies on the
+//! OpenRPC specification: content descriptors, JSON Schema types and local `$ref`s
+//! into `components`. Any OpenRPC-described Bitcoin service can feed the pipeline
+//! through it.
+//!
+//! JSON Schema maps onto the IR as follows:
+//!
+//! - `string` / `boolean` / `null` -> primitives (`string`, `boolean`, `none`);
+//!   `integer` and `number` -> `number`; a missing `type` -> `any`
+//! - `object` -> `TypeKind::Object`, with `required` deciding `FieldDef::required`
+//! - `array` -> `TypeKind::Array` with one `FieldKey::Anonymous(0)` element prototype;
+//!   tuple forms (`prefixItems`, `items: [...]`) get one anonymous field per position
+//! - `enum` -> `TypeKind::Enum`; `oneOf` / `anyOf` -> `TypeKind::Union`
+//! - a `null` member in `type` arrays or `oneOf` only makes the value nullable
+//! - `$ref: "#/components/schemas/Foo"` -> the resolved schema named `Foo`;
+//!   recursive references become a `TypeKind::Alias` to `Foo`
+//!
+//! Ethos metadata without a spec equivalent is read from `x-` extensions when present
+//! (`x-version-added`, `x-version-removed`, `x-deprecated-since`, `x-deprecated-replacement`,
+//! `x-deprecated-flag` next to the spec's `deprecated`, `x-examples`, `x-default-hint`, and on schemas
+//! `x-protocol-type` and `x-condition`; a schema `title` names the type), along with the
+//! `x-bitcoin-*` extensions emitted by Bitcoin Core's `getopenrpcinfo`. These are the
+//! extensions written by [`crate::openrpc_export`].
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use ir::{
+    Deprecation, FieldDef, FieldKey, ParamDef, ProtocolDef, ProtocolIR, ProtocolModule, RpcDef,
+    TypeDef, TypeKind, UnionVariantDef, VariantDef,
+};
+use semantics::method_categorization;
+use serde_json::Value;
+
+use crate::conversion_helpers::{
+    determine_requires_private_keys, parse_schema_document, properties_in_order,
+    sort_definitions_by_name,
+};
+use crate::{ProtocolAdapter, ProtocolAdapterError, ProtocolAdapterResult, CAP_RPC};
+
+/// Category used for methods that carry neither `x-bitcoin-category` nor tags.
+pub const DEFAULT_CATEGORY: &str = "core";
+
+/// Adapter for spec-compliant OpenRPC 1.x documents.
+#[derive(Debug, Clone, Default)]
+pub struct OpenRpcAdapter {
+    /// Version reported by the adapter; defaults to the document's `info.version`.
+    pub version: Option<String>,
+}
+
+impl OpenRpcAdapter {
+    /// Create an adapter that reports the document's own `info.version`.
+    pub fn new() -> Self { Self::default() }
+
+    /// Create an adapter reporting a fixed version.
+    pub fn with_version(version: impl Into<String>) -> Self {
+        Self { version: Some(version.into()) }
+    }
+}
+
+impl ProtocolAdapter for OpenRpcAdapter {
+    fn name(&self) -> &'static str { "openrpc" }
+
+    fn version(&self) -> String { self.version.clone().unwrap_or_else(|| "latest".to_string()) }
+
+    fn extract_protocol_ir(&self, path: &Path) -> ProtocolAdapterResult<ProtocolIR> {
+        let content = std::fs::read_to_string(path)?;
+        convert_document(&parse_schema_document(&content)?)
+    }
+
+    fn capabilities(&self) -> Vec<&'static str> { vec![CAP_RPC] }
+}
+
+/// Returns true when `value` looks like an OpenRPC document (has a top-level `openrpc` string).
+pub fn is_openrpc_document(value: &Value) -> bool {
+    value.get("openrpc").and_then(Value::as_str).is_some()
+}
+
+/// Converts an OpenRPC 1.x document into a `ProtocolIR` with a single `rpc` module.
+///
+/// `components/schemas` become named `ProtocolDef::Type` definitions; every method
+/// becomes an `RpcDef` with its `$ref`s resolved inline.
+pub fn convert_document(document: &Value) -> ProtocolAdapterResult<ProtocolIR> {
+    let spec_version = document.get("openrpc").and_then(Value::as_str).ok_or_else(|| {
+        ProtocolAdapterError::Message("Not an OpenRPC document: missing 'openrpc'".to_string())
+    })?;
+    if !spec_version.starts_with("1.") {
+        return Err(ProtocolAdapterError::Message(format!(
+            "Unsupported OpenRPC version '{}': only 1.x is supported",
+            spec_version
+        )));
+    }
+
+    let mut converter = SchemaConverter::new(document);
+    let mut definitions = Vec::new();
+
+    if let Some(schemas) = document.pointer("/components/schemas").and_then(Value::as_object) {
+        for (name, schema) in schemas {
+            converter.stack.push(schema_ref(name));
+            let mut type_def = converter.convert(schema)?;
+            converter.stack.pop();
+            type_def.name = name.clone();
+            definitions.push(ProtocolDef::Type(type_def));
+        }
+    }
+
+    let methods = document.get("methods").and_then(Value::as_array).ok_or_else(|| {
+        ProtocolAdapterError::Message("OpenRPC document has no 'methods' array".to_string())
+    })?;
+    for method in methods {
+        let method = converter.deref(method)?;
+        definitions.push(ProtocolDef::RpcMethod(converter.convert_method(method)?));
+    }
+
+    sort_definitions_by_name(&mut definitions);
+
+    let title = document.pointer("/info/title").and_then(Value::as_str).unwrap_or("OpenRPC API");
+    let module = ProtocolModule::new("rpc".to_string(), title.to_string(), definitions);
+    Ok(ProtocolIR::new(vec![module]))
+}
+
+pub(crate) fn schema_ref(name: &str) -> String { format!("#/components/schemas/{}", name) }
+
+pub(crate) fn str_field<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value.get(key).and_then(Value::as_str).filter(|s| !s.is_empty())
+}
+
+/// `deprecated: true` with the `x-deprecated-*` details of a method, descriptor or schema.
+fn deprecation_of(value: &Value) -> Option<Deprecation> {
+    if value.get("deprecated").and_then(Value::as_bool) != Some(true) {
+        return None;
+    }
+    let detail = |key: &str| str_field(value, key).map(str::to_string);
+    Some(Deprecation {
+        since: detail("x-deprecated-since"),
+        replacement: detail("x-deprecated-replacement"),
+        flag: detail("x-deprecated-flag"),
+    })
+}
+
+/// Description of an OpenRPC object, falling back to its `summary`.
+pub(crate) fn description_of(value: &Value) -> String {
+    str_field(value, "description")
+        .or_else(|| str_field(value, "summary"))
+        .unwrap_or("")
+        .to_string()
+}
+
+/// Last path segment of a `$ref`, unescaped per RFC 6901.
+fn ref_name(reference: &str) -> String {
+    let segment = reference.rsplit('/').next().unwrap_or(reference);
+    segment.replace("~1", "/").replace("~0", "~")
+}
+
+pub(crate) fn primitive(protocol_type: &str, description: String) -> TypeDef {
+    TypeDef {
+        name: protocol_type.to_string(),
+        description,
+        kind: TypeKind::Primitive,
+        protocol_type: Some(protocol_type.to_string()),
+        ..TypeDef::default()
+    }
+}
+
+/// Maps a JSON Schema primitive `type` onto the IR's protocol type vocabulary.
+fn map_json_schema_type(json_type: &str) -> ProtocolAdapterResult<&'static str> {
+    match json_type {
+        "string" => Ok("string"),
+        "integer" | "number" => Ok("number"),
+        "boolean" => Ok("boolean"),
+        "null" => Ok("none"),
+        "object" => Ok("object"),
+        "array" => Ok("array"),
+        unknown =>
+            Err(ProtocolAdapterError::Message(format!("Unknown JSON Schema type '{}'", unknown))),
+    }
+}
+
+/// Converts JSON Schema (and the OpenRPC objects around it) into IR types.
+///
+/// [`crate::openapi`] reuses it for OpenAPI schema objects.
+pub(crate) struct SchemaConverter<'a> {
+    root: &'a Value,
+    /// `$ref`s currently being expanded; a repeat is a recursive type.
+    pub(crate) stack: Vec<String>,
+}
+
+impl<'a> SchemaConverter<'a> {
+    pub(crate) fn new(root: &'a Value) -> Self { Self { root, stack: Vec::new() } }
+
+    /// Resolves a local `$ref` (`#/...`) against the document root.
+    fn resolve(&self, reference: &str) -> ProtocolAdapterResult<&'a Value> {
+        let pointer = reference.strip_prefix('#').ok_or_else(|| {
+            ProtocolAdapterError::Message(format!(
+                "Only local $ref values are supported, got '{}'",
+                reference
+            ))
+        })?;
+        self.root.pointer(pointer).ok_or_else(|| {
+            ProtocolAdapterError::Message(format!("Unresolved $ref '{}'", reference))
+        })
+    }
+
+    /// Follows `$ref` chains on reference objects (methods, content descriptors, tags).
+    pub(crate) fn deref(&self, value: &'a Value) -> ProtocolAdapterResult<&'a Value> {
+        let mut current = value;
+        let mut seen = HashSet::new();
+        while let Some(reference) = current.get("$ref").and_then(Value::as_str) {
+            if !seen.insert(reference) {
+                return Err(ProtocolAdapterError::Message(format!(
+                    "Circular $ref '{}'",
+                    reference
+                )));
+            }
+            current = self.resolve(reference)?;
+        }
+        Ok(current)
+    }
+
+    fn convert_method(&mut self, method: &'a Value) -> ProtocolAdapterResult<RpcDef> {
+        let name = str_field(method, "name")
+            .ok_or_else(|| {
+                ProtocolAdapterError::Message("OpenRPC method without a name".to_string())
+            })?
+            .to_string();
+
+        let mut params = Vec::new();
+        for param in method.get("params").and_then(Value::as_array).into_iter().flatten() {
+            params.push(self.convert_param(self.deref(param)?)?);
+        }
+
+        let result = match method.get("result") {
+            Some(result) => {
+                let descriptor = self.deref(result)?;
+                let mut type_def = self.convert_descriptor_schema(descriptor)?;
+                if type_def.description.is_empty() {
+                    type_def.description = description_of(descriptor);
+                }
+                Some(type_def)
+            }
+            None => None,
+        };
+
+        let category = self.category_of(method)?;
+        let access_level = method_categorization::access_level_for(&category, &name);
+        let requires_private_keys = determine_requires_private_keys(&category, &name);
+        let examples = examples_of(method);
+
+        Ok(RpcDef {
+            description: description_of(method),
+            params,
+            result,
+            hidden: (category == "hidden").then_some(true),
+            access_level,
+            requires_private_keys,
+            version_added: str_field(method, "x-version-added").map(str::to_string),
+            version_removed: str_field(method, "x-version-removed").map(str::to_string),
+            deprecated: deprecation_of(method),
+            examples: (!examples.is_empty()).then_some(examples),
+            category,
+            name,
+            http: None,
+        })
+    }
+
+    fn convert_param(&mut self, descriptor: &'a Value) -> ProtocolAdapterResult<ParamDef> {
+        let name = str_field(descriptor, "name")
+            .ok_or_else(|| {
+                ProtocolAdapterError::Message("Content descriptor without a name".to_string())
+            })?
+            .to_string();
+        let mut param_type = self.convert_descriptor_schema(descriptor)?;
+        if let Some(arg_type) = str_field(descriptor, "x-bitcoin-arg-type") {
+            param_type.protocol_type = Some(arg_type.to_string());
+        }
+        Ok(ParamDef {
+            name,
+            param_type,
+            required: descriptor.get("required").and_then(Value::as_bool).unwrap_or(false),
+            description: description_of(descriptor),
+            default_value: default_of(descriptor),
+            version_added: str_field(descriptor, "x-version-added").map(str::to_string),
+            version_removed: str_field(descriptor, "x-version-removed").map(str::to_string),
+            deprecated: deprecation_of(descriptor),
+        })
+    }
+
+    fn convert_descriptor_schema(
+        &mut self,
+        descriptor: &'a Value,
+    ) -> ProtocolAdapterResult<TypeDef> {
+        match descriptor.get("schema") {
+            Some(schema) => self.convert(schema),
+            None => Ok(primitive("any", description_of(descriptor))),
+        }
+    }
+
+    /// `x-bitcoin-category`, else the first tag name, else [`DEFAULT_CATEGORY`].
+    fn category_of(&self, method: &'a Value) -> ProtocolAdapterResult<String> {
+        if let Some(category) = str_field(method, "x-bitcoin-category") {
+            return Ok(category.to_lowercase());
+        }
+        if let Some(tag) = method.get("tags").and_then(Value::as_array).and_then(|t| t.first()) {
+            if let Some(name) = str_field(self.deref(tag)?, "name") {
+                return Ok(name.to_lowercase());
+            }
+        }
+        Ok(DEFAULT_CATEGORY.to_string())
+    }
+
+    /// Converts a JSON Schema into a `TypeDef`, applying the Ethos `x-` extensions on it.
+    pub(crate) fn convert(&mut self, schema: &'a Value) -> ProtocolAdapterResult<TypeDef> {
+        let mut type_def = self.convert_keywords(schema)?;
+        if let Some(protocol_type) = str_field(schema, "x-protocol-type") {
+            if matches!(type_def.kind, TypeKind::Primitive) {
+                type_def.name = protocol_type.to_string();
+            }
+            type_def.protocol_type = Some(protocol_type.to_string());
+        }
+        if let Some(title) = str_field(schema, "title") {
+            if !matches!(type_def.kind, TypeKind::Primitive | TypeKind::Alias) {
+                type_def.name = title.to_string();
+            }
+        }
+        if let Some(condition) = str_field(schema, "x-condition") {
+            type_def.condition = Some(condition.to_string());
+        }
+        Ok(type_def)
+    }
+
+    fn convert_keywords(&mut self, schema: &'a Value) -> ProtocolAdapterResult<TypeDef> {
+        let object = match schema {
+            // `true` accepts anything; `false` accepts nothing.
+            Value::Bool(true) => return Ok(primitive("any", String::new())),
+            Value::Bool(false) => return Ok(primitive("none", String::new())),
+            Value::Object(object) => object,
+            other =>
+                return Err(ProtocolAdapterError::Message(format!(
+                    "Invalid JSON Schema: {}",
+                    other
+                ))),
+        };
+        let description = description_of(schema);
+
+        if let Some(reference) = object.get("$ref").and_then(Value::as_str) {
+            return self.convert_ref(reference, description);
+        }
+
+        for keyword in ["oneOf", "anyOf"] {
+            if let Some(members) = object.get(keyword).and_then(Value::as_array) {
+                return self.convert_union(members, description);
+            }
+        }
+
+        if let Some(members) = object.get("allOf").and_then(Value::as_array) {
+            return self.convert_all_of(members, description);
+        }
+
+        if let Some(values) = object.get("enum").and_then(Value::as_array) {
+            return self.convert_enum(schema, values, description);
+        }
+
+        match object.get("type") {
+            None => Ok(primitive("any", description)),
+            Some(Value::String(json_type)) => self.convert_typed(schema, json_type, description),
+            Some(Value::Array(types)) => {
+                let non_null: Vec<&str> =
+                    types.iter().filter_map(Value::as_str).filter(|t| *t != "null").collect();
+                match non_null.as_slice() {
+                    [] => Ok(primitive("none", description)),
+                    [single] => self.convert_typed(schema, single, description),
+                    several => {
+                        let mut variants = Vec::new();
+                        for json_type in several {
+                            variants.push(self.convert_typed(schema, json_type, String::new())?);
+                        }
+                        Ok(union_of(variants, description))
+                    }
+                }
+            }
+            Some(other) =>
+                Err(ProtocolAdapterError::Message(format!("Invalid JSON Schema type: {}", other))),
+        }
+    }
+
+    fn convert_ref(
+        &mut self,
+        reference: &str,
+        description: String,
+    ) -> ProtocolAdapterResult<TypeDef> {
+        let name = ref_name(reference);
+        if self.stack.iter().any(|r| r == reference) {
+            return Ok(TypeDef {
+                name: name.clone(),
+                description,
+                kind: TypeKind::Alias,
+                base_type: Some(name),
+                ..TypeDef::default()
+            });
+        }
+
+        let target = self.resolve(reference)?;
+        self.stack.push(reference.to_string());
+        let converted = self.convert(target);
+        self.stack.pop();
+
+        let mut type_def = converted?;
+        if matches!(type_def.kind, TypeKind::Object | TypeKind::Enum | TypeKind::Union) {
+            type_def.name = name;
+        } else {
+            type_def.canonical_name = Some(name);
+        }
+        if !description.is_empty() {
+            type_def.description = description;
+        }
+        Ok(type_def)
+    }
+
+    fn convert_union(
+        &mut self,
+        members: &'a [Value],
+        description: String,
+    ) -> ProtocolAdapterResult<TypeDef> {
+        let mut variants = Vec::new();
+        for member in members {
+            let is_null = member.get("type").and_then(Value::as_str) == Some("null");
+            if !is_null {
+                variants.push(self.convert(member)?);
+            }
+        }
+        if variants.len() == 1 {
+            let mut only = variants.remove(0);
+            if only.description.is_empty() {
+                only.description = description;
+            }
+            return Ok(only);
+        }
+        Ok(union_of(variants, description))
+    }
+
+    /// `allOf` of object schemas merges their fields; anything else keeps the first member.
+    fn convert_all_of(
+        &mut self,
+        members: &'a [Value],
+        description: String,
+    ) -> ProtocolAdapterResult<TypeDef> {
+        let mut merged: Option<TypeDef> = None;
+        for member in members {
+            let part = self.convert(member)?;
+            merged = Some(match merged {
+                None => part,
+                Some(mut acc) => {
+                    if matches!(acc.kind, TypeKind::Object) && matches!(part.kind, TypeKind::Object)
+                    {
+                        acc.fields
+                            .get_or_insert_with(Vec::new)
+                            .extend(part.fields.unwrap_or_default());
+                    }
+                    acc
+                }
+            });
+        }
+        let mut type_def = merged.unwrap_or_else(|| primitive("any", String::new()));
+        if !description.is_empty() {
+            type_def.description = description;
+        }
+        Ok(type_def)
+    }
+
+    fn convert_enum(
+        &self,
+        schema: &'a Value,
+        values: &[Value],
+        description: String,
+    ) -> ProtocolAdapterResult<TypeDef> {
+        let protocol_type = match schema.get("type").and_then(Value::as_str) {
+            Some(json_type) => map_json_schema_type(json_type)?,
+            None => "string",
+        };
+        let variants = values
+            .iter()
+            .filter(|value| !value.is_null())
+            .map(|value| {
+                let value = match value {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                VariantDef {
+                    name: value.clone(),
+                    description: String::new(),
+                    value: Some(value),
+                    associated_data: None,
+                }
+            })
+            .collect();
+        Ok(TypeDef {
+            name: protocol_type.to_string(),
+            description,
+            kind: TypeKind::Enum,
+            variants: Some(variants),
+            protocol_type: Some(protocol_type.to_string()),
+            ..TypeDef::default()
+        })
+    }
+
+    fn convert_typed(
+        &mut self,
+        schema: &'a Value,
+        json_type: &str,
+        description: String,
+    ) -> ProtocolAdapterResult<TypeDef> {
+        let protocol_type = map_json_schema_type(json_type)?;
+        match protocol_type {
+            "object" => {
+                let required: HashSet<&str> = schema
+                    .get("required")
+                    .and_then(Value::as_array)
+                    .map(|names| names.iter().filter_map(Value::as_str).collect())
+                    .unwrap_or_default();
+                let mut fields = Vec::new();
+                for (key, property) in properties_in_order(schema) {
+                    let field_type = self.convert(property)?;
+                    fields.push(FieldDef {
+                        key: FieldKey::Named(key.to_string()),
+                        description: field_type.description.clone(),
+                        required: required.contains(key),
+                        default_value: default_of(property),
+                        version_added: str_field(property, "x-version-added").map(str::to_string),
+                        version_removed: str_field(property, "x-version-removed")
+                            .map(str::to_string),
+                        deprecated: deprecation_of(property),
+                        field_type,
+                    });
+                }
+                Ok(TypeDef {
+                    name: "object".to_string(),
+                    description,
+                    kind: TypeKind::Object,
+                    fields: (!fields.is_empty()).then_some(fields),
+                    protocol_type: Some("object".to_string()),
+                    ..TypeDef::default()
+                })
+            }
+            "array" => {
+                let positional: Option<&'a Vec<Value>> = schema
+                    .get("prefixItems")
+                    .and_then(Value::as_array)
+                    .or_else(|| schema.get("items").and_then(Value::as_array));
+                let elements: Vec<&'a Value> = match (positional, schema.get("items")) {
+                    (Some(items), _) => items.iter().collect(),
+                    (None, Some(items)) => vec![items],
+                    (None, None) => Vec::new(),
+                };
+                let mut fields = Vec::new();
+                for (index, element) in elements.into_iter().enumerate() {
+                    let field_type = self.convert(element)?;
+                    fields.push(FieldDef {
+                        key: FieldKey::Anonymous(index),
+                        description: field_type.description.clone(),
+                        required: true,
+                        default_value: None,
+                        version_added: None,
+                        version_removed: None,
+                        deprecated: None,
+                        field_type,
+                    });
+                }
+                if fields.is_empty() {
+                    fields.push(FieldDef {
+                        key: FieldKey::Anonymous(0),
+                        field_type: primitive("any", String::new()),
+                        required: true,
+                        description: String::new(),
+                        default_value: None,
+                        version_added: None,
+                        version_removed: None,
+                        deprecated: None,
+                    });
+                }
+                Ok(TypeDef {
+                    name: "array".to_string(),
+                    description,
+                    kind: TypeKind::Array,
+                    fields: Some(fields),
+                    protocol_type: Some("array".to_string()),
+                    ..TypeDef::default()
+                })
+            }
+            scalar => Ok(primitive(scalar, description)),
+        }
+    }
+}
+
+fn union_of(variants: Vec<TypeDef>, description: String) -> TypeDef {
+    let union_variants = variants
+        .into_iter()
+        .enumerate()
+        .map(|(index, type_def)| UnionVariantDef {
+            name: if type_def.name.is_empty() {
+                format!("variant_{}", index)
+            } else {
+                type_def.name.clone()
+            },
+            description: type_def.description.clone(),
+            condition: type_def.condition.clone(),
+            type_def,
+        })
+        .collect();
+    TypeDef {
+        name: "union".to_string(),
+        description,
+        kind: TypeKind::Union,
+        union_variants: Some(union_variants),
+        protocol_type: Some("any".to_string()),
+        ..TypeDef::default()
+    }
+}
+
+/// Default for a content descriptor or property: JSON Schema `default`, else a free-text hint.
+pub(crate) fn default_of(value: &Value) -> Option<String> {
+    let schema_default = value.get("default").or_else(|| value.pointer("/schema/default"));
+    schema_default
+        .or_else(|| value.get("x-bitcoin-default"))
+        .map(Value::to_string)
+        .or_else(|| str_field(value, "x-default-hint").map(str::to_string))
+}
+
+/// Examples from `x-examples` / `x-bitcoin-examples`, else the spec's example pairings as JSON.
+fn examples_of(method: &Value) -> Vec<String> {
+    match method.get("x-examples").or_else(|| method.get("x-bitcoin-examples")) {
+        Some(Value::String(example)) if !example.is_empty() => vec![example.clone()],
+        Some(Value::Array(examples)) =>
+            examples.iter().filter_map(Value::as_str).map(str::to_string).collect(),
+        _ => method
+            .get("examples")
+            .and_then(Value::as_array)
+            .map(|pairings| pairings.iter().map(Value::to_string).collect())
+            .unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn document(methods: Value, schemas: Value) -> Value {
+        json!({
+            "openrpc": "1.3.2",
+            "info": { "title": "Test service", "version": "1.0.0" },
+            "methods": methods,
+            "components": {
+                "schemas": schemas,
+                "contentDescriptors": {
+                    "BlockHash": {
+                        "name": "blockhash",
+                        "required": true,
+                        "schema": { "$ref": "#/components/schemas/Hash" }
+                    }
+                }
+            }
+        })
+    }
+
+    fn rpc<'a>(ir: &'a ProtocolIR, name: &str) -> &'a RpcDef {
+        ir.get_rpc_methods().into_iter().find(|m| m.name == name).expect("method present")
+    }
+
+    #[test]
+    fn resolves_refs_required_and_unions() {
+        let doc = document(
+            json!([{
+                "name": "getblock",
+                "summary": "Get a block",
+                "tags": [{ "name": "Blockchain" }],
+                "params": [
+                    { "$ref": "#/components/contentDescriptors/BlockHash" },
+                    { "name": "verbosity", "schema": { "type": "integer", "default": 1 } }
+                ],
+                "result": {
+                    "name": "block",
+                    "schema": { "oneOf": [
+                        { "type": "string" },
+                        { "$ref": "#/components/schemas/Block" }
+                    ] }
+                }
+            }]),
+            json!({
+                "Hash": { "type": "string", "pattern": "^[0-9a-f]{64}$" },
+                "Block": {
+                    "type": "object",
+                    "required": ["hash"],
+                    "properties": {
+                        "hash": { "$ref": "#/components/schemas/Hash" },
+                        "previous": { "$ref": "#/components/schemas/Block" },
+                        "tx": { "type": "array", "items": { "type": "string" } }
+                    }
+                }
+            }),
+        );
+
+        let ir = convert_document(&doc).expect("converts");
+        let method = rpc(&ir, "getblock");
+        assert_eq!(method.description, "Get a block");
+        assert_eq!(method.category, "blockchain");
+
+        assert_eq!(method.params[0].name, "blockhash");
+        assert!(method.params[0].required);
+        assert_eq!(method.params[0].param_type.canonical_name.as_deref(), Some("Hash"));
+        assert!(!method.params[1].required);
+        assert_eq!(method.params[1].default_value.as_deref(), Some("1"));
+
+        let result = method.result.as_ref().expect("result");
+        assert_eq!(result.kind, TypeKind::Union);
+        let variants = result.union_variants.as_ref().expect("variants");
+        assert_eq!(variants.len(), 2);
+        let block = &variants[1].type_def;
+        assert_eq!(block.name, "Block");
+        let fields = block.fields.as_ref().expect("fields");
+        let field = |key: &str| {
+            fields.iter().find(|f| f.key == FieldKey::Named(key.to_string())).expect("field")
+        };
+        assert!(field("hash").required);
+        assert!(!field("tx").required);
+        assert_eq!(field("previous").field_type.kind, TypeKind::Alias);
+        assert_eq!(
+            field("tx").field_type.array_element_type().and_then(|t| t.protocol_type.as_deref()),
+            Some("string")
+        );
+
+        assert!(ir.get_type_definitions().iter().any(|t| t.name == "Block"));
+    }
+
+    #[test]
+    fn converts_enums_and_nullable_types() {
+        let doc = document(
+            json!([{
+                "name": "estimatefee",
+                "params": [{
+                    "name": "mode",
+                    "required": true,
+                    "schema": { "type": "string", "enum": ["economical", "conservative"] }
+                }],
+                "result": { "name": "fee", "schema": { "type": ["number", "null"] } }
+            }]),
+            json!({}),
+        );
+
+        let ir = convert_document(&doc).expect("converts");
+        let method = rpc(&ir, "estimatefee");
+        assert_eq!(method.category, DEFAULT_CATEGORY);
+        let mode = &method.params[0].param_type;
+        assert_eq!(mode.kind, TypeKind::Enum);
+        let values: Vec<_> = mode
+            .variants
+            .as_ref()
+            .expect("variants")
+            .iter()
+            .filter_map(|v| v.value.as_deref())
+            .collect();
+        assert_eq!(values, ["economical", "conservative"]);
+        let result = method.result.as_ref().expect("result");
+        assert_eq!(result.protocol_type.as_deref(), Some("number"));
+    }
+
+    #[test]
+    fn rejects_unsupported_documents() {
+        assert!(convert_document(&json!({ "openrpc": "2.0.0", "methods": [] })).is_err());
+        let dangling = document(
+            json!([{ "name": "x", "params": [{ "$ref": "#/components/contentDescriptors/Nope" }] }]),
+            json!({}),
+        );
+        assert!(convert_document(&dangling).is_err());
+    }
+
+    #[test]
+    fn converts_bitcoin_core_openrpc_document() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources/ir/openrpc.json");
+        let ir = OpenRpcAdapter::new().extract_protocol_ir(&path).expect("converts");
+        let methods = ir.get_rpc_methods();
+        assert!(methods.len() > 100);
+
+        let getblock = rpc(&ir, "getblock");
+        assert_eq!(getblock.category, "blockchain");
+        assert_eq!(getblock.result.as_ref().map(|r| r.kind.clone()), Some(TypeKind::Union));
+        assert!(getblock.params.iter().any(|p| p.name == "verbosity" && !p.required));
+    }
+}
@@ -0,0 +1,435 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! OpenRPC 1.3 exporter (IR -> spec-compliant OpenRPC document).
+//!
+//! The inverse of [`crate::openrpc`]: every RPC method in a `ProtocolIR` becomes an OpenRPC
+//! method whose params and result are content descriptors with JSON Schema types, so teams
+//! working in other languages can consume the interface with standard OpenRPC tooling.
+//! Type definitions become `components/schemas`. REST endpoints and notifications have no
+//! OpenRPC equivalent and are not exported.
+//!
+//! Metadata that JSON Schema cannot express is kept in `x-` extensions:
+//!
+//! - methods: `x-version-added`, `x-version-removed`, `x-examples`; the category is the first tag
+//! - params and fields: `x-version-added`, `x-version-removed`; defaults that are not JSON go
+//!   in `x-default-hint` (JSON defaults use the schema `default`)
+//! - deprecated methods, params and fields: the spec's `deprecated: true`, with the details in
+//!   `x-deprecated-since`, `x-deprecated-replacement` and `x-deprecated-flag`
+//! - schemas: `x-protocol-type` when the IR type is richer than the JSON type (`hex`, `amount`,
+//!   `timestamp`, ...), `x-condition` for conditional result shapes, and `title` for named types
+
+use ir::{Deprecation, FieldDef, ParamDef, ProtocolDef, ProtocolIR, RpcDef, TypeDef, TypeKind};
+use serde_json::{json, Map, Value};
+
+/// OpenRPC specification version written by the exporter.
+pub const OPENRPC_SPEC_VERSION: &str = "1.3.2";
+
+const HEX_PATTERN: &str = "^[0-9a-fA-F]*$";
+
+/// Document-level `info` for an exported OpenRPC document.
+#[derive(Debug, Clone)]
+pub struct OpenRpcExportInfo {
+    /// `info.title`
+    pub title: String,
+    /// `info.version`: the version the IR was sliced for, or a label such as "latest"
+    pub version: String,
+    /// Optional `info.description`
+    pub description: Option<String>,
+}
+
+/// Renders the RPC methods and type definitions of `ir` as an OpenRPC 1.3 document.
+///
+/// Methods are emitted sorted by name. Version slicing is up to the caller (see
+/// [`crate::bitcoin_core::openrpc::extract_version_ir`]).
+pub fn export_openrpc(ir: &ProtocolIR, info: &OpenRpcExportInfo) -> Value {
+    let mut methods: Vec<&RpcDef> = ir.get_rpc_methods();
+    methods.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut info_object = Map::new();
+    info_object.insert("title".to_string(), json!(info.title));
+    info_object.insert("version".to_string(), json!(info.version));
+    if let Some(description) = &info.description {
+        info_object.insert("description".to_string(), json!(description));
+    }
+    info_object.insert("x-ethos-ir-version".to_string(), json!(ir.version()));
+
+    let mut document = Map::new();
+    document.insert("openrpc".to_string(), json!(OPENRPC_SPEC_VERSION));
+    document.insert("info".to_string(), Value::Object(info_object));
+    document.insert("methods".to_string(), methods.into_iter().map(method_object).collect());
+
+    let schemas: Map<String, Value> = ir
+        .modules()
+        .iter()
+        .flat_map(|module| module.definitions())
+        .filter_map(|definition| match definition {
+            ProtocolDef::Type(type_def) => Some((type_def.name.clone(), type_schema(type_def))),
+            _ => None,
+        })
+        .collect();
+    if !schemas.is_empty() {
+        document.insert("components".to_string(), json!({ "schemas": schemas }));
+    }
+
+    Value::Object(document)
+}
+
+fn method_object(method: &RpcDef) -> Value {
+    let mut object = Map::new();
+    object.insert("name".to_string(), json!(method.name));
+    if !method.description.is_empty() {
+        object.insert("description".to_string(), json!(method.description));
+    }
+    if !method.category.is_empty() {
+        object.insert("tags".to_string(), json!([{ "name": method.category }]));
+    }
+    object.insert("params".to_string(), method.params.iter().map(param_descriptor).collect());
+    if let Some(result) = &method.result {
+        let mut descriptor = Map::new();
+        descriptor.insert("name".to_string(), json!("result"));
+        if !result.description.is_empty() {
+            descriptor.insert("description".to_string(), json!(result.description));
+        }
+        descriptor.insert("schema".to_string(), type_schema(result));
+        object.insert("result".to_string(), Value::Object(descriptor));
+    }
+    insert_versions(&mut object, &method.version_added, &method.version_removed);
+    insert_deprecation(&mut object, &method.deprecated);
+    if let Some(examples) = method.examples.as_ref().filter(|e| !e.is_empty()) {
+        object.insert("x-examples".to_string(), json!(examples));
+    }
+    Value::Object(object)
+}
+
+fn param_descriptor(param: &ParamDef) -> Value {
+    let mut schema = type_schema(&param.param_type);
+    let mut descriptor = Map::new();
+    descriptor.insert("name".to_string(), json!(param.name));
+    if !param.description.is_empty() {
+        descriptor.insert("description".to_string(), json!(param.description));
+    }
+    descriptor.insert("required".to_string(), json!(param.required));
+    if let Some(default) = &param.default_value {
+        let (key, value) = default_entry(default);
+        match (key, &mut schema) {
+            ("default", Value::Object(schema)) => schema.insert(key.to_string(), value),
+            _ => descriptor.insert(key.to_string(), value),
+        };
+    }
+    descriptor.insert("schema".to_string(), schema);
+    insert_versions(&mut descriptor, &param.version_added, &param.version_removed);
+    insert_deprecation(&mut descriptor, &param.deprecated);
+    Value::Object(descriptor)
+}
+
+fn field_schema(field: &FieldDef) -> Value {
+    let mut schema = type_schema(&field.field_type);
+    if let Value::Object(object) = &mut schema {
+        if !field.description.is_empty() {
+            object.insert("description".to_string(), json!(field.description));
+        }
+        if let Some(default) = &field.default_value {
+            let (key, value) = default_entry(default);
+            object.insert(key.to_string(), value);
+        }
+        insert_versions(object, &field.version_added, &field.version_removed);
+        insert_deprecation(object, &field.deprecated);
+    }
+    schema
+}
+
+/// JSON defaults become the schema `default`; free-text hints (e.g. "all wallets") `x-default-hint`.
+fn default_entry(default: &str) -> (&'static str, Value) {
+    match serde_json::from_str::<Value>(default) {
+        Ok(value) => ("default", value),
+        Err(_) => ("x-default-hint", json!(default)),
+    }
+}
+
+fn insert_versions(
+    object: &mut Map<String, Value>,
+    version_added: &Option<String>,
+    version_removed: &Option<String>,
+) {
+    if let Some(version) = version_added {
+        object.insert("x-version-added".to_string(), json!(version));
+    }
+    if let Some(version) = version_removed {
+        object.insert("x-version-removed".to_string(), json!(version));
+    }
+}
+
+fn insert_deprecation(object: &mut Map<String, Value>, deprecated: &Option<Deprecation>) {
+    let Some(deprecation) = deprecated else {
+        return;
+    };
+    object.insert("deprecated".to_string(), json!(true));
+    for (key, value) in [
+        ("x-deprecated-since", &deprecation.since),
+        ("x-deprecated-replacement", &deprecation.replacement),
+        ("x-deprecated-flag", &deprecation.flag),
+    ] {
+        if let Some(value) = value {
+            object.insert(key.to_string(), json!(value));
+        }
+    }
+}
+
+/// JSON Schema `type` for an IR protocol type, and whether the IR type is richer than it.
+fn json_schema_type(protocol_type: &str) -> (Option<&'static str>, bool) {
+    match protocol_type {
+        "string" => (Some("string"), false),
+        "number" => (Some("number"), false),
+        "boolean" => (Some("boolean"), false),
+        "none" => (Some("null"), false),
+        "object" => (Some("object"), false),
+        "array" => (Some("array"), false),
+        "any" => (None, false),
+        "amount" | "timestamp" => (Some("number"), true),
+        "elision" => (None, true),
+        _ => (Some("string"), true),
+    }
+}
+
+/// Renders a `TypeDef` as a JSON Schema.
+pub fn type_schema(type_def: &TypeDef) -> Value {
+    let protocol_type = type_def.protocol_type.as_deref().unwrap_or(match type_def.kind {
+        TypeKind::Object => "object",
+        TypeKind::Array => "array",
+        _ => "any",
+    });
+    let mut schema = Map::new();
+
+    match type_def.kind {
+        TypeKind::Union => {
+            let variants: Vec<Value> = type_def
+                .union_variants
+                .iter()
+                .flatten()
+                .map(|variant| {
+                    let mut schema = type_schema(&variant.type_def);
+                    if let Value::Object(object) = &mut schema {
+                        if !variant.description.is_empty() {
+                            object.insert("description".to_string(), json!(variant.description));
+                        }
+                        if let Some(condition) = &variant.condition {
+                            object.insert("x-condition".to_string(), json!(condition));
+                        }
+                    }
+                    schema
+                })
+                .collect();
+            schema.insert("oneOf".to_string(), Value::Array(variants));
+        }
+        TypeKind::Alias =>
+            if let Some(base) = &type_def.base_type {
+                schema.insert("$ref".to_string(), json!(format!("#/components/schemas/{}", base)));
+            },
+        TypeKind::Enum => {
+            if let (Some(json_type), _) = json_schema_type(protocol_type) {
+                schema.insert("type".to_string(), json!(json_type));
+            }
+            let values: Vec<Value> = type_def
+                .variants
+                .iter()
+                .flatten()
+                .map(|variant| json!(variant.value.as_deref().unwrap_or(&variant.name)))
+                .collect();
+            schema.insert("enum".to_string(), Value::Array(values));
+        }
+        _ => insert_structural_schema(&mut schema, type_def, protocol_type),
+    }
+
+    if let (_, true) = json_schema_type(protocol_type) {
+        schema.insert("x-protocol-type".to_string(), json!(protocol_type));
+    }
+    if !type_def.description.is_empty() {
+        schema.insert("description".to_string(), json!(type_def.description));
+    }
+    if let Some(condition) = &type_def.condition {
+        schema.insert("x-condition".to_string(), json!(condition));
+    }
+    let generic_names = ["object", "array", "union", protocol_type];
+    if !matches!(type_def.kind, TypeKind::Primitive | TypeKind::Alias)
+        && !generic_names.contains(&type_def.name.as_str())
+    {
+        schema.insert("title".to_string(), json!(type_def.name));
+    }
+    Value::Object(schema)
+}
+
+/// Objects, arrays and primitives. The JSON shape follows `protocol_type`: Bitcoin Core's IR
+/// models arrays of objects as `TypeKind::Object` with `protocol_type: "array"`.
+fn insert_structural_schema(
+    schema: &mut Map<String, Value>,
+    type_def: &TypeDef,
+    protocol_type: &str,
+) {
+    let fields = type_def.fields.as_deref().unwrap_or_default();
+    let (json_type, _) = json_schema_type(protocol_type);
+    if let Some(json_type) = json_type {
+        schema.insert("type".to_string(), json!(json_type));
+    }
+    match protocol_type {
+        "object" => {
+            if fields.is_empty() {
+                return;
+            }
+            let properties: Map<String, Value> =
+                fields.iter().map(|field| (field.key.as_ident(), field_schema(field))).collect();
+            let required: Vec<String> =
+                fields.iter().filter(|f| f.required).map(|f| f.key.as_ident()).collect();
+            schema.insert("properties".to_string(), Value::Object(properties));
+            if !required.is_empty() {
+                schema.insert("required".to_string(), json!(required));
+            }
+            schema.insert("additionalProperties".to_string(), json!(false));
+        }
+        "array" => match fields {
+            [] => {}
+            [element] => {
+                schema.insert("items".to_string(), type_schema(&element.field_type));
+            }
+            elements => {
+                let items: Vec<Value> = elements.iter().map(field_schema).collect();
+                schema.insert("prefixItems".to_string(), Value::Array(items));
+            }
+        },
+        "hex" => {
+            schema.insert("pattern".to_string(), json!(HEX_PATTERN));
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::openrpc::{convert_document, OpenRpcAdapter};
+    use crate::ProtocolAdapter;
+
+    fn info() -> OpenRpcExportInfo {
+        OpenRpcExportInfo {
+            title: "Bitcoin Core JSON-RPC".to_string(),
+            version: "v30.2".to_string(),
+            description: None,
+        }
+    }
+
+    fn bitcoin_core_openrpc_ir() -> ProtocolIR {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources/ir/openrpc.json");
+        OpenRpcAdapter::new().extract_protocol_ir(&path).expect("converts")
+    }
+
+    /// Param names, required flags, defaults, categories and result shapes survive a round trip.
+    fn assert_same_interface(expected: &ProtocolIR, actual: &ProtocolIR) {
+        let expected = expected.get_rpc_methods();
+        let actual = actual.get_rpc_methods();
+        assert_eq!(expected.len(), actual.len());
+        for (a, b) in expected.iter().zip(actual.iter()) {
+            assert_eq!(a.name, b.name);
+            assert_eq!(a.description, b.description, "{}", a.name);
+            assert_eq!(a.category, b.category, "{}", a.name);
+            assert_eq!(a.version_added, b.version_added, "{}", a.name);
+            assert_eq!(a.examples, b.examples, "{}", a.name);
+            let params = |m: &RpcDef| {
+                m.params
+                    .iter()
+                    .map(|p| {
+                        (
+                            p.name.clone(),
+                            p.required,
+                            p.default_value.clone(),
+                            p.param_type.protocol_type.clone(),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(params(a), params(b), "{}", a.name);
+            // Bitcoin Core's IR models arrays of objects as `TypeKind::Object`; they come back
+            // as plain arrays, so compare the JSON shape rather than the kind for those.
+            let shape = |m: &RpcDef| {
+                m.result.as_ref().map(|r| match r.protocol_type.as_deref() {
+                    Some("array") => (TypeKind::Array, r.protocol_type.clone()),
+                    _ => (r.kind.clone(), r.protocol_type.clone()),
+                })
+            };
+            assert_eq!(shape(a), shape(b), "{}", a.name);
+        }
+    }
+
+    #[test]
+    fn bitcoin_core_openrpc_round_trips() {
+        let imported = bitcoin_core_openrpc_ir();
+        let exported = export_openrpc(&imported, &info());
+        assert_eq!(exported["openrpc"], OPENRPC_SPEC_VERSION);
+
+        let reimported = convert_document(&exported).expect("exported document converts");
+        assert_same_interface(&imported, &reimported);
+
+        // Exporting again is stable.
+        assert_eq!(export_openrpc(&reimported, &info()), exported);
+    }
+
+    #[test]
+    fn exported_methods_keep_the_source_document_shape() {
+        let source: Value = serde_json::from_str(
+            &std::fs::read_to_string(
+                Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources/ir/openrpc.json"),
+            )
+            .expect("read openrpc.json"),
+        )
+        .expect("valid json");
+        let exported = export_openrpc(&bitcoin_core_openrpc_ir(), &info());
+
+        let names = |doc: &Value| {
+            doc["methods"]
+                .as_array()
+                .expect("methods")
+                .iter()
+                .map(|m| m["name"].as_str().expect("name").to_string())
+                .collect::<std::collections::BTreeSet<_>>()
+        };
+        assert_eq!(names(&source), names(&exported));
+
+        let getblock = exported["methods"]
+            .as_array()
+            .expect("methods")
+            .iter()
+            .find(|m| m["name"] == "getblock")
+            .expect("getblock");
+        assert_eq!(getblock["tags"][0]["name"], "blockchain");
+        assert_eq!(getblock["params"][0]["schema"]["x-protocol-type"], "hex");
+        assert_eq!(getblock["params"][1]["schema"]["default"], 1);
+        assert!(getblock["result"]["schema"]["oneOf"].is_array());
+        assert!(getblock["x-examples"][0].as_str().is_some_and(|e| e.contains("getblock")));
+    }
+
+    #[test]
+    fn canonical_ir_round_trips() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources/ir/bitcoin.ir.json");
+        let canonical = ProtocolIR::from_file(&path).expect("canonical IR loads");
+        let exported = export_openrpc(&canonical, &info());
+        let reimported = convert_document(&exported).expect("exported document converts");
+        assert_same_interface(&canonical, &reimported);
+
+        let gettransaction = exported["methods"]
+            .as_array()
+            .expect("methods")
+            .iter()
+            .find(|m| m["name"] == "gettransaction")
+            .expect("gettransaction");
+        assert_eq!(gettransaction["params"][1]["name"], "include_watchonly");
+        assert_eq!(gettransaction["params"][1]["deprecated"], true);
+        let deprecated = |ir: &ProtocolIR| {
+            ir.get_rpc_methods()
+                .into_iter()
+                .flat_map(|m| m.params.iter())
+                .filter_map(|p| p.deprecated.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(deprecated(&canonical), deprecated(&reimported));
+    }
+}
@@ -15,6 +15,8 @@ pub const CAP_RPC: &str = "rpc";
 pub const CAP_P2P: &str = "p2p";
 /// PSBT capability
 pub const CAP_PSBT: &str = "psbt";
+/// REST interface capability
+pub const CAP_REST: &str = "rest";
 
 #[derive(Debug, Error)]
 /// Errors that can occur during protocol extraction
@@ -164,7 +164,7 @@ impl fuzz_types::ProtocolAdapter for RpcAdapter {
         let start_time = std::time::Instant::now();
 
         // Translate canonical method name to adapter-specific name
-        let adapter_kind = AdapterKind::BitcoinCore;
+        let adapter_kind = AdapterKind::from(self.implementation);
         let method_name =
             crate::normalization_registry::NormalizationRegistry::for_adapter(adapter_kind)
                 .unwrap_or_default()
@@ -192,7 +192,7 @@ impl fuzz_types::ProtocolAdapter for RpcAdapter {
 
     fn normalize_output(&self, value: &serde_json::Value) -> serde_json::Value {
         // Use unified normalization registry created on demand
-        let adapter_kind = AdapterKind::BitcoinCore;
+        let adapter_kind = AdapterKind::from(self.implementation);
         let registry =
             crate::normalization_registry::NormalizationRegistry::for_adapter(adapter_kind)
                 .unwrap_or_default();
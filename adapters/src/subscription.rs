@@ -0,0 +1,692 @@
+//! Polling-based chain event subscriptions
+//!
+//! Builds `ChainEvent` streams on top of plain RPC calls so consumers get one event
+//! abstraction whether or not the node publishes ZMQ notifications:
+//!
+//! - `blocks`: `waitfornewblock` long-polling (falling back to `getbestblockhash` polling when
+//!   the node does not serve it), with reorg detection that emits disconnects before the new
+//!   branch's connects
+//! - `mempool`: `getrawmempool` snapshots diffed into add/remove events
+//! - `wallet`: a `listsinceblock` cursor, including transactions removed by reorgs
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::stream::{self, BoxStream, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::rpc_adapter::ProtocolBackend;
+use crate::{ProtocolAdapterError, ProtocolAdapterResult};
+
+/// Shared backend handle used by subscriptions
+pub type SharedBackend = Arc<dyn ProtocolBackend + Send + Sync>;
+
+/// Topics available through polling subscriptions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubscriptionTopic {
+    /// Blocks connected to / disconnected from the active chain
+    Blocks,
+    /// Transactions entering / leaving the mempool
+    Mempool,
+    /// Wallet transactions
+    Wallet,
+}
+
+impl FromStr for SubscriptionTopic {
+    type Err = ProtocolAdapterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "blocks" | "hashblock" => Ok(Self::Blocks),
+            "mempool" | "hashtx" => Ok(Self::Mempool),
+            "wallet" => Ok(Self::Wallet),
+            other => Err(ProtocolAdapterError::Message(format!(
+                "Unknown subscription topic '{}' (expected blocks, mempool or wallet)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Event emitted by a subscription
+///
+/// Serialized with an `event` tag, e.g. `{"event":"block_connected","hash":"..","height":1}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ChainEvent {
+    /// Block connected to the active chain
+    BlockConnected {
+        /// Block hash
+        hash: String,
+        /// Block height
+        height: u64,
+    },
+    /// Block disconnected from the active chain by a reorg
+    BlockDisconnected {
+        /// Block hash
+        hash: String,
+        /// Block height
+        height: u64,
+    },
+    /// Transaction added to the mempool
+    MempoolAdded {
+        /// Transaction id
+        txid: String,
+    },
+    /// Transaction removed from the mempool (mined, replaced or evicted)
+    MempoolRemoved {
+        /// Transaction id
+        txid: String,
+    },
+    /// New or newly confirmed wallet transaction
+    WalletTransaction {
+        /// Transaction id
+        txid: String,
+        /// Containing block, if confirmed
+        #[serde(skip_serializing_if = "Option::is_none")]
+        blockhash: Option<String>,
+        /// The `listsinceblock` entry as returned by the node
+        entry: Value,
+    },
+    /// Wallet transaction whose block was disconnected by a reorg
+    WalletTransactionRemoved {
+        /// Transaction id
+        txid: String,
+    },
+    /// Polling failed; the subscription keeps going
+    Error {
+        /// Error description
+        message: String,
+    },
+}
+
+impl From<ChainEvent> for Value {
+    fn from(event: ChainEvent) -> Self {
+        serde_json::to_value(event)
+            .unwrap_or_else(|e| json!({ "event": "error", "message": e.to_string() }))
+    }
+}
+
+/// Options read from the subscription params
+#[derive(Debug, Clone, PartialEq)]
+pub struct PollOptions {
+    /// Delay between polls (`interval_ms`, default 1000)
+    pub interval: Duration,
+    /// Number of recent blocks remembered for reorg detection (`reorg_window`, default 100)
+    pub reorg_window: usize,
+    /// Block hash to start the wallet cursor from (`since`); defaults to the current tip
+    pub since: Option<String>,
+}
+
+impl Default for PollOptions {
+    fn default() -> Self {
+        Self { interval: Duration::from_millis(1000), reorg_window: 100, since: None }
+    }
+}
+
+impl PollOptions {
+    /// Read options from subscription params, keeping defaults for missing keys
+    pub fn from_params(params: &Value) -> Self {
+        let defaults = Self::default();
+        Self {
+            interval: params
+                .get("interval_ms")
+                .and_then(Value::as_u64)
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.interval),
+            reorg_window: params
+                .get("reorg_window")
+                .and_then(Value::as_u64)
+                .map(|w| (w as usize).max(1))
+                .unwrap_or(defaults.reorg_window),
+            since: params.get("since").and_then(Value::as_str).map(str::to_string),
+        }
+    }
+}
+
+/// Stream of chain events for `topic`, produced by polling `backend`
+pub fn poll_events(
+    backend: SharedBackend,
+    topic: SubscriptionTopic,
+    options: PollOptions,
+) -> BoxStream<'static, ChainEvent> {
+    let poller =
+        Poller { backend, options, state: PollState::from(topic), pending: VecDeque::new() };
+    stream::unfold(poller, |mut poller| async move {
+        let event = poller.next_event().await;
+        Some((event, poller))
+    })
+    .boxed()
+}
+
+/// Per-topic polling state
+enum PollState {
+    Blocks(BlockTracker),
+    Mempool(Option<BTreeSet<String>>),
+    Wallet(Option<WalletCursor>),
+}
+
+impl From<SubscriptionTopic> for PollState {
+    fn from(topic: SubscriptionTopic) -> Self {
+        match topic {
+            SubscriptionTopic::Blocks => Self::Blocks(BlockTracker::default()),
+            SubscriptionTopic::Mempool => Self::Mempool(None),
+            SubscriptionTopic::Wallet => Self::Wallet(None),
+        }
+    }
+}
+
+struct Poller {
+    backend: SharedBackend,
+    options: PollOptions,
+    state: PollState,
+    pending: VecDeque<ChainEvent>,
+}
+
+impl Poller {
+    async fn next_event(&mut self) -> ChainEvent {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return event;
+            }
+            match self.poll().await {
+                Ok(events) => self.pending.extend(events),
+                Err(e) => {
+                    tokio::time::sleep(self.options.interval).await;
+                    return ChainEvent::Error { message: e.to_string() };
+                }
+            }
+        }
+    }
+
+    /// One polling round; returns the events it produced (possibly none)
+    async fn poll(&mut self) -> ProtocolAdapterResult<Vec<ChainEvent>> {
+        let backend = &*self.backend;
+        let options = &self.options;
+        match &mut self.state {
+            PollState::Blocks(tracker) => tracker.poll(backend, options).await,
+            PollState::Mempool(previous) => {
+                if previous.is_some() {
+                    tokio::time::sleep(options.interval).await;
+                }
+                let snapshot: BTreeSet<String> =
+                    serde_json::from_value(call(backend, "getrawmempool", json!([false])).await?)
+                        .map_err(|e| ProtocolAdapterError::Message(e.to_string()))?;
+                let events = match previous.as_ref() {
+                    Some(previous) => mempool_diff(previous, &snapshot),
+                    None => Vec::new(),
+                };
+                *previous = Some(snapshot);
+                Ok(events)
+            }
+            PollState::Wallet(cursor) => {
+                let cursor = match cursor {
+                    Some(cursor) => {
+                        tokio::time::sleep(options.interval).await;
+                        cursor
+                    }
+                    None => {
+                        let since = match &options.since {
+                            Some(hash) => hash.clone(),
+                            None =>
+                                string_result(call(backend, "getbestblockhash", json!([])).await?)?,
+                        };
+                        cursor.insert(WalletCursor { since, seen: HashMap::new() })
+                    }
+                };
+                cursor.poll(backend).await
+            }
+        }
+    }
+}
+
+/// Remembers recent blocks of the active chain to detect reorgs
+#[derive(Default)]
+struct BlockTracker {
+    /// Height -> hash of the most recent blocks
+    chain: BTreeMap<u64, String>,
+    /// Set once `waitfornewblock` turns out to be unavailable
+    use_polling: bool,
+    /// Consecutive `waitfornewblock` failures, used to back off before retrying
+    long_poll_failures: u32,
+}
+
+impl BlockTracker {
+    async fn poll(
+        &mut self,
+        backend: &dyn ProtocolBackend,
+        options: &PollOptions,
+    ) -> ProtocolAdapterResult<Vec<ChainEvent>> {
+        let tip_hash = match self.chain.last_key_value() {
+            // First round: remember the current tip without emitting anything
+            None => {
+                let hash = string_result(call(backend, "getbestblockhash", json!([])).await?)?;
+                let header = BlockHeader::fetch(backend, &hash).await?;
+                self.chain.insert(header.height, header.hash);
+                return Ok(Vec::new());
+            }
+            Some(_) => self.wait_for_tip(backend, options).await?,
+        };
+        if self.chain.values().next_back() == Some(&tip_hash) {
+            return Ok(Vec::new());
+        }
+        self.advance_to(backend, &tip_hash, options.reorg_window).await
+    }
+
+    /// Block until the tip may have changed and return the current tip hash
+    async fn wait_for_tip(
+        &mut self,
+        backend: &dyn ProtocolBackend,
+        options: &PollOptions,
+    ) -> ProtocolAdapterResult<String> {
+        if !self.use_polling {
+            if self.long_poll_failures > 0 {
+                tokio::time::sleep(backoff(options.interval, self.long_poll_failures)).await;
+            }
+            let timeout_ms = options.interval.as_millis() as u64;
+            match backend.call("waitfornewblock", json!([timeout_ms])).await {
+                Ok(value) => {
+                    self.long_poll_failures = 0;
+                    if let Some(hash) = value.get("hash").and_then(Value::as_str) {
+                        return Ok(hash.to_string());
+                    }
+                    self.use_polling = true;
+                }
+                Err(e) if is_unavailable(&e.to_string()) => self.use_polling = true,
+                // Transient failure (connection, timeout, warmup): keep long-polling
+                Err(e) => {
+                    self.long_poll_failures = self.long_poll_failures.saturating_add(1);
+                    return Err(ProtocolAdapterError::Message(format!(
+                        "waitfornewblock failed: {}",
+                        e
+                    )));
+                }
+            }
+        }
+        tokio::time::sleep(options.interval).await;
+        string_result(call(backend, "getbestblockhash", json!([])).await?)
+    }
+
+    /// Walk back from the new tip to the fork point, emitting disconnects then connects
+    async fn advance_to(
+        &mut self,
+        backend: &dyn ProtocolBackend,
+        tip_hash: &str,
+        reorg_window: usize,
+    ) -> ProtocolAdapterResult<Vec<ChainEvent>> {
+        let lowest_known = self.chain.keys().next().copied().unwrap_or(0);
+        let mut branch = Vec::new();
+        let mut header = BlockHeader::fetch(backend, tip_hash).await?;
+        let fork_height = loop {
+            if self.chain.get(&header.height) == Some(&header.hash) {
+                break header.height;
+            }
+            let height = header.height;
+            let previous = header.previous.clone();
+            branch.push(header);
+            match previous {
+                // Deeper than the remembered window: treat everything remembered as replaced
+                _ if height <= lowest_known => break height.saturating_sub(1),
+                Some(previous) => header = BlockHeader::fetch(backend, &previous).await?,
+                None => break height.saturating_sub(1),
+            }
+        };
+
+        let mut events = Vec::new();
+        let disconnected = self.chain.split_off(&(fork_height + 1));
+        events.extend(
+            disconnected
+                .into_iter()
+                .rev()
+                .map(|(height, hash)| ChainEvent::BlockDisconnected { hash, height }),
+        );
+        for header in branch.into_iter().rev() {
+            events.push(ChainEvent::BlockConnected {
+                hash: header.hash.clone(),
+                height: header.height,
+            });
+            self.chain.insert(header.height, header.hash);
+        }
+        while self.chain.len() > reorg_window {
+            self.chain.pop_first();
+        }
+        Ok(events)
+    }
+}
+
+/// The parts of `getblockheader` needed to walk the chain
+struct BlockHeader {
+    hash: String,
+    height: u64,
+    previous: Option<String>,
+}
+
+impl BlockHeader {
+    async fn fetch(backend: &dyn ProtocolBackend, hash: &str) -> ProtocolAdapterResult<Self> {
+        let value = call(backend, "getblockheader", json!([hash, true])).await?;
+        let height = value.get("height").and_then(Value::as_u64).ok_or_else(|| {
+            ProtocolAdapterError::Message(format!("getblockheader {} returned no height", hash))
+        })?;
+        Ok(Self {
+            hash: hash.to_string(),
+            height,
+            previous: value.get("previousblockhash").and_then(Value::as_str).map(str::to_string),
+        })
+    }
+}
+
+/// `listsinceblock` cursor and the wallet transactions already reported
+struct WalletCursor {
+    since: String,
+    /// Txid -> containing block (None while unconfirmed)
+    seen: HashMap<String, Option<String>>,
+}
+
+impl WalletCursor {
+    async fn poll(
+        &mut self,
+        backend: &dyn ProtocolBackend,
+    ) -> ProtocolAdapterResult<Vec<ChainEvent>> {
+        // listsinceblock "blockhash" target_confirmations include_watchonly include_removed
+        let value = call(backend, "listsinceblock", json!([self.since, 1, true, true])).await?;
+        let mut events = Vec::new();
+
+        for entry in value.get("removed").and_then(Value::as_array).into_iter().flatten() {
+            if let Some(txid) = entry.get("txid").and_then(Value::as_str) {
+                if self.seen.remove(txid).is_some() {
+                    events.push(ChainEvent::WalletTransactionRemoved { txid: txid.to_string() });
+                }
+            }
+        }
+        for entry in value.get("transactions").and_then(Value::as_array).into_iter().flatten() {
+            let Some(txid) = entry.get("txid").and_then(Value::as_str) else { continue };
+            let blockhash = entry.get("blockhash").and_then(Value::as_str).map(str::to_string);
+            // Report each transaction once when first seen and again when its block changes
+            if self.seen.get(txid) == Some(&blockhash) {
+                continue;
+            }
+            self.seen.insert(txid.to_string(), blockhash.clone());
+            events.push(ChainEvent::WalletTransaction {
+                txid: txid.to_string(),
+                blockhash,
+                entry: entry.clone(),
+            });
+        }
+
+        if let Some(lastblock) = value.get("lastblock").and_then(Value::as_str) {
+            self.since = lastblock.to_string();
+        }
+        Ok(events)
+    }
+}
+
+/// Longest delay between `waitfornewblock` retries, as a multiple of the poll interval
+const MAX_BACKOFF_FACTOR: u32 = 32;
+
+/// Delay before the next `waitfornewblock` attempt after `failures` consecutive failures
+fn backoff(interval: Duration, failures: u32) -> Duration {
+    let factor = 1u32.checked_shl(failures.saturating_sub(1)).unwrap_or(u32::MAX);
+    interval.saturating_mul(factor.min(MAX_BACKOFF_FACTOR))
+}
+
+/// Whether an RPC error means the method cannot be called at all: unknown to the node
+/// (`-32601`, "Method not found") or disabled / not whitelisted for this user
+fn is_unavailable(message: &str) -> bool {
+    let message = message.to_ascii_lowercase();
+    ["-32601", "method not found", "disabled", "forbidden"]
+        .iter()
+        .any(|marker| message.contains(marker))
+}
+
+/// Add/remove events between two mempool snapshots
+fn mempool_diff(previous: &BTreeSet<String>, current: &BTreeSet<String>) -> Vec<ChainEvent> {
+    let removed =
+        previous.difference(current).map(|txid| ChainEvent::MempoolRemoved { txid: txid.clone() });
+    let added =
+        current.difference(previous).map(|txid| ChainEvent::MempoolAdded { txid: txid.clone() });
+    removed.chain(added).collect()
+}
+
+async fn call(
+    backend: &dyn ProtocolBackend,
+    method: &str,
+    params: Value,
+) -> ProtocolAdapterResult<Value> {
+    backend
+        .call(method, params)
+        .await
+        .map_err(|e| ProtocolAdapterError::Message(format!("{} failed: {}", method, e)))
+}
+
+fn string_result(value: Value) -> ProtocolAdapterResult<String> {
+    value
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| ProtocolAdapterError::Message(format!("expected a string, got {}", value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    /// Backend serving a scripted block tree, mempool and `listsinceblock` responses
+    #[derive(Default)]
+    struct ScriptedBackend {
+        tip: Mutex<String>,
+        /// Hash -> (height, previous hash)
+        headers: Mutex<HashMap<String, (u64, Option<String>)>>,
+        mempool: Mutex<Vec<&'static str>>,
+        since_block: Mutex<VecDeque<Value>>,
+        /// `waitfornewblock`: None = not served, Some(n) = n transient failures, then served
+        long_poll: Mutex<Option<u32>>,
+    }
+
+    impl ScriptedBackend {
+        /// Add blocks on top of `parent` (or from genesis) and make the last one the tip
+        fn extend(&self, parent: Option<&str>, hashes: &[&str]) {
+            let mut headers = self.headers.lock().expect("headers");
+            let mut previous = parent.map(str::to_string);
+            for hash in hashes {
+                let height = previous.as_ref().map_or(0, |p| headers[p].0 + 1);
+                headers.insert(hash.to_string(), (height, previous.clone()));
+                previous = Some(hash.to_string());
+            }
+            *self.tip.lock().expect("tip") = previous.unwrap_or_default();
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ProtocolBackend for ScriptedBackend {
+        fn name(&self) -> &'static str { "scripted" }
+
+        fn version(&self) -> String { "0".to_string() }
+
+        fn capabilities(&self) -> Vec<&'static str> { vec![] }
+
+        fn extract_protocol_ir(
+            &self,
+            _path: &std::path::Path,
+        ) -> ProtocolAdapterResult<ir::ProtocolIR> {
+            Err(ProtocolAdapterError::Message("unsupported".to_string()))
+        }
+
+        async fn call(
+            &self,
+            method: &str,
+            params: Value,
+        ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+            match method {
+                "getbestblockhash" => Ok(json!(*self.tip.lock().expect("tip"))),
+                "getblockheader" => {
+                    let hash = params[0].as_str().unwrap_or_default();
+                    let (height, previous) = self
+                        .headers
+                        .lock()
+                        .expect("headers")
+                        .get(hash)
+                        .cloned()
+                        .ok_or("unknown block")?;
+                    Ok(json!({ "hash": hash, "height": height, "previousblockhash": previous }))
+                }
+                "getrawmempool" => Ok(json!(*self.mempool.lock().expect("mempool"))),
+                "waitfornewblock" => match self.long_poll.lock().expect("long poll").as_mut() {
+                    Some(0) => Ok(json!({ "hash": *self.tip.lock().expect("tip") })),
+                    Some(failures) => {
+                        *failures -= 1;
+                        Err("connection reset by peer".into())
+                    }
+                    None => Err(format!("Method not found: {}", method).into()),
+                },
+                "listsinceblock" => self
+                    .since_block
+                    .lock()
+                    .expect("since")
+                    .pop_front()
+                    .ok_or_else(|| "no response".into()),
+                _ => Err(format!("Method not found: {}", method).into()),
+            }
+        }
+
+        fn normalize_output(&self, value: &Value) -> Value { value.clone() }
+    }
+
+    fn fast() -> PollOptions {
+        PollOptions { interval: Duration::from_millis(1), ..PollOptions::default() }
+    }
+
+    fn connected(hash: &str, height: u64) -> ChainEvent {
+        ChainEvent::BlockConnected { hash: hash.to_string(), height }
+    }
+
+    #[tokio::test]
+    async fn block_subscription_reports_reorgs() {
+        let backend = Arc::new(ScriptedBackend::default());
+        backend.extend(None, &["a0", "a1", "a2"]);
+        let mut tracker = BlockTracker::default();
+        assert!(tracker.poll(&*backend, &fast()).await.expect("initial poll").is_empty());
+
+        backend.extend(Some("a2"), &["a3"]);
+        assert_eq!(
+            tracker.poll(&*backend, &fast()).await.expect("advance"),
+            vec![connected("a3", 3)]
+        );
+
+        backend.extend(Some("a1"), &["b2", "b3", "b4"]);
+        let events = tracker.poll(&*backend, &fast()).await.expect("reorg");
+
+        assert_eq!(
+            events,
+            vec![
+                ChainEvent::BlockDisconnected { hash: "a3".to_string(), height: 3 },
+                ChainEvent::BlockDisconnected { hash: "a2".to_string(), height: 2 },
+                connected("b2", 2),
+                connected("b3", 3),
+                connected("b4", 4),
+            ]
+        );
+        // waitfornewblock is not served, so the tracker fell back to polling
+        assert!(tracker.use_polling);
+    }
+
+    #[tokio::test]
+    async fn block_subscription_keeps_long_polling_after_transient_errors() {
+        let backend = Arc::new(ScriptedBackend::default());
+        backend.extend(None, &["a0", "a1"]);
+        *backend.long_poll.lock().expect("long poll") = Some(2);
+        let mut tracker = BlockTracker::default();
+        assert!(tracker.poll(&*backend, &fast()).await.expect("initial poll").is_empty());
+
+        backend.extend(Some("a1"), &["a2"]);
+        assert!(tracker.poll(&*backend, &fast()).await.is_err());
+        assert!(tracker.poll(&*backend, &fast()).await.is_err());
+        assert_eq!(
+            tracker.poll(&*backend, &fast()).await.expect("long poll recovered"),
+            vec![connected("a2", 2)]
+        );
+        assert!(!tracker.use_polling);
+        assert_eq!(tracker.long_poll_failures, 0);
+    }
+
+    #[test]
+    fn long_poll_errors_are_classified() {
+        assert!(is_unavailable(r#"RPC error: {"code":-32601,"message":"Method not found"}"#));
+        assert!(is_unavailable("Method not found (disabled)"));
+        assert!(!is_unavailable("error sending request: connection refused"));
+        assert_eq!(backoff(Duration::from_millis(10), 1), Duration::from_millis(10));
+        assert_eq!(backoff(Duration::from_millis(10), 3), Duration::from_millis(40));
+        assert_eq!(backoff(Duration::from_millis(10), 40), Duration::from_millis(320));
+    }
+
+    #[tokio::test]
+    async fn mempool_subscription_streams_diffs() {
+        let backend = Arc::new(ScriptedBackend::default());
+        *backend.mempool.lock().expect("mempool") = vec!["t1", "t2"];
+        let mut poller = Poller {
+            backend: backend.clone(),
+            options: fast(),
+            state: PollState::from(SubscriptionTopic::Mempool),
+            pending: VecDeque::new(),
+        };
+        assert!(poller.poll().await.expect("baseline").is_empty());
+
+        *backend.mempool.lock().expect("mempool") = vec!["t1", "t3"];
+
+        assert_eq!(
+            poller.next_event().await,
+            ChainEvent::MempoolRemoved { txid: "t2".to_string() }
+        );
+        assert_eq!(poller.next_event().await, ChainEvent::MempoolAdded { txid: "t3".to_string() });
+    }
+
+    #[tokio::test]
+    async fn subscription_stream_surfaces_errors() {
+        let backend = Arc::new(ScriptedBackend::default());
+        let mut events = poll_events(backend, SubscriptionTopic::Wallet, fast());
+
+        // No listsinceblock responses are scripted, so polling fails but the stream continues
+        assert!(matches!(events.next().await, Some(ChainEvent::Error { .. })));
+        assert!(matches!(events.next().await, Some(ChainEvent::Error { .. })));
+    }
+
+    #[tokio::test]
+    async fn wallet_cursor_reports_confirmations_and_removals() {
+        let backend = Arc::new(ScriptedBackend::default());
+        backend.since_block.lock().expect("since").extend([
+            json!({ "transactions": [{ "txid": "w1" }], "removed": [], "lastblock": "a1" }),
+            json!({
+                "transactions": [{ "txid": "w1", "blockhash": "a2" }],
+                "removed": [],
+                "lastblock": "a2"
+            }),
+            json!({ "transactions": [], "removed": [{ "txid": "w1" }], "lastblock": "b2" }),
+        ]);
+        let mut cursor = WalletCursor { since: "a0".to_string(), seen: HashMap::new() };
+
+        let unconfirmed = cursor.poll(&*backend).await.expect("first poll");
+        let confirmed = cursor.poll(&*backend).await.expect("second poll");
+        let removed = cursor.poll(&*backend).await.expect("third poll");
+
+        assert!(matches!(
+            &unconfirmed[..],
+            [ChainEvent::WalletTransaction { blockhash: None, .. }]
+        ));
+        assert!(matches!(
+            &confirmed[..],
+            [ChainEvent::WalletTransaction { blockhash: Some(hash), .. }] if hash == "a2"
+        ));
+        assert_eq!(removed, vec![ChainEvent::WalletTransactionRemoved { txid: "w1".to_string() }]);
+        assert_eq!(cursor.since, "b2");
+    }
+
+    #[test]
+    fn events_serialize_with_tag() {
+        let value = Value::from(connected("a1", 1));
+        assert_eq!(value, json!({ "event": "block_connected", "hash": "a1", "height": 1 }));
+        assert!("unknown".parse::<SubscriptionTopic>().is_err());
+    }
+}
@@ -0,0 +1,27 @@
+//! Configuration interface for btcd RPC clients
+
+use std::fmt;
+use std::path::PathBuf;
+
+#[derive(Clone)]
+pub struct Config {
+    /// The RPC URL endpoint for the btcd daemon
+    pub rpc_url: String,
+    /// Username for RPC authentication
+    pub rpc_user: String,
+    /// Password for RPC authentication
+    pub rpc_password: String,
+    /// Certificate to trust for the daemon's TLS, usually its self-signed `rpc.cert`
+    pub rpc_cert: Option<PathBuf>,
+}
+
+impl fmt::Debug for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Config")
+            .field("rpc_url", &self.rpc_url)
+            .field("rpc_user", &"[redacted]")
+            .field("rpc_password", &"[redacted]")
+            .field("rpc_cert", &self.rpc_cert)
+            .finish()
+    }
+}
@@ -0,0 +1,11 @@
+//! Configuration interface for Core Lightning RPC clients
+
+use std::path::PathBuf;
+
+/// Core Lightning speaks JSON-RPC over a unix socket (`<lightning-dir>/<network>/lightning-rpc`)
+/// and authenticates callers through filesystem permissions, so no credentials are needed.
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// Path to lightningd's `lightning-rpc` socket
+    pub rpc_socket: PathBuf,
+}
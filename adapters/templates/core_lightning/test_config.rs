@@ -0,0 +1,156 @@
+//! Test configuration for Core Lightning RPC testing
+//!
+//! This module provides configuration utilities for running lightningd nodes in test environments.
+
+use std::env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use bitcoin::Network;
+use crate::config::Config;
+
+const DEFAULT_EXTRA_ARGS: [&str; 1] = ["--log-level=debug"];
+
+/// Error returned when the configured network is not supported for node startup.
+#[derive(Debug)]
+pub struct UnsupportedNetwork;
+
+impl std::fmt::Display for UnsupportedNetwork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unsupported network")
+    }
+}
+
+impl std::error::Error for UnsupportedNetwork {}
+
+/// TestConfig represents the configuration needed to run a lightningd node in a test environment.
+/// lightningd needs a running bitcoind to follow the chain, so the RPC port and credentials here
+/// are those of the **backing bitcoind**; lightningd's own RPC is the unix socket in its data
+/// directory. Defaults are:
+/// - `network = Network::Regtest`
+/// - `rpc_port = 18443` (bitcoind's regtest RPC port)
+/// - `rpc_username = "rpcuser"`
+/// - `rpc_password = "rpcpassword"`
+/// - `lightningd_path = None` (use executable from PATH)
+/// - `extra_args = ["--log-level=debug"]`
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let mut cfg = TestConfig::default();
+/// cfg.rpc_port = 28443;
+/// cfg.lightningd_path = Some("/opt/cln/bin/lightningd".into());
+/// ```
+///
+/// # Environment Overrides
+///
+/// Reads `RPC_NETWORK`, `RPC_PORT`, `RPC_USER`, `RPC_PASS` (all describing the backing bitcoind),
+/// and `LIGHTNINGD_PATH` (path to the lightningd executable) to override defaults.
+#[derive(Clone)]
+pub struct TestConfig {
+    /// Which network to run lightningd against.
+    pub network: Network,
+    /// RPC port of the bitcoind that lightningd uses as its chain backend.
+    pub rpc_port: u16,
+    /// RPC username of the backing bitcoind.
+    pub rpc_username: String,
+    /// RPC password of the backing bitcoind.
+    pub rpc_password: String,
+    /// Path to the lightningd executable. If None, the default executable name is used (e.g. from PATH).
+    pub lightningd_path: Option<PathBuf>,
+    /// Extra command-line arguments to pass to lightningd
+    pub extra_args: Vec<String>,
+}
+
+impl fmt::Debug for TestConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TestConfig")
+            .field("network", &self.network)
+            .field("rpc_port", &self.rpc_port)
+            .field("rpc_username", &"[redacted]")
+            .field("rpc_password", &"[redacted]")
+            .field("lightningd_path", &self.lightningd_path)
+            .field("extra_args", &self.extra_args)
+            .finish()
+    }
+}
+
+impl TestConfig {
+    /// Return the `--network` value lightningd uses for the configured network; this is also
+    /// the subdirectory of the lightning dir that holds the RPC socket.
+    pub fn as_chain_str(&self) -> Result<&'static str, UnsupportedNetwork> {
+        #[allow(unreachable_patterns)]
+        match self.network {
+            Network::Bitcoin => Ok("bitcoin"),
+            Network::Regtest => Ok("regtest"),
+            Network::Signet => Ok("signet"),
+            Network::Testnet => Ok("testnet"),
+            Network::Testnet4 => Ok("testnet4"),
+            _ => Err(UnsupportedNetwork),
+        }
+    }
+
+    /// Parse network from common strings (case-insensitive). Accepts: regtest, testnet|test,
+    /// signet, mainnet|main|bitcoin, testnet4.
+    pub fn network_from_str(s: &str) -> Option<Network> {
+        match s.to_ascii_lowercase().as_str() {
+            "regtest" => Some(Network::Regtest),
+            "testnet" | "test" => Some(Network::Testnet),
+            "signet" => Some(Network::Signet),
+            "mainnet" | "main" | "bitcoin" => Some(Network::Bitcoin),
+            "testnet4" => Some(Network::Testnet4),
+            _ => None,
+        }
+    }
+
+    /// Create a `TestConfig`, overriding defaults with environment variables:
+    /// - `RPC_NETWORK`: overrides `network`; one of `regtest`, `testnet|test`, `signet`, `mainnet|main|bitcoin`, `testnet4`
+    /// - `RPC_PORT`: overrides `rpc_port` (backing bitcoind)
+    /// - `RPC_USER`: overrides `rpc_username` (backing bitcoind)
+    /// - `RPC_PASS`: overrides `rpc_password` (backing bitcoind)
+    /// - `LIGHTNINGD_PATH`: overrides `lightningd_path` (path to the lightningd executable)
+    #[allow(clippy::field_reassign_with_default)]
+    pub fn from_env() -> Self {
+        let mut cfg = Self::default();
+        if let Ok(net) = env::var("RPC_NETWORK") {
+            if let Some(n) = Self::network_from_str(&net) {
+                cfg.network = n;
+            }
+        }
+        if let Ok(port_str) = env::var("RPC_PORT") {
+            if let Ok(port) = port_str.parse() {
+                cfg.rpc_port = port;
+            }
+        }
+        if let Ok(user) = env::var("RPC_USER") {
+            cfg.rpc_username = user;
+        }
+        if let Ok(pass) = env::var("RPC_PASS") {
+            cfg.rpc_password = pass;
+        }
+        if let Ok(path) = env::var("LIGHTNINGD_PATH") {
+            cfg.lightningd_path = Some(PathBuf::from(path));
+        }
+        cfg
+    }
+
+    /// Convert this test configuration into a client `Config` for a node whose
+    /// `--lightning-dir` is `lightning_dir`.
+    pub fn into_config(self, lightning_dir: &Path) -> Result<Config, UnsupportedNetwork> {
+        let chain = self.as_chain_str()?;
+        Ok(Config { rpc_socket: lightning_dir.join(chain).join("lightning-rpc") })
+    }
+}
+
+impl Default for TestConfig {
+    fn default() -> Self {
+        Self {
+            network: Network::Regtest,
+            rpc_port: 18443,
+            rpc_username: "rpcuser".to_string(),
+            rpc_password: "rpcpassword".to_string(),
+            lightningd_path: None,
+            extra_args: DEFAULT_EXTRA_ARGS.map(String::from).to_vec(),
+        }
+    }
+}
@@ -0,0 +1,9 @@
+//! Configuration interface for Electrum server clients
+
+/// Electrum servers accept newline-delimited JSON-RPC on a plain TCP port (`--electrum-rpc-addr`
+/// for electrs); there is no authentication.
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// Address of the Electrum RPC listener as `host:port` (e.g. `127.0.0.1:50001`)
+    pub server_addr: String,
+}
@@ -0,0 +1,10 @@
+//! Configuration interface for Esplora clients
+
+/// Esplora serves a read-only REST API over plain HTTP (`--http-addr` for Blockstream's
+/// electrs fork); there is no authentication.
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// Base URL of the Esplora API (e.g. `http://127.0.0.1:3002` or
+    /// `https://blockstream.info/api`)
+    pub base_url: String,
+}
@@ -0,0 +1,157 @@
+//! Test configuration for Esplora testing
+//!
+//! This module provides configuration utilities for running Blockstream's electrs fork, which serves the
+//! Esplora HTTP API, in test environments.
+
+use std::env;
+use std::fmt;
+use std::path::PathBuf;
+
+use bitcoin::Network;
+use crate::config::Config;
+
+const DEFAULT_EXTRA_ARGS: [&str; 2] =
+    ["--daemon-rpc-addr=127.0.0.1:18443", "--cookie=rpcuser:rpcpassword"];
+
+/// Error returned when the configured network is not supported for node startup.
+#[derive(Debug)]
+pub struct UnsupportedNetwork;
+
+impl std::fmt::Display for UnsupportedNetwork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unsupported network")
+    }
+}
+
+impl std::error::Error for UnsupportedNetwork {}
+
+/// TestConfig represents the configuration needed to run electrs in a test environment.
+/// `rpc_port` is the Esplora HTTP port, while the credentials are those of the **backing
+/// bitcoind**; electrs takes its address and credentials as `--daemon-rpc-addr` and `--cookie`
+/// in `extra_args`. Defaults are:
+/// - `network = Network::Regtest`
+/// - `rpc_port = 0` (pick a free HTTP port)
+/// - `rpc_username = "rpcuser"`
+/// - `rpc_password = "rpcpassword"`
+/// - `electrs_path = None` (use executable from PATH)
+/// - `extra_args = ["--daemon-rpc-addr=127.0.0.1:18443", "--cookie=rpcuser:rpcpassword"]`
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let mut cfg = TestConfig::default();
+/// cfg.extra_args[0] = "--daemon-rpc-addr=127.0.0.1:28443".into();
+/// cfg.electrs_path = Some("/opt/electrs/bin/electrs".into());
+/// ```
+///
+/// # Environment Overrides
+///
+/// Reads `RPC_NETWORK`, `RPC_PORT` (the Esplora HTTP port), `RPC_USER`, `RPC_PASS` (the
+/// backing bitcoind's credentials), and `ELECTRS_PATH` (path to the electrs executable) to
+/// override defaults.
+#[derive(Clone)]
+pub struct TestConfig {
+    /// Which network to run electrs against.
+    pub network: Network,
+    /// Port of the Esplora HTTP listener; 0 picks a free port.
+    pub rpc_port: u16,
+    /// RPC username of the backing bitcoind.
+    pub rpc_username: String,
+    /// RPC password of the backing bitcoind.
+    pub rpc_password: String,
+    /// Path to the electrs executable. If None, the default executable name is used (e.g. from PATH).
+    pub electrs_path: Option<PathBuf>,
+    /// Extra command-line arguments to pass to electrs
+    pub extra_args: Vec<String>,
+}
+
+impl fmt::Debug for TestConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TestConfig")
+            .field("network", &self.network)
+            .field("rpc_port", &self.rpc_port)
+            .field("rpc_username", &"[redacted]")
+            .field("rpc_password", &"[redacted]")
+            .field("electrs_path", &self.electrs_path)
+            .field("extra_args", &self.extra_args)
+            .finish()
+    }
+}
+
+impl TestConfig {
+    /// Return the value electrs expects for `--network`.
+    pub fn as_chain_str(&self) -> Result<&'static str, UnsupportedNetwork> {
+        #[allow(unreachable_patterns)]
+        match self.network {
+            Network::Bitcoin => Ok("bitcoin"),
+            Network::Regtest => Ok("regtest"),
+            Network::Signet => Ok("signet"),
+            Network::Testnet => Ok("testnet"),
+            Network::Testnet4 => Ok("testnet4"),
+            _ => Err(UnsupportedNetwork),
+        }
+    }
+
+    /// Parse network from common strings (case-insensitive). Accepts: regtest, testnet|test,
+    /// signet, mainnet|main|bitcoin, testnet4.
+    pub fn network_from_str(s: &str) -> Option<Network> {
+        match s.to_ascii_lowercase().as_str() {
+            "regtest" => Some(Network::Regtest),
+            "testnet" | "test" => Some(Network::Testnet),
+            "signet" => Some(Network::Signet),
+            "mainnet" | "main" | "bitcoin" => Some(Network::Bitcoin),
+            "testnet4" => Some(Network::Testnet4),
+            _ => None,
+        }
+    }
+
+    /// Create a `TestConfig`, overriding defaults with environment variables:
+    /// - `RPC_NETWORK`: overrides `network`; one of `regtest`, `testnet|test`, `signet`, `mainnet|main|bitcoin`, `testnet4`
+    /// - `RPC_PORT`: overrides `rpc_port` (the Esplora HTTP port)
+    /// - `RPC_USER`: overrides `rpc_username` (backing bitcoind)
+    /// - `RPC_PASS`: overrides `rpc_password` (backing bitcoind)
+    /// - `ELECTRS_PATH`: overrides `electrs_path` (path to the electrs executable)
+    #[allow(clippy::field_reassign_with_default)]
+    pub fn from_env() -> Self {
+        let mut cfg = Self::default();
+        if let Ok(net) = env::var("RPC_NETWORK") {
+            if let Some(n) = Self::network_from_str(&net) {
+                cfg.network = n;
+            }
+        }
+        if let Ok(port_str) = env::var("RPC_PORT") {
+            if let Ok(port) = port_str.parse() {
+                cfg.rpc_port = port;
+            }
+        }
+        if let Ok(user) = env::var("RPC_USER") {
+            cfg.rpc_username = user;
+        }
+        if let Ok(pass) = env::var("RPC_PASS") {
+            cfg.rpc_password = pass;
+        }
+        if let Ok(path) = env::var("ELECTRS_PATH") {
+            cfg.electrs_path = Some(PathBuf::from(path));
+        }
+        cfg
+    }
+
+    /// Convert this test configuration into a client `Config` for a server listening on
+    /// `http_port`.
+    pub fn into_config(self, http_port: u16) -> Config {
+        Config { base_url: format!("http://127.0.0.1:{}", http_port) }
+    }
+}
+
+impl Default for TestConfig {
+    fn default() -> Self {
+        Self {
+            network: Network::Regtest,
+            rpc_port: 0,
+            rpc_username: "rpcuser".to_string(),
+            rpc_password: "rpcpassword".to_string(),
+            electrs_path: None,
+            extra_args: DEFAULT_EXTRA_ARGS.map(String::from).to_vec(),
+        }
+    }
+}
@@ -0,0 +1,14 @@
+//! Configuration interface for LND REST clients
+
+use std::path::PathBuf;
+
+/// LND serves its REST interface (a grpc-gateway in front of its gRPC services) on
+/// `--restlisten` and authenticates callers with a macaroon, sent hex-encoded in the
+/// `Grpc-Metadata-macaroon` header.
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// Base URL of the REST listener (e.g. `https://127.0.0.1:8080`)
+    pub rest_url: String,
+    /// Path to the macaroon file (usually `admin.macaroon`)
+    pub macaroon_path: PathBuf,
+}
@@ -0,0 +1,309 @@
+//! `drift` subcommand: compare a live node's RPC surface with the IR
+//!
+//! The node (or any JSON-RPC server standing in for one) is asked for `getrpcinfo` to check
+//! that the RPC server answers, `getnetworkinfo` for its version, `help` for its method set
+//! and `help <method>` for each method's arguments. The help text is read with the
+//! `bitcoin-cli help` adapter and compared with the IR sliced to the node's version.
+
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::path::Path;
+use std::time::Duration;
+
+use adapters::bitcoin_core::cli_help::{parse_help_dump, parse_overview};
+use adapters::bitcoin_core::openrpc::convert_to_protocol_ir_with_version_map;
+use analysis::drift::{NodeArgument, NodeMethod, NodeSurface};
+use ir::ProtocolIR;
+use serde_json::{json, Value};
+
+/// Default Bitcoin Core RPC endpoint (mainnet)
+pub const DEFAULT_RPC_URL: &str = "http://127.0.0.1:8332";
+
+/// Minimal blocking JSON-RPC 1.0 client over HTTP/1.1
+#[derive(Debug, Clone)]
+pub struct NodeRpc {
+    /// `host:port` to connect to
+    address: String,
+    /// Request path (e.g. "/" or "/wallet/w1")
+    path: String,
+    /// `Authorization` header value, if any
+    authorization: Option<String>,
+}
+
+impl NodeRpc {
+    /// Create a client for `url` (`http://host[:port][/path]`) with optional credentials
+    pub fn new(url: &str, user: Option<&str>, password: Option<&str>) -> Result<Self, String> {
+        let rest = url
+            .strip_prefix("http://")
+            .ok_or_else(|| format!("unsupported RPC URL '{}' (expected http://host:port)", url))?;
+        let (authority, path) = match rest.find('/') {
+            Some(i) => (&rest[..i], &rest[i..]),
+            None => (rest, "/"),
+        };
+        let address = if authority.contains(':') {
+            authority.to_string()
+        } else {
+            format!("{}:8332", authority)
+        };
+        let authorization = user.map(|user| {
+            format!("Basic {}", base64(format!("{}:{}", user, password.unwrap_or("")).as_bytes()))
+        });
+        Ok(Self { address, path: path.to_string(), authorization })
+    }
+
+    /// Create a client authenticating with a `.cookie` file (`__cookie__:<secret>`)
+    pub fn with_cookie(url: &str, cookie: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(cookie)
+            .map_err(|e| format!("failed to read cookie file {}: {}", cookie.display(), e))?;
+        let (user, password) = content
+            .trim()
+            .split_once(':')
+            .ok_or_else(|| format!("malformed cookie file {}", cookie.display()))?;
+        Self::new(url, Some(user), Some(password))
+    }
+
+    /// Call `method` with positional `params` and return its result
+    pub fn call(&self, method: &str, params: Value) -> Result<Value, String> {
+        let body =
+            json!({"jsonrpc": "1.0", "id": "ethos-drift", "method": method, "params": params})
+                .to_string();
+        let mut request = format!(
+            "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+            self.path,
+            self.address,
+            body.len()
+        );
+        if let Some(authorization) = &self.authorization {
+            request.push_str(&format!("Authorization: {}\r\n", authorization));
+        }
+        request.push_str("\r\n");
+        request.push_str(&body);
+
+        let io_error = |e: std::io::Error| format!("{} ({}): {}", method, self.address, e);
+        let mut stream = TcpStream::connect(&self.address).map_err(io_error)?;
+        stream.set_read_timeout(Some(Duration::from_secs(60))).map_err(io_error)?;
+        stream.write_all(request.as_bytes()).map_err(io_error)?;
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).map_err(io_error)?;
+
+        let response = String::from_utf8_lossy(&response);
+        let (head, body) = response.split_once("\r\n\r\n").unwrap_or((&response, ""));
+        let status = head.lines().next().unwrap_or_default();
+        let reply: Value = match serde_json::from_str(body) {
+            Ok(reply) => reply,
+            Err(_) => return Err(format!("{}: unexpected HTTP response '{}'", method, status)),
+        };
+        match reply.get("error") {
+            Some(error) if !error.is_null() => Err(format!(
+                "{}: {}",
+                method,
+                error.get("message").and_then(Value::as_str).unwrap_or(&error.to_string())
+            )),
+            _ => Ok(reply.get("result").cloned().unwrap_or(Value::Null)),
+        }
+    }
+}
+
+/// Version reported by `getnetworkinfo` (`300200` -> "30.2.0", `210100` -> "0.21.1")
+pub fn node_version(client: &NodeRpc) -> Result<String, String> {
+    client.call("getrpcinfo", json!([]))?;
+    let info = client.call("getnetworkinfo", json!([]))?;
+    let version = info
+        .get("version")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| "getnetworkinfo: no numeric `version`".to_string())?;
+    Ok(if version < 220000 {
+        format!("0.{}.{}", version / 10000, (version / 100) % 100)
+    } else {
+        format!("{}.{}.{}", version / 10000, (version / 100) % 100, version % 100)
+    })
+}
+
+/// Reads the node's method set and argument lists from `help`.
+///
+/// Methods of `ir` that `help` does not list are probed with `help <method>`, so hidden
+/// methods the node still serves are not reported as absent.
+pub fn observe_surface(
+    client: &NodeRpc,
+    version: &str,
+    ir: &ProtocolIR,
+) -> Result<NodeSurface, String> {
+    let overview = text_result(client.call("help", json!([]))?)?;
+    let listed: Vec<String> = parse_overview(&overview).into_iter().map(|(name, _)| name).collect();
+
+    let mut commands = Vec::new();
+    for name in &listed {
+        commands.push(text_result(client.call("help", json!([name]))?)?);
+    }
+    let mut hidden = Vec::new();
+    for rpc in ir.get_rpc_methods() {
+        if listed.contains(&rpc.name) {
+            continue;
+        }
+        // Unknown commands answer "help: unknown command: <name>"
+        let text = text_result(client.call("help", json!([rpc.name]))?)?;
+        if text.split_whitespace().next() == Some(rpc.name.as_str()) {
+            hidden.push(rpc.name.clone());
+            commands.push(text);
+        }
+    }
+
+    let dump = parse_help_dump(Some(&overview), &commands, version);
+    for part in dump.unparsed.iter().filter(|part| !hidden.contains(&part.method)) {
+        eprintln!("warning: unparsed help text: {}", part);
+    }
+
+    let node_ir = convert_to_protocol_ir_with_version_map(
+        dump.doc,
+        Some(version.to_string()),
+        &HashMap::new(),
+    );
+    let methods = node_ir
+        .get_rpc_methods()
+        .into_iter()
+        .map(|rpc| NodeMethod {
+            name: rpc.name.clone(),
+            arguments: rpc
+                .params
+                .iter()
+                .map(|p| NodeArgument { name: p.name.clone(), required: p.required })
+                .collect(),
+            hidden: hidden.contains(&rpc.name),
+        })
+        .collect();
+    Ok(NodeSurface { version: version.to_string(), methods })
+}
+
+fn text_result(value: Value) -> Result<String, String> {
+    match value {
+        Value::String(text) => Ok(text),
+        other => Err(format!("help: expected text, got {}", other)),
+    }
+}
+
+/// Standard base64 (RFC 4648) with padding, for HTTP basic auth
+fn base64(input: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::new();
+    for chunk in input.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufRead;
+    use std::net::TcpListener;
+
+    use analysis::DriftReport;
+    use ir::test_utils::{minimal_module, param, primitive_type, rpc};
+    use ir::ProtocolDef;
+
+    use super::*;
+
+    const OVERVIEW: &str =
+        "== Blockchain ==\ngetblock \"blockhash\" ( verbosity )\ngetblockcount\n";
+    const GETBLOCK: &str = "getblock \"blockhash\" ( verbosity )\n\nReturns a block.\n\nArguments:\n1. blockhash    (string, required) The block hash\n2. verbosity    (numeric, optional, default=1) 0 for hex-encoded data\n\nResult:\n\"hex\"    (string) The block\n";
+    const GETBLOCKCOUNT: &str = "getblockcount\n\nReturns the height of the most-work fully-validated chain.\n\nResult:\nn    (numeric) The current block count\n";
+    const ECHO: &str = "echo ( \"arg0\" )\n\nSimply echo back the input arguments.\n\nArguments:\n1. arg0    (string, optional)\n";
+
+    /// Local stand-in for a node's JSON-RPC server, answering the calls `drift` makes
+    fn spawn_standin() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind stand-in");
+        let address = listener.local_addr().expect("stand-in address");
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                let mut reader = std::io::BufReader::new(stream.try_clone().expect("clone stream"));
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).expect("read header");
+                    if line == "\r\n" {
+                        break;
+                    }
+                    if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        length = value.trim().parse().expect("content length");
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).expect("read body");
+                let request: Value = serde_json::from_slice(&body).expect("request json");
+                let result = match (request["method"].as_str(), request["params"][0].as_str()) {
+                    (Some("getrpcinfo"), _) => json!({"active_commands": [], "logpath": ""}),
+                    (Some("getnetworkinfo"), _) => json!({"version": 300200}),
+                    (Some("help"), None) => json!(OVERVIEW),
+                    (Some("help"), Some("getblock")) => json!(GETBLOCK),
+                    (Some("help"), Some("getblockcount")) => json!(GETBLOCKCOUNT),
+                    (Some("help"), Some("echo")) => json!(ECHO),
+                    (Some("help"), Some(other)) =>
+                        json!(format!("help: unknown command: {}", other)),
+                    _ => Value::Null,
+                };
+                let reply =
+                    json!({"result": result, "error": null, "id": request["id"]}).to_string();
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                    reply.len(),
+                    reply
+                );
+            }
+        });
+        format!("http://{}", address)
+    }
+
+    #[test]
+    fn drift_against_standin_node() {
+        let client = NodeRpc::new(&spawn_standin(), Some("user"), Some("pass")).expect("client");
+        let version = node_version(&client).expect("version");
+        assert_eq!(version, "30.2.0");
+
+        let string = || primitive_type("String", Some("string".to_string()));
+        let ir = ProtocolIR::new(vec![minimal_module(
+            "rpc",
+            vec![
+                ProtocolDef::RpcMethod(rpc(
+                    "getblock",
+                    vec![param("blockhash", string(), true), param("verbose", string(), false)],
+                    None,
+                    "blockchain",
+                )),
+                ProtocolDef::RpcMethod(rpc(
+                    "echo",
+                    vec![param("arg0", string(), false)],
+                    None,
+                    "hidden",
+                )),
+                ProtocolDef::RpcMethod(rpc("getinfo", vec![], None, "control")),
+            ],
+        )]);
+
+        let surface = observe_surface(&client, &version, &ir).expect("surface");
+        let echo = surface.methods.iter().find(|m| m.name == "echo").expect("hidden echo");
+        assert!(echo.hidden);
+
+        let report = DriftReport::compare(&surface, &ir);
+        assert_eq!(report.missing_from_ir, ["getblockcount"]);
+        assert_eq!(report.absent_from_node, ["getinfo"]);
+        assert_eq!(report.argument_mismatches.len(), 1);
+        assert_eq!(report.argument_mismatches[0].method, "getblock");
+        assert_eq!(report.distance(), 4);
+    }
+
+    #[test]
+    fn basic_auth_is_base64_encoded() {
+        assert_eq!(base64(b"user:pass"), "dXNlcjpwYXNz");
+        assert_eq!(base64(b"__cookie__:ab"), "X19jb29raWVfXzphYg==");
+    }
+}
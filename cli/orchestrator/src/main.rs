@@ -12,6 +12,8 @@ use std::path::PathBuf;
 
 use types::{Implementation, ProtocolVersion};
 
+mod drift;
+
 #[tokio::main]
 async fn main() {
     let args: Vec<String> = env::args().collect();
@@ -25,17 +27,52 @@ async fn main() {
         println!();
         println!("SUBCOMMANDS:");
         println!("    pipeline                       Run code generation pipeline");
+        println!("    dump-ir                        Write the ProtocolIR for an implementation to --output");
+        println!("    export --format openrpc        Render the ProtocolIR as an OpenRPC 1.3 document (stdout unless --output)");
+        println!("    drift                          Compare a running node's RPC surface with the IR for its version");
+        println!("    infer-schema                   Propose IR fields from recorded responses (--cassette/--fixtures)");
+        println!("    matrix                         Render a method × version compatibility table from the IR");
         println!("    --help, -h                    Show this help message");
         println!("FLAGS:");
-        println!("    --input <ir_file>             Load ProtocolIR directly from .ir.json file (optional if --implementation is provided)");
-        println!("    --implementation <impl>       Implementation to generate (bitcoin_core) [REQUIRED]");
+        println!("    --input <ir_file>             Load ProtocolIR directly from .ir.json file or an OpenRPC 1.x document (optional if --implementation is provided)");
+        println!("    --implementation <impl>       Implementation to generate (bitcoin_core, bitcoin_knots, ...) [REQUIRED]");
         println!("    --version <version>           Override version (e.g., v30.2.11)");
+        println!("    --versions <v1,v2,...>        Generate one crate for several versions with v<major>[_<minor>] features (e.g., v27.0,v28.0,v29.0,v30.2)");
         println!("    --output <path>               Write generated crate to <path> (e.g. a separate git repo). Preserves .git for easier diff review.");
         println!("    --exclude-hidden-rpcs         Do not generate code for hidden/testing-only RPCs (default: include them).");
+        println!("    --overlay <file>              Apply an IR overlay of local corrections, e.g. overriding getblock.result.tx[].fee (repeatable)");
+        println!("DRIFT FLAGS:");
+        println!(
+            "    --rpc-url <url>               Node (or stand-in) JSON-RPC endpoint (default: {})",
+            drift::DEFAULT_RPC_URL
+        );
+        println!("    --rpc-user <user>             RPC user name (with --rpc-password)");
+        println!("    --rpc-password <password>     RPC password");
+        println!(
+            "    --rpc-cookie <path>           Authenticate with the node's .cookie file instead"
+        );
+        println!("    --format <text|json>          Report format (default: text)");
+        println!("INFER-SCHEMA FLAGS:");
+        println!("    --cassette <file>             Recorded calls ({{\"interactions\": [{{\"method\", \"params\", \"result\"}}]}})");
+        println!("    --fixtures <dir>              Directory of <method>.json responses");
+        println!("    --version <version>           Node version the responses were recorded on (becomes version_added)");
+        println!("    --apply                       Write the updated IR instead of the patch");
+        println!("MATRIX FLAGS:");
+        println!("    --versions <v1,v2,...>        Versions to tabulate (default: every version the IR mentions)");
+        println!("    --format <markdown|html|json> Output format (default: markdown)");
         println!();
         println!("EXAMPLES:");
         println!("    ethos-cli pipeline --input resources/ir/bitcoin.ir.json --implementation bitcoin_core");
         println!("    ethos-cli pipeline --implementation bitcoin_core --output ../ethos-bitcoind   # generate into a separate repo");
+        println!("    ethos-cli pipeline --implementation bitcoin_core --versions v27.0,v28.0,v29.0,v30.2");
+        println!("    ethos-cli pipeline --implementation bitcoin_knots   # Bitcoin Core IR plus the Knots overlay");
+        println!(
+            "    ethos-cli pipeline --implementation bitcoin_core --overlay fixes.overlay.json"
+        );
+        println!("    ethos-cli export --format openrpc --implementation bitcoin_core --version v30.2 --output openrpc.json");
+        println!("    ethos-cli drift --rpc-url http://127.0.0.1:18443 --rpc-cookie ~/.bitcoin/regtest/.cookie --format json");
+        println!("    ethos-cli infer-schema --cassette regtest.json --version 30 --apply --output resources/ir/bitcoin.ir.json");
+        println!("    ethos-cli matrix --versions v27,v28,v29,v29.1,v30 --format html --output matrix.html");
         return;
     }
 
@@ -71,15 +108,206 @@ async fn main() {
         }
     }
 
+    // Handle export subcommand
+    if args.iter().any(|a| a == "export") {
+        let arg = |flag: &str| args.iter().position(|a| a == flag).and_then(|i| args.get(i + 1));
+
+        let format = arg("--format").map(String::as_str).unwrap_or("openrpc");
+        if format != "openrpc" {
+            eprintln!("Error: Unsupported export format '{}' (supported: openrpc)", format);
+            std::process::exit(1);
+        }
+        if arg("--implementation").is_none() && arg("--input").is_none() {
+            eprintln!("Error: export requires --implementation <impl> or --input <ir_file>");
+            eprintln!("Use 'ethos-cli --help' for usage information");
+            std::process::exit(1);
+        }
+
+        match export_openrpc(
+            arg("--implementation").map(String::as_str),
+            arg("--input").map(PathBuf::from),
+            arg("--version").map(String::as_str),
+        ) {
+            Ok(document) => match arg("--output") {
+                Some(output) => {
+                    if let Err(e) = std::fs::write(output, document) {
+                        eprintln!("Failed to write {}: {}", output, e);
+                        std::process::exit(1);
+                    }
+                    println!("OpenRPC document written to {}", output);
+                }
+                None => println!("{}", document),
+            },
+            Err(e) => {
+                eprintln!("Failed to export OpenRPC: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // Handle drift subcommand
+    if args.iter().any(|a| a == "drift") {
+        let arg = |flag: &str| args.iter().position(|a| a == flag).and_then(|i| args.get(i + 1));
+
+        let format = arg("--format").map(String::as_str).unwrap_or("text");
+        if format != "text" && format != "json" {
+            eprintln!("Error: Unsupported drift format '{}' (supported: text, json)", format);
+            std::process::exit(1);
+        }
+
+        let url = arg("--rpc-url").map(String::as_str).unwrap_or(drift::DEFAULT_RPC_URL);
+        let client = match arg("--rpc-cookie") {
+            Some(cookie) => drift::NodeRpc::with_cookie(url, std::path::Path::new(cookie)),
+            None => drift::NodeRpc::new(
+                url,
+                arg("--rpc-user").map(String::as_str),
+                arg("--rpc-password").map(String::as_str),
+            ),
+        };
+
+        match client
+            .map_err(Into::into)
+            .and_then(|client| surface_drift(&client, arg("--input").map(PathBuf::from)))
+        {
+            Ok(report) => {
+                let rendered = match format {
+                    "json" => serde_json::to_string_pretty(&report).unwrap_or_default(),
+                    _ => report.to_string(),
+                };
+                match arg("--output") {
+                    Some(output) => {
+                        if let Err(e) = std::fs::write(output, rendered) {
+                            eprintln!("Failed to write {}: {}", output, e);
+                            std::process::exit(1);
+                        }
+                        println!(
+                            "Drift report written to {} (|B − Δ| = {})",
+                            output,
+                            report.distance()
+                        );
+                    }
+                    None => println!("{}", rendered),
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to measure drift: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // Handle infer-schema subcommand
+    if args.iter().any(|a| a == "infer-schema") {
+        let arg = |flag: &str| args.iter().position(|a| a == flag).and_then(|i| args.get(i + 1));
+
+        let format = arg("--format").map(String::as_str).unwrap_or("text");
+        if format != "text" && format != "json" {
+            eprintln!(
+                "Error: Unsupported infer-schema format '{}' (supported: text, json)",
+                format
+            );
+            std::process::exit(1);
+        }
+        let Some(version) = arg("--version") else {
+            eprintln!("Error: infer-schema requires --version <node_version>");
+            eprintln!("Use 'ethos-cli --help' for usage information");
+            std::process::exit(1);
+        };
+        if arg("--cassette").is_none() && arg("--fixtures").is_none() {
+            eprintln!("Error: infer-schema requires --cassette <file> or --fixtures <dir>");
+            eprintln!("Use 'ethos-cli --help' for usage information");
+            std::process::exit(1);
+        }
+
+        let patch = infer_schema(
+            arg("--input").map(PathBuf::from),
+            arg("--implementation").map(String::as_str),
+            arg("--cassette").map(PathBuf::from),
+            arg("--fixtures").map(PathBuf::from),
+            version,
+        );
+        let (ir, patch) = match patch {
+            Ok(inferred) => inferred,
+            Err(e) => {
+                eprintln!("Failed to infer schema: {}", e);
+                std::process::exit(1);
+            }
+        };
+        let rendered = if args.iter().any(|a| a == "--apply") {
+            serde_json::to_string_pretty(&patch.apply(&ir)).unwrap_or_default()
+        } else if format == "json" {
+            serde_json::to_string_pretty(&patch).unwrap_or_default()
+        } else {
+            patch.to_string()
+        };
+        match arg("--output") {
+            Some(output) => {
+                if let Err(e) = std::fs::write(output, rendered) {
+                    eprintln!("Failed to write {}: {}", output, e);
+                    std::process::exit(1);
+                }
+                println!("{} inferred fields written to {}", patch.additions.len(), output);
+            }
+            None => println!("{}", rendered),
+        }
+        return;
+    }
+
+    // Handle matrix subcommand
+    if args.iter().any(|a| a == "matrix") {
+        let arg = |flag: &str| args.iter().position(|a| a == flag).and_then(|i| args.get(i + 1));
+
+        let format = arg("--format").map(String::as_str).unwrap_or("markdown");
+        if !["markdown", "html", "json"].contains(&format) {
+            eprintln!(
+                "Error: Unsupported matrix format '{}' (supported: markdown, html, json)",
+                format
+            );
+            std::process::exit(1);
+        }
+
+        let matrix = match compatibility_matrix(
+            arg("--input").map(PathBuf::from),
+            arg("--implementation").map(String::as_str),
+            arg("--versions").map(String::as_str),
+        ) {
+            Ok(matrix) => matrix,
+            Err(e) => {
+                eprintln!("Failed to build the compatibility matrix: {}", e);
+                std::process::exit(1);
+            }
+        };
+        let rendered = match format {
+            "html" => matrix.to_html(),
+            "json" => serde_json::to_string_pretty(&matrix).unwrap_or_default(),
+            _ => matrix.to_markdown(),
+        };
+        match arg("--output") {
+            Some(output) => {
+                if let Err(e) = std::fs::write(output, rendered) {
+                    eprintln!("Failed to write {}: {}", output, e);
+                    std::process::exit(1);
+                }
+                println!(
+                    "Compatibility matrix of {} methods × {} versions written to {}",
+                    matrix.methods.len(),
+                    matrix.versions.len(),
+                    output
+                );
+            }
+            None => println!("{}", rendered),
+        }
+        return;
+    }
+
     // Handle pipeline subcommand
     if args.iter().any(|a| a == "pipeline") {
-        use ir::ProtocolIR;
-        use registry::ir_resolver::IrResolver;
-
         // Get implementation first (required for auto-resolution)
         let implementation =
             match args.iter().position(|a| a == "--implementation").and_then(|i| args.get(i + 1)) {
-                Some(impl_str) => match impl_str.parse::<Implementation>() {
+                Some(impl_str) => match parse_implementation(impl_str) {
                     Ok(impl_type) => impl_type,
                     Err(e) => {
                         eprintln!("Error: Invalid implementation '{}': {}", impl_str, e);
@@ -93,35 +321,41 @@ async fn main() {
                 }
             };
 
-        // Resolve IR file: use --input if provided, otherwise auto-resolve from implementation
-        let ir_path = match args.iter().position(|a| a == "--input").and_then(|i| args.get(i + 1)) {
-            Some(ir_file) => PathBuf::from(ir_file),
-            None => {
-                // Auto-resolve IR file from implementation using registry
-                let resolver = match IrResolver::new() {
-                    Ok(resolver) => resolver,
-                    Err(e) => {
-                        eprintln!("Error: Failed to create IR resolver: {}", e);
-                        std::process::exit(1);
-                    }
-                };
-                match resolver.resolve_ir_path_for_implementation(&implementation) {
-                    Ok(path) => path,
-                    Err(e) => {
-                        eprintln!("Error: Failed to resolve IR file for {}: {}", implementation, e);
-                        std::process::exit(1);
-                    }
+        // Load IR: use --input if provided, otherwise auto-resolve from implementation (applying
+        // the dialect's overlay, if it is defined as one)
+        let mut ir = match args.iter().position(|a| a == "--input").and_then(|i| args.get(i + 1)) {
+            Some(ir_file) => match load_input_ir(std::path::Path::new(ir_file)) {
+                Ok(ir) => ir,
+                Err(e) => {
+                    eprintln!("Error: Failed to load IR from file '{}': {}", ir_file, e);
+                    std::process::exit(1);
                 }
-            }
+            },
+            None => match pipeline::load_implementation_ir(implementation) {
+                Ok((ir, _)) => ir,
+                Err(e) => {
+                    eprintln!("Error: Failed to load IR for {}: {}", implementation, e);
+                    std::process::exit(1);
+                }
+            },
         };
 
-        let mut ir = match ProtocolIR::from_file(&ir_path) {
-            Ok(ir) => ir,
-            Err(e) => {
-                eprintln!("Error: Failed to load IR from file '{}': {}", ir_path.display(), e);
-                std::process::exit(1);
-            }
-        };
+        // --overlay <file> (repeatable): local IR corrections, applied in order right after
+        // loading (like dialect overlays) so stripping and filtering see the corrected IR
+        let overlays: Vec<PathBuf> = args
+            .windows(2)
+            .filter(|pair| pair[0] == "--overlay")
+            .map(|pair| PathBuf::from(&pair[1]))
+            .collect();
+        for overlay in &overlays {
+            ir = match pipeline::apply_overlay_file(ir, overlay) {
+                Ok(ir) => ir,
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            };
+        }
 
         let exclude_hidden_rpcs = args.iter().any(|a| a == "--exclude-hidden-rpcs");
         if exclude_hidden_rpcs {
@@ -154,6 +388,20 @@ async fn main() {
             }
         };
 
+        // --versions v27.0,v28.0,...: one crate with a feature per version
+        let versions_arg =
+            args.iter().position(|a| a == "--versions").and_then(|i| args.get(i + 1));
+        let versions = match versions_arg {
+            Some(list) => match parse_version_list(list, implementation) {
+                Ok(versions) => versions,
+                Err(e) => {
+                    eprintln!("Error: Failed to parse --versions '{}': {}", list, e);
+                    std::process::exit(1);
+                }
+            },
+            None => vec![protocol_version],
+        };
+
         // Output directory: --output <path> or default outputs/generated/{crate_name}
         let output_arg = args.iter().position(|a| a == "--output").and_then(|i| args.get(i + 1));
         let crate_dir = match output_arg {
@@ -177,7 +425,7 @@ async fn main() {
         }
 
         // Run compilation with the loaded IR
-        if let Err(e) = compile_with_ir(ir, implementation, &protocol_version, &crate_dir) {
+        if let Err(e) = compile_with_ir(ir, implementation, &versions, &overlays, &crate_dir) {
             eprintln!("IR compilation failed: {}", e);
             std::process::exit(1);
         }
@@ -190,70 +438,246 @@ async fn main() {
     std::process::exit(1);
 }
 
+/// Parse an implementation name given on the command line
+///
+/// Names that are not built in are looked up among the registry's dialects, which are only
+/// registered then. Also checks that the implementation's metadata can be read.
+fn parse_implementation(name: &str) -> Result<Implementation, String> {
+    let implementation = match name.parse::<Implementation>() {
+        Ok(implementation) => implementation,
+        Err(e) => {
+            registry::ir_resolver::IrResolver::new()
+                .and_then(|resolver| resolver.register_dialects())
+                .map_err(|err| format!("{} (failed to register dialects: {})", e, err))?;
+            name.parse::<Implementation>()?
+        }
+    };
+    implementation.metadata()?;
+    Ok(implementation)
+}
+
 /// Dump the ProtocolIR for a given implementation to a file
 fn dump_ir_for_implementation(
     implementation: &str,
     output_path: &std::path::Path,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    use ir::ProtocolIR;
+    let implementation = parse_implementation(implementation)?;
+    let (ir, _) = pipeline::load_implementation_ir(implementation)?;
+    ir.to_file(output_path)?;
+    Ok(())
+}
+
+/// Render the ProtocolIR (optionally sliced to `version`) as a pretty-printed OpenRPC document
+fn export_openrpc(
+    implementation: Option<&str>,
+    input: Option<PathBuf>,
+    version: Option<&str>,
+) -> Result<String, Box<dyn std::error::Error>> {
+    use adapters::openrpc_export::OpenRpcExportInfo;
+
+    let implementation = implementation.map(parse_implementation).transpose()?;
+    let mut ir = match (input, implementation) {
+        (Some(path), _) => load_input_ir(&path)?,
+        (None, Some(implementation)) => pipeline::load_implementation_ir(implementation)?.0,
+        (None, None) => return Err("no --implementation or --input given".into()),
+    };
+    if let Some(version) = version {
+        ir = adapters::bitcoin_core::openrpc::extract_version_ir(ir, version);
+    }
+
+    let title = match &implementation {
+        Some(implementation) => format!("{} JSON-RPC", implementation.display_name()),
+        None => "JSON-RPC API".to_string(),
+    };
+    let info = OpenRpcExportInfo {
+        title,
+        version: version.unwrap_or("latest").to_string(),
+        description: Some("Exported from the Ethos protocol IR.".to_string()),
+    };
+    let document = adapters::openrpc_export::export_openrpc(&ir, &info);
+    Ok(serde_json::to_string_pretty(&document)?)
+}
+
+/// Compare the node's advertised RPC surface with the IR (canonical Bitcoin Core IR unless
+/// `input` is given) sliced to the node's version
+fn surface_drift(
+    client: &drift::NodeRpc,
+    input: Option<PathBuf>,
+) -> Result<analysis::DriftReport, Box<dyn std::error::Error>> {
     use registry::ir_resolver::IrResolver;
-    use types::Implementation as ImplEnum;
 
-    let impl_enum = implementation.parse::<ImplEnum>()?;
-    let resolver = IrResolver::new()?;
-    let ir_path = resolver.resolve_ir_path_for_implementation(&impl_enum)?;
+    let version = drift::node_version(client)?;
+    let ir_path = match input {
+        Some(path) => path,
+        None =>
+            IrResolver::new()?.resolve_ir_path_for_implementation(&Implementation::BitcoinCore)?,
+    };
+    let ir =
+        adapters::bitcoin_core::openrpc::extract_version_ir(load_input_ir(&ir_path)?, &version);
+    let surface = drift::observe_surface(client, &version, &ir)?;
+    Ok(analysis::DriftReport::compare(&surface, &ir))
+}
 
-    let ir = ProtocolIR::from_file(&ir_path)?;
-    ir.to_file(output_path)?;
-    Ok(())
+/// Infer IR additions for the IR (canonical IR of `implementation`, Bitcoin Core by default,
+/// unless `input` is given) from recorded responses
+fn infer_schema(
+    input: Option<PathBuf>,
+    implementation: Option<&str>,
+    cassette: Option<PathBuf>,
+    fixtures: Option<PathBuf>,
+    version: &str,
+) -> Result<(ir::ProtocolIR, analysis::SchemaPatch), Box<dyn std::error::Error>> {
+    use analysis::conformance::{load_fixtures, Cassette};
+    let ir = match input {
+        Some(path) => load_input_ir(&path)?,
+        None => {
+            let implementation =
+                implementation.map(parse_implementation).transpose()?.unwrap_or_default();
+            pipeline::load_implementation_ir(implementation)?.0
+        }
+    };
+
+    let mut samples = Vec::new();
+    if let Some(cassette) = cassette {
+        samples.extend(Cassette::load(&cassette)?.samples());
+    }
+    if let Some(fixtures) = fixtures {
+        samples.extend(load_fixtures(&fixtures)?);
+    }
+    let mut inferrer = analysis::SchemaInferrer::new(&ir);
+    inferrer.observe(version, &samples);
+    let patch = inferrer.infer();
+    Ok((ir, patch))
+}
+
+/// Tabulate the IR (canonical IR of `implementation`, Bitcoin Core by default, unless `input`
+/// is given) for `versions`, or every version the IR mentions
+fn compatibility_matrix(
+    input: Option<PathBuf>,
+    implementation: Option<&str>,
+    versions: Option<&str>,
+) -> Result<analysis::CompatibilityMatrix, Box<dyn std::error::Error>> {
+    let ir = match input {
+        Some(path) => load_input_ir(&path)?,
+        None => {
+            let implementation =
+                implementation.map(parse_implementation).transpose()?.unwrap_or_default();
+            pipeline::load_implementation_ir(implementation)?.0
+        }
+    };
+
+    let versions = match versions {
+        Some(list) => {
+            let versions: Vec<String> = list
+                .split(',')
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+                .collect();
+            for version in &versions {
+                ProtocolVersion::from_string(version)?;
+            }
+            versions
+        }
+        None => analysis::CompatibilityMatrix::versions_in_ir(&ir),
+    };
+    Ok(analysis::CompatibilityMatrix::build(&ir, &versions))
+}
+
+/// Load the pipeline input: a serialized ProtocolIR, or any OpenRPC 1.x document
+fn load_input_ir(path: &std::path::Path) -> Result<ir::ProtocolIR, Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(path)?;
+    let value: serde_json::Value = serde_json::from_str(&content)?;
+    if adapters::openrpc::is_openrpc_document(&value) {
+        let document = adapters::conversion_helpers::parse_schema_document(&content)?;
+        return Ok(adapters::openrpc::convert_document(&document)?);
+    }
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Parse a comma-separated version list; bare majors (`v27`) mean `<major>.0`
+fn parse_version_list(
+    list: &str,
+    implementation: Implementation,
+) -> Result<Vec<ProtocolVersion>, Box<dyn std::error::Error>> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(|v| {
+            let v = if v.contains('.') { v.to_string() } else { format!("{}.0", v) };
+            Ok(ProtocolVersion::from_string_with_protocol(&v, Some(implementation.to_string()))?)
+        })
+        .collect()
 }
 
 /// Compile using a pre-loaded ProtocolIR instead of extracting from schema
+///
+/// More than one version generates a single crate with a `v<major>[_<minor>]` feature per version.
+/// `overlays` are the local overlay files already applied to `ir`, recorded in the README.
 fn compile_with_ir(
-    mut ir: ir::ProtocolIR,
+    ir: ir::ProtocolIR,
     implementation: Implementation,
-    version: &ProtocolVersion,
+    versions: &[ProtocolVersion],
+    overlays: &[PathBuf],
     output_dir: &std::path::Path,
 ) -> Result<(), Box<dyn std::error::Error>> {
     use path::find_project_root;
-    use pipeline::codegen_orchestration::{analyze_implementation, generate_into};
-    use pipeline::project_setup::setup_project_files;
+    use pipeline::codegen_orchestration::{
+        analyze_implementation, generate_into, generate_versions_into,
+    };
+    use pipeline::project_setup::{setup_project_files, write_applied_overlays};
     use pipeline::protocol_compiler::EthosCompiler;
     use pipeline::template_management::create_source_directory_with_templates;
 
-    // Filter IR to only methods available in the target version (excludes unreleased / future
-    // methods) for Bitcoin Core.
-    if implementation == Implementation::BitcoinCore {
-        let filtered =
-            adapters::bitcoin_core::openrpc::extract_version_ir(ir.clone(), version.as_str());
-        if filtered.get_rpc_methods().is_empty() {
-            eprintln!(
-                "warning: version filter for {} would remove all methods (e.g. IR has \
-                 version_added from unreleased/doc only). Using full IR. Fix canonical IR \
-                 version_added or load so version preservation works.",
-                version.as_str()
-            );
-        } else {
-            ir = filtered;
-        }
-    }
+    let newest = versions.iter().max().ok_or("no version to generate")?;
 
     // Create source directory structure and copy template files
     let src_dir = create_source_directory_with_templates(output_dir, implementation)?;
 
-    // Run compiler passes (validation, canonicalization, etc.)
-    let compiler = EthosCompiler::new();
-    ir = compiler.run_compiler_passes(ir)?;
-
     // Setup project files (Cargo.toml, README, etc.)
-    setup_project_files(output_dir, version, implementation)?;
+    setup_project_files(output_dir, newest, implementation)?;
+    write_applied_overlays(output_dir, overlays)?;
 
-    // Run semantic analysis on the IR
     let project_root = find_project_root()?;
-    let compiler_ctx = analyze_implementation(implementation, ir, version, project_root)?;
+    let mut compiler_ctxs = Vec::with_capacity(versions.len());
+    for version in versions {
+        let mut ir = ir.clone();
+
+        // Filter IR to only methods available in the target version (excludes unreleased /
+        // future methods) for Bitcoin Core and its derivatives.
+        if implementation.is_bitcoin_core_family() {
+            let filtered =
+                adapters::bitcoin_core::openrpc::extract_version_ir(ir.clone(), version.as_str());
+            if filtered.get_rpc_methods().is_empty() {
+                eprintln!(
+                    "warning: version filter for {} would remove all methods (e.g. IR has \
+                     version_added from unreleased/doc only). Using full IR. Fix canonical IR \
+                     version_added or load so version preservation works.",
+                    version.as_str()
+                );
+            } else {
+                ir = filtered;
+            }
+        }
+
+        // Run compiler passes (validation, canonicalization, etc.)
+        let compiler = EthosCompiler::new();
+        ir = compiler.run_compiler_passes(ir)?;
+
+        // Run semantic analysis on the IR
+        compiler_ctxs.push(analyze_implementation(
+            implementation,
+            ir,
+            version,
+            project_root.clone(),
+        )?);
+    }
 
     // Generate code
-    generate_into(&src_dir, &compiler_ctx)?;
+    match compiler_ctxs.as_slice() {
+        [compiler_ctx] => generate_into(&src_dir, compiler_ctx)?,
+        _ => generate_versions_into(&src_dir, &compiler_ctxs)?,
+    }
 
     println!("Compilation completed successfully.");
     Ok(())
@@ -263,5 +687,13 @@ fn compile_with_ir(
 fn get_latest_version_fallback(implementation: &Implementation) -> &'static str {
     match implementation {
         Implementation::BitcoinCore => "v30.2",
+        Implementation::Btcd => "v0.24.2",
+        Implementation::CoreLightning => "v25.09",
+        Implementation::Lnd => "v0.19.3",
+        Implementation::Electrs => "v0.10.10",
+        Implementation::Esplora => "v1.0",
+        Implementation::BitcoinKnots => "v29.1.knots20250903",
+        Implementation::Registered(_) =>
+            get_latest_version_fallback(&implementation.builtin_base()),
     }
 }
@@ -0,0 +1,642 @@
+//! Response Conformance
+//!
+//! Validates real responses against the `RpcDef::result` type tree of the IR. Every value
+//! compared with a type, every required field and every key in a response object counts as
+//! one check; missing required fields, unexpected fields and type mismatches are violations.
+//! The share of checks without a violation is the method's convergence score, the
+//! response-shape counterpart of the surface distance in [`crate::drift`].
+//!
+//! Responses come from cassettes (recorded request/response pairs), fixture directories
+//! (one response per file) or a live [`InvocationEngine`].
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::Path;
+
+use adapters::InvocationEngine;
+use ir::{FieldDef, FieldKey, ProtocolIR, RpcDef, TypeDef, TypeKind};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{PhaseError, Result};
+
+/// One response to check
+#[derive(Debug, Clone)]
+pub struct ResponseSample {
+    /// Method name
+    pub method: String,
+    /// Request parameters
+    pub params: Value,
+    /// The call's result, or the error it failed with
+    pub response: std::result::Result<Value, String>,
+}
+
+/// Recorded request/response pairs
+///
+/// ```json
+/// { "interactions": [ { "method": "getblockcount", "params": [], "result": 840000 } ] }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Cassette {
+    /// Recorded calls, in order
+    pub interactions: Vec<CassetteInteraction>,
+}
+
+/// One recorded call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CassetteInteraction {
+    /// Method name
+    pub method: String,
+    /// Request parameters
+    #[serde(default)]
+    pub params: Value,
+    /// Result returned by the node
+    #[serde(default)]
+    pub result: Value,
+    /// Error returned by the node, if the call failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<Value>,
+}
+
+impl Cassette {
+    /// Reads a cassette file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Responses recorded in the cassette
+    pub fn samples(&self) -> Vec<ResponseSample> {
+        self.interactions
+            .iter()
+            .map(|interaction| ResponseSample {
+                method: interaction.method.clone(),
+                params: interaction.params.clone(),
+                response: match &interaction.error {
+                    Some(error) if !error.is_null() => Err(error.to_string()),
+                    _ => Ok(interaction.result.clone()),
+                },
+            })
+            .collect()
+    }
+}
+
+/// Reads a fixture directory.
+///
+/// Each `<method>.json` (or `<method>.<label>.json`) file holds one response, either the bare
+/// result or a JSON-RPC envelope with a `result` or `error` member.
+pub fn load_fixtures(dir: &Path) -> Result<Vec<ResponseSample>> {
+    let mut paths: Vec<_> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+
+    let mut samples = Vec::with_capacity(paths.len());
+    for path in paths {
+        let Some(method) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.split('.').next())
+            .filter(|method| !method.is_empty())
+        else {
+            continue;
+        };
+        let content = std::fs::read_to_string(&path)?;
+        let value: Value = serde_json::from_str(&content)
+            .map_err(|e| PhaseError::Other(format!("invalid fixture {}: {}", path.display(), e)))?;
+        samples.push(ResponseSample {
+            method: method.to_string(),
+            params: Value::Null,
+            response: unwrap_envelope(value),
+        });
+    }
+    Ok(samples)
+}
+
+fn unwrap_envelope(value: Value) -> std::result::Result<Value, String> {
+    let is_envelope = value.as_object().is_some_and(|obj| {
+        (obj.contains_key("result") || obj.contains_key("error"))
+            && obj.keys().all(|k| matches!(k.as_str(), "result" | "error" | "id" | "jsonrpc"))
+    });
+    if !is_envelope {
+        return Ok(value);
+    }
+    match (value.get("error"), value.get("result")) {
+        (Some(error), _) if !error.is_null() => Err(error.to_string()),
+        (_, Some(result)) => Ok(result.clone()),
+        _ => Ok(Value::Null),
+    }
+}
+
+/// Calls each method on a live engine and collects the responses.
+pub async fn sample_live(
+    engine: &dyn InvocationEngine,
+    calls: &[(String, Value)],
+) -> Vec<ResponseSample> {
+    let mut samples = Vec::with_capacity(calls.len());
+    for (method, params) in calls {
+        let response = engine.invoke(method, params).await.map_err(|e| e.to_string());
+        samples.push(ResponseSample { method: method.clone(), params: params.clone(), response });
+    }
+    samples
+}
+
+/// What a finding reports
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FindingKind {
+    /// A required field is absent
+    MissingField {
+        /// Field name
+        field: String,
+    },
+    /// The response has a field the IR does not describe
+    UnexpectedField {
+        /// Field name
+        field: String,
+    },
+    /// The value has a different JSON type than the IR describes
+    TypeMismatch {
+        /// Type described by the IR
+        expected: String,
+        /// JSON type of the value
+        actual: String,
+    },
+    /// The value matched a shape the IR only describes under a condition (not a violation)
+    Conditional {
+        /// Condition from the IR (e.g. "for verbosity = 0")
+        condition: String,
+    },
+}
+
+/// One finding in a response
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Finding {
+    /// Index of the sample within the method's samples
+    pub sample: usize,
+    /// JSON pointer (RFC 6901) to the value within the response
+    pub path: String,
+    /// What was found
+    #[serde(flatten)]
+    pub kind: FindingKind,
+}
+
+impl Finding {
+    /// Whether the finding counts against the convergence score
+    pub fn is_violation(&self) -> bool { !matches!(self.kind, FindingKind::Conditional { .. }) }
+}
+
+/// Conformance of one method's responses
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MethodConformance {
+    /// Method name
+    pub method: String,
+    /// Responses checked
+    pub samples: usize,
+    /// Checks performed
+    pub checks: usize,
+    /// Checks that failed
+    pub violations: usize,
+    /// Findings, in sample order
+    pub findings: Vec<Finding>,
+}
+
+impl MethodConformance {
+    /// Share of checks without a violation (1.0 when nothing was checked)
+    pub fn score(&self) -> f64 {
+        if self.checks == 0 {
+            return 1.0;
+        }
+        (self.checks - self.violations) as f64 / self.checks as f64
+    }
+}
+
+/// A call whose response could not be checked
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FailedCall {
+    /// Method name
+    pub method: String,
+    /// Error returned for the call
+    pub error: String,
+}
+
+/// Conformance of a set of responses against the IR
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConformanceReport {
+    /// Per-method results, by method name
+    pub methods: Vec<MethodConformance>,
+    /// Methods with responses but no definition in the IR
+    pub unknown_methods: Vec<String>,
+    /// Calls that returned an error instead of a result
+    pub failed_calls: Vec<FailedCall>,
+}
+
+impl ConformanceReport {
+    /// Share of all checks without a violation (1.0 when nothing was checked)
+    pub fn convergence(&self) -> f64 {
+        let checks: usize = self.methods.iter().map(|m| m.checks).sum();
+        let violations: usize = self.methods.iter().map(|m| m.violations).sum();
+        if checks == 0 {
+            return 1.0;
+        }
+        (checks - violations) as f64 / checks as f64
+    }
+
+    /// Conformance of one method
+    pub fn method(&self, name: &str) -> Option<&MethodConformance> {
+        self.methods.iter().find(|m| m.method == name)
+    }
+}
+
+/// Checks responses against the result types of an IR
+pub struct ConformanceEngine<'a> {
+    methods: HashMap<&'a str, &'a RpcDef>,
+}
+
+impl<'a> ConformanceEngine<'a> {
+    /// Creates an engine for the RPC methods of `ir`.
+    pub fn new(ir: &'a ProtocolIR) -> Self {
+        let methods =
+            ir.get_rpc_methods().into_iter().map(|rpc| (rpc.name.as_str(), rpc)).collect();
+        Self { methods }
+    }
+
+    /// Checks one response of `method`; `None` if the IR does not define the method.
+    ///
+    /// Methods without a result type accept any response.
+    pub fn check(&self, method: &str, response: &Value) -> Option<MethodConformance> {
+        let rpc = self.methods.get(method)?;
+        let mut checker = Checker::default();
+        if let Some(result) = &rpc.result {
+            checker.check(response, result, "");
+        }
+        Some(MethodConformance {
+            method: method.to_string(),
+            samples: 1,
+            checks: checker.checks,
+            violations: checker.violations(),
+            findings: checker.findings,
+        })
+    }
+
+    /// Checks a set of responses and scores each method.
+    pub fn check_samples(&self, samples: &[ResponseSample]) -> ConformanceReport {
+        let mut methods: BTreeMap<String, MethodConformance> = BTreeMap::new();
+        let mut report = ConformanceReport::default();
+        for sample in samples {
+            let response = match &sample.response {
+                Ok(response) => response,
+                Err(error) => {
+                    report
+                        .failed_calls
+                        .push(FailedCall { method: sample.method.clone(), error: error.clone() });
+                    continue;
+                }
+            };
+            let Some(result) = self.check(&sample.method, response) else {
+                if !report.unknown_methods.contains(&sample.method) {
+                    report.unknown_methods.push(sample.method.clone());
+                }
+                continue;
+            };
+            let entry = methods.entry(sample.method.clone()).or_insert_with(|| MethodConformance {
+                method: sample.method.clone(),
+                ..MethodConformance::default()
+            });
+            let index = entry.samples;
+            entry.samples += 1;
+            entry.checks += result.checks;
+            entry.violations += result.violations;
+            entry
+                .findings
+                .extend(result.findings.into_iter().map(|f| Finding { sample: index, ..f }));
+        }
+        report.methods = methods.into_values().collect();
+        report.unknown_methods.sort();
+        report
+    }
+
+    /// Calls each method on a live engine and checks the responses.
+    pub async fn check_live(
+        &self,
+        engine: &dyn InvocationEngine,
+        calls: &[(String, Value)],
+    ) -> ConformanceReport {
+        self.check_samples(&sample_live(engine, calls).await)
+    }
+}
+
+#[derive(Default)]
+struct Checker {
+    checks: usize,
+    findings: Vec<Finding>,
+}
+
+impl Checker {
+    fn violations(&self) -> usize { self.findings.iter().filter(|f| f.is_violation()).count() }
+
+    fn report(&mut self, path: &str, kind: FindingKind) {
+        self.findings.push(Finding { sample: 0, path: path.to_string(), kind });
+    }
+
+    fn mismatch(&mut self, path: &str, expected: impl Into<String>, value: &Value) {
+        let kind = FindingKind::TypeMismatch {
+            expected: expected.into(),
+            actual: json_type(value).into(),
+        };
+        self.report(path, kind);
+    }
+
+    /// Whether `value` matches `ty` without recording anything
+    fn matches(value: &Value, ty: &TypeDef) -> bool {
+        let mut scratch = Checker::default();
+        scratch.check(value, ty, "");
+        scratch.violations() == 0
+    }
+
+    fn check(&mut self, value: &Value, ty: &TypeDef, path: &str) {
+        self.checks += 1;
+        match ty.kind {
+            TypeKind::Object if ty.protocol_type.as_deref() == Some("array") =>
+                self.check_array(value, wrapped_element_type(ty), path),
+            TypeKind::Object => self.check_object(value, ty, path),
+            TypeKind::Array => self.check_array(value, ty.array_element_type(), path),
+            TypeKind::Optional => {
+                let inner = ty.fields.as_ref().and_then(|f| f.first()).map(|f| &f.field_type);
+                if let (false, Some(inner)) = (value.is_null(), inner) {
+                    self.checks -= 1;
+                    self.check(value, inner, path);
+                }
+            }
+            TypeKind::Union => self.check_union(value, ty, path),
+            TypeKind::Enum => self.check_enum(value, ty, path),
+            TypeKind::Primitive | TypeKind::Alias | TypeKind::Custom =>
+                if let Some(expected) = primitive_mismatch(value, ty) {
+                    self.mismatch(path, expected, value);
+                },
+        }
+    }
+
+    fn check_array(&mut self, value: &Value, element: Option<&TypeDef>, path: &str) {
+        let Some(items) = value.as_array() else {
+            self.mismatch(path, "array", value);
+            return;
+        };
+        if let Some(element) = element {
+            for (index, item) in items.iter().enumerate() {
+                self.check(item, element, &format!("{}/{}", path, index));
+            }
+        }
+    }
+
+    fn check_object(&mut self, value: &Value, ty: &TypeDef, path: &str) {
+        let fields = ty.fields.as_deref().unwrap_or_default();
+        let (conditional, regular): (Vec<&FieldDef>, Vec<&FieldDef>) =
+            fields.iter().partition(|f| f.field_type.condition.is_some());
+
+        let Some(object) = value.as_object() else {
+            // A non-object response may be one of the conditional alternatives
+            // (e.g. the hex string `getrawtransaction` returns for verbosity 0).
+            let alternative = conditional
+                .iter()
+                .chain(regular.iter().filter(|f| is_synthetic(&f.key)))
+                .find(|f| Self::matches(value, &f.field_type));
+            match alternative {
+                Some(field) =>
+                    if let Some(condition) = &field.field_type.condition {
+                        self.report(
+                            path,
+                            FindingKind::Conditional { condition: condition.clone() },
+                        );
+                    },
+                None => self.mismatch(path, "object", value),
+            }
+            return;
+        };
+
+        if is_free_form(fields) {
+            return;
+        }
+        if let Some(entry) = dynamic_entry(fields, object) {
+            for (name, item) in object {
+                self.check(item, &entry.field_type, &child_path(path, name));
+            }
+            return;
+        }
+
+        let named: Vec<&FieldDef> =
+            regular.iter().copied().filter(|f| !is_synthetic(&f.key)).collect();
+        for field in &named {
+            let key = json_key(field);
+            let child = child_path(path, key);
+            match object.get(key) {
+                Some(Value::Null) if !field.required => {}
+                Some(item) => self.check(item, &field.field_type, &child),
+                None if field.required => {
+                    self.checks += 1;
+                    self.report(&child, FindingKind::MissingField { field: key.to_string() });
+                }
+                None => {}
+            }
+        }
+        for field in conditional.iter().filter(|f| !is_synthetic(&f.key)) {
+            let key = json_key(field);
+            if let Some(item) = object.get(key) {
+                let child = child_path(path, key);
+                if let Some(condition) = &field.field_type.condition {
+                    self.report(&child, FindingKind::Conditional { condition: condition.clone() });
+                }
+                self.check(item, &field.field_type, &child);
+            }
+        }
+        if is_open(fields) {
+            return;
+        }
+        for name in object.keys() {
+            if !fields.iter().any(|f| !is_synthetic(&f.key) && json_key(f) == name) {
+                self.checks += 1;
+                self.report(
+                    &child_path(path, name),
+                    FindingKind::UnexpectedField { field: name.clone() },
+                );
+            }
+        }
+    }
+
+    fn check_union(&mut self, value: &Value, ty: &TypeDef, path: &str) {
+        let variants = ty.union_variants.as_deref().unwrap_or_default();
+        if variants.is_empty() {
+            return;
+        }
+        match variants.iter().find(|v| Self::matches(value, &v.type_def)) {
+            Some(variant) => {
+                self.checks -= 1;
+                self.check(value, &variant.type_def, path);
+                if let Some(condition) = &variant.condition {
+                    self.report(path, FindingKind::Conditional { condition: condition.clone() });
+                }
+            }
+            None => {
+                let expected = variants.iter().map(|v| v.name.as_str()).collect::<Vec<_>>();
+                self.mismatch(path, format!("one of {}", expected.join(" | ")), value);
+            }
+        }
+    }
+
+    fn check_enum(&mut self, value: &Value, ty: &TypeDef, path: &str) {
+        let Some(text) = value.as_str() else {
+            self.mismatch(path, "string", value);
+            return;
+        };
+        let variants = ty.variants.as_deref().unwrap_or_default();
+        if !variants.is_empty()
+            && !variants.iter().any(|v| v.value.as_deref().unwrap_or(&v.name) == text)
+        {
+            self.mismatch(path, format!("enum {}", ty.name), value);
+        }
+    }
+}
+
+/// Whether an object type names no fields of its own (a free-form object)
+pub(crate) fn is_free_form(fields: &[FieldDef]) -> bool {
+    fields.iter().all(|f| is_synthetic(&f.key))
+}
+
+/// Whether an object type ends in an elision ("...") and so admits further fields
+pub(crate) fn is_open(fields: &[FieldDef]) -> bool {
+    fields.iter().any(|f| f.field_type.protocol_type.as_deref() == Some("elision"))
+}
+
+/// The entry type if `object` is a map keyed by dynamic names (e.g. addresses or txids):
+/// the help text renders such maps as a single object-typed field whose name is a
+/// placeholder, so the field is absent from a non-empty response.
+pub(crate) fn dynamic_entry<'t>(
+    fields: &'t [FieldDef],
+    object: &serde_json::Map<String, Value>,
+) -> Option<&'t FieldDef> {
+    let mut named =
+        fields.iter().filter(|f| f.field_type.condition.is_none() && !is_synthetic(&f.key));
+    let entry = named.next()?;
+    (named.next().is_none()
+        && matches!(entry.field_type.kind, TypeKind::Object)
+        && !object.is_empty()
+        && !object.contains_key(json_key(entry)))
+    .then_some(entry)
+}
+
+/// Element type of the array-of-objects wrapper the OpenRPC converter emits:
+/// an `Object` with protocol type "array" holding a single `field` whose object type
+/// may wrap the element once more as `field_0`.
+pub(crate) fn wrapped_element_type(ty: &TypeDef) -> Option<&TypeDef> {
+    let [field] = ty.fields.as_deref()? else { return None };
+    let inner = &field.field_type;
+    match inner.fields.as_deref() {
+        Some([element])
+            if is_synthetic(&element.key)
+                && matches!(element.field_type.kind, TypeKind::Object) =>
+            Some(&element.field_type),
+        _ => Some(inner),
+    }
+}
+
+/// Expected type name if `value` does not match the primitive `ty`
+fn primitive_mismatch(value: &Value, ty: &TypeDef) -> Option<&'static str> {
+    let protocol_type = ty.protocol_type.as_deref().unwrap_or(ty.name.as_str());
+    let (expected, ok) = match protocol_type {
+        "number" | "amount" | "f64" | "f32" => ("number", value.is_number()),
+        "timestamp" | "satoshis" | "i64" | "i32" | "u64" | "u32" | "u16" | "u8" | "integer" =>
+            ("integer", value.is_i64() || value.is_u64()),
+        "hex" =>
+            ("hex string", value.as_str().is_some_and(|s| s.chars().all(|c| c.is_ascii_hexdigit()))),
+        "string" | "str" | "String" => ("string", value.is_string()),
+        "boolean" | "bool" => ("boolean", value.is_boolean()),
+        "none" | "null" | "()" => ("null", value.is_null()),
+        _ => return None,
+    };
+    (!ok).then_some(expected)
+}
+
+/// Keys the converters synthesize for unnamed results ("field_0", "field_0_1", ...)
+pub(crate) fn is_synthetic(key: &FieldKey) -> bool {
+    match key {
+        FieldKey::Anonymous(_) => true,
+        FieldKey::Named(name) => name.strip_prefix("field_").is_some_and(|rest| {
+            rest.split('_').all(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+        }),
+    }
+}
+
+/// JSON key of a named field (empty for anonymous fields, which callers skip)
+pub(crate) fn json_key(field: &FieldDef) -> &str { field.key.json_key().unwrap_or_default() }
+
+pub(crate) fn child_path(path: &str, key: &str) -> String {
+    format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"))
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+impl fmt::Display for FindingKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FindingKind::MissingField { field } => write!(f, "missing required field `{}`", field),
+            FindingKind::UnexpectedField { field } => write!(f, "unexpected field `{}`", field),
+            FindingKind::TypeMismatch { expected, actual } =>
+                write!(f, "expected {}, found {}", expected, actual),
+            FindingKind::Conditional { condition } => write!(f, "conditional: {}", condition),
+        }
+    }
+}
+
+impl fmt::Display for ConformanceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Response conformance")?;
+        writeln!(
+            f,
+            "  {} methods checked, convergence {:.1}%",
+            self.methods.len(),
+            self.convergence() * 100.0
+        )?;
+        if !self.methods.is_empty() {
+            writeln!(f, "\nMethods:")?;
+            for method in &self.methods {
+                writeln!(
+                    f,
+                    "  {:<32} {:>6.1}%  ({} samples, {}/{} checks failed)",
+                    method.method,
+                    method.score() * 100.0,
+                    method.samples,
+                    method.violations,
+                    method.checks
+                )?;
+                for finding in &method.findings {
+                    let path = if finding.path.is_empty() { "/" } else { &finding.path };
+                    writeln!(f, "    [{}] {}: {}", finding.sample, path, finding.kind)?;
+                }
+            }
+        }
+        if !self.unknown_methods.is_empty() {
+            writeln!(f, "\nMethods missing from the IR ({}):", self.unknown_methods.len())?;
+            for name in &self.unknown_methods {
+                writeln!(f, "  + {}", name)?;
+            }
+        }
+        if !self.failed_calls.is_empty() {
+            writeln!(f, "\nFailed calls ({}):", self.failed_calls.len())?;
+            for call in &self.failed_calls {
+                writeln!(f, "  {}: {}", call.method, call.error)?;
+            }
+        }
+        Ok(())
+    }
+}
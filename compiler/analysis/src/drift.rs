@@ -0,0 +1,230 @@
+//! Surface Drift Analysis
+//!
+//! Compares the RPC surface a running node advertises (its method set and argument lists,
+//! as read from `help`) with the IR for the node's version. This measures the interface
+//! part of |B − Δ| from `docs/semantic-convergence.md`: every method or argument present on
+//! one side only, or disagreeing on position or optionality, counts as one unit of drift.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use ir::ProtocolIR;
+use serde::{Deserialize, Serialize};
+
+/// RPC surface observed on a node
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NodeSurface {
+    /// Node version (e.g. "30.2.0")
+    pub version: String,
+    /// Advertised methods
+    pub methods: Vec<NodeMethod>,
+}
+
+/// One method advertised by a node
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NodeMethod {
+    /// Method name
+    pub name: String,
+    /// Positional arguments, in order
+    pub arguments: Vec<NodeArgument>,
+    /// Whether the node serves the method without listing it in `help`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hidden: bool,
+}
+
+/// One argument of a node method
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeArgument {
+    /// Argument name
+    pub name: String,
+    /// Whether the argument is required
+    pub required: bool,
+}
+
+/// Disagreement on one argument of a method present on both sides
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ArgumentDrift {
+    /// The node accepts an argument the IR does not describe
+    MissingFromIr {
+        /// Argument name
+        argument: String,
+    },
+    /// The IR describes an argument the node does not accept
+    AbsentFromNode {
+        /// Argument name
+        argument: String,
+    },
+    /// The argument is at a different position
+    Position {
+        /// Argument name
+        argument: String,
+        /// Zero-based position in the IR
+        ir: usize,
+        /// Zero-based position on the node
+        node: usize,
+    },
+    /// The argument's optionality differs
+    Required {
+        /// Argument name
+        argument: String,
+        /// Whether the IR marks it required
+        ir: bool,
+        /// Whether the node marks it required
+        node: bool,
+    },
+}
+
+/// Argument drift of one method
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArgumentMismatch {
+    /// Method name
+    pub method: String,
+    /// Disagreements, in node argument order followed by IR-only arguments
+    pub drift: Vec<ArgumentDrift>,
+}
+
+/// Differences between a node's advertised surface and the IR
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DriftReport {
+    /// Node version the IR was selected for
+    pub node_version: String,
+    /// Methods compared on both sides
+    pub compared_methods: usize,
+    /// Methods the node serves that the IR does not describe
+    pub missing_from_ir: Vec<String>,
+    /// Methods the IR describes that the node does not serve
+    pub absent_from_node: Vec<String>,
+    /// Methods present on both sides whose arguments disagree
+    pub argument_mismatches: Vec<ArgumentMismatch>,
+}
+
+impl DriftReport {
+    /// Compares a node surface with the IR for the node's version.
+    pub fn compare(surface: &NodeSurface, ir: &ProtocolIR) -> Self {
+        let ir_methods: BTreeMap<&str, &ir::RpcDef> =
+            ir.get_rpc_methods().into_iter().map(|rpc| (rpc.name.as_str(), rpc)).collect();
+        let node_methods: BTreeMap<&str, &NodeMethod> =
+            surface.methods.iter().map(|m| (m.name.as_str(), m)).collect();
+
+        let mut report = DriftReport { node_version: surface.version.clone(), ..Self::default() };
+        for (name, method) in &node_methods {
+            let Some(rpc) = ir_methods.get(name) else {
+                report.missing_from_ir.push(name.to_string());
+                continue;
+            };
+            report.compared_methods += 1;
+            let drift = compare_arguments(&method.arguments, &rpc.params);
+            if !drift.is_empty() {
+                report
+                    .argument_mismatches
+                    .push(ArgumentMismatch { method: name.to_string(), drift });
+            }
+        }
+        report.absent_from_node = ir_methods
+            .keys()
+            .filter(|name| !node_methods.contains_key(*name))
+            .map(|name| name.to_string())
+            .collect();
+        report
+    }
+
+    /// Total units of drift (the measured |B − Δ|)
+    pub fn distance(&self) -> usize {
+        self.missing_from_ir.len()
+            + self.absent_from_node.len()
+            + self.argument_mismatches.iter().map(|m| m.drift.len()).sum::<usize>()
+    }
+
+    /// Whether the node and the IR agree
+    pub fn is_empty(&self) -> bool { self.distance() == 0 }
+}
+
+fn compare_arguments(node: &[NodeArgument], ir: &[ir::ParamDef]) -> Vec<ArgumentDrift> {
+    let mut drift = Vec::new();
+    for (position, argument) in node.iter().enumerate() {
+        let Some(ir_position) = ir.iter().position(|p| p.name == argument.name) else {
+            drift.push(ArgumentDrift::MissingFromIr { argument: argument.name.clone() });
+            continue;
+        };
+        if ir_position != position {
+            drift.push(ArgumentDrift::Position {
+                argument: argument.name.clone(),
+                ir: ir_position,
+                node: position,
+            });
+        }
+        let ir_required = ir[ir_position].required;
+        if ir_required != argument.required {
+            drift.push(ArgumentDrift::Required {
+                argument: argument.name.clone(),
+                ir: ir_required,
+                node: argument.required,
+            });
+        }
+    }
+    for param in ir {
+        if !node.iter().any(|a| a.name == param.name) {
+            drift.push(ArgumentDrift::AbsentFromNode { argument: param.name.clone() });
+        }
+    }
+    drift
+}
+
+impl fmt::Display for ArgumentDrift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let required = |r: bool| if r { "required" } else { "optional" };
+        match self {
+            ArgumentDrift::MissingFromIr { argument } =>
+                write!(f, "`{}` accepted by the node, missing from the IR", argument),
+            ArgumentDrift::AbsentFromNode { argument } =>
+                write!(f, "`{}` in the IR, not accepted by the node", argument),
+            ArgumentDrift::Position { argument, ir, node } => write!(
+                f,
+                "`{}` is argument {} on the node, {} in the IR",
+                argument,
+                node + 1,
+                ir + 1
+            ),
+            ArgumentDrift::Required { argument, ir, node } => write!(
+                f,
+                "`{}` is {} on the node, {} in the IR",
+                argument,
+                required(*node),
+                required(*ir)
+            ),
+        }
+    }
+}
+
+impl fmt::Display for DriftReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Surface drift for node version {}", self.node_version)?;
+        writeln!(f, "  {} methods compared, |B − Δ| = {}", self.compared_methods, self.distance())?;
+        if !self.missing_from_ir.is_empty() {
+            writeln!(f, "\nMethods missing from the IR ({}):", self.missing_from_ir.len())?;
+            for name in &self.missing_from_ir {
+                writeln!(f, "  + {}", name)?;
+            }
+        }
+        if !self.absent_from_node.is_empty() {
+            writeln!(f, "\nMethods absent from the node ({}):", self.absent_from_node.len())?;
+            for name in &self.absent_from_node {
+                writeln!(f, "  - {}", name)?;
+            }
+        }
+        if !self.argument_mismatches.is_empty() {
+            writeln!(f, "\nArgument mismatches ({} methods):", self.argument_mismatches.len())?;
+            for mismatch in &self.argument_mismatches {
+                writeln!(f, "  {}", mismatch.method)?;
+                for drift in &mismatch.drift {
+                    writeln!(f, "    {}", drift)?;
+                }
+            }
+        }
+        if self.is_empty() {
+            writeln!(f, "\nNo drift: the node matches the IR.")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,399 @@
+//! Schema Inference
+//!
+//! Proposes IR additions from observed responses. Responses are aggregated per method and
+//! walked alongside the method's `RpcDef::result` type with the same rules the
+//! [conformance](crate::conformance) check uses (array-of-object wrappers, maps keyed by
+//! dynamic names, open and free-form objects). Every response key the IR does not describe
+//! becomes a new [`FieldDef`] whose type, optionality and array element shape are inferred
+//! from all observed values, and whose `version_added` is the earliest version it was
+//! observed on.
+//!
+//! The result is a [`SchemaPatch`]: a reviewable list of additions plus a patch IR holding
+//! the amended methods, which replace their base definitions as in [`ProtocolIR::merge`].
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use ir::{FieldDef, FieldKey, ProtocolDef, ProtocolIR, ProtocolModule, RpcDef, TypeDef, TypeKind};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::conformance::{
+    child_path, dynamic_entry, is_free_form, is_open, is_synthetic, json_key, ResponseSample,
+};
+
+/// One observed value and the version it was observed on
+type Observation<'v> = (&'v str, &'v Value);
+
+/// Aggregates observed responses and infers IR additions from them
+pub struct SchemaInferrer<'a> {
+    ir: &'a ProtocolIR,
+    observed: BTreeMap<String, Vec<(String, Value)>>,
+}
+
+impl<'a> SchemaInferrer<'a> {
+    /// Creates an inferrer proposing additions to `ir`.
+    pub fn new(ir: &'a ProtocolIR) -> Self { Self { ir, observed: BTreeMap::new() } }
+
+    /// Records the successful responses in `samples`, observed on `version`.
+    pub fn observe(&mut self, version: &str, samples: &[ResponseSample]) {
+        for sample in samples {
+            if let Ok(response) = &sample.response {
+                self.observed
+                    .entry(sample.method.clone())
+                    .or_default()
+                    .push((version.to_string(), response.clone()));
+            }
+        }
+    }
+
+    /// Infers the additions supported by the recorded responses.
+    pub fn infer(&self) -> SchemaPatch {
+        let mut patch = SchemaPatch::default();
+        let mut amended: BTreeMap<String, Vec<ProtocolDef>> = BTreeMap::new();
+
+        for (method, responses) in &self.observed {
+            let Some((module, rpc)) = find_rpc(self.ir, method) else {
+                patch.unknown_methods.push(method.clone());
+                continue;
+            };
+            let Some(result) = &rpc.result else { continue };
+
+            let mut result = result.clone();
+            let values: Vec<Observation> = responses.iter().map(|(v, r)| (v.as_str(), r)).collect();
+            let mut additions = Vec::new();
+            extend(&mut result, &values, "", &mut additions);
+            if additions.is_empty() {
+                continue;
+            }
+            patch.additions.extend(additions.into_iter().map(|(path, samples, field)| {
+                FieldAddition { method: method.clone(), path, samples, field }
+            }));
+            amended
+                .entry(module.name().to_string())
+                .or_default()
+                .push(ProtocolDef::RpcMethod(RpcDef { result: Some(result), ..rpc.clone() }));
+        }
+
+        let modules = amended
+            .into_iter()
+            .map(|(name, definitions)| {
+                let description = self
+                    .ir
+                    .modules()
+                    .iter()
+                    .find(|m| m.name() == name)
+                    .map(|m| m.description().to_string())
+                    .unwrap_or_default();
+                ProtocolModule::new(name, description, definitions)
+            })
+            .collect();
+        patch.ir = ProtocolIR::new_with_version(self.ir.version().to_string(), modules);
+        patch
+    }
+}
+
+/// A field the observed responses carry but the IR does not describe
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldAddition {
+    /// Method name
+    pub method: String,
+    /// JSON pointer to the new field; `*` stands for any array index or dynamic key
+    pub path: String,
+    /// Responses the field was observed in
+    pub samples: usize,
+    /// Proposed field definition
+    pub field: FieldDef,
+}
+
+/// IR additions inferred from observed responses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaPatch {
+    /// Proposed fields, for review
+    pub additions: Vec<FieldAddition>,
+    /// Methods with responses but no definition in the IR
+    pub unknown_methods: Vec<String>,
+    /// Amended definitions of the methods that gained fields
+    pub ir: ProtocolIR,
+}
+
+impl Default for SchemaPatch {
+    fn default() -> Self {
+        Self { additions: Vec::new(), unknown_methods: Vec::new(), ir: ProtocolIR::new(Vec::new()) }
+    }
+}
+
+impl SchemaPatch {
+    /// Whether the patch proposes nothing
+    pub fn is_empty(&self) -> bool { self.additions.is_empty() }
+
+    /// Returns `base` with the amended methods replacing their originals.
+    ///
+    /// Follows the precedence of [`ProtocolIR::merge`] with the patch first (a patch
+    /// definition wins over the base definition of the same name), but replaces in place:
+    /// `merge` re-sorts modules and definitions, which would turn a one-field patch into a
+    /// whole-file diff of the IR.
+    pub fn apply(&self, base: &ProtocolIR) -> ProtocolIR {
+        let amended: BTreeMap<&str, &RpcDef> =
+            self.ir.get_rpc_methods().into_iter().map(|rpc| (rpc.name.as_str(), rpc)).collect();
+        let mut updated = base.clone();
+        for module in updated.modules_mut() {
+            for def in module.definitions_mut() {
+                if let ProtocolDef::RpcMethod(rpc) = def {
+                    if let Some(patched) = amended.get(rpc.name.as_str()) {
+                        *rpc = (*patched).clone();
+                    }
+                }
+            }
+        }
+        updated
+    }
+}
+
+fn find_rpc<'i>(ir: &'i ProtocolIR, method: &str) -> Option<(&'i ProtocolModule, &'i RpcDef)> {
+    ir.modules().iter().find_map(|module| {
+        module.get_rpc_methods().into_iter().find(|rpc| rpc.name == method).map(|rpc| (module, rpc))
+    })
+}
+
+/// Adds the fields `values` carry beyond `ty`, recording `(path, samples, field)` for each.
+fn extend(
+    ty: &mut TypeDef,
+    values: &[Observation],
+    path: &str,
+    additions: &mut Vec<(String, usize, FieldDef)>,
+) {
+    match ty.kind {
+        TypeKind::Object if ty.protocol_type.as_deref() == Some("array") => {
+            if let Some(element) = wrapped_element_type_mut(ty) {
+                extend(element, &elements(values), &format!("{}/*", path), additions);
+            }
+        }
+        TypeKind::Object => extend_object(ty, values, path, additions),
+        TypeKind::Array if ty.array_element_type().is_some() => {
+            if let Some(element) = ty.fields.as_mut().and_then(|f| f.first_mut()) {
+                extend(
+                    &mut element.field_type,
+                    &elements(values),
+                    &format!("{}/*", path),
+                    additions,
+                );
+            }
+        }
+        _ => {}
+    }
+}
+
+fn extend_object(
+    ty: &mut TypeDef,
+    values: &[Observation],
+    path: &str,
+    additions: &mut Vec<(String, usize, FieldDef)>,
+) {
+    let objects: Vec<(&str, &serde_json::Map<String, Value>)> =
+        values.iter().filter_map(|(version, value)| Some((*version, value.as_object()?))).collect();
+    let Some(fields) = ty.fields.as_mut() else { return };
+    if objects.is_empty() || is_free_form(fields) || is_open(fields) {
+        return;
+    }
+
+    if let Some(entry) = objects.iter().find_map(|(_, object)| dynamic_entry(fields, object)) {
+        let key = json_key(entry).to_string();
+        let entries: Vec<Observation> = objects
+            .iter()
+            .flat_map(|(version, object)| object.values().map(move |v| (*version, v)))
+            .collect();
+        if let Some(entry) = fields.iter_mut().find(|f| f.key.json_key() == Some(key.as_str())) {
+            extend(&mut entry.field_type, &entries, &format!("{}/*", path), additions);
+        }
+        return;
+    }
+
+    for field in fields.iter_mut().filter(|f| !is_synthetic(&f.key)) {
+        let key = json_key(field).to_string();
+        let children: Vec<Observation> = objects
+            .iter()
+            .filter_map(|(version, object)| Some((*version, object.get(&key)?)))
+            .filter(|(_, value)| !value.is_null())
+            .collect();
+        extend(&mut field.field_type, &children, &child_path(path, &key), additions);
+    }
+
+    let known: BTreeSet<String> =
+        fields.iter().filter(|f| !is_synthetic(&f.key)).map(|f| json_key(f).to_string()).collect();
+    let new_keys: BTreeSet<&String> = objects
+        .iter()
+        .flat_map(|(_, object)| object.keys())
+        .filter(|k| !known.contains(*k))
+        .collect();
+    for key in new_keys {
+        let present: Vec<Observation> = objects
+            .iter()
+            .filter_map(|(version, object)| Some((*version, object.get(key)?)))
+            .collect();
+        let non_null: Vec<Observation> =
+            present.iter().copied().filter(|(_, value)| !value.is_null()).collect();
+        let field = FieldDef {
+            key: FieldKey::Named(key.clone()),
+            field_type: infer_type(&non_null),
+            required: non_null.len() == objects.len(),
+            description: String::new(),
+            default_value: None,
+            version_added: earliest_version(&present),
+            version_removed: None,
+            deprecated: None,
+        };
+        additions.push((child_path(path, key), present.len(), field.clone()));
+        fields.push(field);
+    }
+}
+
+/// Mutable counterpart of [`crate::conformance::wrapped_element_type`]
+fn wrapped_element_type_mut(ty: &mut TypeDef) -> Option<&mut TypeDef> {
+    let [field] = ty.fields.as_deref_mut()? else { return None };
+    let inner = &mut field.field_type;
+    let unwrap = matches!(
+        inner.fields.as_deref(),
+        Some([element]) if is_synthetic(&element.key) && matches!(element.field_type.kind, TypeKind::Object)
+    );
+    if unwrap {
+        return inner.fields.as_mut().and_then(|f| f.first_mut()).map(|f| &mut f.field_type);
+    }
+    Some(inner)
+}
+
+fn elements<'v>(values: &[Observation<'v>]) -> Vec<Observation<'v>> {
+    values
+        .iter()
+        .filter_map(|(version, value)| Some(value.as_array()?.iter().map(move |v| (*version, v))))
+        .flatten()
+        .collect()
+}
+
+/// Infers a type from non-null observed values.
+fn infer_type(values: &[Observation]) -> TypeDef {
+    let primitive = |protocol_type: &str| TypeDef {
+        name: protocol_type.to_string(),
+        kind: TypeKind::Primitive,
+        protocol_type: Some(protocol_type.to_string()),
+        ..TypeDef::default()
+    };
+    if values.is_empty() {
+        return primitive("none");
+    }
+    let all = |predicate: fn(&Value) -> bool| values.iter().all(|(_, v)| predicate(v));
+
+    if all(Value::is_boolean) {
+        primitive("boolean")
+    } else if all(Value::is_number) {
+        primitive("number")
+    } else if all(Value::is_string) {
+        let hex = values.iter().all(|(_, v)| {
+            v.as_str().is_some_and(|s| {
+                !s.is_empty() && s.len() % 2 == 0 && s.chars().all(|c| c.is_ascii_hexdigit())
+            })
+        });
+        primitive(if hex { "hex" } else { "string" })
+    } else if all(Value::is_array) {
+        let items = elements(values);
+        let element = (!items.is_empty()).then(|| FieldDef {
+            key: FieldKey::Named("field_0".to_string()),
+            field_type: infer_type(&items),
+            required: true,
+            description: String::new(),
+            default_value: None,
+            version_added: None,
+            version_removed: None,
+            deprecated: None,
+        });
+        TypeDef {
+            name: "array".to_string(),
+            kind: TypeKind::Array,
+            fields: element.map(|e| vec![e]),
+            protocol_type: Some("array".to_string()),
+            ..TypeDef::default()
+        }
+    } else if all(Value::is_object) {
+        let mut keys: BTreeMap<&String, Vec<Observation>> = BTreeMap::new();
+        for (version, value) in values {
+            for (key, child) in value.as_object().into_iter().flatten() {
+                keys.entry(key).or_default().push((*version, child));
+            }
+        }
+        let fields = keys
+            .into_iter()
+            .map(|(key, present)| {
+                let non_null: Vec<Observation> =
+                    present.iter().copied().filter(|(_, v)| !v.is_null()).collect();
+                FieldDef {
+                    key: FieldKey::Named(key.clone()),
+                    field_type: infer_type(&non_null),
+                    required: non_null.len() == values.len(),
+                    description: String::new(),
+                    default_value: None,
+                    version_added: None,
+                    version_removed: None,
+                    deprecated: None,
+                }
+            })
+            .collect();
+        TypeDef {
+            name: "object".to_string(),
+            kind: TypeKind::Object,
+            fields: Some(fields),
+            protocol_type: Some("object".to_string()),
+            ..TypeDef::default()
+        }
+    } else {
+        primitive("any")
+    }
+}
+
+/// Earliest version in `values` (numeric components, `v` prefix ignored)
+fn earliest_version(values: &[Observation]) -> Option<String> {
+    let key = |version: &str| -> Vec<u64> {
+        version
+            .trim_start_matches('v')
+            .split(['.', '-'])
+            .map_while(|part| part.parse().ok())
+            .collect()
+    };
+    values.iter().map(|(version, _)| *version).min_by_key(|v| key(v)).map(str::to_string)
+}
+
+impl fmt::Display for SchemaPatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Inferred schema additions")?;
+        writeln!(f, "  {} fields proposed", self.additions.len())?;
+        let mut by_method: BTreeMap<&str, Vec<&FieldAddition>> = BTreeMap::new();
+        for addition in &self.additions {
+            by_method.entry(addition.method.as_str()).or_default().push(addition);
+        }
+        for (method, additions) in by_method {
+            writeln!(f, "\n{}", method)?;
+            for addition in additions {
+                let field = &addition.field;
+                writeln!(
+                    f,
+                    "  + {} : {}{} (seen in {} responses{})",
+                    addition.path,
+                    field.field_type.protocol_type.as_deref().unwrap_or(&field.field_type.name),
+                    if field.required { "" } else { ", optional" },
+                    addition.samples,
+                    field
+                        .version_added
+                        .as_deref()
+                        .map(|v| format!(", since {}", v))
+                        .unwrap_or_default()
+                )?;
+            }
+        }
+        if !self.unknown_methods.is_empty() {
+            writeln!(f, "\nMethods missing from the IR ({}):", self.unknown_methods.len())?;
+            for name in &self.unknown_methods {
+                writeln!(f, "  + {}", name)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -16,14 +16,22 @@ use types::{Implementation, ProtocolVersion};
 
 // Import all analysis components
 pub mod canonicalizer;
+pub mod conformance;
 pub mod differential;
+pub mod drift;
+pub mod inference;
+pub mod matrix;
 pub mod normalizer;
 pub mod semantic;
 pub mod validator;
 
 // Re-export analysis types
 pub use canonicalizer::TypeCanonicalizer;
+pub use conformance::{ConformanceEngine, ConformanceReport};
 pub use differential::DifferentialAnalyzer;
+pub use drift::DriftReport;
+pub use inference::{SchemaInferrer, SchemaPatch};
+pub use matrix::CompatibilityMatrix;
 pub use normalizer::IRNormalizer;
 pub use semantic::SemanticAnalyzer;
 pub use validator::IrValidator;
@@ -0,0 +1,357 @@
+//! Version Compatibility Matrix
+//!
+//! Tabulates, from the canonical IR, which RPC methods exist on each of a list of versions and
+//! how their params and results change from one listed version to the next, down to single
+//! param and result fields. Each version is sliced with
+//! [`extract_version_ir`](adapters::bitcoin_core::openrpc::extract_version_ir), the slicing
+//! code generation uses, so a cell answers whether a crate generated for that version has the
+//! method or field.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+
+use adapters::bitcoin_core::openrpc::extract_version_ir;
+use ir::{FieldKey, ProtocolIR, RpcDef, TypeDef};
+use serde::{Deserialize, Serialize};
+use types::ProtocolVersion;
+
+/// Availability of one method on one version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CellStatus {
+    /// Not yet added on this version
+    Unavailable,
+    /// First listed version with the method
+    Added,
+    /// Present and unchanged since the previous listed version
+    Available,
+    /// Present with params added or removed since the previous listed version
+    ChangedParams,
+    /// Present with result fields added or removed since the previous listed version
+    ChangedResult,
+    /// Present with both params and result fields changed
+    ChangedParamsAndResult,
+    /// Gone since an earlier listed version
+    Removed,
+}
+
+impl CellStatus {
+    /// Key used in JSON output and as the HTML cell class
+    pub fn key(self) -> &'static str {
+        match self {
+            CellStatus::Unavailable => "unavailable",
+            CellStatus::Added => "added",
+            CellStatus::Available => "available",
+            CellStatus::ChangedParams => "changed_params",
+            CellStatus::ChangedResult => "changed_result",
+            CellStatus::ChangedParamsAndResult => "changed_params_and_result",
+            CellStatus::Removed => "removed",
+        }
+    }
+
+    /// Label used in rendered tables
+    pub fn label(self) -> &'static str {
+        match self {
+            CellStatus::Unavailable => "—",
+            CellStatus::Added => "added",
+            CellStatus::Available => "available",
+            CellStatus::ChangedParams => "changed params",
+            CellStatus::ChangedResult => "changed result",
+            CellStatus::ChangedParamsAndResult => "changed params, result",
+            CellStatus::Removed => "removed",
+        }
+    }
+}
+
+/// Which side of a method a field change is on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeLocation {
+    /// A param or a field nested in one
+    Params,
+    /// A field of the result
+    Result,
+}
+
+/// Whether a field appeared or disappeared
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    /// Present on this version, not on the previous listed one
+    Added,
+    /// Present on the previous listed version, not on this one
+    Removed,
+}
+
+/// One param or result field added or removed between two listed versions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldChange {
+    /// Params or result
+    pub location: ChangeLocation,
+    /// Dotted path of the field (`[]` marks array elements, e.g. `vout[].scriptPubKey.address`)
+    pub path: String,
+    /// Added or removed
+    pub kind: ChangeKind,
+}
+
+/// One method on one version
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatrixCell {
+    /// Availability on the version
+    pub status: CellStatus,
+    /// Field-level changes since the previous listed version
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<FieldChange>,
+}
+
+/// One method across all listed versions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MethodRow {
+    /// Method name
+    pub method: String,
+    /// One cell per listed version, in the order of [`CompatibilityMatrix::versions`]
+    pub cells: Vec<MatrixCell>,
+}
+
+/// Method × version table
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompatibilityMatrix {
+    /// Listed versions, oldest first
+    pub versions: Vec<String>,
+    /// Methods present on at least one listed version, sorted by name
+    pub methods: Vec<MethodRow>,
+}
+
+impl CompatibilityMatrix {
+    /// Builds the matrix of the canonical `ir` for `versions` (sorted oldest first).
+    pub fn build(ir: &ProtocolIR, versions: &[String]) -> Self {
+        let mut versions = versions.to_vec();
+        versions.sort_by_key(|v| parse_version(v));
+
+        // method -> one field set per version (None where the method is absent)
+        let mut surfaces: BTreeMap<String, Vec<Option<MethodFields>>> = BTreeMap::new();
+        for (index, version) in versions.iter().enumerate() {
+            for rpc in extract_version_ir(ir.clone(), version).get_rpc_methods() {
+                surfaces.entry(rpc.name.clone()).or_insert_with(|| vec![None; versions.len()])
+                    [index] = Some(method_fields(rpc));
+            }
+        }
+
+        let methods = surfaces
+            .into_iter()
+            .map(|(method, fields)| MethodRow { method, cells: cells(&fields) })
+            .collect();
+        Self { versions, methods }
+    }
+
+    /// Every version the IR mentions in a `version_added` or `version_removed` of a method,
+    /// param or result field, oldest first.
+    pub fn versions_in_ir(ir: &ProtocolIR) -> Vec<String> {
+        let mut versions = BTreeSet::new();
+        for rpc in ir.get_rpc_methods() {
+            versions.extend(rpc.version_added.clone());
+            versions.extend(rpc.version_removed.clone());
+            for param in &rpc.params {
+                versions.extend(param.version_added.clone());
+                versions.extend(param.version_removed.clone());
+                collect_type_versions(&param.param_type, &mut versions);
+            }
+            if let Some(result) = &rpc.result {
+                collect_type_versions(result, &mut versions);
+            }
+        }
+        let mut versions: Vec<String> = versions
+            .into_iter()
+            .filter(|v| ProtocolVersion::from_string(v.trim()).is_ok())
+            .collect();
+        versions.sort_by_key(|v| parse_version(v));
+        versions.dedup_by_key(|v| parse_version(v));
+        versions
+    }
+
+    /// The row of `method`, if it is present on any listed version
+    pub fn method(&self, method: &str) -> Option<&MethodRow> {
+        self.methods.iter().find(|row| row.method == method)
+    }
+
+    /// Renders the table, followed by the field-level changes of each method, as Markdown.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from("# Version compatibility matrix\n\n");
+        writeln!(out, "| Method | {} |", self.versions.join(" | ")).expect("write to String");
+        writeln!(out, "|---|{}", "---|".repeat(self.versions.len())).expect("write to String");
+        for row in &self.methods {
+            let cells: Vec<&str> = row.cells.iter().map(|c| c.status.label()).collect();
+            writeln!(out, "| `{}` | {} |", row.method, cells.join(" | ")).expect("write to String");
+        }
+
+        let changed: Vec<&MethodRow> = self
+            .methods
+            .iter()
+            .filter(|row| row.cells.iter().any(|c| !c.changes.is_empty()))
+            .collect();
+        if !changed.is_empty() {
+            out.push_str("\n## Field-level changes\n");
+        }
+        for row in changed {
+            writeln!(out, "\n### `{}`\n", row.method).expect("write to String");
+            for (version, cell) in self.versions.iter().zip(&row.cells) {
+                for change in &cell.changes {
+                    writeln!(out, "- {}: {}", version, describe(change)).expect("write to String");
+                }
+            }
+        }
+        out
+    }
+
+    /// Renders the table as a standalone HTML page; cells with field-level changes expand to
+    /// list them.
+    pub fn to_html(&self) -> String {
+        let mut out = String::from(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>Version compatibility matrix</title>\n<style>\n\
+             table { border-collapse: collapse; font-family: sans-serif; font-size: 0.9em; }\n\
+             th, td { border: 1px solid #ccc; padding: 0.2em 0.5em; vertical-align: top; }\n\
+             td.unavailable { color: #999; }\n\
+             td.added { background: #e6ffe6; }\n\
+             td.changed_params, td.changed_result, td.changed_params_and_result { background: #fff6d6; }\n\
+             td.removed { background: #ffe6e6; }\n\
+             </style>\n</head>\n<body>\n<h1>Version compatibility matrix</h1>\n<table>\n",
+        );
+        out.push_str("<tr><th>Method</th>");
+        for version in &self.versions {
+            write!(out, "<th>{}</th>", escape_html(version)).expect("write to String");
+        }
+        out.push_str("</tr>\n");
+        for row in &self.methods {
+            write!(out, "<tr><td><code>{}</code></td>", escape_html(&row.method))
+                .expect("write to String");
+            for cell in &row.cells {
+                write!(out, "<td class=\"{}\">", cell.status.key()).expect("write to String");
+                if cell.changes.is_empty() {
+                    out.push_str(cell.status.label());
+                } else {
+                    write!(out, "<details><summary>{}</summary><ul>", cell.status.label())
+                        .expect("write to String");
+                    for change in &cell.changes {
+                        let path = format!("<code>{}</code>", escape_html(&change.path));
+                        write!(out, "<li>{}</li>", describe_with(change, &path))
+                            .expect("write to String");
+                    }
+                    out.push_str("</ul></details>");
+                }
+                out.push_str("</td>");
+            }
+            out.push_str("</tr>\n");
+        }
+        out.push_str("</table>\n</body>\n</html>\n");
+        out
+    }
+}
+
+/// Param and result field paths of one method on one version
+type MethodFields = BTreeSet<(ChangeLocation, String)>;
+
+fn parse_version(version: &str) -> ProtocolVersion {
+    ProtocolVersion::from_string(version.trim()).unwrap_or_default()
+}
+
+/// Param and result field paths of a method
+fn method_fields(rpc: &RpcDef) -> MethodFields {
+    let mut fields = BTreeSet::new();
+    for param in &rpc.params {
+        fields.insert((ChangeLocation::Params, param.name.clone()));
+        collect_field_paths(&param.param_type, &param.name, ChangeLocation::Params, &mut fields);
+    }
+    if let Some(result) = &rpc.result {
+        collect_field_paths(result, "", ChangeLocation::Result, &mut fields);
+    }
+    fields
+}
+
+fn collect_field_paths(
+    ty: &TypeDef,
+    prefix: &str,
+    location: ChangeLocation,
+    out: &mut MethodFields,
+) {
+    if let Some(element) = ty.array_element_type() {
+        collect_field_paths(element, &format!("{}[]", prefix), location, out);
+        return;
+    }
+    for field in ty.fields.iter().flatten() {
+        let name = match &field.key {
+            FieldKey::Named(name) => name.clone(),
+            FieldKey::Anonymous(_) => continue,
+        };
+        let path = if prefix.is_empty() { name } else { format!("{}.{}", prefix, name) };
+        collect_field_paths(&field.field_type, &path, location, out);
+        out.insert((location, path));
+    }
+}
+
+fn collect_type_versions(ty: &TypeDef, out: &mut BTreeSet<String>) {
+    for field in ty.fields.iter().flatten() {
+        out.extend(field.version_added.clone());
+        out.extend(field.version_removed.clone());
+        collect_type_versions(&field.field_type, out);
+    }
+}
+
+/// Cells of one method from its field sets per version
+fn cells(fields: &[Option<MethodFields>]) -> Vec<MatrixCell> {
+    let mut cells = Vec::with_capacity(fields.len());
+    let mut seen = false;
+    for (index, current) in fields.iter().enumerate() {
+        let previous = index.checked_sub(1).and_then(|i| fields[i].as_ref());
+        let cell = match (previous, current) {
+            (_, None) if seen => MatrixCell { status: CellStatus::Removed, changes: vec![] },
+            (_, None) => MatrixCell { status: CellStatus::Unavailable, changes: vec![] },
+            (None, Some(_)) if index == 0 =>
+                MatrixCell { status: CellStatus::Available, changes: vec![] },
+            (None, Some(_)) => MatrixCell { status: CellStatus::Added, changes: vec![] },
+            (Some(previous), Some(current)) => {
+                let added = current.difference(previous).map(|f| (f, ChangeKind::Added));
+                let removed = previous.difference(current).map(|f| (f, ChangeKind::Removed));
+                let mut changes: Vec<FieldChange> = added
+                    .chain(removed)
+                    .map(|((location, path), kind)| FieldChange {
+                        location: *location,
+                        path: path.clone(),
+                        kind,
+                    })
+                    .collect();
+                changes.sort_by(|a, b| (a.location, &a.path).cmp(&(b.location, &b.path)));
+                let params = changes.iter().any(|c| c.location == ChangeLocation::Params);
+                let result = changes.iter().any(|c| c.location == ChangeLocation::Result);
+                let status = match (params, result) {
+                    (true, true) => CellStatus::ChangedParamsAndResult,
+                    (true, false) => CellStatus::ChangedParams,
+                    (false, true) => CellStatus::ChangedResult,
+                    (false, false) => CellStatus::Available,
+                };
+                MatrixCell { status, changes }
+            }
+        };
+        seen |= current.is_some();
+        cells.push(cell);
+    }
+    cells
+}
+
+fn describe(change: &FieldChange) -> String { describe_with(change, &format!("`{}`", change.path)) }
+
+fn describe_with(change: &FieldChange, path: &str) -> String {
+    let kind = match change.kind {
+        ChangeKind::Added => "added",
+        ChangeKind::Removed => "removed",
+    };
+    let location = match change.location {
+        ChangeLocation::Params => "param",
+        ChangeLocation::Result => "result field",
+    };
+    format!("{} {} {}", kind, location, path)
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
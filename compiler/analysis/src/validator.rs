@@ -4,7 +4,7 @@
 //! Performs various checks to ensure the IR is well-formed before
 //! proceeding to code generation.
 
-use ir::{AccessLevel, ProtocolDef, ProtocolIR, RpcDef, TypeDef, TypeKind};
+use ir::{AccessLevel, IrOverlay, ProtocolDef, ProtocolIR, RpcDef, TypeDef, TypeKind};
 
 use crate::{CompilerContext, CompilerPhase, PhaseResult};
 
@@ -52,6 +52,28 @@ impl IrValidator {
         errors
     }
 
+    /// Apply a dialect overlay to its base IR and return the validated overlaid IR
+    ///
+    /// Fails with every conflict between the overlay and the base (removing or changing what
+    /// the base lacks, adding what it already has), otherwise with the errors the overlaid IR
+    /// has that the base does not.
+    pub fn validate_overlay(
+        &self,
+        base: &ProtocolIR,
+        overlay: &IrOverlay,
+    ) -> Result<ProtocolIR, Vec<String>> {
+        let overlaid = overlay.apply(base).map_err(|e| e.conflicts)?;
+        let base_errors: std::collections::HashSet<String> =
+            self.validate(base).into_iter().collect();
+        let errors: Vec<String> =
+            self.validate(&overlaid).into_iter().filter(|e| !base_errors.contains(e)).collect();
+        if errors.is_empty() {
+            Ok(overlaid)
+        } else {
+            Err(errors)
+        }
+    }
+
     fn validate_rpc(&self, rpc: &RpcDef, errors: &mut Vec<String>) {
         // params: non-empty names, unique within method
         {
@@ -0,0 +1,198 @@
+use adapters::{InvocationEngine, ProtocolAdapterError, ProtocolAdapterResult};
+use ethos_analysis::conformance::{load_fixtures, Cassette, ConformanceEngine, FindingKind};
+use ir::test_utils::{minimal_module, primitive_type, rpc, type_def};
+use ir::{FieldDef, FieldKey, ProtocolDef, ProtocolIR, TypeDef, TypeKind};
+use serde_json::{json, Value};
+
+fn field(name: &str, field_type: TypeDef, required: bool) -> FieldDef {
+    FieldDef {
+        key: FieldKey::Named(name.to_string()),
+        field_type,
+        required,
+        description: String::new(),
+        default_value: None,
+        version_added: None,
+        version_removed: None,
+        deprecated: None,
+    }
+}
+
+fn object(fields: Vec<FieldDef>) -> TypeDef {
+    TypeDef { fields: Some(fields), ..type_def("object", TypeKind::Object) }
+}
+
+fn primitive(protocol_type: &str) -> TypeDef {
+    primitive_type(protocol_type, Some(protocol_type.to_string()))
+}
+
+fn ir() -> ProtocolIR {
+    let getblockheader = object(vec![
+        field("hash", primitive("hex"), true),
+        field("height", primitive("number"), true),
+        field("nextblockhash", primitive("hex"), false),
+        field("chainwork", object(vec![field("a/b", primitive("string"), true)]), true),
+    ]);
+    let raw_hex = TypeDef {
+        condition: Some("if verbosity is not set or set to 0".to_string()),
+        ..primitive("string")
+    };
+    let getrawtransaction =
+        object(vec![field("data", raw_hex, true), field("txid", primitive("hex"), false)]);
+    let getrawmempool = TypeDef {
+        fields: Some(vec![field("field_0", primitive("hex"), true)]),
+        ..type_def("array", TypeKind::Array)
+    };
+    let getaddressesbylabel = object(vec![field(
+        "address",
+        object(vec![field("purpose", primitive("string"), true)]),
+        true,
+    )]);
+
+    ProtocolIR::new(vec![minimal_module(
+        "rpc",
+        vec![
+            ProtocolDef::RpcMethod(rpc("getblockheader", vec![], Some(getblockheader), "chain")),
+            ProtocolDef::RpcMethod(rpc(
+                "getrawtransaction",
+                vec![],
+                Some(getrawtransaction),
+                "raw",
+            )),
+            ProtocolDef::RpcMethod(rpc("getrawmempool", vec![], Some(getrawmempool), "chain")),
+            ProtocolDef::RpcMethod(rpc(
+                "getaddressesbylabel",
+                vec![],
+                Some(getaddressesbylabel),
+                "wallet",
+            )),
+        ],
+    )])
+}
+
+#[test]
+fn reports_findings_with_json_pointer_paths() {
+    let ir = ir();
+    let engine = ConformanceEngine::new(&ir);
+
+    let result = engine
+        .check(
+            "getblockheader",
+            &json!({ "hash": "00ab", "height": "1", "chainwork": { "c~d": 1 }, "extra": true }),
+        )
+        .expect("method is defined");
+    let findings: Vec<(&str, &FindingKind)> =
+        result.findings.iter().map(|f| (f.path.as_str(), &f.kind)).collect();
+    assert_eq!(
+        findings,
+        vec![
+            (
+                "/height",
+                &FindingKind::TypeMismatch {
+                    expected: "number".to_string(),
+                    actual: "string".to_string()
+                }
+            ),
+            ("/chainwork/a~1b", &FindingKind::MissingField { field: "a/b".to_string() }),
+            ("/chainwork/c~0d", &FindingKind::UnexpectedField { field: "c~d".to_string() }),
+            ("/extra", &FindingKind::UnexpectedField { field: "extra".to_string() }),
+        ]
+    );
+    assert_eq!(result.violations, 4);
+    assert!(result.score() < 1.0);
+
+    let conforming = engine
+        .check(
+            "getblockheader",
+            &json!({ "hash": "00ab", "height": 1, "chainwork": { "a/b": "x" } }),
+        )
+        .expect("method is defined");
+    assert!(conforming.findings.is_empty());
+    assert_eq!(conforming.score(), 1.0);
+}
+
+#[test]
+fn matches_conditional_alternatives_arrays_and_dynamic_keys() {
+    let ir = ir();
+    let engine = ConformanceEngine::new(&ir);
+
+    let raw = engine.check("getrawtransaction", &json!("0200")).expect("method is defined");
+    assert_eq!(raw.violations, 0);
+    assert_eq!(
+        raw.findings[0].kind,
+        FindingKind::Conditional { condition: "if verbosity is not set or set to 0".to_string() }
+    );
+
+    let verbose = engine.check("getrawtransaction", &json!({ "txid": "ab" })).expect("defined");
+    assert!(verbose.findings.is_empty());
+
+    let mempool = engine.check("getrawmempool", &json!(["ab", "zz"])).expect("defined");
+    assert_eq!(mempool.findings.len(), 1);
+    assert_eq!(mempool.findings[0].path, "/1");
+
+    let labels = engine
+        .check("getaddressesbylabel", &json!({ "bc1qa": { "purpose": "receive" }, "bc1qb": {} }))
+        .expect("defined");
+    assert_eq!(labels.findings.len(), 1);
+    assert_eq!(labels.findings[0].path, "/bc1qb/purpose");
+}
+
+#[test]
+fn scores_cassettes_and_fixtures_per_method() {
+    let ir = ir();
+    let engine = ConformanceEngine::new(&ir);
+
+    let cassette: Cassette = serde_json::from_value(json!({
+        "interactions": [
+            { "method": "getrawmempool", "params": [], "result": ["ab"] },
+            { "method": "getrawmempool", "params": [], "result": [1] },
+            { "method": "getblockcount", "params": [], "result": 1 },
+            { "method": "getrawtransaction", "params": ["ff"], "error": { "code": -5 } }
+        ]
+    }))
+    .expect("valid cassette");
+    let report = engine.check_samples(&cassette.samples());
+    let mempool = report.method("getrawmempool").expect("checked");
+    assert_eq!(mempool.samples, 2);
+    assert_eq!(mempool.findings[0].sample, 1);
+    assert_eq!(mempool.score(), 0.75);
+    assert_eq!(report.unknown_methods, vec!["getblockcount".to_string()]);
+    assert_eq!(report.failed_calls.len(), 1);
+
+    let dir = std::env::temp_dir().join(format!("ethos-conformance-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("create fixture dir");
+    std::fs::write(dir.join("getrawmempool.json"), r#"{"result": ["ab"], "error": null, "id": 1}"#)
+        .expect("write fixture");
+    std::fs::write(dir.join("getrawtransaction.verbose.json"), r#"{"txid": "ab"}"#)
+        .expect("write fixture");
+    let samples = load_fixtures(&dir).expect("fixtures load");
+    std::fs::remove_dir_all(&dir).ok();
+    let report = engine.check_samples(&samples);
+    assert_eq!(report.methods.len(), 2);
+    assert_eq!(report.convergence(), 1.0);
+}
+
+struct StubNode;
+
+#[async_trait::async_trait]
+impl InvocationEngine for StubNode {
+    async fn invoke(&self, method: &str, _params: &Value) -> ProtocolAdapterResult<Value> {
+        match method {
+            "getrawmempool" => Ok(json!("ab")),
+            _ => Err(ProtocolAdapterError::Message(format!("Method not found: {}", method))),
+        }
+    }
+}
+
+#[test]
+fn checks_live_responses() {
+    let ir = ir();
+    let engine = ConformanceEngine::new(&ir);
+    let calls =
+        vec![("getrawmempool".to_string(), json!([])), ("getblockheader".to_string(), json!([]))];
+
+    let report = futures::executor::block_on(engine.check_live(&StubNode, &calls));
+    let mempool = report.method("getrawmempool").expect("checked");
+    assert_eq!(mempool.score(), 0.0);
+    assert_eq!(mempool.findings[0].path, "");
+    assert_eq!(report.failed_calls[0].method, "getblockheader");
+}
@@ -0,0 +1,96 @@
+use ethos_analysis::drift::{
+    ArgumentDrift, ArgumentMismatch, NodeArgument, NodeMethod, NodeSurface,
+};
+use ethos_analysis::DriftReport;
+use ir::test_utils::{minimal_module, param, primitive_type, rpc};
+use ir::{ProtocolDef, ProtocolIR};
+
+fn ir() -> ProtocolIR {
+    let string = || primitive_type("String", Some("string".to_string()));
+    let number = || primitive_type("i64", Some("number".to_string()));
+    ProtocolIR::new(vec![minimal_module(
+        "rpc",
+        vec![
+            ProtocolDef::RpcMethod(rpc(
+                "getblock",
+                vec![param("blockhash", string(), true), param("verbosity", number(), false)],
+                None,
+                "blockchain",
+            )),
+            ProtocolDef::RpcMethod(rpc(
+                "getblockhash",
+                vec![param("height", number(), true)],
+                None,
+                "blockchain",
+            )),
+            ProtocolDef::RpcMethod(rpc("getinfo", vec![], None, "control")),
+        ],
+    )])
+}
+
+fn method(name: &str, arguments: &[(&str, bool)]) -> NodeMethod {
+    NodeMethod {
+        name: name.to_string(),
+        arguments: arguments
+            .iter()
+            .map(|(name, required)| NodeArgument { name: name.to_string(), required: *required })
+            .collect(),
+        hidden: false,
+    }
+}
+
+#[test]
+fn matching_surface_has_no_drift() {
+    let surface = NodeSurface {
+        version: "30.2.0".to_string(),
+        methods: vec![
+            method("getblock", &[("blockhash", true), ("verbosity", false)]),
+            method("getblockhash", &[("height", true)]),
+            method("getinfo", &[]),
+        ],
+    };
+    let report = DriftReport::compare(&surface, &ir());
+    assert!(report.is_empty(), "unexpected drift: {}", report);
+    assert_eq!(report.compared_methods, 3);
+}
+
+#[test]
+fn reports_method_and_argument_drift() {
+    let surface = NodeSurface {
+        version: "30.2.0".to_string(),
+        methods: vec![
+            method("getblock", &[("verbosity", true), ("blockhash", true), ("extra", false)]),
+            method("getblockhash", &[("height", true)]),
+            method("getdeploymentinfo", &[("blockhash", false)]),
+        ],
+    };
+    let report = DriftReport::compare(&surface, &ir());
+
+    assert_eq!(report.missing_from_ir, ["getdeploymentinfo"]);
+    assert_eq!(report.absent_from_node, ["getinfo"]);
+    assert_eq!(
+        report.argument_mismatches,
+        [ArgumentMismatch {
+            method: "getblock".to_string(),
+            drift: vec![
+                ArgumentDrift::Position { argument: "verbosity".to_string(), ir: 1, node: 0 },
+                ArgumentDrift::Required {
+                    argument: "verbosity".to_string(),
+                    ir: false,
+                    node: true
+                },
+                ArgumentDrift::Position { argument: "blockhash".to_string(), ir: 0, node: 1 },
+                ArgumentDrift::MissingFromIr { argument: "extra".to_string() },
+            ],
+        }]
+    );
+    assert_eq!(report.distance(), 6);
+
+    let json = serde_json::to_value(&report).expect("serialize report");
+    assert_eq!(json["argument_mismatches"][0]["drift"][1]["kind"], "required");
+
+    let text = report.to_string();
+    assert!(text.contains("|B − Δ| = 6"));
+    assert!(text.contains("  + getdeploymentinfo"));
+    assert!(text.contains("    `verbosity` is required on the node, optional in the IR"));
+}
@@ -0,0 +1,120 @@
+use ethos_analysis::conformance::{ConformanceEngine, ResponseSample};
+use ethos_analysis::SchemaInferrer;
+use ir::test_utils::{minimal_module, primitive_type, rpc, type_def};
+use ir::{FieldDef, FieldKey, ProtocolDef, ProtocolIR, TypeDef, TypeKind};
+use serde_json::{json, Value};
+
+fn field(name: &str, field_type: TypeDef) -> FieldDef {
+    FieldDef {
+        key: FieldKey::Named(name.to_string()),
+        field_type,
+        required: true,
+        description: String::new(),
+        default_value: None,
+        version_added: None,
+        version_removed: None,
+        deprecated: None,
+    }
+}
+
+fn object(fields: Vec<FieldDef>) -> TypeDef {
+    TypeDef { fields: Some(fields), ..type_def("object", TypeKind::Object) }
+}
+
+fn ir() -> ProtocolIR {
+    let number = primitive_type("number", Some("number".to_string()));
+    let getpeerinfo = TypeDef {
+        fields: Some(vec![field("field_0", object(vec![field("id", number.clone())]))]),
+        ..type_def("array", TypeKind::Array)
+    };
+    let getblockcount = number;
+    ProtocolIR::new_with_version(
+        "30.2".to_string(),
+        vec![minimal_module(
+            "rpc",
+            vec![
+                ProtocolDef::RpcMethod(rpc("getblockcount", vec![], Some(getblockcount), "chain")),
+                ProtocolDef::RpcMethod(rpc("getpeerinfo", vec![], Some(getpeerinfo), "network")),
+            ],
+        )],
+    )
+}
+
+fn samples(method: &str, responses: &[Value]) -> Vec<ResponseSample> {
+    responses
+        .iter()
+        .map(|response| ResponseSample {
+            method: method.to_string(),
+            params: Value::Null,
+            response: Ok(response.clone()),
+        })
+        .collect()
+}
+
+#[test]
+fn infers_types_optionality_and_earliest_version() {
+    let ir = ir();
+    let mut inferrer = SchemaInferrer::new(&ir);
+    inferrer.observe(
+        "30",
+        &samples(
+            "getpeerinfo",
+            &[json!([{ "id": 1, "addr": "1.2.3.4:8333", "services": "0409" }, { "id": 2, "addr": "h:1" }])],
+        ),
+    );
+    inferrer.observe(
+        "29",
+        &samples(
+            "getpeerinfo",
+            &[json!([{ "id": 3, "addr": "h:2", "networks": [{ "ok": true }] }])],
+        ),
+    );
+    inferrer.observe("30", &samples("getnodeaddresses", &[json!([])]));
+
+    let patch = inferrer.infer();
+    let added: Vec<(&str, &str, bool, Option<&str>)> = patch
+        .additions
+        .iter()
+        .map(|a| {
+            (
+                a.path.as_str(),
+                a.field.field_type.protocol_type.as_deref().unwrap_or_default(),
+                a.field.required,
+                a.field.version_added.as_deref(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        added,
+        vec![
+            ("/*/addr", "string", true, Some("29")),
+            ("/*/networks", "array", false, Some("29")),
+            ("/*/services", "hex", false, Some("30")),
+        ]
+    );
+    let networks = &patch.additions[1].field.field_type;
+    let element = networks.array_element_type().expect("element shape inferred");
+    assert_eq!(element.kind, TypeKind::Object);
+    assert_eq!(patch.unknown_methods, vec!["getnodeaddresses".to_string()]);
+}
+
+#[test]
+fn applied_patch_makes_responses_conform() {
+    let ir = ir();
+    let observed = samples("getpeerinfo", &[json!([{ "id": 1, "inbound": false }])]);
+    let before = ConformanceEngine::new(&ir).check_samples(&observed);
+    assert!(before.convergence() < 1.0);
+
+    let mut inferrer = SchemaInferrer::new(&ir);
+    inferrer.observe("30", &observed);
+    inferrer.observe("30", &samples("getblockcount", &[json!(840000)]));
+    let patch = inferrer.infer();
+    assert_eq!(patch.additions.len(), 1);
+    assert_eq!(patch.ir.get_rpc_methods().len(), 1);
+
+    let updated = patch.apply(&ir);
+    assert_eq!(updated.version(), "30.2");
+    assert_eq!(updated.get_rpc_methods().len(), 2);
+    let after = ConformanceEngine::new(&updated).check_samples(&observed);
+    assert_eq!(after.convergence(), 1.0);
+}
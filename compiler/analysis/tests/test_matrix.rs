@@ -0,0 +1,128 @@
+use ethos_analysis::matrix::{CellStatus, ChangeKind, ChangeLocation, FieldChange};
+use ethos_analysis::CompatibilityMatrix;
+use ir::test_utils::{minimal_module, param, primitive_type, rpc, type_def};
+use ir::{FieldDef, FieldKey, ProtocolDef, ProtocolIR, RpcDef, TypeKind};
+
+fn added(mut rpc: RpcDef, version_added: &str, version_removed: Option<&str>) -> ProtocolDef {
+    rpc.version_added = Some(version_added.to_string());
+    rpc.version_removed = version_removed.map(str::to_string);
+    ProtocolDef::RpcMethod(rpc)
+}
+
+fn field(name: &str, version_added: Option<&str>) -> FieldDef {
+    FieldDef {
+        key: FieldKey::Named(name.to_string()),
+        field_type: primitive_type("String", Some("string".to_string())),
+        required: true,
+        description: String::new(),
+        default_value: None,
+        version_added: version_added.map(str::to_string),
+        version_removed: None,
+        deprecated: None,
+    }
+}
+
+fn ir() -> ProtocolIR {
+    let string = || primitive_type("String", Some("string".to_string()));
+    let mut fee_rate = param("fee_rate", string(), false);
+    fee_rate.version_added = Some("29.1".to_string());
+    let mut result = type_def("GetMempoolInfo", TypeKind::Object);
+    result.fields = Some(vec![field("size", None), field("maxdatacarriersize", Some("30"))]);
+
+    ProtocolIR::new(vec![minimal_module(
+        "rpc",
+        vec![
+            added(rpc("getblockcount", vec![], None, "blockchain"), "0.17", None),
+            added(
+                rpc(
+                    "sendtoaddress",
+                    vec![param("address", string(), true), fee_rate],
+                    None,
+                    "wallet",
+                ),
+                "0.17",
+                None,
+            ),
+            added(rpc("getmempoolinfo", vec![], Some(result), "blockchain"), "0.17", None),
+            added(rpc("getdescriptoractivity", vec![], None, "blockchain"), "29", None),
+            added(rpc("getinfo", vec![], None, "control"), "0.17", Some("29")),
+        ],
+    )])
+}
+
+fn statuses(matrix: &CompatibilityMatrix, method: &str) -> Vec<CellStatus> {
+    matrix.method(method).expect("method row").cells.iter().map(|c| c.status).collect()
+}
+
+#[test]
+fn tabulates_availability_and_changes() {
+    let versions: Vec<String> =
+        ["v30", "v28", "v29", "v29.1"].iter().map(|v| v.to_string()).collect();
+    let matrix = CompatibilityMatrix::build(&ir(), &versions);
+
+    assert_eq!(matrix.versions, vec!["v28", "v29", "v29.1", "v30"]);
+    assert_eq!(statuses(&matrix, "getblockcount"), vec![CellStatus::Available; 4]);
+    assert_eq!(
+        statuses(&matrix, "getdescriptoractivity"),
+        vec![
+            CellStatus::Unavailable,
+            CellStatus::Added,
+            CellStatus::Available,
+            CellStatus::Available
+        ]
+    );
+    assert_eq!(
+        statuses(&matrix, "getinfo"),
+        vec![CellStatus::Available, CellStatus::Removed, CellStatus::Removed, CellStatus::Removed]
+    );
+    assert_eq!(
+        statuses(&matrix, "sendtoaddress"),
+        vec![
+            CellStatus::Available,
+            CellStatus::Available,
+            CellStatus::ChangedParams,
+            CellStatus::Available
+        ]
+    );
+    assert_eq!(
+        matrix.method("sendtoaddress").expect("row").cells[2].changes,
+        vec![FieldChange {
+            location: ChangeLocation::Params,
+            path: "fee_rate".to_string(),
+            kind: ChangeKind::Added,
+        }]
+    );
+    assert_eq!(statuses(&matrix, "getmempoolinfo")[3], CellStatus::ChangedResult);
+}
+
+#[test]
+fn renders_markdown_html_and_json() {
+    let versions: Vec<String> = ["29", "29.1"].iter().map(|v| v.to_string()).collect();
+    let matrix = CompatibilityMatrix::build(&ir(), &versions);
+
+    let markdown = matrix.to_markdown();
+    assert!(markdown.contains("| Method | 29 | 29.1 |\n|---|---|---|\n"));
+    assert!(markdown.contains("| `sendtoaddress` | available | changed params |"));
+    assert!(markdown.contains("### `sendtoaddress`\n\n- 29.1: added param `fee_rate`\n"));
+
+    let html = matrix.to_html();
+    assert!(html.contains(
+        "<td class=\"changed_params\"><details><summary>changed params</summary>\
+         <ul><li>added param <code>fee_rate</code></li></ul></details></td>"
+    ));
+
+    let json = serde_json::to_value(&matrix).expect("serializes");
+    let row = json["methods"]
+        .as_array()
+        .expect("methods")
+        .iter()
+        .find(|row| row["method"] == "sendtoaddress")
+        .expect("row");
+    assert_eq!(row["cells"][1]["status"], "changed_params");
+    assert_eq!(row["cells"][1]["changes"][0]["path"], "fee_rate");
+}
+
+#[test]
+fn lists_versions_mentioned_in_the_ir() {
+    assert_eq!(CompatibilityMatrix::versions_in_ir(&ir()), vec!["0.17", "29", "29.1", "30"]);
+}
@@ -1,6 +1,6 @@
 use ethos_analysis::IrValidator;
 use ir::test_utils::{minimal_module, param, primitive_type, rpc, type_def};
-use ir::{ProtocolDef, ProtocolIR, ProtocolModule, TypeKind};
+use ir::{IrOverlay, IrOverride, MethodChange, ProtocolDef, ProtocolIR, ProtocolModule, TypeKind};
 
 #[test]
 fn allows_hashorheight_as_primitive() {
@@ -148,3 +148,72 @@ fn passes_with_valid_ir() {
     let errors = validator.validate(&ir);
     assert!(errors.is_empty(), "Expected no validation errors for valid IR, got: {:?}", errors);
 }
+
+fn overlay_base() -> ProtocolIR {
+    let getblock =
+        rpc("getblock", vec![param("hash", primitive_type("String", None), true)], None, "core");
+    ProtocolIR::new(vec![minimal_module("rpc", vec![ProtocolDef::RpcMethod(getblock)])])
+}
+
+#[test]
+fn validates_overlay_conflicts() {
+    let overlay = IrOverlay {
+        remove_methods: vec!["getinfo".into()],
+        change_methods: vec![MethodChange {
+            method: "getblock".into(),
+            remove_params: vec!["verbosity".into()],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    let errors = IrValidator::new()
+        .validate_overlay(&overlay_base(), &overlay)
+        .expect_err("overlay conflicts with its base");
+    assert_eq!(errors.len(), 2, "Expected one error per conflict, got: {:?}", errors);
+    assert!(errors[0].contains("cannot remove method `getinfo`"));
+    assert!(errors[1].contains("cannot remove param `verbosity` of `getblock`"));
+}
+
+#[test]
+fn validates_overlaid_ir() {
+    let overlay = IrOverlay {
+        change_methods: vec![MethodChange {
+            method: "getblock".into(),
+            add_params: vec![param("", primitive_type("String", None), false)],
+            ..Default::default()
+        }],
+        add_methods: vec![rpc("getgeneralinfo", vec![], None, "control")],
+        ..Default::default()
+    };
+
+    let errors = IrValidator::new()
+        .validate_overlay(&overlay_base(), &overlay)
+        .expect_err("overlaid IR is invalid");
+    assert_eq!(errors, vec!["RPC `getblock` has a param with empty name".to_string()]);
+
+    let valid = IrOverlay { change_methods: vec![], ..overlay };
+    let overlaid =
+        IrValidator::new().validate_overlay(&overlay_base(), &valid).expect("overlay is valid");
+    assert!(overlaid.get_rpc_methods().iter().any(|m| m.name == "getgeneralinfo"));
+}
+
+#[test]
+fn validates_overridden_ir() {
+    let overlay = IrOverlay {
+        overrides: vec![IrOverride {
+            path: "getblock.params.hash".into(),
+            protocol_type: Some(String::new()),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    let errors = IrValidator::new()
+        .validate_overlay(&overlay_base(), &overlay)
+        .expect_err("overridden IR is invalid");
+    assert_eq!(
+        errors,
+        vec!["RPC `getblock` field `hash`: Primitive type name is empty".to_string()]
+    );
+}
@@ -3,7 +3,7 @@
 use std::collections::BTreeMap;
 
 use ir::{ProtocolIR, RpcDef, TypeDef};
-use types::ProtocolVersion;
+use types::{Implementation, ProtocolVersion};
 
 use super::version_specific_client_trait::VersionSpecificClientTraitGenerator;
 use super::version_specific_response_type::VersionSpecificResponseTypeGenerator;
@@ -14,6 +14,15 @@ use crate::{CodeGenerator, Result};
 /// Bitcoin Core version-specific type generator
 pub struct BitcoinCoreVersionedGenerator {
     version: ProtocolVersion,
+    implementation: Implementation,
+}
+
+impl BitcoinCoreVersionedGenerator {
+    /// Generator for a dialect serving Bitcoin Core's RPC interface (e.g. Knots), whose crate
+    /// name and client prefix come from `implementation`
+    pub fn for_dialect(version: ProtocolVersion, implementation: Implementation) -> Self {
+        Self { version, implementation }
+    }
 }
 
 impl VersionedTypeGenerator for BitcoinCoreVersionedGenerator {
@@ -23,12 +32,15 @@ impl VersionedTypeGenerator for BitcoinCoreVersionedGenerator {
             .iter()
             .map(|ty| (sanitize_type_name_for_rust(&ty.name), (**ty).clone()))
             .collect();
-        Ok(Self { version })
+        Ok(Self::for_dialect(version, Implementation::BitcoinCore))
     }
 
     fn generate_response_types(&self, methods: &[RpcDef]) -> Result<Vec<(String, String)>> {
-        VersionSpecificResponseTypeGenerator::new(self.version.clone(), "bitcoin_core".to_string())
-            .generate(methods)
+        VersionSpecificResponseTypeGenerator::new(
+            self.version.clone(),
+            self.implementation.as_str().to_string(),
+        )
+        .generate(methods)
     }
 
     fn generate_client_trait(
@@ -52,7 +64,7 @@ impl VersionedTypeGenerator for BitcoinCoreVersionedGenerator {
         true
     }
 
-    fn implementation(&self) -> &'static str { "bitcoin_core" }
+    fn implementation(&self) -> &'static str { self.implementation.as_str() }
 }
 
 #[cfg(test)]
@@ -67,6 +79,8 @@ mod tests {
             minor: 0,
             patch: 0,
             protocol: Some("bitcoin_core".to_string()),
+            release_candidate: None,
+            suffix: None,
         };
         let ir = ProtocolIR::new(vec![]);
 
@@ -74,7 +88,7 @@ mod tests {
             version.clone(),
             &ir,
         )
-        .unwrap();
+        .expect("generation succeeds");
 
         assert_eq!(generator.version.as_str(), version.as_str());
     }
@@ -87,15 +101,18 @@ mod tests {
             minor: 0,
             patch: 0,
             protocol: Some("bitcoin_core".to_string()),
+            release_candidate: None,
+            suffix: None,
         };
-        let generator = BitcoinCoreVersionedGenerator { version };
+        let generator =
+            BitcoinCoreVersionedGenerator::for_dialect(version, Implementation::BitcoinCore);
 
         let result =
             <BitcoinCoreVersionedGenerator as VersionedTypeGenerator>::generate_response_types(
                 &generator,
                 &[],
             )
-            .unwrap();
+            .expect("generation succeeds");
 
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].0, "responses.rs");
@@ -110,8 +127,11 @@ mod tests {
             minor: 0,
             patch: 0,
             protocol: Some("bitcoin_core".to_string()),
+            release_candidate: None,
+            suffix: None,
         };
-        let generator = BitcoinCoreVersionedGenerator { version };
+        let generator =
+            BitcoinCoreVersionedGenerator::for_dialect(version, Implementation::BitcoinCore);
 
         let result =
             <BitcoinCoreVersionedGenerator as VersionedTypeGenerator>::generate_client_trait(
@@ -119,7 +139,7 @@ mod tests {
                 "bitcoin_core",
                 &[],
             )
-            .unwrap();
+            .expect("generation succeeds");
 
         assert_eq!(result.len(), 2);
         let filenames: Vec<_> = result.iter().map(|(name, _)| name.as_str()).collect();
@@ -129,15 +149,18 @@ mod tests {
 
     #[test]
     fn test_implementation() {
-        let generator = BitcoinCoreVersionedGenerator {
-            version: ProtocolVersion {
+        let generator = BitcoinCoreVersionedGenerator::for_dialect(
+            ProtocolVersion {
                 version_string: "v25.0.0".to_string(),
                 major: 25,
                 minor: 0,
                 patch: 0,
                 protocol: Some("bitcoin_core".to_string()),
+                release_candidate: None,
+                suffix: None,
             },
-        };
+            Implementation::BitcoinCore,
+        );
 
         assert_eq!(generator.implementation(), "bitcoin_core");
     }
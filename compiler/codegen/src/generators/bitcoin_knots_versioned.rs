@@ -0,0 +1,82 @@
+//! Bitcoin Knots version-specific type generator
+
+use ir::{ProtocolIR, RpcDef};
+use types::{Implementation, ProtocolVersion};
+
+use super::bitcoin_core_versioned::BitcoinCoreVersionedGenerator;
+use super::versioned_generator::VersionedTypeGenerator;
+use crate::Result;
+
+/// Bitcoin Knots version-specific type generator
+///
+/// Knots' IR is Bitcoin Core's plus an overlay, and Knots releases follow Core's version
+/// numbers, so generation is Core's under Knots' crate name and client prefix.
+pub struct BitcoinKnotsVersionedGenerator {
+    core: BitcoinCoreVersionedGenerator,
+}
+
+impl VersionedTypeGenerator for BitcoinKnotsVersionedGenerator {
+    fn from_ir(version: ProtocolVersion, _ir: &ProtocolIR) -> Result<Self> {
+        Ok(Self {
+            core: BitcoinCoreVersionedGenerator::for_dialect(version, Implementation::BitcoinKnots),
+        })
+    }
+
+    fn generate_response_types(&self, methods: &[RpcDef]) -> Result<Vec<(String, String)>> {
+        self.core.generate_response_types(methods)
+    }
+
+    fn generate_client_trait(
+        &self,
+        implementation: &str,
+        methods: &[RpcDef],
+    ) -> Result<Vec<(String, String)>> {
+        self.core.generate_client_trait(implementation, methods)
+    }
+
+    fn supports_version(&self, version: &ProtocolVersion) -> bool {
+        // Same release line as Bitcoin Core, from v17 on
+        version.major >= 17
+    }
+
+    fn implementation(&self) -> &'static str { self.core.implementation() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version() -> ProtocolVersion {
+        ProtocolVersion::from_string_with_protocol(
+            "v29.1.knots20250903",
+            Some("bitcoin_knots".to_string()),
+        )
+        .expect("valid version")
+    }
+
+    fn generator() -> BitcoinKnotsVersionedGenerator {
+        <BitcoinKnotsVersionedGenerator as VersionedTypeGenerator>::from_ir(
+            version(),
+            &ProtocolIR::new(vec![]),
+        )
+        .expect("generator")
+    }
+
+    #[test]
+    fn test_supports_version() {
+        let generator = generator();
+        assert!(generator.supports_version(&version()));
+        assert!(!generator
+            .supports_version(&ProtocolVersion::from_string("v0.24.2").expect("valid version")));
+        assert_eq!(generator.implementation(), "bitcoin_knots");
+    }
+
+    #[test]
+    fn test_generate_client_trait() {
+        let result =
+            generator().generate_client_trait("bitcoin_knots", &[]).expect("generation succeeds");
+        let filenames: Vec<_> = result.iter().map(|(name, _)| name.as_str()).collect();
+        assert!(filenames.contains(&"client.rs"));
+        assert!(filenames.contains(&"mod.rs"));
+    }
+}
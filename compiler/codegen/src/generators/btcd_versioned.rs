@@ -0,0 +1,74 @@
+//! btcd version-specific type generator
+
+use ir::{ProtocolIR, RpcDef};
+use types::ProtocolVersion;
+
+use super::version_specific_client_trait::VersionSpecificClientTraitGenerator;
+use super::version_specific_response_type::VersionSpecificResponseTypeGenerator;
+use super::versioned_generator::VersionedTypeGenerator;
+use crate::{CodeGenerator, Result};
+
+/// btcd version-specific type generator
+///
+/// btcd's IR comes from a single btcjson snapshot and carries no per-version
+/// availability, so every method in the IR is generated as-is.
+pub struct BtcdVersionedGenerator {
+    version: ProtocolVersion,
+}
+
+impl VersionedTypeGenerator for BtcdVersionedGenerator {
+    fn from_ir(version: ProtocolVersion, _ir: &ProtocolIR) -> Result<Self> { Ok(Self { version }) }
+
+    fn generate_response_types(&self, methods: &[RpcDef]) -> Result<Vec<(String, String)>> {
+        VersionSpecificResponseTypeGenerator::new(self.version.clone(), "btcd".to_string())
+            .generate(methods)
+    }
+
+    fn generate_client_trait(
+        &self,
+        implementation: &str,
+        methods: &[RpcDef],
+    ) -> Result<Vec<(String, String)>> {
+        Ok(VersionSpecificClientTraitGenerator::new(self.version.clone(), implementation)
+            .generate(methods))
+    }
+
+    fn supports_version(&self, version: &ProtocolVersion) -> bool {
+        // The btcjson snapshot tracks the v0.24 release line
+        version.major == 0 && version.minor >= 24
+    }
+
+    fn implementation(&self) -> &'static str { "btcd" }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version() -> ProtocolVersion {
+        ProtocolVersion::from_string_with_protocol("v0.24.2", Some("btcd".to_string()))
+            .expect("valid version")
+    }
+
+    #[test]
+    fn test_supports_version() {
+        let generator = <BtcdVersionedGenerator as VersionedTypeGenerator>::from_ir(
+            version(),
+            &ProtocolIR::new(vec![]),
+        )
+        .expect("generator");
+        assert!(generator.supports_version(&version()));
+        assert!(!generator
+            .supports_version(&ProtocolVersion::from_string("v30.2").expect("valid version")));
+        assert_eq!(generator.implementation(), "btcd");
+    }
+
+    #[test]
+    fn test_generate_client_trait() {
+        let generator = BtcdVersionedGenerator { version: version() };
+        let result = generator.generate_client_trait("btcd", &[]).expect("generation succeeds");
+        let filenames: Vec<_> = result.iter().map(|(name, _)| name.as_str()).collect();
+        assert!(filenames.contains(&"client.rs"));
+        assert!(filenames.contains(&"mod.rs"));
+    }
+}
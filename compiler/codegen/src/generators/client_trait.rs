@@ -305,10 +305,10 @@ impl<'a> MethodTemplate<'a> {
                     } else {
                         // Optional parameter: only include if Some
                         lines.push(format!("            if let Some(val) = {name} {{"));
-                        lines.push(format!(
-                            "                rpc_params.push(serde_json::json!(val));"
-                        ));
-                        lines.push(format!("            }}"));
+                        lines.push(
+                            "                rpc_params.push(serde_json::json!(val));".to_string(),
+                        );
+                        lines.push("            }".to_string());
                     }
                 }
                 lines.join("\n")
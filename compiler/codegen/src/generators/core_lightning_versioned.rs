@@ -0,0 +1,78 @@
+//! Core Lightning version-specific type generator
+
+use ir::{ProtocolIR, RpcDef};
+use types::ProtocolVersion;
+
+use super::version_specific_client_trait::VersionSpecificClientTraitGenerator;
+use super::version_specific_response_type::VersionSpecificResponseTypeGenerator;
+use super::versioned_generator::VersionedTypeGenerator;
+use crate::{CodeGenerator, Result};
+
+/// Core Lightning version-specific type generator
+///
+/// The CLN schemas record `added` / `deprecated` releases, but like btcd only the
+/// snapshot's own release is generated, so every method in the IR is generated as-is.
+pub struct CoreLightningVersionedGenerator {
+    version: ProtocolVersion,
+}
+
+impl VersionedTypeGenerator for CoreLightningVersionedGenerator {
+    fn from_ir(version: ProtocolVersion, _ir: &ProtocolIR) -> Result<Self> { Ok(Self { version }) }
+
+    fn generate_response_types(&self, methods: &[RpcDef]) -> Result<Vec<(String, String)>> {
+        VersionSpecificResponseTypeGenerator::new(
+            self.version.clone(),
+            "core_lightning".to_string(),
+        )
+        .generate(methods)
+    }
+
+    fn generate_client_trait(
+        &self,
+        implementation: &str,
+        methods: &[RpcDef],
+    ) -> Result<Vec<(String, String)>> {
+        Ok(VersionSpecificClientTraitGenerator::new(self.version.clone(), implementation)
+            .generate(methods))
+    }
+
+    fn supports_version(&self, version: &ProtocolVersion) -> bool {
+        // The schema snapshot's field `added` markers start at the v23 releases
+        version.major >= 23
+    }
+
+    fn implementation(&self) -> &'static str { "core_lightning" }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version() -> ProtocolVersion {
+        ProtocolVersion::from_string_with_protocol("v25.09", Some("core_lightning".to_string()))
+            .expect("valid version")
+    }
+
+    #[test]
+    fn test_supports_version() {
+        let generator = <CoreLightningVersionedGenerator as VersionedTypeGenerator>::from_ir(
+            version(),
+            &ProtocolIR::new(vec![]),
+        )
+        .expect("generator");
+        assert!(generator.supports_version(&version()));
+        assert!(!generator
+            .supports_version(&ProtocolVersion::from_string("v0.24.2").expect("valid version")));
+        assert_eq!(generator.implementation(), "core_lightning");
+    }
+
+    #[test]
+    fn test_generate_client_trait() {
+        let generator = CoreLightningVersionedGenerator { version: version() };
+        let result =
+            generator.generate_client_trait("core_lightning", &[]).expect("generation succeeds");
+        let filenames: Vec<_> = result.iter().map(|(name, _)| name.as_str()).collect();
+        assert!(filenames.contains(&"client.rs"));
+        assert!(filenames.contains(&"mod.rs"));
+    }
+}
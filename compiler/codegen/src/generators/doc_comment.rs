@@ -4,7 +4,7 @@
 //! rustdoc sees escaped brackets and proper URL links (e.g. placeholders like `<txid>` and
 //! bare URLs are handled correctly).
 
-use ir::RpcDef;
+use ir::{Deprecation, RpcDef};
 use regex::Regex;
 
 use crate::utils::rpc_method_to_rust_name;
@@ -243,6 +243,22 @@ pub fn generate_example_docs(method: &RpcDef) -> String {
     docs.trim_end().to_string()
 }
 
+/// `#[deprecated(since = "..", note = "..")]` attribute for a deprecated method or field
+pub fn deprecated_attribute(deprecation: &Deprecation) -> String {
+    let mut args = Vec::new();
+    if let Some(since) = &deprecation.since {
+        args.push(format!("since = {:?}", since));
+    }
+    if let Some(note) = deprecation.note() {
+        args.push(format!("note = {:?}", note));
+    }
+    if args.is_empty() {
+        "#[deprecated]".to_string()
+    } else {
+        format!("#[deprecated({})]", args.join(", "))
+    }
+}
+
 /// Write a doc comment line with proper prefix (sanitized for rustdoc).
 pub fn write_doc_line(buf: &mut String, text: &str, indent: &str) -> std::fmt::Result {
     use std::fmt::Write;
@@ -0,0 +1,65 @@
+//! electrs version-specific type generator
+
+use ir::{ProtocolIR, RpcDef};
+use types::ProtocolVersion;
+
+use super::version_specific_client_trait::VersionSpecificClientTraitGenerator;
+use super::version_specific_response_type::VersionSpecificResponseTypeGenerator;
+use super::versioned_generator::VersionedTypeGenerator;
+use crate::{CodeGenerator, Result};
+
+/// electrs version-specific type generator
+///
+/// The Electrum IR describes protocol 1.4 rather than a server release, so every method is
+/// generated as-is for any electrs release speaking it.
+pub struct ElectrsVersionedGenerator {
+    version: ProtocolVersion,
+}
+
+impl VersionedTypeGenerator for ElectrsVersionedGenerator {
+    fn from_ir(version: ProtocolVersion, _ir: &ProtocolIR) -> Result<Self> { Ok(Self { version }) }
+
+    fn generate_response_types(&self, methods: &[RpcDef]) -> Result<Vec<(String, String)>> {
+        VersionSpecificResponseTypeGenerator::new(self.version.clone(), "electrs".to_string())
+            .generate(methods)
+    }
+
+    fn generate_client_trait(
+        &self,
+        implementation: &str,
+        methods: &[RpcDef],
+    ) -> Result<Vec<(String, String)>> {
+        Ok(VersionSpecificClientTraitGenerator::new(self.version.clone(), implementation)
+            .generate(methods))
+    }
+
+    fn supports_version(&self, version: &ProtocolVersion) -> bool {
+        // electrs negotiates protocol 1.4 from v0.9 on
+        version.major == 0 && version.minor >= 9
+    }
+
+    fn implementation(&self) -> &'static str { "electrs" }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version() -> ProtocolVersion {
+        ProtocolVersion::from_string_with_protocol("v0.10.10", Some("electrs".to_string()))
+            .expect("valid version")
+    }
+
+    #[test]
+    fn test_supports_version() {
+        let generator = <ElectrsVersionedGenerator as VersionedTypeGenerator>::from_ir(
+            version(),
+            &ProtocolIR::new(vec![]),
+        )
+        .expect("generator");
+        assert!(generator.supports_version(&version()));
+        assert!(!generator
+            .supports_version(&ProtocolVersion::from_string("v0.8.12").expect("valid version")));
+        assert_eq!(generator.implementation(), "electrs");
+    }
+}
@@ -0,0 +1,40 @@
+//! Esplora version-specific type generator
+
+use ir::{ProtocolIR, RpcDef};
+use types::ProtocolVersion;
+
+use super::version_specific_client_trait::VersionSpecificClientTraitGenerator;
+use super::version_specific_response_type::VersionSpecificResponseTypeGenerator;
+use super::versioned_generator::VersionedTypeGenerator;
+use crate::{CodeGenerator, Result};
+
+/// Esplora version-specific type generator
+///
+/// The Esplora IR describes REST endpoints only, so the RPC response types and client trait
+/// are empty; the typed API is the generated REST client.
+pub struct EsploraVersionedGenerator {
+    version: ProtocolVersion,
+}
+
+impl VersionedTypeGenerator for EsploraVersionedGenerator {
+    fn from_ir(version: ProtocolVersion, _ir: &ProtocolIR) -> Result<Self> { Ok(Self { version }) }
+
+    fn generate_response_types(&self, methods: &[RpcDef]) -> Result<Vec<(String, String)>> {
+        VersionSpecificResponseTypeGenerator::new(self.version.clone(), "esplora".to_string())
+            .generate(methods)
+    }
+
+    fn generate_client_trait(
+        &self,
+        implementation: &str,
+        methods: &[RpcDef],
+    ) -> Result<Vec<(String, String)>> {
+        Ok(VersionSpecificClientTraitGenerator::new(self.version.clone(), implementation)
+            .generate(methods))
+    }
+
+    // The API is unversioned; endpoints carry their own `x-version-added` bounds
+    fn supports_version(&self, _version: &ProtocolVersion) -> bool { true }
+
+    fn implementation(&self) -> &'static str { "esplora" }
+}
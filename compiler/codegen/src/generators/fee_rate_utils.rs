@@ -114,7 +114,7 @@ where
     I: IntoIterator<Item = &'a RpcDef>,
 {
     methods.into_iter().any(|m| {
-        m.params.iter().any(|p| map_param_to_ty(p, adapter).map_or(false, |ty| ty.contains(needle)))
+        m.params.iter().any(|p| map_param_to_ty(p, adapter).is_some_and(|ty| ty.contains(needle)))
     })
 }
 
@@ -0,0 +1,74 @@
+//! LND version-specific type generator
+
+use ir::{ProtocolIR, RpcDef};
+use types::ProtocolVersion;
+
+use super::version_specific_client_trait::VersionSpecificClientTraitGenerator;
+use super::version_specific_response_type::VersionSpecificResponseTypeGenerator;
+use super::versioned_generator::VersionedTypeGenerator;
+use crate::{CodeGenerator, Result};
+
+/// LND version-specific type generator
+///
+/// The protos carry no per-release markers, so every method in the IR is generated as-is
+/// for the snapshot's release.
+pub struct LndVersionedGenerator {
+    version: ProtocolVersion,
+}
+
+impl VersionedTypeGenerator for LndVersionedGenerator {
+    fn from_ir(version: ProtocolVersion, _ir: &ProtocolIR) -> Result<Self> { Ok(Self { version }) }
+
+    fn generate_response_types(&self, methods: &[RpcDef]) -> Result<Vec<(String, String)>> {
+        VersionSpecificResponseTypeGenerator::new(self.version.clone(), "lnd".to_string())
+            .generate(methods)
+    }
+
+    fn generate_client_trait(
+        &self,
+        implementation: &str,
+        methods: &[RpcDef],
+    ) -> Result<Vec<(String, String)>> {
+        Ok(VersionSpecificClientTraitGenerator::new(self.version.clone(), implementation)
+            .generate(methods))
+    }
+
+    fn supports_version(&self, version: &ProtocolVersion) -> bool {
+        // The REST bindings in the fixture protos match the v0.15+ gateway routes
+        version.major == 0 && version.minor >= 15
+    }
+
+    fn implementation(&self) -> &'static str { "lnd" }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version() -> ProtocolVersion {
+        ProtocolVersion::from_string_with_protocol("v0.19.3", Some("lnd".to_string()))
+            .expect("valid version")
+    }
+
+    #[test]
+    fn test_supports_version() {
+        let generator = <LndVersionedGenerator as VersionedTypeGenerator>::from_ir(
+            version(),
+            &ProtocolIR::new(vec![]),
+        )
+        .expect("generator");
+        assert!(generator.supports_version(&version()));
+        assert!(!generator
+            .supports_version(&ProtocolVersion::from_string("v25.09").expect("valid version")));
+        assert_eq!(generator.implementation(), "lnd");
+    }
+
+    #[test]
+    fn test_generate_client_trait() {
+        let generator = LndVersionedGenerator { version: version() };
+        let result = generator.generate_client_trait("lnd", &[]).expect("generation succeeds");
+        let filenames: Vec<_> = result.iter().map(|(name, _)| name.as_str()).collect();
+        assert!(filenames.contains(&"client.rs"));
+        assert!(filenames.contains(&"mod.rs"));
+    }
+}
@@ -16,6 +16,13 @@ pub use node_manager::NodeManagerGenerator;
 /// Shared helpers for generator modules
 pub mod fee_rate_utils;
 
+/// REST client generator
+pub mod rest_client;
+pub use rest_client::RestClientGenerator;
+/// ZMQ subscriber generator
+pub mod zmq_subscriber;
+pub use zmq_subscriber::ZmqSubscriberGenerator;
+
 pub mod test_node;
 
 /// Version-specific response type generator
@@ -30,6 +37,10 @@ pub use version_transitions::VersionTransitionRegistry;
 pub mod version_specific_client_trait;
 pub use version_specific_client_trait::VersionSpecificClientTraitGenerator;
 
+/// Runtime version negotiation generator for multi-version crates
+pub mod version_negotiation;
+pub use version_negotiation::VersionNegotiationGenerator;
+
 /// Version-specific generator trait for extensible implementation support
 pub mod versioned_generator;
 pub use versioned_generator::VersionedTypeGenerator;
@@ -38,6 +49,28 @@ pub use versioned_generator::VersionedTypeGenerator;
 pub mod bitcoin_core_versioned;
 pub use bitcoin_core_versioned::BitcoinCoreVersionedGenerator;
 
+/// Bitcoin Knots version-specific generator
+pub mod bitcoin_knots_versioned;
+pub use bitcoin_knots_versioned::BitcoinKnotsVersionedGenerator;
+
+/// btcd version-specific generator
+pub mod btcd_versioned;
+pub use btcd_versioned::BtcdVersionedGenerator;
+
+/// Core Lightning version-specific generator
+pub mod core_lightning_versioned;
+pub use core_lightning_versioned::CoreLightningVersionedGenerator;
+
+/// LND version-specific generator
+pub mod lnd_versioned;
+pub use lnd_versioned::LndVersionedGenerator;
+/// electrs version-specific type generator
+pub mod electrs_versioned;
+pub use electrs_versioned::ElectrsVersionedGenerator;
+/// Esplora version-specific type generator
+pub mod esplora_versioned;
+pub use esplora_versioned::EsploraVersionedGenerator;
+
 /// Registry for version-specific generators
 pub mod versioned_registry;
 pub use versioned_registry::VersionedGeneratorRegistry;
@@ -25,6 +25,10 @@ impl CodeGenerator for NodeManagerGenerator {
         let metadata = self.implementation.node_metadata();
         let node_manager_name = self.implementation.node_manager_name();
         let display_name = self.implementation.display_name();
+        // The test config comes from the built-in base's templates, so its executable path
+        // field is named after the base's executable
+        let exe_path_field =
+            format!("{}_path", self.implementation.builtin_base().executable_name());
 
         let mut code = String::new();
 
@@ -43,7 +47,7 @@ impl CodeGenerator for NodeManagerGenerator {
         // Generate implementation
         generate_node_manager_struct(&mut code, node_manager_name);
         generate_node_manager_impl(&mut code, node_manager_name);
-        generate_trait_impl(&mut code, node_manager_name, &metadata);
+        generate_trait_impl(&mut code, node_manager_name, &metadata, &exe_path_field);
 
         vec![("node_manager.rs".to_string(), code)]
     }
@@ -277,6 +281,7 @@ fn generate_trait_impl(
     code: &mut String,
     node_manager_name: &str,
     metadata: &types::node_metadata::NodeMetadata,
+    exe_path_field: &str,
 ) {
     writeln!(
         code,
@@ -288,7 +293,7 @@ impl NodeManager for {} {{"#,
     .expect("Failed to write trait impl start");
 
     // Generate start method
-    generate_start_method(code, metadata);
+    generate_start_method(code, metadata, exe_path_field);
 
     // Generate stop method
     generate_stop_method(code, metadata);
@@ -325,7 +330,11 @@ impl NodeManager for {} {{"#,
     .expect("Failed to write trait impl end");
 }
 
-fn generate_start_method(code: &mut String, metadata: &types::node_metadata::NodeMetadata) {
+fn generate_start_method(
+    code: &mut String,
+    metadata: &types::node_metadata::NodeMetadata,
+    exe_path_field: &str,
+) {
     writeln!(
         code,
         r#"
@@ -338,7 +347,7 @@ fn generate_start_method(code: &mut String, metadata: &types::node_metadata::Nod
     )
     .expect("Failed to write start method start");
 
-    generate_http_start_logic(code, metadata);
+    generate_http_start_logic(code, metadata, exe_path_field);
 
     writeln!(
         code,
@@ -348,34 +357,53 @@ fn generate_start_method(code: &mut String, metadata: &types::node_metadata::Nod
     .expect("Failed to write start method end");
 }
 
-fn generate_http_start_logic(code: &mut String, metadata: &types::node_metadata::NodeMetadata) {
+/// Value arguments in the order they are passed to the node, with the generated
+/// expression each template is formatted with.
+const VALUE_ARG_SOURCES: [(&str, &str); 6] = [
+    ("chain", "chain_str"),
+    ("datadir", "datadir.display()"),
+    ("rpc_port", "self.rpc_port"),
+    ("rpc_bind", "self.rpc_port"),
+    ("rpc_user", "self.config.rpc_username"),
+    ("rpc_password", "self.config.rpc_password"),
+];
+
+/// Render the `let` bindings and the `args` vector from the implementation's CLI templates.
+fn render_cli_args(cli_args: &types::node_metadata::CliArgs) -> (String, String) {
+    let mut bindings = String::new();
+    let mut entries = Vec::new();
+    for (name, source) in VALUE_ARG_SOURCES {
+        if let Some(template) = cli_args.value_args.get(name) {
+            bindings.push_str(&format!(
+                "        let {}_arg = format!({:?}, {});\n",
+                name, template, source
+            ));
+            entries.push(format!("            &{}_arg,", name));
+        }
+    }
+    for arg in &cli_args.static_args {
+        entries.push(format!("            {:?},", arg));
+    }
+    (bindings, entries.join("\n"))
+}
+
+fn generate_http_start_logic(
+    code: &mut String,
+    metadata: &types::node_metadata::NodeMetadata,
+    exe_path_field: &str,
+) {
+    let (arg_bindings, arg_entries) = render_cli_args(&metadata.cli_args);
     writeln!(
         code,
         r##"
         let datadir = self._datadir.as_ref().expect("datadir is set at construction").path();
-        let exe = self.config.bitcoind_path.as_deref().unwrap_or_else(|| std::path::Path::new("{}"));
+        let exe = self.config.{}.as_deref().unwrap_or_else(|| std::path::Path::new("{}"));
         let mut cmd = Command::new(exe);
 
         let chain_str = self.config.as_chain_str().map_err(|_| TransportError::Rpc("Unsupported network".into()))?;
-        let chain = format!("-chain={{}}", chain_str);
-        let data_dir = format!("-datadir={{}}", datadir.display());
-        let rpc_port = format!("-rpcport={{}}", self.rpc_port);
-        let rpc_bind = format!("-rpcbind=127.0.0.1:{{}}", self.rpc_port);
-        let rpc_user = format!("-rpcuser={{}}", self.config.rpc_username);
-        let rpc_password = format!("-rpcpassword={{}}", self.config.rpc_password);
-
+{}
         let mut args = vec![
-            &chain,
-            "-listen=0",
-            &data_dir,
-            &rpc_port,
-            &rpc_bind,
-            "-rpcallowip=127.0.0.1",
-            "-fallbackfee=0.0002",
-            "-server=1",
-            "-prune=1",
-            &rpc_user,
-            &rpc_password,
+{}
         ];
 
         for arg in &self.config.extra_args {{
@@ -418,10 +446,7 @@ fn generate_http_start_logic(code: &mut String, metadata: &types::node_metadata:
         tokio::time::sleep(Duration::from_millis(150)).await;
 
         // Create transport for RPC health check
-        let transport = DefaultTransport::new(
-            format!("http://127.0.0.1:{{}}/", self.rpc_port),
-            Some((self.config.rpc_username.clone(), self.config.rpc_password.clone())),
-        );
+        let transport = {};
 
         // Wait for node to be ready
         let deadline = Instant::now() + Duration::from_secs(10);
@@ -465,10 +490,14 @@ fn generate_http_start_logic(code: &mut String, metadata: &types::node_metadata:
         }};
         error!("{{}}", error);
         return Err(TransportError::Rpc(error));"##,
+        exe_path_field,
         metadata.executable,
+        arg_bindings,
+        arg_entries,
         metadata.executable,
         metadata.executable,
         metadata.executable,
+        transport_expr(metadata),
         metadata.executable,
         metadata.readiness_method,
         metadata.executable
@@ -509,10 +538,7 @@ fn generate_create_transport_method(
         code,
         r#"
     async fn create_transport(&self) -> Result<std::sync::Arc<crate::transport::DefaultTransport>, TransportError> {{
-        // Create HTTP transport for Bitcoin Core
-        let rpc_url = format!("http://127.0.0.1:{{}}", self.rpc_port());
-        let auth = Some((self.rpc_username().to_string(), self.rpc_password().to_string()));
-        let transport = Arc::new(DefaultTransport::new(rpc_url, auth));
+{}        let transport = Arc::new({});
 
         let mut retries = 0;
 
@@ -539,7 +565,90 @@ fn generate_create_transport_method(
 
         Ok(transport)
     }}"#,
+        create_transport_prelude(metadata),
+        transport_expr(metadata),
         metadata.readiness_method
     )
     .expect("Failed to write create_transport method");
 }
+
+/// Expression constructing the node's `DefaultTransport`. Unix-socket nodes are reached
+/// through `socket_path_pattern`, REST nodes authenticate with the macaroon at
+/// `macaroon_path_pattern` and HTTPS nodes are trusted through the certificate at
+/// `tls_cert_path_pattern` (all with `datadir` and `chain_str` in scope); TCP and REST GET
+/// nodes are reached on `self.rpc_port` without auth, and everything else over HTTP on
+/// `self.rpc_port` with basic auth.
+fn transport_expr(metadata: &types::node_metadata::NodeMetadata) -> String {
+    match (metadata.transport.as_str(), metadata.socket_path_pattern.as_deref()) {
+        ("unix", Some(pattern)) => format!("DefaultTransport::new({})", path_pattern_expr(pattern)),
+        ("rest_macaroon", _) => {
+            let mut expr = "DefaultTransport::new(format!(\"http://127.0.0.1:{}\", self.rpc_port), None)".to_string();
+            if let Some(pattern) = metadata.macaroon_path_pattern.as_deref() {
+                expr.push_str(&format!("\n            .with_macaroon_path({})", path_pattern_expr(pattern)));
+            }
+            expr
+        }
+        ("https", _) => {
+            let mut expr = "DefaultTransport::new(\n            format!(\"https://127.0.0.1:{}/\", self.rpc_port),\n            Some((self.config.rpc_username.clone(), self.config.rpc_password.clone())),\n        )".to_string();
+            if let Some(pattern) = metadata.tls_cert_path_pattern.as_deref() {
+                expr.push_str(&format!("\n        .with_cert_path({})", path_pattern_expr(pattern)));
+            }
+            expr
+        }
+        ("tcp", _) => "DefaultTransport::new(format!(\"127.0.0.1:{}\", self.rpc_port))".to_string(),
+        ("rest_get", _) => "DefaultTransport::new(format!(\"http://127.0.0.1:{}\", self.rpc_port))".to_string(),
+        _ => "DefaultTransport::new(\n            format!(\"http://127.0.0.1:{}/\", self.rpc_port),\n            Some((self.config.rpc_username.clone(), self.config.rpc_password.clone())),\n        )".to_string(),
+    }
+}
+
+/// `format!` expression for a path pattern with `{datadir}` / `{chain}` placeholders.
+fn path_pattern_expr(pattern: &str) -> String {
+    let mut format_str = String::new();
+    let mut sources = Vec::new();
+    let mut rest = pattern;
+    while let Some(start) = rest.find('{') {
+        format_str.push_str(&rest[..start]);
+        let end = start + rest[start..].find('}').expect("unterminated path placeholder");
+        sources.push(match &rest[start + 1..end] {
+            "datadir" => "datadir.display()",
+            "chain" => "chain_str",
+            other => panic!("unknown path placeholder '{{{}}}'", other),
+        });
+        format_str.push_str("{}");
+        rest = &rest[end + 1..];
+    }
+    format_str.push_str(rest);
+    format!("format!({:?}, {})", format_str, sources.join(", "))
+}
+
+/// Bindings `create_transport` needs before calling [`transport_expr`].
+fn create_transport_prelude(metadata: &types::node_metadata::NodeMetadata) -> String {
+    let prelude = match metadata.transport.as_str() {
+        "unix" =>
+            r#"        // Connect to the node's JSON-RPC unix socket inside its data directory
+        let datadir = self._datadir.as_ref().expect("datadir is set at construction").path();
+        let chain_str = self.config.as_chain_str().map_err(|_| TransportError::Rpc("Unsupported network".into()))?;
+"#,
+        "rest_macaroon" =>
+            r#"        // Create REST transport authenticated with the macaroon the node writes to its data directory
+        let datadir = self._datadir.as_ref().expect("datadir is set at construction").path();
+        let chain_str = self.config.as_chain_str().map_err(|_| TransportError::Rpc("Unsupported network".into()))?;
+"#,
+        // Only the placeholders the certificate pattern uses are bound
+        "https" => {
+            let pattern = metadata.tls_cert_path_pattern.as_deref().unwrap_or_default();
+            let mut prelude = "        // Create HTTPS transport trusting the certificate the node writes to its data directory\n".to_string();
+            if pattern.contains("{datadir}") {
+                prelude.push_str("        let datadir = self._datadir.as_ref().expect(\"datadir is set at construction\").path();\n");
+            }
+            if pattern.contains("{chain}") {
+                prelude.push_str("        let chain_str = self.config.as_chain_str().map_err(|_| TransportError::Rpc(\"Unsupported network\".into()))?;\n");
+            }
+            return prelude;
+        }
+        "tcp" => "        // Connect to the node's line-delimited JSON-RPC port over TCP\n",
+        "rest_get" => "        // Create REST transport for the node's unauthenticated HTTP API\n",
+        _ => "        // Create HTTP transport for the node's RPC port\n",
+    };
+    prelude.to_string()
+}
@@ -0,0 +1,455 @@
+//! REST client generator
+//!
+//! Generates a typed client for a protocol's REST endpoints (`ProtocolDef::RestEndpoint`).
+//! JSON responses that reference an RPC result (`shared_result`) use the RPC response types
+//! from `crate::types`; endpoints with their own shape get structs emitted alongside the client.
+//! Their fields are typed through the same registry as the RPC response types, so hashes,
+//! txids and scripts come out as the `bitcoin` crate types the RPC client uses.
+
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::str::FromStr;
+
+use adapters::BitcoinCoreTypeRegistry;
+use ir::{RestEndpointDef, RestFormat, RestResultContainer, RpcDef, TypeDef, TypeKind};
+use types::Implementation;
+
+use crate::utils::{pascal_to_snake_case, sanitize_external_identifier};
+use crate::CodeGenerator;
+
+/// Generator for the typed REST client (`rest_client.rs`)
+pub struct RestClientGenerator {
+    implementation: String,
+    endpoints: Vec<RestEndpointDef>,
+}
+
+impl RestClientGenerator {
+    /// Create a REST client generator for the given implementation and endpoints
+    pub fn new(implementation: impl Into<String>, endpoints: Vec<RestEndpointDef>) -> Self {
+        Self { implementation: implementation.into(), endpoints }
+    }
+
+    /// Rust type for a path or query parameter, using the implementation's type adapter
+    fn param_rust_type(&self, param: &ir::ParamDef) -> String {
+        let protocol_type = param.param_type.protocol_type.as_deref().unwrap_or("string");
+        Implementation::from_str(&self.implementation)
+            .ok()
+            .and_then(|implementation| implementation.create_type_adapter().ok())
+            .map(|adapter| adapter.map_parameter_type_to_rust(protocol_type, &param.name))
+            .unwrap_or_else(|| "String".to_string())
+    }
+
+    /// Rust type of the JSON response, or `serde_json::Value` when the endpoint has no known shape
+    fn json_result_type(&self, endpoint: &RestEndpointDef, rpc_methods: &BTreeSet<&str>) -> String {
+        if let Some(result) = &endpoint.result {
+            return rest_field_type(result, None);
+        }
+        let Some(shared) = &endpoint.shared_result else {
+            return "serde_json::Value".to_string();
+        };
+        if !rpc_methods.contains(shared.method.as_str()) {
+            return "serde_json::Value".to_string();
+        }
+        let canonical =
+            crate::utils::canonical_from_adapter_method(&self.implementation, &shared.method, None)
+                .unwrap_or_else(|_| normalization::suggest_canonical_key(&shared.method));
+        let response = format!("crate::types::{}Response", canonical);
+        match shared.container {
+            RestResultContainer::Single => response,
+            RestResultContainer::Array => format!("Vec<{}>", response),
+            RestResultContainer::Map => format!("std::collections::BTreeMap<String, {}>", response),
+        }
+    }
+}
+
+impl CodeGenerator for RestClientGenerator {
+    fn generate(&self, methods: &[RpcDef]) -> Vec<(String, String)> {
+        let rpc_methods: BTreeSet<&str> = methods.iter().map(|m| m.name.as_str()).collect();
+        let mut code = String::new();
+
+        writeln!(
+            code,
+            "//! Typed client for the REST interface
+//!
+//! JSON responses reuse the RPC response types from `crate::types` where the layouts match.
+
+use serde::{{Deserialize, Serialize}};
+
+use crate::transport::core::TransportError;
+use crate::transport::rest::RestTransport;
+"
+        )
+        .expect("Failed to write REST client header");
+
+        let mut emitted = BTreeSet::new();
+        for endpoint in &self.endpoints {
+            if let Some(result) = &endpoint.result {
+                emit_result_structs(&mut code, result, &mut emitted);
+            }
+        }
+
+        writeln!(
+            code,
+            "/// Typed client for the REST interface
+#[derive(Debug, Clone)]
+pub struct RestClient {{
+    transport: RestTransport,
+}}
+
+impl RestClient {{
+    /// Create a REST client for the node at `base_url` (e.g. `http://127.0.0.1:8332`).
+    pub fn new(base_url: impl Into<String>) -> Self {{
+        Self {{ transport: RestTransport::new(base_url) }}
+    }}
+
+    /// Create a REST client over an existing transport.
+    pub fn with_transport(transport: RestTransport) -> Self {{
+        Self {{ transport }}
+    }}
+
+    /// The underlying REST transport.
+    pub fn transport(&self) -> &RestTransport {{
+        &self.transport
+    }}
+"
+        )
+        .expect("Failed to write REST client struct");
+
+        for endpoint in &self.endpoints {
+            for format in &endpoint.formats {
+                let ok_ty = match format {
+                    RestFormat::Bin => "Vec<u8>".to_string(),
+                    RestFormat::Hex => "String".to_string(),
+                    RestFormat::Json => self.json_result_type(endpoint, &rpc_methods),
+                    RestFormat::Text => endpoint
+                        .result
+                        .as_ref()
+                        .map(|result| rest_field_type(result, None))
+                        .unwrap_or_else(|| "String".to_string()),
+                };
+                emit_endpoint_method(&mut code, self, endpoint, *format, &ok_ty);
+            }
+        }
+
+        writeln!(code, "}}").expect("Failed to write REST client impl end");
+
+        vec![("rest_client.rs".to_string(), code)]
+    }
+}
+
+/// Emit one `async fn {endpoint}_{format}` method (`async fn {endpoint}` for bare paths)
+fn emit_endpoint_method(
+    code: &mut String,
+    generator: &RestClientGenerator,
+    endpoint: &RestEndpointDef,
+    format: RestFormat,
+    ok_ty: &str,
+) {
+    let mut args = vec!["&self".to_string()];
+    for param in &endpoint.params {
+        let ty = generator.param_rust_type(param);
+        let ty = match ty.strip_prefix("Vec<").and_then(|t| t.strip_suffix('>')) {
+            Some(elem) => format!("&[{}]", elem),
+            None if ty == "String" => "&str".to_string(),
+            None => ty,
+        };
+        args.push(format!("{}: {}", sanitize_external_identifier(&param.name), ty));
+    }
+    for param in &endpoint.query_params {
+        let ty = generator.param_rust_type(param);
+        let ty = if param.required { ty } else { format!("Option<{}>", ty) };
+        args.push(format!("{}: {}", sanitize_external_identifier(&param.name), ty));
+    }
+
+    let mut body = String::new();
+    let mut literal = String::new();
+    for segment in endpoint.path.split('/').filter(|s| !s.is_empty()) {
+        let Some(name) = segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) else {
+            literal.push('/');
+            literal.push_str(segment);
+            continue;
+        };
+        flush_literal(&mut body, &mut literal);
+        let param = endpoint.params.iter().find(|p| p.name == name);
+        let ident = sanitize_external_identifier(name);
+        match param.map(|p| generator.param_rust_type(p)).as_deref() {
+            // Flag segments appear only when set (e.g. `/checkmempool`)
+            Some("bool") => writeln!(body, "    if {ident} {{ path.push_str(\"/{name}\"); }}"),
+            Some("Vec<bitcoin::OutPoint>") => writeln!(
+                body,
+                "    for outpoint in {ident} {{ path.push_str(&format!(\"/{{}}-{{}}\", outpoint.txid, outpoint.vout)); }}"
+            ),
+            Some(ty) if ty.starts_with("Vec<") =>
+                writeln!(body, "    for item in {ident} {{ path.push_str(&format!(\"/{{}}\", item)); }}"),
+            _ => writeln!(body, "    path.push_str(&format!(\"/{{}}\", {ident}));"),
+        }
+        .expect("Failed to write REST path segment");
+    }
+    if !endpoint.bare_path {
+        literal.push('.');
+        literal.push_str(format.extension());
+    }
+    flush_literal(&mut body, &mut literal);
+
+    if !endpoint.query_params.is_empty() {
+        writeln!(body, "    let mut query: Vec<String> = Vec::new();")
+            .expect("Failed to write REST query");
+        for param in &endpoint.query_params {
+            let ident = sanitize_external_identifier(&param.name);
+            let name = &param.name;
+            if param.required {
+                writeln!(body, "    query.push(format!(\"{name}={{}}\", {ident}));")
+            } else {
+                writeln!(
+                    body,
+                    "    if let Some(value) = {ident} {{ query.push(format!(\"{name}={{}}\", value)); }}"
+                )
+            }
+            .expect("Failed to write REST query param");
+        }
+        writeln!(
+            body,
+            "    if !query.is_empty() {{ path.push('?'); path.push_str(&query.join(\"&\")); }}"
+        )
+        .expect("Failed to write REST query string");
+    }
+
+    let fetch = match format {
+        RestFormat::Bin => "get_bytes",
+        RestFormat::Hex => "get_hex",
+        RestFormat::Json => "get_json",
+        RestFormat::Text => "get_text",
+    };
+    let (fn_name, path_doc) = if endpoint.bare_path {
+        (endpoint.name.clone(), endpoint.path.clone())
+    } else {
+        (
+            format!("{}_{}", endpoint.name, format.extension()),
+            format!("{}.{}", endpoint.path, format.extension()),
+        )
+    };
+    let description = endpoint.description.trim();
+
+    writeln!(
+        code,
+        "    /// {description}
+    ///
+    /// `GET {path_doc}`
+    pub async fn {fn_name}({args}) -> Result<{ok_ty}, TransportError> {{
+        let mut path = String::new();
+{body}        self.transport.{fetch}(&path).await
+    }}
+",
+        args = args.join(", "),
+    )
+    .expect("Failed to write REST endpoint method");
+}
+
+/// Append accumulated literal path text to the generated body
+fn flush_literal(body: &mut String, literal: &mut String) {
+    if !literal.is_empty() {
+        writeln!(body, "    path.push_str(\"{}\");", literal).expect("Failed to write REST path");
+        literal.clear();
+    }
+}
+
+/// Emit the struct for an endpoint-specific result type, nested types first
+///
+/// Object types shared between endpoints are emitted once, tracked by name in `emitted`.
+fn emit_result_structs(code: &mut String, type_def: &TypeDef, emitted: &mut BTreeSet<String>) {
+    if type_def.kind == TypeKind::Array {
+        if let Some(element) = type_def.array_element_type() {
+            emit_result_structs(code, element, emitted);
+        }
+        return;
+    }
+    let Some(fields) = type_def.fields.as_ref().filter(|fields| !fields.is_empty()) else { return };
+    if type_def.kind != TypeKind::Object || !emitted.insert(type_def.name.clone()) {
+        return;
+    }
+    for field in fields {
+        emit_result_structs(code, &field.field_type, emitted);
+    }
+
+    if !type_def.description.is_empty() {
+        writeln!(code, "/// {}", type_def.description).expect("Failed to write struct doc");
+    } else {
+        writeln!(code, "/// `{}` REST response object", type_def.name)
+            .expect("Failed to write struct doc");
+    }
+    writeln!(
+        code,
+        "#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]\npub struct {} {{",
+        type_def.name
+    )
+    .expect("Failed to write struct header");
+    for field in fields {
+        let Some(json_key) = field.key.json_key() else { continue };
+        let ident = sanitize_external_identifier(&pascal_to_snake_case(json_key));
+        let rust_type = rest_field_type(&field.field_type, Some(json_key));
+        if !field.description.is_empty() {
+            writeln!(code, "    /// {}", field.description).expect("Failed to write field doc");
+        }
+        if ident.trim_start_matches("r#") != json_key {
+            writeln!(code, "    #[serde(rename = \"{}\")]", json_key)
+                .expect("Failed to write serde rename");
+        }
+        if field.required {
+            writeln!(code, "    pub {}: {},", ident, rust_type)
+        } else {
+            writeln!(
+                code,
+                "    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n    pub {}: Option<{}>,",
+                ident, rust_type
+            )
+        }
+        .expect("Failed to write field");
+    }
+    writeln!(code, "}}\n").expect("Failed to write struct end");
+}
+
+/// Rust type for an endpoint-specific result or one of its fields
+///
+/// Scalars are mapped by the referenced schema name (`canonical_name`) or else the JSON key,
+/// like RPC result fields. `amount` values are BTC floats and stay `f64`; `satoshis` values
+/// are integer satoshi counts, which is how `bitcoin::Amount` (de)serializes.
+fn rest_field_type(type_def: &TypeDef, json_key: Option<&str>) -> String {
+    match type_def.kind {
+        // Objects without declared fields (e.g. maps keyed by confirmation target) stay untyped
+        TypeKind::Object if type_def.fields.as_deref().unwrap_or_default().is_empty() =>
+            "serde_json::Value".to_string(),
+        TypeKind::Object => type_def.name.clone(),
+        TypeKind::Array => type_def
+            .array_element_type()
+            .map(|elem| format!("Vec<{}>", rest_field_type(elem, None)))
+            .unwrap_or_else(|| "Vec<serde_json::Value>".to_string()),
+        _ => {
+            let protocol_type = type_def.protocol_type.as_deref().unwrap_or(type_def.name.as_str());
+            let name = type_def.canonical_name.as_deref().or(json_key).unwrap_or_default();
+            match protocol_type {
+                "amount" => "f64".to_string(),
+                "satoshis" => "bitcoin::Amount".to_string(),
+                _ => BitcoinCoreTypeRegistry::map(protocol_type, name).0.to_string(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ir::test_utils::{param, primitive_type, rest_endpoint, rpc};
+    use ir::{RestSharedResult, TypeDef};
+
+    use super::*;
+
+    fn hex_param(name: &str) -> ir::ParamDef {
+        param(name, primitive_type("hex", Some("hex".to_string())), true)
+    }
+
+    #[test]
+    fn shared_result_uses_rpc_response_type() {
+        let mut headers = rest_endpoint(
+            "headers",
+            "/rest/headers/{blockhash}",
+            vec![hex_param("blockhash")],
+            vec![RestFormat::Json],
+        );
+        headers.shared_result = Some(RestSharedResult {
+            method: "getblockheader".to_string(),
+            container: RestResultContainer::Array,
+        });
+        headers.query_params =
+            vec![param("count", primitive_type("number", Some("number".to_string())), false)];
+
+        let generator = RestClientGenerator::new("bitcoin_core", vec![headers]);
+        let files = generator.generate(&[rpc("getblockheader", vec![], None, "blockchain")]);
+        let code = &files[0].1;
+
+        assert_eq!(files[0].0, "rest_client.rs");
+        assert!(code.contains(
+            "pub async fn headers_json(&self, block_hash: bitcoin::BlockHash, count: Option<i64>) \
+             -> Result<Vec<crate::types::GetBlockHeaderResponse>, TransportError>"
+        ));
+        assert!(code.contains("path.push_str(\"/rest/headers\");"));
+        assert!(code.contains("path.push_str(\".json\");"));
+        assert!(code.contains("query.push(format!(\"count={}\", value));"));
+    }
+
+    #[test]
+    fn shared_result_without_rpc_falls_back_to_value() {
+        let mut tx =
+            rest_endpoint("tx", "/rest/tx/{txid}", vec![hex_param("txid")], vec![RestFormat::Json]);
+        tx.shared_result = Some(RestSharedResult {
+            method: "getrawtransaction".to_string(),
+            container: RestResultContainer::Single,
+        });
+
+        let files = RestClientGenerator::new("bitcoin_core", vec![tx]).generate(&[]);
+
+        assert!(files[0].1.contains("Result<serde_json::Value, TransportError>"));
+    }
+
+    #[test]
+    fn flag_and_outpoint_segments() {
+        let outpoints = TypeDef {
+            name: "array".to_string(),
+            kind: TypeKind::Array,
+            protocol_type: Some("array".to_string()),
+            ..TypeDef::default()
+        };
+        let getutxos = rest_endpoint(
+            "getutxos",
+            "/rest/getutxos/{checkmempool}/{outpoints}",
+            vec![
+                param(
+                    "checkmempool",
+                    primitive_type("boolean", Some("boolean".to_string())),
+                    false,
+                ),
+                param("outpoints", outpoints, true),
+            ],
+            vec![RestFormat::Bin, RestFormat::Hex],
+        );
+
+        let code = &RestClientGenerator::new("bitcoin_core", vec![getutxos]).generate(&[])[0].1;
+
+        assert!(code.contains(
+            "pub async fn getutxos_bin(&self, checkmempool: bool, outpoints: &[bitcoin::OutPoint]) \
+             -> Result<Vec<u8>, TransportError>"
+        ));
+        assert!(code.contains("if checkmempool { path.push_str(\"/checkmempool\"); }"));
+        assert!(code.contains("outpoint.txid, outpoint.vout"));
+        assert!(code.contains("self.transport.get_hex(&path).await"));
+    }
+
+    #[test]
+    fn bare_path_endpoints_use_registry_types() {
+        let mut tip_hash =
+            rest_endpoint("blocks_tip_hash", "/blocks/tip/hash", vec![], vec![RestFormat::Text]);
+        tip_hash.bare_path = true;
+        tip_hash.result = Some(TypeDef {
+            canonical_name: Some("BlockHash".to_string()),
+            ..primitive_type("string", Some("string".to_string()))
+        });
+        let mut fees =
+            rest_endpoint("fee_estimates", "/fee-estimates", vec![], vec![RestFormat::Json]);
+        fees.bare_path = true;
+        fees.result = Some(TypeDef {
+            name: "FeeEstimatesResponse".to_string(),
+            kind: TypeKind::Object,
+            protocol_type: Some("object".to_string()),
+            ..TypeDef::default()
+        });
+
+        let code = &RestClientGenerator::new("esplora", vec![tip_hash, fees]).generate(&[])[0].1;
+
+        assert!(code.contains(
+            "pub async fn blocks_tip_hash(&self) -> Result<bitcoin::BlockHash, TransportError>"
+        ));
+        assert!(code.contains("self.transport.get_text(&path).await"));
+        assert!(!code.contains("path.push_str(\".txt\");"));
+        // Objects without declared fields are not emitted as empty structs
+        assert!(code.contains("Result<serde_json::Value, TransportError>"));
+        assert!(!code.contains("pub struct FeeEstimatesResponse"));
+    }
+}
@@ -52,8 +52,10 @@ impl TestNodeGenerator {
 
     /// Generate params code using the same approach as versioned generators
     fn generate_params_code(&self, methods: &[RpcDef]) -> String {
-        let mut header =
-            String::from("//! Parameter structs for RPC method calls\nuse serde::Serialize;\n");
+        let mut header = String::from("//! Parameter structs for RPC method calls\n");
+        if methods.iter().any(|m| !m.params.is_empty()) {
+            header.push_str("use serde::Serialize;\n");
+        }
 
         // Get type adapter for mapping protocol types to Rust types
         let type_adapter = self.implementation.create_type_adapter().unwrap_or_else(|_| {
@@ -63,6 +65,9 @@ impl TestNodeGenerator {
             )
         });
 
+        // The serde helpers below live in this module
+        let params_mod = self.implementation.client_dir_name();
+
         // Check for custom types that need imports
         let uses_hash_or_height = methods
             .iter()
@@ -279,11 +284,11 @@ pub struct GetBlockTemplateRequest {
                 // FeeRate has no Serialize; use crate serde(with) helpers or bitcoin_units.
                 if base_ty == "FeeRate" {
                     let with_path = if p.name == "maxfeerate" {
-                        "crate::bitcoin_core_client::params::serde_fee_rate::maxfeerate_opt"
+                        format!("crate::{}::params::serde_fee_rate::maxfeerate_opt", params_mod)
                     } else if p.required {
-                        "bitcoin_units::fee_rate::serde::as_sat_per_vb_floor"
+                        "bitcoin_units::fee_rate::serde::as_sat_per_vb_floor".to_string()
                     } else {
-                        "bitcoin_units::fee_rate::serde::as_sat_per_vb_floor::opt"
+                        "bitcoin_units::fee_rate::serde::as_sat_per_vb_floor::opt".to_string()
                     };
                     writeln!(code, "    #[serde(with = \"{}\")]", with_path)
                         .expect("Failed to write serde attribute");
@@ -291,8 +296,12 @@ pub struct GetBlockTemplateRequest {
                 // sendmany "amounts": HashMap<Address, Amount> serializes values as BTC in JSON.
                 if p.name == "amounts" && base_ty.contains("HashMap") && base_ty.contains("Amount")
                 {
-                    writeln!(code, "    #[serde(with = \"crate::bitcoin_core_client::params::serde_amounts_map\")]")
-                        .expect("Failed to write serde attribute");
+                    writeln!(
+                        code,
+                        "    #[serde(with = \"crate::{}::params::serde_amounts_map\")]",
+                        params_mod
+                    )
+                    .expect("Failed to write serde attribute");
                 }
                 // Preserve RPC JSON key when Rust field name differs (e.g. minconf -> min_conf)
                 if field != p.name {
@@ -0,0 +1,202 @@
+//! Runtime version negotiation generator
+//!
+//! Emits `negotiation.rs` for multi-version crates: a `VersionedClient` enum with one variant
+//! per version module, a `connect_auto()` constructor that binds a transport to the module
+//! matching the node's `getnetworkinfo` version, and a method availability table so calls to
+//! methods the node's version lacks fail with a typed `UnsupportedOnVersion` error.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use ir::RpcDef;
+use types::Implementation;
+
+use super::versioned_registry::VersionModule;
+use crate::CodeGenerator;
+
+/// Generator for the version negotiation module of a multi-version crate
+pub struct VersionNegotiationGenerator<'a> {
+    implementation: Implementation,
+    modules: &'a [VersionModule],
+}
+
+impl<'a> VersionNegotiationGenerator<'a> {
+    /// Create a generator for the version modules of a multi-version crate
+    pub fn new(implementation: Implementation, modules: &'a [VersionModule]) -> Self {
+        Self { implementation, modules }
+    }
+
+    /// `cfg` attribute gating a version module's items
+    fn cfg(module: &VersionModule) -> String { format!("#[cfg(feature = \"{}\")]", module.feature) }
+
+    /// Enum variant name of a version module (`v27` -> `V27`)
+    fn variant(module: &VersionModule) -> String { module.feature.to_uppercase() }
+
+    /// Version modules, oldest first
+    fn sorted_modules(&self) -> Vec<&VersionModule> {
+        let mut modules: Vec<&VersionModule> = self.modules.iter().collect();
+        modules.sort_by(|a, b| a.version.cmp(&b.version));
+        modules
+    }
+}
+
+impl CodeGenerator for VersionNegotiationGenerator<'_> {
+    fn generate(&self, _methods: &[RpcDef]) -> Vec<(String, String)> {
+        let modules = self.sorted_modules();
+
+        let mut variants = String::new();
+        let mut version_arms = String::new();
+        let mut module_arms = String::new();
+        let mut transport_arms = String::new();
+        for module in &modules {
+            let (cfg, variant) = (Self::cfg(module), Self::variant(module));
+            writeln!(
+                variants,
+                "    /// Bound to [`crate::client_trait::{feature}`]\n    {cfg}\n    {variant} {{\n        \
+                 /// The node's version\n        version: ProtocolVersion,\n        \
+                 /// The node's transport\n        transport: T,\n    }},",
+                feature = module.feature
+            )
+            .expect("write to String");
+            writeln!(
+                version_arms,
+                "            {cfg}\n            Self::{variant} {{ version, .. }} => *version,"
+            )
+            .expect("write to String");
+            writeln!(
+                module_arms,
+                "            {cfg}\n            Self::{variant} {{ .. }} => {},",
+                version_expr(&module.version)
+            )
+            .expect("write to String");
+            writeln!(
+                transport_arms,
+                "            {cfg}\n            Self::{variant} {{ transport, .. }} => transport,"
+            )
+            .expect("write to String");
+        }
+
+        // Newest first, so a node binds to the newest module not newer than itself
+        let mut bind = String::new();
+        for module in modules.iter().rev() {
+            writeln!(
+                bind,
+                "        {cfg}\n        if version >= {module_version} {{\n            \
+                 return Ok(Self::{variant} {{ version, transport }});\n        }}",
+                cfg = Self::cfg(module),
+                module_version = version_expr(&module.version),
+                variant = Self::variant(module)
+            )
+            .expect("write to String");
+        }
+
+        let mut availability: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+        for module in &modules {
+            for method in &module.methods {
+                availability
+                    .entry(method.as_str())
+                    .or_default()
+                    .push(version_expr(&module.version));
+            }
+        }
+        let mut method_availability = String::new();
+        for (method, versions) in &availability {
+            writeln!(method_availability, "    (\"{}\", &[{}]),", method, versions.join(", "))
+                .expect("write to String");
+        }
+
+        let features: Vec<&str> = modules.iter().map(|m| m.feature.as_str()).collect();
+        let content = include_str!("../../templates/bitcoin_core/negotiation.rs")
+            .replace("{{PROTOCOL_DISPLAY}}", self.implementation.display_name())
+            .replace("{{CLIENT_TRAIT}}", self.implementation.client_prefix())
+            .replace("{{FEATURES}}", &features.join(", "))
+            .replace("{{FIRST_FEATURE}}", features.first().copied().unwrap_or_default())
+            .replace("{{METHOD_AVAILABILITY}}", &method_availability)
+            .replace("{{VARIANTS}}", &variants)
+            .replace("{{BIND}}", &bind)
+            .replace("{{VERSION_ARMS}}", &version_arms)
+            .replace("{{MODULE_ARMS}}", &module_arms)
+            .replace("{{TRANSPORT_ARMS}}", &transport_arms);
+
+        vec![("negotiation.rs".to_string(), content)]
+    }
+}
+
+/// `ProtocolVersion` constructor expression for `version` in the generated module
+fn version_expr(version: &types::ProtocolVersion) -> String {
+    format!("ProtocolVersion::new({}, {}, {})", version.major, version.minor, version.patch)
+}
+
+#[cfg(test)]
+mod tests {
+    use types::ProtocolVersion;
+
+    use super::*;
+
+    fn module(version: &str, methods: &[&str]) -> VersionModule {
+        let version = ProtocolVersion::from_string(version).expect("valid version");
+        VersionModule {
+            feature: match version.minor {
+                0 => format!("v{}", version.major),
+                minor => format!("v{}_{}", version.major, minor),
+            },
+            version,
+            methods: methods.iter().map(|m| m.to_string()).collect(),
+            response_types: None,
+            client_trait: vec![],
+        }
+    }
+
+    #[test]
+    fn binds_newest_first_and_tracks_availability() {
+        let modules = vec![
+            module("30.2", &["getblockcount", "getdescriptoractivity"]),
+            module("28.0", &["getblockcount"]),
+        ];
+        let files =
+            VersionNegotiationGenerator::new(Implementation::BitcoinCore, &modules).generate(&[]);
+        let content = &files[0].1;
+
+        assert!(!content.contains("{{"));
+        let v30 = content.find("if version >= ProtocolVersion::new(30, 2, 0)").expect("v30 bind");
+        let v28 = content.find("if version >= ProtocolVersion::new(28, 0, 0)").expect("v28 bind");
+        assert!(v30 < v28);
+        assert!(content.contains(
+            "(\"getblockcount\", &[ProtocolVersion::new(28, 0, 0), ProtocolVersion::new(30, 2, 0)]),\n    \
+             (\"getdescriptoractivity\", &[ProtocolVersion::new(30, 2, 0)]),"
+        ));
+        assert!(content.contains("#[cfg(feature = \"v28\")]\n    V28 {"));
+    }
+
+    #[test]
+    fn keys_minor_releases_apart() {
+        let modules = vec![
+            module("29.1", &["getblockcount", "getnewfeature"]),
+            module("29.0", &["getblockcount"]),
+        ];
+        let files =
+            VersionNegotiationGenerator::new(Implementation::BitcoinCore, &modules).generate(&[]);
+        let content = &files[0].1;
+
+        let v29_1 =
+            content.find("if version >= ProtocolVersion::new(29, 1, 0)").expect("v29.1 bind");
+        let v29 = content.find("if version >= ProtocolVersion::new(29, 0, 0)").expect("v29.0 bind");
+        assert!(v29_1 < v29);
+        assert!(content.contains("(\"getnewfeature\", &[ProtocolVersion::new(29, 1, 0)]),"));
+        assert!(content.contains("#[cfg(feature = \"v29_1\")]\n    V29_1 {"));
+    }
+
+    #[test]
+    fn trait_methods_carry_the_negotiation_error() {
+        let modules = vec![module("30.0", &["getblockcount"])];
+        let files =
+            VersionNegotiationGenerator::new(Implementation::BitcoinCore, &modules).generate(&[]);
+        let content = &files[0].1;
+
+        assert!(content.contains("impl From<NegotiationError> for TransportError"));
+        assert!(content.contains("error => TransportError::Negotiation(Box::new(error)),"));
+        assert!(content.contains("TransportError::Negotiation(source) => source.downcast_ref(),"));
+        assert_eq!(content.matches("self.ensure_supported(method)?;").count(), 3);
+        assert!(!content.contains("TransportError::Rpc(e.to_string())"));
+    }
+}
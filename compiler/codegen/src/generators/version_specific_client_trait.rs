@@ -9,7 +9,7 @@ use ir::{ParamDef, RpcDef};
 use types::type_adapter::TypeAdapter;
 use types::{Implementation, ProtocolVersion, TypeRegistry};
 
-use super::doc_comment::format_doc_comment;
+use super::doc_comment::{deprecated_attribute, format_doc_comment};
 use super::fee_rate_utils::{methods_use_amounts_map, methods_use_get_block_template_request};
 use crate::generators::version_specific_response_type::record_external_symbol_usage;
 use crate::utils::{
@@ -38,10 +38,10 @@ impl VersionSpecificClientTraitGenerator {
     }
 
     /// Params visible for the generator's target version. Delegates to the openrpc adapter
-    /// for Bitcoin Core; other implementations use all params as-is.
+    /// for Bitcoin Core and Knots; other implementations use all params as-is.
     fn params_for_version(&self, rpc: &RpcDef) -> Vec<ParamDef> {
         let target = self.version.as_str();
-        if self.protocol.as_str() == "bitcoin_core" {
+        if self.protocol.is_bitcoin_core_family() {
             adapters::bitcoin_core::openrpc::filter_params_for_version(&rpc.params, target)
         } else {
             rpc.params.clone()
@@ -55,19 +55,20 @@ impl CodeGenerator for VersionSpecificClientTraitGenerator {
         let available_methods: Vec<&RpcDef> = methods.iter().collect();
 
         // render client_trait.rs
-        let template = match self.protocol.as_str() {
-            "bitcoin_core" => {
+        let template = match self.protocol.builtin_base().as_str() {
+            // btcd, lightningd and Electrum servers accept Core-style positional JSON-RPC
+            // params; lnd's REST transport maps the same positional params onto each route's
+            // fields; Esplora's REST transport turns them into path segments
+            "bitcoin_core" | "bitcoin_knots" | "btcd" | "core_lightning" | "lnd" | "electrs"
+            | "esplora" => {
                 include_str!("../../templates/bitcoin_core/client_trait.rs")
             }
             _ => panic!("Unsupported protocol: {}", self.protocol),
         };
-        let client_trait = self.render_client_trait(template, &available_methods);
+        let client_name = self.protocol.client_prefix();
+        let client_trait = self.render_client_trait(template, client_name, &available_methods);
 
         // render mod.rs that re-exports the trait
-        let client_name = match self.protocol.as_str() {
-            "bitcoin_core" => "BitcoinClient",
-            _ => panic!("Unsupported protocol: {}", self.protocol),
-        };
         let exported_trait_name = client_name.to_string();
         let protocol_display = self.protocol.display_name();
         let version_short = self.version.short();
@@ -90,9 +91,17 @@ impl VersionSpecificClientTraitGenerator {
     // Removed filter_methods_for_version as it's no longer needed with RpcDef
 
     /// Render the client trait with version-specific information
-    fn render_client_trait(&self, template: &str, methods: &[&RpcDef]) -> String {
+    fn render_client_trait(
+        &self,
+        template: &str,
+        client_name: &str,
+        methods: &[&RpcDef],
+    ) -> String {
         let mut out = template.to_owned();
 
+        out = out.replace("{{CLIENT_TRAIT}}", client_name);
+        out = out.replace("{{PROTOCOL_DISPLAY}}", self.protocol.display_name());
+
         let version_str = self.version.short();
         out = out.replace("{{VERSION}}", &version_str);
 
@@ -120,8 +129,12 @@ impl VersionSpecificClientTraitGenerator {
 
     /// Build imports for the generated trait
     fn build_imports(&self, methods: &[&RpcDef]) -> String {
-        let mut imports =
-            vec!["use crate::types::*".to_string(), "use std::future::Future".to_string()];
+        let mut imports = Vec::new();
+        // REST-only protocols have no RPC methods, so nothing refers to the response types
+        if !methods.is_empty() {
+            imports.push("use crate::types::*".to_string());
+        }
+        imports.push("use std::future::Future".to_string());
 
         // Check for custom types that need imports, using version-filtered params.
         let uses_hash_or_height = methods.iter().any(|m| {
@@ -136,18 +149,17 @@ impl VersionSpecificClientTraitGenerator {
 
         let adapter = self.get_adapter();
         let uses_fee_rate = crate::generators::fee_rate_utils::methods_use_fee_rate(
-            methods.iter().map(|m| *m),
+            methods.iter().copied(),
             adapter.as_ref(),
         );
         let uses_sendall_recipient =
             crate::generators::fee_rate_utils::methods_use_sendall_recipient(
-                methods.iter().map(|m| *m),
+                methods.iter().copied(),
                 adapter.as_ref(),
             );
         let uses_get_block_template_request =
-            methods_use_get_block_template_request(methods.iter().map(|m| *m), adapter.as_ref());
-        let uses_amounts_map =
-            methods_use_amounts_map(methods.iter().map(|m| *m), adapter.as_ref());
+            methods_use_get_block_template_request(methods.iter().copied(), adapter.as_ref());
+        let uses_amounts_map = methods_use_amounts_map(methods.iter().copied(), adapter.as_ref());
 
         // Add necessary imports
         let params_mod = self.protocol.client_dir_name();
@@ -261,6 +273,26 @@ impl VersionSpecificClientTraitGenerator {
             writeln!(buf, "    /// Requires wallet private keys to be available (e.g. unlocked).")
                 .expect("write");
         }
+        let deprecated_params: Vec<_> =
+            params.iter().filter_map(|p| Some((&p.name, p.deprecated.as_ref()?))).collect();
+        if !deprecated_params.is_empty() {
+            writeln!(buf, "    ///").expect("write");
+            for (name, deprecation) in deprecated_params {
+                let mut line = format!("    /// `{}` is deprecated", name);
+                if let Some(since) = &deprecation.since {
+                    line.push_str(&format!(" since {}", since));
+                }
+                match deprecation.note() {
+                    Some(note) => line.push_str(&format!(": {}.", note)),
+                    None => line.push('.'),
+                }
+                writeln!(buf, "{}", line).expect("write");
+            }
+        }
+        // Only the trait declaration carries the attribute; on impl items it has no effect
+        if let Some(deprecation) = &rpc.deprecated {
+            writeln!(buf, "    {}", deprecated_attribute(deprecation)).expect("write");
+        }
 
         // Generate method signature (trait definition)
         writeln!(
@@ -355,7 +387,8 @@ impl VersionSpecificClientTraitGenerator {
         if !rpc.params.is_empty() {
             let adapter = self.get_adapter();
             // Create params array from individual parameters
-            // Optional parameters (Option<T>) should only be included if they're Some(...)
+            // Omitted optional parameters hold their position with `null` (which the nodes treat
+            // as "not given") so later arguments are not shifted; trailing nulls are dropped.
             // Use rpc_params as variable name to avoid conflict with parameter named "params"
             writeln!(buf, "        let mut rpc_params = vec![];")
                 .expect("Failed to write params array initialization");
@@ -409,6 +442,10 @@ impl VersionSpecificClientTraitGenerator {
                         .expect("Failed to write optional parameter check");
                     writeln!(buf, "            rpc_params.push({});", val_expr)
                         .expect("Failed to write optional parameter push");
+                    writeln!(buf, "        }} else {{")
+                        .expect("Failed to write optional parameter else");
+                    writeln!(buf, "            rpc_params.push(serde_json::Value::Null);")
+                        .expect("Failed to write optional parameter placeholder");
                     writeln!(buf, "        }}")
                         .expect("Failed to write optional parameter closing");
                 } else {
@@ -416,6 +453,15 @@ impl VersionSpecificClientTraitGenerator {
                         .expect("Failed to write required parameter serialization");
                 }
             }
+            if rpc.params.iter().any(|param| !param.required) {
+                writeln!(
+                    buf,
+                    "        while rpc_params.last().is_some_and(serde_json::Value::is_null) {{"
+                )
+                .expect("Failed to write trailing null trim");
+                writeln!(buf, "            rpc_params.pop();").expect("Failed to write trim pop");
+                writeln!(buf, "        }}").expect("Failed to write trim closing");
+            }
             writeln!(
                 buf,
                 "        self.call::<{}>(\"{}\", &rpc_params).await",
@@ -9,10 +9,10 @@ use std::fmt::Write as _;
 use std::str::FromStr;
 use std::sync::{Mutex, OnceLock};
 
-use ir::{ProtocolIR, RpcDef, TypeDef, TypeKind};
+use ir::{FieldDef, ProtocolIR, RpcDef, TypeDef, TypeKind};
 use types::{Implementation, ProtocolVersion};
 
-use super::doc_comment::{write_doc_comment, write_doc_line};
+use super::doc_comment::{deprecated_attribute, write_doc_comment, write_doc_line};
 use crate::utils::sanitize_type_name_for_rust;
 use crate::Result;
 
@@ -42,6 +42,46 @@ pub fn record_external_symbol_usage(crate_name: &str, symbol: &str) {
     record_external_symbol(crate_name, symbol);
 }
 
+/// A field of a generated response struct
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseStructField {
+    /// Rust field name
+    pub name: String,
+    /// Rust field type, including the `Option` wrapper of optional fields
+    pub rust_type: String,
+    /// Cargo feature the field is gated behind (see [`deprecatedrpc_feature`])
+    pub feature: Option<String>,
+}
+
+/// Cargo feature gating fields that the node only returns with `-deprecatedrpc=<flag>`
+pub fn deprecatedrpc_feature(flag: &str) -> String { format!("deprecatedrpc-{}", flag) }
+
+/// The [`deprecatedrpc_feature`]s of all response fields of `methods`, sorted
+pub fn deprecatedrpc_features(methods: &[RpcDef]) -> Vec<String> {
+    fn visit(ty: &TypeDef, features: &mut BTreeSet<String>) {
+        for field in ty.fields.iter().flatten() {
+            if let Some(flag) = field.deprecated.as_ref().and_then(|d| d.flag.as_deref()) {
+                features.insert(deprecatedrpc_feature(flag));
+            }
+            visit(&field.field_type, features);
+        }
+    }
+    let mut features = BTreeSet::new();
+    for result in methods.iter().filter_map(|m| m.result.as_ref()) {
+        visit(result, &mut features);
+    }
+    features.into_iter().collect()
+}
+
+/// Feature gate of a response field; fields of string-or-object responses are not gated, since
+/// their hand-written deserializer fills every field
+fn field_feature(field: &FieldDef, conditional: bool) -> Option<String> {
+    if conditional {
+        return None;
+    }
+    field.deprecated.as_ref()?.flag.as_deref().map(deprecatedrpc_feature)
+}
+
 /// Enhanced response type generator that uses version-specific metadata
 pub struct VersionSpecificResponseTypeGenerator {
     version: ProtocolVersion,
@@ -65,10 +105,10 @@ impl VersionSpecificResponseTypeGenerator {
     }
 
     /// Filter a `TypeDef`'s fields (and nested types) by `version_added` / `version_removed`
-    /// for the generator's target version. Delegates to the openrpc adapter for Bitcoin Core;
-    /// other implementations use the IR as-is.
+    /// for the generator's target version. Delegates to the openrpc adapter for Bitcoin Core and
+    /// Knots; other implementations use the IR as-is.
     fn filter_type_def_for_version(&self, ty: &ir::TypeDef) -> ir::TypeDef {
-        if self.implementation != "bitcoin_core" {
+        if !matches!(self.implementation.as_str(), "bitcoin_core" | "bitcoin_knots") {
             return ty.clone();
         }
         adapters::bitcoin_core::openrpc::filter_type_def_for_version(ty, self.version.as_str())
@@ -257,21 +297,21 @@ impl VersionSpecificResponseTypeGenerator {
         }
 
         // Emit decoded tx types once so they are defined before any method response that references them.
-        let mut decoded_tx_buf = String::new();
-        let fee_required = methods
-            .iter()
-            .find(|m| m.name == "getblock")
-            .and_then(|method| Self::get_getblock_decoded_tx_element_type(method))
-            .and_then(|ty| Self::find_field_in_type(ty, "fee"))
-            .map(|f| f.required);
-        self.emit_decoded_tx_types(
-            &mut decoded_tx_buf,
-            fee_required,
-            type_registry.contains_key("DecodedScriptPubKey"),
-        )?;
-        if !decoded_tx_buf.is_empty() {
-            out.push_str(&decoded_tx_buf);
-            out.push_str("\n\n");
+        // The getblock wrappers embed GetBlockResponse, so only protocols with getblock get them.
+        if let Some(getblock) = methods.iter().find(|m| m.name == "getblock") {
+            let mut decoded_tx_buf = String::new();
+            let fee_required = Self::get_getblock_decoded_tx_element_type(getblock)
+                .and_then(|ty| Self::find_field_in_type(ty, "fee"))
+                .map(|f| f.required);
+            self.emit_decoded_tx_types(
+                &mut decoded_tx_buf,
+                fee_required,
+                type_registry.contains_key("DecodedScriptPubKey"),
+            )?;
+            if !decoded_tx_buf.is_empty() {
+                out.push_str(&decoded_tx_buf);
+                out.push_str("\n\n");
+            }
         }
 
         // Emit GetBlockTemplateTransaction when getblocktemplate is present so GetBlockTemplateResponse can use it.
@@ -279,7 +319,7 @@ impl VersionSpecificResponseTypeGenerator {
             let mut gbt_tx_buf = String::new();
             self.emit_get_block_template_transaction(&mut gbt_tx_buf)?;
             out.push_str(&gbt_tx_buf);
-            out.push_str("\n");
+            out.push('\n');
         }
 
         // Generate response structs for each method
@@ -305,12 +345,12 @@ impl VersionSpecificResponseTypeGenerator {
             out.push_str("{\n");
             out.push_str("    use serde::de::{self, Visitor};\n");
             out.push_str("    use std::fmt;\n");
-            out.push_str("\n");
+            out.push('\n');
             out.push_str("    struct AmountVisitor;\n");
-            out.push_str("\n");
+            out.push('\n');
             out.push_str("    impl Visitor<'_> for AmountVisitor {\n");
             out.push_str("        type Value = bitcoin::Amount;\n");
-            out.push_str("\n");
+            out.push('\n');
             out.push_str(
                 "        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {\n",
             );
@@ -318,21 +358,21 @@ impl VersionSpecificResponseTypeGenerator {
                 "            formatter.write_str(\"a number (float BTC or integer satoshis)\")\n",
             );
             out.push_str("        }\n");
-            out.push_str("\n");
+            out.push('\n');
             out.push_str("        fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>\n");
             out.push_str("        where\n");
             out.push_str("            E: de::Error,\n");
             out.push_str("        {\n");
             out.push_str("            bitcoin::Amount::from_btc(v).map_err(|e| E::custom(format!(\"Invalid BTC amount: {}\", e)))\n");
             out.push_str("        }\n");
-            out.push_str("\n");
+            out.push('\n');
             out.push_str("        fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>\n");
             out.push_str("        where\n");
             out.push_str("            E: de::Error,\n");
             out.push_str("        {\n");
             out.push_str("            Ok(bitcoin::Amount::from_sat(v))\n");
             out.push_str("        }\n");
-            out.push_str("\n");
+            out.push('\n');
             out.push_str("        fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>\n");
             out.push_str("        where\n");
             out.push_str("            E: de::Error,\n");
@@ -343,7 +383,7 @@ impl VersionSpecificResponseTypeGenerator {
             out.push_str("            Ok(bitcoin::Amount::from_sat(v as u64))\n");
             out.push_str("        }\n");
             out.push_str("    }\n");
-            out.push_str("\n");
+            out.push('\n');
             out.push_str("    deserializer.deserialize_any(AmountVisitor)\n");
             out.push_str("}\n");
 
@@ -357,33 +397,33 @@ impl VersionSpecificResponseTypeGenerator {
             out.push_str("{\n");
             out.push_str("    use serde::de::{self, Visitor};\n");
             out.push_str("    use std::fmt;\n");
-            out.push_str("\n");
+            out.push('\n');
             out.push_str("    struct OptionAmountVisitor;\n");
-            out.push_str("\n");
+            out.push('\n');
             out.push_str("    #[allow(clippy::needless_lifetimes)]\n");
             out.push_str("    impl<'de> Visitor<'de> for OptionAmountVisitor {\n");
             out.push_str("        type Value = Option<bitcoin::Amount>;\n");
-            out.push_str("\n");
+            out.push('\n');
             out.push_str(
                 "        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {\n",
             );
             out.push_str("            formatter.write_str(\"an optional number (float BTC or integer satoshis)\")\n");
             out.push_str("        }\n");
-            out.push_str("\n");
+            out.push('\n');
             out.push_str("        fn visit_none<E>(self) -> Result<Self::Value, E>\n");
             out.push_str("        where\n");
             out.push_str("            E: de::Error,\n");
             out.push_str("        {\n");
             out.push_str("            Ok(None)\n");
             out.push_str("        }\n");
-            out.push_str("\n");
+            out.push('\n');
             out.push_str("        fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>\n");
             out.push_str("        where\n");
             out.push_str("            D: serde::Deserializer<'de>,\n");
             out.push_str("        {\n");
             out.push_str("            amount_from_btc_float(deserializer).map(Some)\n");
             out.push_str("        }\n");
-            out.push_str("\n");
+            out.push('\n');
             out.push_str("        fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>\n");
             out.push_str("        where\n");
             out.push_str("            E: de::Error,\n");
@@ -394,14 +434,14 @@ impl VersionSpecificResponseTypeGenerator {
             );
             out.push_str("                .map(Some)\n");
             out.push_str("        }\n");
-            out.push_str("\n");
+            out.push('\n');
             out.push_str("        fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>\n");
             out.push_str("        where\n");
             out.push_str("            E: de::Error,\n");
             out.push_str("        {\n");
             out.push_str("            Ok(Some(bitcoin::Amount::from_sat(v)))\n");
             out.push_str("        }\n");
-            out.push_str("\n");
+            out.push('\n');
             out.push_str("        fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>\n");
             out.push_str("        where\n");
             out.push_str("            E: de::Error,\n");
@@ -412,11 +452,20 @@ impl VersionSpecificResponseTypeGenerator {
             out.push_str("            Ok(Some(bitcoin::Amount::from_sat(v as u64)))\n");
             out.push_str("        }\n");
             out.push_str("    }\n");
-            out.push_str("\n");
+            out.push('\n');
             out.push_str("    deserializer.deserialize_any(OptionAmountVisitor)\n");
             out.push_str("}\n");
         }
 
+        // FromStr is only needed by the custom string visitors; drop it when none were emitted
+        if !out.contains("::from_str(") {
+            out = out.replacen("use std::str::FromStr;\n", "", 1);
+        }
+        // Likewise the serde derives when no response type was emitted (REST-only protocols)
+        if !out.contains("#[derive(") {
+            out = out.replacen("use serde::{Deserialize, Serialize};\n", "", 1);
+        }
+
         let filename = "responses.rs".to_string();
         Ok(vec![(filename, out)])
     }
@@ -854,6 +903,37 @@ impl VersionSpecificResponseTypeGenerator {
         self.generate_unit_response(method)
     }
 
+    /// Name and fields of the struct generated for `method`, when its response is a struct
+    /// with named fields (not an array, value or unit wrapper)
+    pub fn response_struct_fields(
+        &self,
+        method: &RpcDef,
+    ) -> Option<(String, Vec<ResponseStructField>)> {
+        let result = method.result.as_ref()?;
+        if Self::array_element_type_from_ir(result).is_some()
+            || Self::ECHO_JSON_VALUE_RPCS.contains(&method.name.as_str())
+            || method.name == "help"
+        {
+            return None;
+        }
+        let result = self.filter_type_def_for_version(result);
+        let fields = result.fields.as_ref().filter(|fields| !fields.is_empty())?;
+        let has_conditional_results = (method.name == "getrawtransaction"
+            || self.check_conditional_results(&result))
+            && method.name != "getblockstats";
+        let fields = fields
+            .iter()
+            .filter(|f| !Self::should_skip_field_in_struct(method.name.as_str(), f))
+            .map(|field| {
+                let (_, name, rust_type) =
+                    self.ir_field_signature(field, method.name.as_str(), has_conditional_results);
+                let feature = field_feature(field, has_conditional_results);
+                ResponseStructField { name, rust_type, feature }
+            })
+            .collect();
+        Some((self.response_struct_name(method), fields))
+    }
+
     /// Generate response type from IR data (TypeDef.fields). The caller must pass a result
     /// already filtered for the generator's target version.
     fn generate_from_ir_data(&self, method: &RpcDef, result: &ir::TypeDef) -> Result<String> {
@@ -942,28 +1022,18 @@ impl VersionSpecificResponseTypeGenerator {
         Ok(())
     }
 
-    /// Generate a field from IR FieldDef
-    fn generate_ir_field(
+    /// Rust base type, field name and field type (with `Option` where the field can be absent)
+    /// of a response struct field
+    fn ir_field_signature(
         &self,
-        buf: &mut String,
         field: &ir::FieldDef,
-        _struct_name: &str,
         rpc_name: &str,
         force_optional_conditional: bool,
-    ) -> Result<()> {
-        // Generate field documentation
-        if !field.description.is_empty() {
-            write_doc_comment(buf, &field.description, "    ")?;
-        }
-
-        // Generate field definition (use stronger type override when set)
+    ) -> (String, String, String) {
+        // Use stronger type override when set
         let base_field_type = Self::response_field_type_override(rpc_name, &field.key.as_ident())
             .map(String::from)
             .unwrap_or_else(|| self.map_ir_type_to_rust(&field.field_type, &field.key.as_ident()));
-        if base_field_type.starts_with("bitcoin::") {
-            let symbol = base_field_type.split("::").last().unwrap_or(&base_field_type);
-            record_external_symbol("bitcoin", symbol);
-        }
         let field_name = self.sanitize_identifier(&field.key.as_ident());
         let mut field_type = if field.required && !force_optional_conditional {
             base_field_type.clone()
@@ -987,6 +1057,36 @@ impl VersionSpecificResponseTypeGenerator {
         if Self::optional_field_override(rpc_name, &field.key.as_ident()) {
             field_type = format!("Option<{}>", base_field_type);
         }
+        (base_field_type, field_name, field_type)
+    }
+
+    /// Generate a field from IR FieldDef
+    fn generate_ir_field(
+        &self,
+        buf: &mut String,
+        field: &ir::FieldDef,
+        _struct_name: &str,
+        rpc_name: &str,
+        force_optional_conditional: bool,
+    ) -> Result<()> {
+        // Generate field documentation
+        if !field.description.is_empty() {
+            write_doc_comment(buf, &field.description, "    ")?;
+        }
+
+        let (base_field_type, field_name, field_type) =
+            self.ir_field_signature(field, rpc_name, force_optional_conditional);
+        if base_field_type.starts_with("bitcoin::") {
+            let symbol = base_field_type.split("::").last().unwrap_or(&base_field_type);
+            record_external_symbol("bitcoin", symbol);
+        }
+
+        if let Some(feature) = field_feature(field, force_optional_conditional) {
+            writeln!(buf, "    #[cfg(feature = \"{}\")]", feature)?;
+        }
+        if let Some(deprecation) = &field.deprecated {
+            writeln!(buf, "    {}", deprecated_attribute(deprecation))?;
+        }
 
         // Add serde rename attribute if the field name was changed
         if field_name != field.key.as_ident() {
@@ -1139,7 +1239,7 @@ impl VersionSpecificResponseTypeGenerator {
             match &result.kind {
                 ir::TypeKind::Array => {
                     // Generate array wrapper for methods that return arrays
-                    return Ok(Some(self.generate_array_wrapper(method, &struct_name, &result)?));
+                    return Ok(Some(self.generate_array_wrapper(method, &struct_name, result)?));
                 }
                 ir::TypeKind::Primitive => {
                     // Generate primitive wrapper for methods that return primitives
@@ -1193,6 +1293,9 @@ impl VersionSpecificResponseTypeGenerator {
         result: &ir::TypeDef,
         struct_name: &str,
     ) -> Result<()> {
+        if result.fields.iter().flatten().any(|f| f.deprecated.is_some()) {
+            writeln!(buf, "#[allow(deprecated)]")?;
+        }
         writeln!(buf, "impl<'de> serde::Deserialize<'de> for {} {{", struct_name)?;
         writeln!(buf, "    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>")?;
         writeln!(buf, "    where")?;
@@ -2167,27 +2270,27 @@ mod tests {
 
     #[test]
     fn scalar_type_aliases_use_bitcoin_types() {
-        let version = ProtocolVersion::from_str("30.0.0").unwrap();
+        let version = ProtocolVersion::from_str("30.0.0").expect("valid version");
         let gen = VersionSpecificResponseTypeGenerator::new(version, "bitcoin_core".to_string());
 
-        let amount_alias = gen.generate_type_alias("amount").unwrap();
+        let amount_alias = gen.generate_type_alias("amount").expect("type alias");
         assert!(amount_alias.contains("pub type Amount = bitcoin::Amount;"));
 
-        let blockhash_alias = gen.generate_type_alias("BlockHash").unwrap();
+        let blockhash_alias = gen.generate_type_alias("BlockHash").expect("type alias");
         assert!(blockhash_alias.contains("pub type BlockHash = bitcoin::BlockHash;"));
 
         // Also accept lowercase input; left-side casing is derived from `sanitize_type_name_for_rust`.
-        let blockhash_alias_lower = gen.generate_type_alias("blockhash").unwrap();
+        let blockhash_alias_lower = gen.generate_type_alias("blockhash").expect("type alias");
         assert!(blockhash_alias_lower.contains("pub type Blockhash = bitcoin::BlockHash;"));
 
         // `Txid` must be mapped (it was previously missing and defaulted to `String`)
-        let txid_alias = gen.generate_type_alias("Txid").unwrap();
+        let txid_alias = gen.generate_type_alias("Txid").expect("type alias");
         assert!(txid_alias.contains("pub type Txid = bitcoin::Txid;"));
     }
 
     #[test]
     fn array_wrapper_uses_ir_element_type() {
-        let version = ProtocolVersion::from_str("30.0.0").unwrap();
+        let version = ProtocolVersion::from_str("30.0.0").expect("valid version");
         let gen = VersionSpecificResponseTypeGenerator::new(version, "bitcoin_core".to_string());
 
         // IR: top-level array of primitive strings.
@@ -2217,6 +2320,7 @@ mod tests {
                 default_value: None,
                 version_added: None,
                 version_removed: None,
+                deprecated: None,
             }]),
             variants: None,
             union_variants: None,
@@ -2236,8 +2340,10 @@ mod tests {
             requires_private_keys: false,
             version_added: None,
             version_removed: None,
+            deprecated: None,
             examples: None,
             hidden: None,
+            http: None,
         };
 
         let code = gen
@@ -2254,7 +2360,7 @@ mod tests {
 
     #[test]
     fn array_wrapper_recognizes_named_field_0_element() {
-        let version = ProtocolVersion::from_str("30.0.0").unwrap();
+        let version = ProtocolVersion::from_str("30.0.0").expect("valid version");
         let gen = VersionSpecificResponseTypeGenerator::new(version, "bitcoin_core".to_string());
 
         // IR: top-level array of primitive strings with a synthetic Named(\"field_0\") key.
@@ -2283,6 +2389,7 @@ mod tests {
                 default_value: None,
                 version_added: None,
                 version_removed: None,
+                deprecated: None,
             }]),
             variants: None,
             union_variants: None,
@@ -2302,8 +2409,10 @@ mod tests {
             requires_private_keys: false,
             version_added: None,
             version_removed: None,
+            deprecated: None,
             examples: None,
             hidden: None,
+            http: None,
         };
 
         let code = gen
@@ -2323,7 +2432,7 @@ mod tests {
     /// rather than serde_json::Value, so schema changes propagate.
     #[test]
     fn decodepsbt_response_uses_ir_nested_types() {
-        let version = ProtocolVersion::from_str("30.0.0").unwrap();
+        let version = ProtocolVersion::from_str("30.0.0").expect("valid version");
         let gen = VersionSpecificResponseTypeGenerator::new(version, "bitcoin_core".to_string());
 
         let tx_obj = TypeDef {
@@ -2349,6 +2458,7 @@ mod tests {
                 default_value: None,
                 version_added: None,
                 version_removed: None,
+                deprecated: None,
             }]),
             variants: None,
             union_variants: None,
@@ -2402,6 +2512,7 @@ mod tests {
                         default_value: None,
                         version_added: None,
                         version_removed: None,
+                        deprecated: None,
                     }]),
                     variants: None,
                     union_variants: None,
@@ -2415,6 +2526,7 @@ mod tests {
                 default_value: None,
                 version_added: None,
                 version_removed: None,
+                deprecated: None,
             }]),
             variants: None,
             union_variants: None,
@@ -2440,6 +2552,7 @@ mod tests {
                     default_value: None,
                     version_added: None,
                     version_removed: None,
+                    deprecated: None,
                 },
                 ir::FieldDef {
                     key: ir::FieldKey::Named("inputs".to_string()),
@@ -2449,6 +2562,7 @@ mod tests {
                     default_value: None,
                     version_added: None,
                     version_removed: None,
+                    deprecated: None,
                 },
                 ir::FieldDef {
                     key: ir::FieldKey::Named("outputs".to_string()),
@@ -2458,6 +2572,7 @@ mod tests {
                     default_value: None,
                     version_added: None,
                     version_removed: None,
+                    deprecated: None,
                 },
             ]),
             variants: None,
@@ -2478,8 +2593,10 @@ mod tests {
             requires_private_keys: false,
             version_added: None,
             version_removed: None,
+            deprecated: None,
             examples: None,
             hidden: None,
+            http: None,
         };
 
         let code = gen
@@ -2503,7 +2620,7 @@ mod tests {
 
     #[test]
     fn array_wrapper_uses_value_vec_for_any() {
-        let version = ProtocolVersion::from_str("30.0.0").unwrap();
+        let version = ProtocolVersion::from_str("30.0.0").expect("valid version");
         let gen = VersionSpecificResponseTypeGenerator::new(version, "bitcoin_core".to_string());
 
         // IR: top-level array of primitive `any` (maps to serde_json::Value).
@@ -2532,6 +2649,7 @@ mod tests {
                 default_value: None,
                 version_added: None,
                 version_removed: None,
+                deprecated: None,
             }]),
             variants: None,
             union_variants: None,
@@ -2551,8 +2669,10 @@ mod tests {
             requires_private_keys: false,
             version_added: None,
             version_removed: None,
+            deprecated: None,
             examples: None,
             hidden: None,
+            http: None,
         };
 
         let code = gen
@@ -2569,7 +2689,7 @@ mod tests {
 
     #[test]
     fn getblocktemplate_placeholder_field_optional() {
-        let version = ProtocolVersion::from_str("30.0.0").unwrap();
+        let version = ProtocolVersion::from_str("30.0.0").expect("valid version");
         let gen = VersionSpecificResponseTypeGenerator::new(version, "bitcoin_core".to_string());
 
         // IR: object result whose first anonymous field encodes the proposal-accepted `none` result.
@@ -2612,6 +2732,7 @@ mod tests {
                     default_value: None,
                     version_added: None,
                     version_removed: None,
+                    deprecated: None,
                 },
                 ir::FieldDef {
                     key: ir::FieldKey::Named("version".to_string()),
@@ -2621,6 +2742,7 @@ mod tests {
                     default_value: None,
                     version_added: None,
                     version_removed: None,
+                    deprecated: None,
                 },
             ]),
             variants: None,
@@ -2641,8 +2763,10 @@ mod tests {
             requires_private_keys: false,
             version_added: None,
             version_removed: None,
+            deprecated: None,
             examples: None,
             hidden: None,
+            http: None,
         };
 
         let code = gen
@@ -2674,7 +2798,7 @@ mod tests {
 
         use ir::AccessLevel;
 
-        let version = ProtocolVersion::from_str("30.0.0").unwrap();
+        let version = ProtocolVersion::from_str("30.0.0").expect("valid version");
         let gen = VersionSpecificResponseTypeGenerator::new(version, "bitcoin_core".to_string());
 
         fn object_type(name: &str) -> TypeDef {
@@ -2702,6 +2826,7 @@ mod tests {
                     default_value: None,
                     version_added: None,
                     version_removed: None,
+                    deprecated: None,
                 }]),
                 variants: None,
                 union_variants: None,
@@ -2723,8 +2848,10 @@ mod tests {
                 requires_private_keys: false,
                 version_added: None,
                 version_removed: None,
+                deprecated: None,
                 examples: None,
                 hidden: None,
+                http: None,
             }
         }
 
@@ -2776,4 +2903,63 @@ mod tests {
             positions2
         );
     }
+
+    #[test]
+    fn deprecated_fields_are_annotated_and_gated() {
+        let version = ProtocolVersion::from_str("30.0.0").expect("valid version");
+        let gen = VersionSpecificResponseTypeGenerator::new(version, "bitcoin_core".to_string());
+
+        let field = |key: &str, deprecated: Option<ir::Deprecation>| ir::FieldDef {
+            key: ir::FieldKey::Named(key.to_string()),
+            field_type: ir::test_utils::primitive_type("number", Some("number".to_string())),
+            required: false,
+            description: String::new(),
+            default_value: None,
+            version_added: None,
+            version_removed: None,
+            deprecated,
+        };
+        // A required field keeps the response from being treated as string-or-object
+        let connections = ir::FieldDef { required: true, ..field("connections", None) };
+        let result = TypeDef {
+            fields: Some(vec![
+                connections,
+                field(
+                    "localrelay",
+                    Some(ir::Deprecation {
+                        since: Some("28".to_string()),
+                        replacement: Some("relay".to_string()),
+                        ..Default::default()
+                    }),
+                ),
+                field(
+                    "startingheight",
+                    Some(ir::Deprecation {
+                        flag: Some("startingheight".to_string()),
+                        ..Default::default()
+                    }),
+                ),
+            ]),
+            ..ir::test_utils::type_def("object", TypeKind::Object)
+        };
+        let method = ir::test_utils::rpc("getnetworkinfo", vec![], Some(result), "network");
+
+        let content = gen
+            .generate(std::slice::from_ref(&method))
+            .expect("generation succeeds")
+            .into_iter()
+            .map(|(_, content)| content)
+            .collect::<String>();
+        assert!(content.contains(
+            "    #[deprecated(since = \"28\", note = \"Use `relay` instead\")]\n    #[serde(rename = \"localrelay\")]"
+        ));
+        assert!(content.contains(
+            "    #[cfg(feature = \"deprecatedrpc-startingheight\")]\n    #[deprecated(note = \"Only served with `-deprecatedrpc=startingheight`\")]"
+        ));
+
+        let (_, fields) = gen.response_struct_fields(&method).expect("struct response");
+        let features: Vec<Option<&str>> = fields.iter().map(|f| f.feature.as_deref()).collect();
+        assert_eq!(features, vec![None, None, Some("deprecatedrpc-startingheight")]);
+        assert_eq!(deprecatedrpc_features(&[method]), vec!["deprecatedrpc-startingheight"]);
+    }
 }
@@ -5,8 +5,22 @@
 
 use std::collections::HashMap;
 
+use ir::{FieldDef, TypeDef};
 use types::ProtocolVersion;
 
+use super::version_specific_response_type::ResponseStructField;
+
+/// What happened to a field between two versions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldTransitionKind {
+    /// The field only exists in the target version
+    Added,
+    /// The field only exists in the source version
+    Removed,
+    /// The field was renamed
+    Renamed,
+}
+
 /// Information about a field transition between versions
 #[derive(Debug, Clone)]
 pub struct FieldTransition {
@@ -16,8 +30,8 @@ pub struct FieldTransition {
     pub to_name: String,
     /// The version when the transition occurred
     pub transition_version: String,
-    /// Whether this is a rename (true) or addition/removal (false)
-    pub is_rename: bool,
+    /// Whether the field was added, removed or renamed
+    pub kind: FieldTransitionKind,
 }
 
 /// Information about a type change between versions
@@ -38,8 +52,8 @@ pub struct TypeTransition {
 pub struct DeprecationInfo {
     /// The field name
     pub field_name: String,
-    /// The version when the field was deprecated
-    pub deprecated_in: String,
+    /// The version when the field was deprecated (None = unknown)
+    pub deprecated_in: Option<String>,
     /// The deprecation message
     pub message: Option<String>,
 }
@@ -65,14 +79,232 @@ impl VersionTransitionRegistry {
         }
     }
 
-    /// Analyze field transitions for a struct across versions
-    /// Simplified - IR doesn't track per-type versions, so this is a no-op
+    /// Analyze field transitions of a struct between two versions
+    ///
+    /// `from` and `to` are the struct's IR type as seen by the source and target versions
+    /// (already filtered by `version_added`/`version_removed`). Records fields only present in
+    /// one of them and fields whose type changed; the transition version is the field's own
+    /// bound when the IR has one, otherwise `to_version`.
     pub fn analyze_struct_transitions(
         &mut self,
-        _struct_name: &str,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        // IR doesn't track per-type versions, so transitions can't be analyzed
-        Ok(())
+        struct_name: &str,
+        from: &TypeDef,
+        to: &TypeDef,
+        to_version: &ProtocolVersion,
+    ) {
+        let from_fields = from.fields.as_deref().unwrap_or_default();
+        let to_fields = to.fields.as_deref().unwrap_or_default();
+        let find = |fields: &'_ [FieldDef], key: &str| -> Option<usize> {
+            fields.iter().position(|f| f.key.as_ident() == key)
+        };
+
+        let mut field_transitions = Vec::new();
+        let mut type_transitions = Vec::new();
+        for field in to_fields {
+            let name = field.key.as_ident();
+            match find(from_fields, &name).map(|i| &from_fields[i]) {
+                None => field_transitions.push(FieldTransition {
+                    from_name: name.clone(),
+                    to_name: name,
+                    transition_version: field
+                        .version_added
+                        .clone()
+                        .unwrap_or_else(|| to_version.as_str().to_string()),
+                    kind: FieldTransitionKind::Added,
+                }),
+                Some(old) if type_shape(&old.field_type) != type_shape(&field.field_type) =>
+                    type_transitions.push(TypeTransition {
+                        field_name: name,
+                        from_type: type_label(&old.field_type),
+                        to_type: type_label(&field.field_type),
+                        transition_version: to_version.as_str().to_string(),
+                    }),
+                Some(_) => {}
+            }
+        }
+        for field in from_fields {
+            let name = field.key.as_ident();
+            if find(to_fields, &name).is_none() {
+                field_transitions.push(FieldTransition {
+                    from_name: name.clone(),
+                    to_name: name,
+                    transition_version: field
+                        .version_removed
+                        .clone()
+                        .unwrap_or_else(|| to_version.as_str().to_string()),
+                    kind: FieldTransitionKind::Removed,
+                });
+            }
+        }
+
+        self.field_transitions.insert(struct_name.to_string(), field_transitions);
+        self.type_transitions.insert(struct_name.to_string(), type_transitions);
+    }
+
+    /// Generate the conversion from a struct into its counterpart at `target_path`
+    ///
+    /// Fields with the same Rust type are moved. Fields whose type differs, or that use a type
+    /// local to the source or target module (`local_types`), are re-decoded through
+    /// `serde_json`, which makes the conversion a `TryFrom`; otherwise it is a `From`. Fields
+    /// the target lacks are dropped and fields the source lacks become `None`.
+    ///
+    /// A required target field the source lacks cannot be filled, so no conversion is
+    /// generated (an empty string is returned); one the source only has behind a feature
+    /// limits the conversion to builds with that feature. A target in a version module is
+    /// gated behind that module's `target_feature`.
+    pub fn generate_conversion(
+        &self,
+        struct_name: &str,
+        source: &[ResponseStructField],
+        target: &[ResponseStructField],
+        target_path: &str,
+        target_feature: Option<&str>,
+        local_types: &[String],
+    ) -> String {
+        let uses_local_type = |rust_type: &str| {
+            rust_type
+                .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .any(|word| local_types.iter().any(|local| local == word))
+        };
+
+        let cfg = |predicates: &[String]| match predicates {
+            [] => String::new(),
+            [predicate] => format!("#[cfg({})]\n            ", predicate),
+            _ => format!("#[cfg(all({}))]\n            ", predicates.join(", ")),
+        };
+
+        let mut fallible = false;
+        let mut unset = Vec::new();
+        // Required target fields the source only has behind a feature, with that feature
+        let mut gated: Vec<(&str, &str)> = Vec::new();
+        let mut initializers = Vec::new();
+        for field in target {
+            let source_field = source.iter().find(|s| s.name == field.name);
+            let initializer = match source_field {
+                Some(s) if s.rust_type == field.rust_type && !uses_local_type(&s.rust_type) =>
+                    format!("value.{}", field.name),
+                Some(_) => {
+                    fallible = true;
+                    format!("serde_json::from_value(serde_json::to_value(value.{})?)?", field.name)
+                }
+                None if field.rust_type.starts_with("Option<") => {
+                    unset.push(format!("`{}`", field.name));
+                    "None".to_string()
+                }
+                None => return String::new(),
+            };
+
+            // A source field gated behind a feature the target field is not is absent when
+            // that feature is off: optional target fields fall back to `None`, required ones
+            // need the feature for the conversion
+            let mut predicates: Vec<String> =
+                field.feature.iter().map(|f| format!("feature = \"{}\"", f)).collect();
+            if let Some(feature) = source_field
+                .and_then(|s| s.feature.as_ref())
+                .filter(|f| field.feature.as_ref() != Some(*f))
+            {
+                if field.rust_type.starts_with("Option<") {
+                    let mut fallback = predicates.clone();
+                    fallback.push(format!("not(feature = \"{}\")", feature));
+                    initializers.push(format!(
+                        "            {}{}: None,",
+                        cfg(&fallback),
+                        field.name
+                    ));
+                    predicates.push(format!("feature = \"{}\"", feature));
+                } else {
+                    gated.push((field.name.as_str(), feature.as_str()));
+                }
+            }
+            initializers.push(format!(
+                "            {}{}: {},",
+                cfg(&predicates),
+                field.name,
+                initializer
+            ));
+        }
+
+        let mut out = format!("/// Converts into the `{}` type\n", target_path);
+        let dropped: Vec<String> = source
+            .iter()
+            .filter(|s| !target.iter().any(|t| t.name == s.name))
+            .map(|s| format!("`{}`", s.name))
+            .collect();
+        if !dropped.is_empty() {
+            out.push_str(&format!(
+                "///\n/// Drops {}, which the target version does not have.\n",
+                dropped.join(", ")
+            ));
+        }
+        if !unset.is_empty() {
+            out.push_str(&format!(
+                "///\n/// Leaves {} unset, which the source version does not have.\n",
+                unset.join(", ")
+            ));
+        }
+        if !gated.is_empty() {
+            let fields: Vec<String> = gated
+                .iter()
+                .map(|(name, feature)| format!("`{}` (with `{}`)", name, feature))
+                .collect();
+            out.push_str(&format!(
+                "///\n/// Only implemented with the features under which the source version has the \
+                 required {}.\n",
+                fields.join(", ")
+            ));
+        }
+
+        let mut gates: Vec<String> =
+            target_feature.map(|f| format!("feature = \"{}\"", f)).into_iter().collect();
+        for (_, feature) in &gated {
+            let gate = format!("feature = \"{}\"", feature);
+            if !gates.contains(&gate) {
+                gates.push(gate);
+            }
+        }
+        match gates.as_slice() {
+            [] => {}
+            [gate] => out.push_str(&format!("#[cfg({})]\n", gate)),
+            _ => out.push_str(&format!("#[cfg(all({}))]\n", gates.join(", "))),
+        }
+        if self.deprecations.get(struct_name).is_some_and(|d| !d.is_empty()) {
+            out.push_str("#[allow(deprecated)]\n");
+        }
+        let body = format!("Self {{\n{}\n        }}", initializers.join("\n"));
+        if fallible {
+            out.push_str(&format!(
+                "impl TryFrom<{source}> for {target} {{\n    type Error = serde_json::Error;\n\n    \
+                 fn try_from(value: {source}) -> Result<Self, Self::Error> {{\n        Ok({body})\n    }}\n}}\n",
+                source = struct_name,
+                target = target_path,
+            ));
+        } else {
+            out.push_str(&format!(
+                "impl From<{source}> for {target} {{\n    \
+                 fn from(value: {source}) -> Self {{\n        {body}\n    }}\n}}\n",
+                source = struct_name,
+                target = target_path,
+            ));
+        }
+        out
+    }
+
+    /// Record the deprecated fields of a struct's IR type
+    pub fn record_deprecations(&mut self, struct_name: &str, ty: &TypeDef) {
+        let deprecations = self.deprecations.entry(struct_name.to_string()).or_default();
+        for field in ty.fields.iter().flatten() {
+            let Some(deprecation) = &field.deprecated else {
+                continue;
+            };
+            let field_name = field.key.as_ident();
+            if deprecations.iter().all(|d| d.field_name != field_name) {
+                deprecations.push(DeprecationInfo {
+                    field_name,
+                    deprecated_in: deprecation.since.clone(),
+                    message: deprecation.note(),
+                });
+            }
+        }
     }
 
     /// Get field transitions for a struct
@@ -103,22 +335,28 @@ impl VersionTransitionRegistry {
         if let Some(deprecations) = self.deprecations.get(struct_name) {
             if let Some(deprecation) = deprecations.iter().find(|d| d.field_name == field_name) {
                 let version_num = parse_version_number(version.as_str()).unwrap_or(0);
-                let deprecation_num = parse_version_number(&deprecation.deprecated_in).unwrap_or(0);
+                let deprecation_num = deprecation
+                    .deprecated_in
+                    .as_deref()
+                    .and_then(parse_version_number)
+                    .unwrap_or(0);
 
                 if version_num >= deprecation_num {
-                    doc_parts.push(format!(
-                        "**DEPRECATED** since {}: {}",
-                        deprecation.deprecated_in,
-                        deprecation.message.as_deref().unwrap_or("This field is deprecated")
-                    ));
+                    let message =
+                        deprecation.message.as_deref().unwrap_or("This field is deprecated");
+                    doc_parts.push(match &deprecation.deprecated_in {
+                        Some(since) => format!("**DEPRECATED** since {}: {}", since, message),
+                        None => format!("**DEPRECATED**: {}", message),
+                    });
                 }
             }
         }
 
         // Check for field renames
         if let Some(transitions) = self.field_transitions.get(struct_name) {
-            if let Some(transition) =
-                transitions.iter().find(|t| t.to_name == field_name && t.is_rename)
+            if let Some(transition) = transitions
+                .iter()
+                .find(|t| t.to_name == field_name && t.kind == FieldTransitionKind::Renamed)
             {
                 doc_parts.push(format!(
                     "Renamed from `{}` in {}",
@@ -157,7 +395,8 @@ impl VersionTransitionRegistry {
             let relevant_deprecations: Vec<_> = deprecations
                 .iter()
                 .filter(|d| {
-                    let dep_version_num = parse_version_number(&d.deprecated_in).unwrap_or(0);
+                    let dep_version_num =
+                        d.deprecated_in.as_deref().and_then(parse_version_number).unwrap_or(0);
                     version_num >= dep_version_num
                 })
                 .collect();
@@ -171,6 +410,14 @@ impl VersionTransitionRegistry {
     }
 }
 
+/// Structural key of a field type, ignoring descriptions and version bounds
+fn type_shape(ty: &TypeDef) -> serde_json::Value { super::versioned_registry::shape_key(ty) }
+
+/// Protocol type (or IR type name) of a field type, for documentation
+fn type_label(ty: &TypeDef) -> String {
+    ty.protocol_type.clone().unwrap_or_else(|| ty.name.clone())
+}
+
 /// Parse a version string into a numeric value for comparison
 fn parse_version_number(version_str: &str) -> Option<i32> {
     let clean_version = version_str.trim_start_matches('v');
@@ -182,3 +429,193 @@ fn parse_version_number(version_str: &str) -> Option<i32> {
 
     parts[0].parse::<i32>().ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use ir::test_utils::{primitive_type, type_def};
+    use ir::{FieldKey, TypeKind};
+
+    use super::*;
+
+    fn field(name: &str, protocol_type: &str, required: bool) -> FieldDef {
+        FieldDef {
+            key: FieldKey::Named(name.to_string()),
+            field_type: primitive_type(protocol_type, Some(protocol_type.to_string())),
+            required,
+            description: String::new(),
+            default_value: None,
+            version_added: None,
+            version_removed: None,
+            deprecated: None,
+        }
+    }
+
+    fn object(fields: Vec<FieldDef>) -> TypeDef {
+        TypeDef { fields: Some(fields), ..type_def("object", TypeKind::Object) }
+    }
+
+    fn rust_field(name: &str, rust_type: &str) -> ResponseStructField {
+        ResponseStructField {
+            name: name.to_string(),
+            rust_type: rust_type.to_string(),
+            feature: None,
+        }
+    }
+
+    #[test]
+    fn analyzes_additions_removals_and_type_changes() {
+        let from = object(vec![
+            field("chain", "string", true),
+            field("warnings", "string", true),
+            field("old", "number", true),
+        ]);
+        let to = object(vec![
+            field("chain", "string", true),
+            field("warnings", "array", true),
+            field("headers", "number", false),
+        ]);
+        let version = ProtocolVersion::from_string("30.0").expect("valid version");
+        let mut registry = VersionTransitionRegistry::new();
+        registry.analyze_struct_transitions("GetInfoResponse", &from, &to, &version);
+
+        let fields = registry.get_field_transitions("GetInfoResponse").expect("analyzed");
+        let changes: Vec<(&str, FieldTransitionKind)> =
+            fields.iter().map(|t| (t.to_name.as_str(), t.kind)).collect();
+        assert_eq!(
+            changes,
+            vec![("headers", FieldTransitionKind::Added), ("old", FieldTransitionKind::Removed)]
+        );
+        let types = registry.get_type_transitions("GetInfoResponse").expect("analyzed");
+        assert_eq!(types.len(), 1);
+        assert_eq!((types[0].from_type.as_str(), types[0].to_type.as_str()), ("string", "array"));
+    }
+
+    #[test]
+    fn generates_from_or_try_from() {
+        let registry = VersionTransitionRegistry::new();
+        let source = vec![rust_field("chain", "String"), rust_field("old", "u64")];
+        let target = vec![rust_field("chain", "String"), rust_field("headers", "Option<u64>")];
+        let from = registry.generate_conversion(
+            "GetInfoResponse",
+            &source,
+            &target,
+            "crate::types::GetInfoResponse",
+            None,
+            &[],
+        );
+        assert!(from.contains("impl From<GetInfoResponse> for crate::types::GetInfoResponse"));
+        assert!(from.contains("chain: value.chain,"));
+        assert!(from.contains("headers: None,"));
+        assert!(from.contains("Drops `old`"));
+        assert!(from.contains("Leaves `headers` unset"));
+
+        let target = vec![rust_field("chain", "String"), rust_field("old", "Vec<String>")];
+        let try_from = registry.generate_conversion(
+            "GetInfoResponse",
+            &source,
+            &target,
+            "crate::types::GetInfoResponse",
+            None,
+            &[],
+        );
+        assert!(
+            try_from.contains("impl TryFrom<GetInfoResponse> for crate::types::GetInfoResponse")
+        );
+        assert!(
+            try_from.contains("old: serde_json::from_value(serde_json::to_value(value.old)?)?,")
+        );
+
+        let target = vec![rust_field("chain", "String"), rust_field("headers", "u64")];
+        let missing = registry.generate_conversion(
+            "GetInfoResponse",
+            &source,
+            &target,
+            "crate::types::GetInfoResponse",
+            None,
+            &[],
+        );
+        assert!(missing.is_empty());
+
+        let target = vec![rust_field("chain", "String"), rust_field("headers", "Option<u64>")];
+        let newer = registry.generate_conversion(
+            "GetInfoResponse",
+            &source,
+            &target,
+            "crate::types::v30::GetInfoResponse",
+            Some("v30"),
+            &["GetInfoResponse".to_string()],
+        );
+        assert!(newer.contains(
+            "#[cfg(feature = \"v30\")]\nimpl From<GetInfoResponse> for crate::types::v30::GetInfoResponse"
+        ));
+    }
+
+    #[test]
+    fn gates_deprecated_fields_in_conversions() {
+        let mut peer = field("startingheight", "number", false);
+        peer.deprecated = Some(ir::Deprecation {
+            flag: Some("startingheight".to_string()),
+            ..Default::default()
+        });
+        let mut registry = VersionTransitionRegistry::new();
+        registry.record_deprecations("PeerInfo", &object(vec![field("id", "number", true), peer]));
+        let deprecations = registry.get_deprecations("PeerInfo").expect("recorded");
+        assert_eq!(deprecations.len(), 1);
+        assert_eq!(deprecations[0].field_name, "startingheight");
+        assert_eq!(deprecations[0].deprecated_in, None);
+
+        let gated = ResponseStructField {
+            feature: Some("deprecatedrpc-startingheight".to_string()),
+            ..rust_field("starting_height", "Option<i64>")
+        };
+        let source = vec![rust_field("id", "u64"), gated.clone()];
+        let target = vec![rust_field("id", "u64"), rust_field("starting_height", "Option<i64>")];
+        let conversion = registry.generate_conversion(
+            "PeerInfo",
+            &source,
+            &target,
+            "crate::types::PeerInfo",
+            None,
+            &[],
+        );
+        assert!(conversion.contains("#[allow(deprecated)]\nimpl From<PeerInfo>"));
+        assert!(conversion.contains(
+            "#[cfg(not(feature = \"deprecatedrpc-startingheight\"))]\n            starting_height: None,"
+        ));
+        assert!(conversion.contains(
+            "#[cfg(feature = \"deprecatedrpc-startingheight\")]\n            starting_height: value.starting_height,"
+        ));
+
+        let target = vec![rust_field("id", "u64"), gated];
+        let conversion = registry.generate_conversion(
+            "PeerInfo",
+            &source,
+            &target,
+            "crate::types::PeerInfo",
+            None,
+            &[],
+        );
+        assert!(!conversion.contains("not(feature"));
+
+        let required = ResponseStructField {
+            feature: Some("deprecatedrpc-startingheight".to_string()),
+            ..rust_field("starting_height", "i64")
+        };
+        let source = vec![rust_field("id", "u64"), required];
+        let target = vec![rust_field("id", "u64"), rust_field("starting_height", "i64")];
+        let conversion = registry.generate_conversion(
+            "PeerInfo",
+            &source,
+            &target,
+            "crate::types::PeerInfo",
+            None,
+            &[],
+        );
+        assert!(conversion.contains(
+            "#[cfg(feature = \"deprecatedrpc-startingheight\")]\n#[allow(deprecated)]\nimpl From<PeerInfo>"
+        ));
+        assert_eq!(conversion.matches("impl ").count(), 1);
+        assert!(!conversion.contains("missing_field"));
+        assert!(conversion.contains("            starting_height: value.starting_height,"));
+    }
+}
@@ -1,15 +1,50 @@
 //! Registry for version-specific type generators
 //!
 //! This module provides a registry that manages different version-specific
-//! generators for various implementations (Bitcoin Core, etc.).
+//! generators for various implementations (Bitcoin Core, Bitcoin Knots, btcd, Core Lightning,
+//! LND, electrs, Esplora).
+//!
+//! A multi-version crate is generated from one registry per version: the newest version's
+//! registry produces the crate root, and every registry produces a [`VersionModule`] that
+//! reuses the root response types wherever a method's response shape is unchanged, and
+//! converts (`From`/`TryFrom`) its own response types into the root ones, and into the next
+//! newer module's own ones, where it changed.
+
+use std::collections::HashMap;
 
-use ir::{ProtocolIR, RpcDef};
-use types::ProtocolVersion;
+use ir::{ProtocolIR, RpcDef, TypeDef};
+use serde_json::Value;
+use types::{Implementation, ProtocolVersion};
 
 use super::bitcoin_core_versioned::BitcoinCoreVersionedGenerator;
+use super::bitcoin_knots_versioned::BitcoinKnotsVersionedGenerator;
+use super::btcd_versioned::BtcdVersionedGenerator;
+use super::core_lightning_versioned::CoreLightningVersionedGenerator;
+use super::electrs_versioned::ElectrsVersionedGenerator;
+use super::esplora_versioned::EsploraVersionedGenerator;
+use super::lnd_versioned::LndVersionedGenerator;
+use super::version_specific_response_type::VersionSpecificResponseTypeGenerator;
+use super::version_transitions::VersionTransitionRegistry;
 use super::versioned_generator::VersionedTypeGenerator;
 use crate::Result;
 
+/// Generated files for one version of a multi-version crate
+#[derive(Debug, Clone)]
+pub struct VersionModule {
+    /// Cargo feature (and module name) of the version, e.g. `v27`
+    pub feature: String,
+    /// The version the module is bound to
+    pub version: ProtocolVersion,
+    /// Names of the RPC methods available in this version, sorted
+    pub methods: Vec<String>,
+    /// Response types whose shapes differ from the crate root's, with conversions into the
+    /// root types and the next newer module's (`types/<feature>.rs`); `None` when every
+    /// response of this version reuses the root types
+    pub response_types: Option<String>,
+    /// Client trait files bound to this version (`client_trait/<feature>/`)
+    pub client_trait: Vec<(String, String)>,
+}
+
 /// Registry for managing version-specific type generators
 ///
 /// This registry is bound to a specific implementation and version at construction time,
@@ -24,13 +59,14 @@ impl VersionedGeneratorRegistry {
     /// Create a version-specific registry from IR
     ///
     /// This method creates the appropriate generator for the specified implementation
-    /// using the provided IR instead of loading from separate metadata files.
+    /// using the provided IR instead of loading from separate metadata files. A dialect
+    /// registered at runtime uses its built-in base's generator.
     pub fn from_ir(
         implementation: &str,
         version: ProtocolVersion,
         ir: &ProtocolIR,
     ) -> Result<Self> {
-        let generator: Box<dyn VersionedTypeGenerator> = match implementation {
+        let generator: Box<dyn VersionedTypeGenerator> = match builtin_base(implementation) {
             "bitcoin_core" => {
                 let bitcoin_gen = BitcoinCoreVersionedGenerator::from_ir(version.clone(), ir)
                     .map_err(|e| {
@@ -38,6 +74,50 @@ impl VersionedGeneratorRegistry {
                     })?;
                 Box::new(bitcoin_gen)
             }
+            "bitcoin_knots" => {
+                let knots_gen = BitcoinKnotsVersionedGenerator::from_ir(version.clone(), ir)
+                    .map_err(|e| {
+                        format!("Failed to create Bitcoin Knots versioned generator from IR: {}", e)
+                    })?;
+                Box::new(knots_gen)
+            }
+            "btcd" => {
+                let btcd_gen =
+                    BtcdVersionedGenerator::from_ir(version.clone(), ir).map_err(|e| {
+                        format!("Failed to create btcd versioned generator from IR: {}", e)
+                    })?;
+                Box::new(btcd_gen)
+            }
+            "core_lightning" => {
+                let cln_gen = CoreLightningVersionedGenerator::from_ir(version.clone(), ir)
+                    .map_err(|e| {
+                        format!(
+                            "Failed to create Core Lightning versioned generator from IR: {}",
+                            e
+                        )
+                    })?;
+                Box::new(cln_gen)
+            }
+            "lnd" => {
+                let lnd_gen = LndVersionedGenerator::from_ir(version.clone(), ir).map_err(|e| {
+                    format!("Failed to create LND versioned generator from IR: {}", e)
+                })?;
+                Box::new(lnd_gen)
+            }
+            "electrs" => {
+                let electrs_gen =
+                    ElectrsVersionedGenerator::from_ir(version.clone(), ir).map_err(|e| {
+                        format!("Failed to create electrs versioned generator from IR: {}", e)
+                    })?;
+                Box::new(electrs_gen)
+            }
+            "esplora" => {
+                let esplora_gen =
+                    EsploraVersionedGenerator::from_ir(version.clone(), ir).map_err(|e| {
+                        format!("Failed to create Esplora versioned generator from IR: {}", e)
+                    })?;
+                Box::new(esplora_gen)
+            }
             _ => {
                 return Err(format!(
                     "No version-specific generator available for implementation: {}",
@@ -65,4 +145,340 @@ impl VersionedGeneratorRegistry {
     pub fn generate_client_trait(&self, methods: &[RpcDef]) -> Result<Vec<(String, String)>> {
         self.generator.generate_client_trait(&self.implementation, methods)
     }
+
+    /// Cargo feature and module name for the bound version: `v<major>`, or
+    /// `v<major>_<minor>[_<patch>]` for minor and patch releases
+    pub fn feature_name(&self) -> String {
+        let version = &self.version;
+        match (version.minor, version.patch) {
+            (0, 0) => format!("v{}", version.major),
+            (minor, 0) => format!("v{}_{}", version.major, minor),
+            (minor, patch) => format!("v{}_{}_{}", version.major, minor, patch),
+        }
+    }
+
+    /// Response type of `method` as seen by the bound version
+    ///
+    /// Bitcoin Core (and Knots) IR carries per-field `version_added`/`version_removed`; other
+    /// implementations use the IR as-is.
+    pub fn response_shape(&self, method: &RpcDef) -> Option<TypeDef> {
+        let result = method.result.as_ref()?;
+        if matches!(builtin_base(&self.implementation), "bitcoin_core" | "bitcoin_knots") {
+            Some(adapters::bitcoin_core::openrpc::filter_type_def_for_version(
+                result,
+                self.version.as_str(),
+            ))
+        } else {
+            Some(result.clone())
+        }
+    }
+
+    /// Generate the module for the bound version of a multi-version crate
+    ///
+    /// `root` is the registry the crate root was generated with and `root_methods` its
+    /// methods. Methods whose response shape matches the root's reuse the root response
+    /// types; the others get their own types in the version module, which the version's
+    /// client trait imports by name so they take precedence over the root glob import.
+    ///
+    /// `newer` is the next newer version module's registry and methods, if any: response
+    /// types both modules have their own versions of also convert into the newer module's.
+    pub fn generate_version_module(
+        &self,
+        methods: &[RpcDef],
+        root: &VersionedGeneratorRegistry,
+        root_methods: &[RpcDef],
+        newer: Option<(&VersionedGeneratorRegistry, &[RpcDef])>,
+    ) -> Result<VersionModule> {
+        let differing = self.differing_methods(methods, root, root_methods);
+
+        let feature = self.feature_name();
+        let mut client_trait = self.generate_client_trait(methods)?;
+        let response_types = if differing.is_empty() {
+            None
+        } else {
+            let (content, names) = self.local_response_types(&differing)?;
+            let mut conversions = self.generate_conversions(
+                &differing,
+                &ConversionTarget { registry: root, methods: root_methods, feature: None },
+                &names,
+            );
+            if let Some((newer, newer_methods)) = newer {
+                let newer_differing = newer.differing_methods(newer_methods, root, root_methods);
+                let (_, newer_names) = newer.local_response_types(&newer_differing)?;
+                let mut local_types = names.clone();
+                local_types.extend(newer_names);
+                conversions.push_str(&self.generate_conversions(
+                    &differing,
+                    &ConversionTarget {
+                        registry: newer,
+                        methods: &newer_differing,
+                        feature: Some(&newer.feature_name()),
+                    },
+                    &local_types,
+                ));
+            }
+            let import = format!(
+                "use crate::types::*;\n#[allow(unused_imports)]\nuse crate::types::{}::{{{}}};\n",
+                feature,
+                names.join(", ")
+            );
+            for (name, content) in client_trait.iter_mut() {
+                if name == "client.rs" {
+                    *content = content.replacen("use crate::types::*;\n", &import, 1);
+                }
+            }
+            Some(content + &conversions)
+        };
+
+        let mut names: Vec<String> = methods.iter().map(|m| m.name.clone()).collect();
+        names.sort();
+        Ok(VersionModule {
+            feature,
+            version: self.version.clone(),
+            methods: names,
+            response_types,
+            client_trait,
+        })
+    }
+
+    /// Methods whose response shape in the bound version differs from the root's
+    fn differing_methods(
+        &self,
+        methods: &[RpcDef],
+        root: &VersionedGeneratorRegistry,
+        root_methods: &[RpcDef],
+    ) -> Vec<RpcDef> {
+        let root_shapes: HashMap<&str, Option<Value>> = root_methods
+            .iter()
+            .map(|m| (m.name.as_str(), root.response_shape(m).as_ref().map(shape_key)))
+            .collect();
+        methods
+            .iter()
+            .filter(|m| {
+                root_shapes.get(m.name.as_str())
+                    != Some(&self.response_shape(m).as_ref().map(shape_key))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Response types of `differing` for the bound version's module, and the names it declares
+    fn local_response_types(&self, differing: &[RpcDef]) -> Result<(String, Vec<String>)> {
+        let files = self.generate_response_types(differing)?;
+        let content = files.into_iter().map(|(_, content)| content).collect::<String>();
+        let names = declared_type_names(&content);
+        Ok((content, names))
+    }
+
+    /// Conversions from this version's response structs into the target's structs of the
+    /// same methods; `local_types` are the types declared by this version's module and, for
+    /// a version module target, by the target's
+    fn generate_conversions(
+        &self,
+        methods: &[RpcDef],
+        target: &ConversionTarget<'_>,
+        local_types: &[String],
+    ) -> String {
+        let source_types = VersionSpecificResponseTypeGenerator::new(
+            self.version.clone(),
+            self.implementation.clone(),
+        );
+        let target_types = VersionSpecificResponseTypeGenerator::new(
+            target.registry.version.clone(),
+            target.registry.implementation.clone(),
+        );
+        let mut transitions = VersionTransitionRegistry::new();
+        let mut conversions = String::new();
+        for method in methods {
+            let Some(target_method) = target.methods.iter().find(|m| m.name == method.name) else {
+                continue;
+            };
+            let (Some(from), Some(to)) =
+                (self.response_shape(method), target.registry.response_shape(target_method))
+            else {
+                continue;
+            };
+            let (Some((name, source)), Some((target_name, target_fields))) = (
+                source_types.response_struct_fields(method),
+                target_types.response_struct_fields(target_method),
+            ) else {
+                continue;
+            };
+            transitions.analyze_struct_transitions(&name, &from, &to, &target.registry.version);
+            transitions.record_deprecations(&name, &from);
+            transitions.record_deprecations(&name, &to);
+            let target_path = match target.feature {
+                Some(feature) => format!("crate::types::{}::{}", feature, target_name),
+                None => format!("crate::types::{}", target_name),
+            };
+            let conversion = transitions.generate_conversion(
+                &name,
+                &source,
+                &target_fields,
+                &target_path,
+                target.feature,
+                local_types,
+            );
+            if !conversion.is_empty() {
+                conversions.push('\n');
+                conversions.push_str(&conversion);
+            }
+        }
+        conversions
+    }
+}
+
+/// The response types a version module's conversions convert into
+struct ConversionTarget<'a> {
+    /// Registry of the target version
+    registry: &'a VersionedGeneratorRegistry,
+    /// Methods whose response structs are converted into
+    methods: &'a [RpcDef],
+    /// Feature (and module) of a version module target; `None` for the crate root
+    feature: Option<&'a str>,
+}
+
+/// Built-in implementation whose generator `implementation` uses; unknown names are kept
+fn builtin_base(implementation: &str) -> &str {
+    implementation
+        .parse::<Implementation>()
+        .map(|i| i.builtin_base().as_str())
+        .unwrap_or(implementation)
+}
+
+/// Structural key of a response type: the serialized `TypeDef` without descriptions and
+/// version bounds, which do not change the generated Rust shape
+pub(crate) fn shape_key(ty: &TypeDef) -> Value {
+    fn strip(value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for key in ["description", "version_added", "version_removed"] {
+                    map.remove(key);
+                }
+                map.values_mut().for_each(strip);
+            }
+            Value::Array(items) => items.iter_mut().for_each(strip),
+            _ => {}
+        }
+    }
+    let mut value = serde_json::to_value(ty).unwrap_or(Value::Null);
+    strip(&mut value);
+    value
+}
+
+/// Names of the top-level `pub struct`/`pub enum`/`pub type` items declared in `content`
+fn declared_type_names(content: &str) -> Vec<String> {
+    let mut names: Vec<String> = content
+        .lines()
+        .filter_map(|line| {
+            let rest = line
+                .strip_prefix("pub struct ")
+                .or_else(|| line.strip_prefix("pub enum "))
+                .or_else(|| line.strip_prefix("pub type "))?;
+            let name: String =
+                rest.chars().take_while(|c| c.is_alphanumeric() || *c == '_').collect();
+            (!name.is_empty()).then_some(name)
+        })
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use ir::test_utils::{primitive_type, rpc, type_def};
+    use ir::{FieldDef, FieldKey, TypeKind};
+
+    use super::*;
+
+    fn registry(version: &str) -> VersionedGeneratorRegistry {
+        let version =
+            ProtocolVersion::from_string_with_protocol(version, Some("bitcoin_core".to_string()))
+                .expect("valid version");
+        VersionedGeneratorRegistry::from_ir("bitcoin_core", version, &ProtocolIR::new(vec![]))
+            .expect("registry")
+    }
+
+    fn field(name: &str, version_added: Option<&str>) -> FieldDef {
+        FieldDef {
+            key: FieldKey::Named(name.to_string()),
+            field_type: primitive_type("number", Some("number".to_string())),
+            required: true,
+            description: format!("{} description", name),
+            default_value: None,
+            version_added: version_added.map(str::to_string),
+            version_removed: None,
+            deprecated: None,
+        }
+    }
+
+    #[test]
+    fn version_module_only_holds_differing_response_types() {
+        let info = TypeDef {
+            fields: Some(vec![field("chain", None), field("blocks", Some("28"))]),
+            ..type_def("object", TypeKind::Object)
+        };
+        let count = primitive_type("number", Some("number".to_string()));
+        let methods = vec![
+            rpc("getblockchaininfo", vec![], Some(info), "blockchain"),
+            rpc("getblockcount", vec![], Some(count), "blockchain"),
+        ];
+        let root = registry("v30.2");
+
+        let v28 = registry("v28.0")
+            .generate_version_module(&methods, &root, &methods, None)
+            .expect("v28");
+        assert_eq!(v28.feature, "v28");
+        assert!(v28.response_types.is_none());
+
+        let v27 = registry("v27.0")
+            .generate_version_module(&methods, &root, &methods, None)
+            .expect("v27");
+        assert_eq!(v27.feature, "v27");
+        let types = v27.response_types.expect("getblockchaininfo differs in v27");
+        assert!(types.contains("pub struct GetBlockchainInfoResponse"));
+        assert!(!types.contains("pub blocks"));
+        let (_, client) =
+            v27.client_trait.iter().find(|(name, _)| name == "client.rs").expect("client.rs");
+        assert!(client.contains("use crate::types::v27::{GetBlockchainInfoResponse};"));
+        // The root's required `blocks` cannot be filled from v27
+        assert!(!types.contains("impl From<") && !types.contains("impl TryFrom<"));
+    }
+
+    #[test]
+    fn version_modules_convert_into_the_root_and_the_next_newer_module() {
+        let optional = |name: &str, version_added: &str| FieldDef {
+            required: false,
+            ..field(name, Some(version_added))
+        };
+        let info = TypeDef {
+            fields: Some(vec![
+                field("chain", None),
+                optional("blocks", "28"),
+                optional("headers", "30"),
+            ]),
+            ..type_def("object", TypeKind::Object)
+        };
+        let methods = vec![rpc("getblockchaininfo", vec![], Some(info), "blockchain")];
+        let root = registry("v30.2");
+        let v28 = registry("v28.0");
+
+        let v27 = registry("v27.0")
+            .generate_version_module(&methods, &root, &methods, Some((&v28, &methods)))
+            .expect("v27");
+        let types = v27.response_types.expect("getblockchaininfo differs in v27");
+        assert!(types.contains(
+            "impl From<GetBlockchainInfoResponse> for crate::types::GetBlockchainInfoResponse"
+        ));
+        assert!(types.contains(
+            "#[cfg(feature = \"v28\")]\nimpl From<GetBlockchainInfoResponse> for \
+             crate::types::v28::GetBlockchainInfoResponse"
+        ));
+
+        // v28 is the newest module here and converts into the root only
+        let v28 = v28.generate_version_module(&methods, &root, &methods, None).expect("v28");
+        let types = v28.response_types.expect("getblockchaininfo differs in v28");
+        assert!(!types.contains("crate::types::v"));
+    }
 }
@@ -0,0 +1,805 @@
+//! ZMQ subscriber generator
+//!
+//! Generates a typed subscriber for a protocol's notifications (`ProtocolDef::Message` with
+//! `MessageType::Notification`). Each notification becomes a struct decoded from its payload
+//! frame; the subscriber yields a `Stream` of decoded events and reports gaps in the per-topic
+//! sequence numbers.
+
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+
+use ir::{FieldDef, MessageDef, RpcDef, TypeDef, TypeKind};
+
+use crate::utils::{capitalize, sanitize_external_identifier};
+use crate::CodeGenerator;
+
+/// Generator for the typed ZMQ subscriber (`zmq.rs`)
+pub struct ZmqSubscriberGenerator {
+    notifications: Vec<MessageDef>,
+}
+
+impl ZmqSubscriberGenerator {
+    /// Create a ZMQ subscriber generator for the given notifications
+    pub fn new(notifications: Vec<MessageDef>) -> Self { Self { notifications } }
+}
+
+impl CodeGenerator for ZmqSubscriberGenerator {
+    fn generate(&self, _methods: &[RpcDef]) -> Vec<(String, String)> {
+        let mut code = String::new();
+        let mut readers = BTreeSet::new();
+
+        emit_header(&mut code);
+        emit_error(&mut code);
+        emit_topic_enum(&mut code, &self.notifications);
+
+        let mut emitted_enums = BTreeSet::new();
+        for message in &self.notifications {
+            for field in &message.fields {
+                if field.field_type.kind == TypeKind::Enum
+                    && emitted_enums.insert(field.field_type.name.clone())
+                {
+                    emit_label_enum(&mut code, &field.field_type);
+                }
+            }
+        }
+        for message in &self.notifications {
+            emit_notification_struct(&mut code, message, &mut readers);
+        }
+
+        emit_event_enum(&mut code, &self.notifications);
+        emit_sequence_tracking(&mut code);
+        emit_subscriber(&mut code);
+        emit_payload_reader(&mut code, &readers);
+        emit_test_publisher(&mut code);
+        emit_zeromq_socket(&mut code);
+
+        vec![("zmq.rs".to_string(), code)]
+    }
+}
+
+/// How a payload field is read and which Rust type it decodes to
+struct FieldCodec {
+    rust_type: String,
+    reader: &'static str,
+    decode: String,
+}
+
+/// Map a payload field's wire encoding (`protocol_type`) to its Rust type and decoder
+fn field_codec(field_type: &TypeDef) -> FieldCodec {
+    let hash = |rust_type: &str| FieldCodec {
+        rust_type: rust_type.to_string(),
+        reader: "hash",
+        decode: format!(
+            "<{} as bitcoin::hashes::Hash>::from_byte_array(reader.hash()?)",
+            rust_type
+        ),
+    };
+    let consensus = |rust_type: &str| FieldCodec {
+        rust_type: rust_type.to_string(),
+        reader: "consensus",
+        decode: "reader.consensus()?".to_string(),
+    };
+
+    if field_type.kind == TypeKind::Enum {
+        return FieldCodec {
+            rust_type: field_type.name.clone(),
+            reader: "byte",
+            decode: format!(
+                "{}::from_byte(reader.byte()?).ok_or_else(|| reader.error(\"unknown {}\"))?",
+                field_type.name, field_type.name
+            ),
+        };
+    }
+
+    match field_type.protocol_type.as_deref().unwrap_or(field_type.name.as_str()) {
+        "blockhash" => hash("bitcoin::BlockHash"),
+        "txid" => hash("bitcoin::Txid"),
+        "hash256" => hash("bitcoin::hashes::sha256d::Hash"),
+        "block" => consensus("bitcoin::Block"),
+        "transaction" => consensus("bitcoin::Transaction"),
+        "u64le" => FieldCodec {
+            rust_type: "u64".to_string(),
+            reader: "u64_le",
+            decode: "reader.u64_le()?".to_string(),
+        },
+        // Unknown encodings fall back to the raw remaining bytes
+        _ => FieldCodec {
+            rust_type: "Vec<u8>".to_string(),
+            reader: "rest",
+            decode: "reader.rest()".to_string(),
+        },
+    }
+}
+
+/// Rust type name for a notification (e.g. `hashblock` -> `HashblockNotification`)
+fn notification_type_name(message: &MessageDef) -> String {
+    format!("{}Notification", topic_variant(message))
+}
+
+/// `ZmqTopic`/`ZmqEvent` variant name for a notification
+fn topic_variant(message: &MessageDef) -> String { capitalize(&message.name) }
+
+/// Wire topic of a notification; defaults to the message name
+fn topic_name(message: &MessageDef) -> &str {
+    message.topic.as_deref().unwrap_or(message.name.as_str())
+}
+
+fn field_ident(field: &FieldDef) -> String { sanitize_external_identifier(&field.key.as_ident()) }
+
+fn emit_header(code: &mut String) {
+    writeln!(
+        code,
+        r#"//! Typed subscriber for ZMQ notifications
+//!
+//! Every message is a multipart `[topic, payload, sequence]` message. The payload is decoded
+//! into a typed event and the little-endian per-topic sequence number is checked for gaps.
+//! Any [`ZmqFrameSource`] can feed the subscriber: a ZMQ SUB socket (with the `zmq` feature)
+//! or the in-process [`ZmqTestPublisher`] stand-in.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use futures_util::Stream;
+use thiserror::Error;
+"#
+    )
+    .expect("Failed to write ZMQ header");
+}
+
+fn emit_error(code: &mut String) {
+    writeln!(
+        code,
+        r#"/// Errors raised while receiving or decoding ZMQ notifications
+#[derive(Debug, Error)]
+pub enum ZmqError {{
+    /// The underlying socket failed
+    #[error("ZMQ transport error: {{0}}")]
+    Transport(String),
+    /// The multipart message does not have the expected frames
+    #[error("Malformed ZMQ message: {{0}}")]
+    Malformed(String),
+    /// The topic frame names a topic this subscriber does not know
+    #[error("Unknown ZMQ topic: {{0}}")]
+    UnknownTopic(String),
+    /// The payload does not match the topic's layout
+    #[error("Failed to decode {{topic}} payload: {{message}}")]
+    Decode {{
+        /// Topic of the message
+        topic: &'static str,
+        /// What went wrong
+        message: String,
+    }},
+}}
+
+/// Source of multipart ZMQ messages
+#[async_trait]
+pub trait ZmqFrameSource: Send {{
+    /// Receive the next multipart message, or `None` once the source is closed
+    async fn recv_multipart(&mut self) -> Result<Option<Vec<Vec<u8>>>, ZmqError>;
+}}
+"#
+    )
+    .expect("Failed to write ZMQ error");
+}
+
+fn emit_topic_enum(code: &mut String, notifications: &[MessageDef]) {
+    let mut variants = String::new();
+    let mut as_str = String::new();
+    let mut from_bytes = String::new();
+    let mut sequenced = String::new();
+    let mut all = Vec::new();
+    for message in notifications {
+        let variant = topic_variant(message);
+        let topic = topic_name(message);
+        writeln!(variants, "    /// `{}`: {}", topic, message.description.trim())
+            .expect("Failed to write topic doc");
+        writeln!(variants, "    {},", variant).expect("Failed to write topic variant");
+        writeln!(as_str, "            ZmqTopic::{} => \"{}\",", variant, topic)
+            .expect("Failed to write topic name");
+        writeln!(from_bytes, "            b\"{}\" => Some(ZmqTopic::{}),", topic, variant)
+            .expect("Failed to write topic match");
+        writeln!(sequenced, "            ZmqTopic::{} => {},", variant, message.sequenced)
+            .expect("Failed to write topic sequencing");
+        all.push(format!("ZmqTopic::{}", variant));
+    }
+
+    writeln!(
+        code,
+        r#"/// Topics a node publishes notifications under
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ZmqTopic {{
+{variants}}}
+
+impl ZmqTopic {{
+    /// Every known topic
+    pub const ALL: [ZmqTopic; {count}] = [{all}];
+
+    /// Topic name as sent in the first frame
+    pub fn as_str(&self) -> &'static str {{
+        match self {{
+{as_str}        }}
+    }}
+
+    /// Parse a topic frame
+    pub fn from_bytes(topic: &[u8]) -> Option<Self> {{
+        match topic {{
+{from_bytes}            _ => None,
+        }}
+    }}
+
+    /// Whether messages on this topic carry a sequence number frame
+    pub fn is_sequenced(&self) -> bool {{
+        match self {{
+{sequenced}        }}
+    }}
+}}
+
+impl std::fmt::Display for ZmqTopic {{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{ f.write_str(self.as_str()) }}
+}}
+"#,
+        count = all.len(),
+        all = all.join(", "),
+    )
+    .expect("Failed to write ZMQ topic enum");
+}
+
+/// Emit an enum encoded as a single ASCII byte (e.g. the `sequence` topic's event label)
+fn emit_label_enum(code: &mut String, type_def: &TypeDef) {
+    let variants = type_def.variants.as_deref().unwrap_or_default();
+    let mut body = String::new();
+    let mut from_byte = String::new();
+    let mut as_byte = String::new();
+    for variant in variants {
+        let Some(value) = variant.value.as_deref().and_then(|v| v.bytes().next()) else {
+            continue;
+        };
+        writeln!(body, "    /// {} (`{}`)", variant.description, value as char)
+            .expect("Failed to write label doc");
+        writeln!(body, "    {},", variant.name).expect("Failed to write label variant");
+        writeln!(from_byte, "            b'{}' => Some(Self::{}),", value as char, variant.name)
+            .expect("Failed to write label match");
+        writeln!(as_byte, "            Self::{} => b'{}',", variant.name, value as char)
+            .expect("Failed to write label byte");
+    }
+
+    writeln!(
+        code,
+        r#"/// {description}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum {name} {{
+{body}}}
+
+impl {name} {{
+    /// Parse the label byte
+    pub fn from_byte(byte: u8) -> Option<Self> {{
+        match byte {{
+{from_byte}            _ => None,
+        }}
+    }}
+
+    /// The label byte on the wire
+    pub fn as_byte(&self) -> u8 {{
+        match self {{
+{as_byte}        }}
+    }}
+}}
+"#,
+        description = type_def.description,
+        name = type_def.name,
+    )
+    .expect("Failed to write label enum");
+}
+
+fn emit_notification_struct(
+    code: &mut String,
+    message: &MessageDef,
+    readers: &mut BTreeSet<&'static str>,
+) {
+    let name = notification_type_name(message);
+    let mut fields = String::new();
+    let mut decode = String::new();
+    let mut idents = Vec::new();
+    for field in &message.fields {
+        let codec = field_codec(&field.field_type);
+        readers.insert(codec.reader);
+        let ident = field_ident(field);
+        if !field.description.is_empty() {
+            writeln!(fields, "    /// {}", field.description).expect("Failed to write field doc");
+        }
+        if field.required {
+            writeln!(fields, "    pub {}: {},", ident, codec.rust_type)
+                .expect("Failed to write field");
+            writeln!(decode, "        let {} = {};", ident, codec.decode)
+                .expect("Failed to write field decode");
+        } else {
+            // Optional trailing fields are present only when bytes remain
+            readers.insert("is_empty");
+            writeln!(fields, "    pub {}: Option<{}>,", ident, codec.rust_type)
+                .expect("Failed to write field");
+            writeln!(
+                decode,
+                "        let {} = if reader.is_empty() {{ None }} else {{ Some({}) }};",
+                ident, codec.decode
+            )
+            .expect("Failed to write field decode");
+        }
+        idents.push(ident);
+    }
+    let reader_binding = if message.fields.is_empty() { "reader" } else { "mut reader" };
+
+    writeln!(
+        code,
+        r#"/// `{topic}` notification: {description}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct {name} {{
+{fields}}}
+
+impl {name} {{
+    /// Decode the payload frame
+    pub fn decode(payload: &[u8]) -> Result<Self, ZmqError> {{
+        let {reader_binding} = PayloadReader::new("{topic}", payload);
+{decode}        reader.finish()?;
+        Ok(Self {{ {idents} }})
+    }}
+}}
+"#,
+        topic = topic_name(message),
+        description = message.description.trim(),
+        idents = idents.join(", "),
+    )
+    .expect("Failed to write notification struct");
+}
+
+fn emit_event_enum(code: &mut String, notifications: &[MessageDef]) {
+    let mut variants = String::new();
+    let mut decode = String::new();
+    let mut topic = String::new();
+    for message in notifications {
+        let variant = topic_variant(message);
+        let ty = notification_type_name(message);
+        writeln!(variants, "    /// `{}` notification", topic_name(message))
+            .expect("Failed to write event doc");
+        writeln!(variants, "    {}({}),", variant, ty).expect("Failed to write event variant");
+        writeln!(
+            decode,
+            "            ZmqTopic::{variant} => {ty}::decode(payload).map(ZmqEvent::{variant}),"
+        )
+        .expect("Failed to write event decode");
+        writeln!(topic, "            ZmqEvent::{variant}(_) => ZmqTopic::{variant},")
+            .expect("Failed to write event topic");
+    }
+
+    writeln!(
+        code,
+        r#"/// A decoded notification payload
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ZmqEvent {{
+{variants}}}
+
+impl ZmqEvent {{
+    /// Decode the payload frame of a message published under `topic`
+    pub fn decode(topic: ZmqTopic, payload: &[u8]) -> Result<Self, ZmqError> {{
+        match topic {{
+{decode}        }}
+    }}
+
+    /// Topic this event was published under
+    pub fn topic(&self) -> ZmqTopic {{
+        match self {{
+{topic}        }}
+    }}
+}}
+"#
+    )
+    .expect("Failed to write ZMQ event enum");
+}
+
+fn emit_sequence_tracking(code: &mut String) {
+    writeln!(
+        code,
+        r#"/// Messages missed on a topic, detected from its sequence numbers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SequenceGap {{
+    /// Sequence number that should have arrived next
+    pub expected: u32,
+    /// Sequence number that arrived instead
+    pub received: u32,
+}}
+
+impl SequenceGap {{
+    /// Number of messages missed (wrapping at `u32::MAX`)
+    pub fn missed(&self) -> u32 {{ self.received.wrapping_sub(self.expected) }}
+}}
+
+/// Tracks the last sequence number seen on each topic
+#[derive(Debug, Clone, Default)]
+pub struct SequenceTracker {{
+    last: HashMap<ZmqTopic, u32>,
+}}
+
+impl SequenceTracker {{
+    /// Create an empty tracker
+    pub fn new() -> Self {{ Self::default() }}
+
+    /// Record `sequence` for `topic`, returning the gap if messages were skipped.
+    ///
+    /// The first message on a topic never reports a gap, since a subscriber may join late.
+    pub fn observe(&mut self, topic: ZmqTopic, sequence: u32) -> Option<SequenceGap> {{
+        let gap = self.last.get(&topic).and_then(|last| {{
+            let expected = last.wrapping_add(1);
+            (sequence != expected).then_some(SequenceGap {{ expected, received: sequence }})
+        }});
+        self.last.insert(topic, sequence);
+        gap
+    }}
+
+    /// Last sequence number seen on `topic`
+    pub fn last(&self, topic: ZmqTopic) -> Option<u32> {{ self.last.get(&topic).copied() }}
+}}
+
+/// A decoded notification with its sequence information
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZmqNotification {{
+    /// Topic the message was published under
+    pub topic: ZmqTopic,
+    /// Per-topic sequence number, for sequenced topics
+    pub sequence: Option<u32>,
+    /// Messages missed on this topic just before this one
+    pub gap: Option<SequenceGap>,
+    /// The decoded payload
+    pub event: ZmqEvent,
+}}
+"#
+    )
+    .expect("Failed to write ZMQ sequence tracking");
+}
+
+fn emit_subscriber(code: &mut String) {
+    writeln!(
+        code,
+        r#"/// Typed subscriber over a [`ZmqFrameSource`]
+#[derive(Debug)]
+pub struct ZmqSubscriber<S> {{
+    source: S,
+    tracker: SequenceTracker,
+}}
+
+impl<S: ZmqFrameSource> ZmqSubscriber<S> {{
+    /// Create a subscriber reading from `source`
+    pub fn new(source: S) -> Self {{ Self {{ source, tracker: SequenceTracker::new() }} }}
+
+    /// Sequence numbers seen so far
+    pub fn tracker(&self) -> &SequenceTracker {{ &self.tracker }}
+
+    /// Receive and decode the next notification, or `None` once the source is closed
+    pub async fn next_notification(&mut self) -> Option<Result<ZmqNotification, ZmqError>> {{
+        match self.source.recv_multipart().await {{
+            Ok(Some(frames)) => Some(self.decode_frames(&frames)),
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }}
+    }}
+
+    /// Turn the subscriber into a stream of notifications
+    pub fn into_stream(self) -> impl Stream<Item = Result<ZmqNotification, ZmqError>> {{
+        futures_util::stream::unfold(self, |mut subscriber| async move {{
+            let item = subscriber.next_notification().await?;
+            Some((item, subscriber))
+        }})
+    }}
+
+    fn decode_frames(&mut self, frames: &[Vec<u8>]) -> Result<ZmqNotification, ZmqError> {{
+        let topic_frame = frames
+            .first()
+            .ok_or_else(|| ZmqError::Malformed("empty multipart message".to_string()))?;
+        let topic = ZmqTopic::from_bytes(topic_frame).ok_or_else(|| {{
+            ZmqError::UnknownTopic(String::from_utf8_lossy(topic_frame).into_owned())
+        }})?;
+        let expected_frames = if topic.is_sequenced() {{ 3 }} else {{ 2 }};
+        if frames.len() != expected_frames {{
+            return Err(ZmqError::Malformed(format!(
+                "{{}} message has {{}} frames, expected {{}}",
+                topic,
+                frames.len(),
+                expected_frames
+            )));
+        }}
+
+        let sequence = match frames.get(2) {{
+            Some(frame) => {{
+                let bytes: [u8; 4] = frame.as_slice().try_into().map_err(|_| {{
+                    ZmqError::Malformed(format!(
+                        "{{}} sequence frame has {{}} bytes, expected 4",
+                        topic,
+                        frame.len()
+                    ))
+                }})?;
+                Some(u32::from_le_bytes(bytes))
+            }}
+            None => None,
+        }};
+        let event = ZmqEvent::decode(topic, &frames[1])?;
+        let gap = sequence.and_then(|sequence| self.tracker.observe(topic, sequence));
+        Ok(ZmqNotification {{ topic, sequence, gap, event }})
+    }}
+}}
+"#
+    )
+    .expect("Failed to write ZMQ subscriber");
+}
+
+/// Emit the payload reader with only the read methods the notifications use
+fn emit_payload_reader(code: &mut String, readers: &BTreeSet<&'static str>) {
+    writeln!(
+        code,
+        r#"/// Sequential reader over a payload frame
+struct PayloadReader<'a> {{
+    topic: &'static str,
+    bytes: &'a [u8],
+}}
+
+impl<'a> PayloadReader<'a> {{
+    fn new(topic: &'static str, bytes: &'a [u8]) -> Self {{ Self {{ topic, bytes }} }}
+
+    fn error(&self, message: impl Into<String>) -> ZmqError {{
+        ZmqError::Decode {{ topic: self.topic, message: message.into() }}
+    }}
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ZmqError> {{
+        if self.bytes.len() < len {{
+            return Err(self.error(format!("expected {{}} more bytes, found {{}}", len, self.bytes.len())));
+        }}
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }}
+
+    fn finish(&self) -> Result<(), ZmqError> {{
+        if self.bytes.is_empty() {{
+            Ok(())
+        }} else {{
+            Err(self.error(format!("{{}} trailing bytes", self.bytes.len())))
+        }}
+    }}"#
+    )
+    .expect("Failed to write payload reader");
+
+    if readers.contains("is_empty") {
+        writeln!(
+            code,
+            r#"
+    fn is_empty(&self) -> bool {{ self.bytes.is_empty() }}"#
+        )
+        .expect("Failed to write payload reader");
+    }
+    if readers.contains("hash") {
+        writeln!(
+            code,
+            r#"
+    /// 32-byte hash sent in display order, returned in internal byte order
+    fn hash(&mut self) -> Result<[u8; 32], ZmqError> {{
+        let mut bytes: [u8; 32] = self.take(32)?.try_into().map_err(|_| self.error("hash"))?;
+        bytes.reverse();
+        Ok(bytes)
+    }}"#
+        )
+        .expect("Failed to write payload reader");
+    }
+    if readers.contains("u64_le") {
+        writeln!(
+            code,
+            r#"
+    fn u64_le(&mut self) -> Result<u64, ZmqError> {{
+        let bytes: [u8; 8] = self.take(8)?.try_into().map_err(|_| self.error("u64"))?;
+        Ok(u64::from_le_bytes(bytes))
+    }}"#
+        )
+        .expect("Failed to write payload reader");
+    }
+    if readers.contains("byte") {
+        writeln!(
+            code,
+            r#"
+    fn byte(&mut self) -> Result<u8, ZmqError> {{ Ok(self.take(1)?[0]) }}"#
+        )
+        .expect("Failed to write payload reader");
+    }
+    if readers.contains("consensus") {
+        writeln!(
+            code,
+            r#"
+    /// Consensus-decode the rest of the payload
+    fn consensus<T: bitcoin::consensus::Decodable>(&mut self) -> Result<T, ZmqError> {{
+        let value = bitcoin::consensus::deserialize(self.bytes).map_err(|e| self.error(e.to_string()))?;
+        self.bytes = &[];
+        Ok(value)
+    }}"#
+        )
+        .expect("Failed to write payload reader");
+    }
+    if readers.contains("rest") {
+        writeln!(
+            code,
+            r#"
+    fn rest(&mut self) -> Vec<u8> {{ std::mem::take(&mut self.bytes).to_vec() }}"#
+        )
+        .expect("Failed to write payload reader");
+    }
+    writeln!(code, "}}\n").expect("Failed to write payload reader end");
+}
+
+fn emit_test_publisher(code: &mut String) {
+    writeln!(
+        code,
+        r#"/// In-process stand-in for a node's ZMQ publisher, for tests
+///
+/// Sequence numbers are assigned per topic starting at 0, like the node does.
+#[derive(Debug)]
+pub struct ZmqTestPublisher {{
+    sender: tokio::sync::mpsc::Sender<Vec<Vec<u8>>>,
+    next_sequence: HashMap<ZmqTopic, u32>,
+}}
+
+/// [`ZmqFrameSource`] receiving from a [`ZmqTestPublisher`]
+#[derive(Debug)]
+pub struct ChannelFrameSource {{
+    receiver: tokio::sync::mpsc::Receiver<Vec<Vec<u8>>>,
+}}
+
+/// Create a connected test publisher and frame source
+pub fn zmq_test_channel(capacity: usize) -> (ZmqTestPublisher, ChannelFrameSource) {{
+    let (sender, receiver) = tokio::sync::mpsc::channel(capacity);
+    (ZmqTestPublisher {{ sender, next_sequence: HashMap::new() }}, ChannelFrameSource {{ receiver }})
+}}
+
+impl ZmqTestPublisher {{
+    /// Publish `payload` on `topic` with the topic's next sequence number
+    pub async fn publish(&mut self, topic: ZmqTopic, payload: Vec<u8>) -> Result<(), ZmqError> {{
+        let sequence = self.next_sequence.get(&topic).copied().unwrap_or(0);
+        self.publish_with_sequence(topic, payload, sequence).await
+    }}
+
+    /// Publish `payload` on `topic` with an explicit sequence number (e.g. to simulate a gap)
+    pub async fn publish_with_sequence(
+        &mut self,
+        topic: ZmqTopic,
+        payload: Vec<u8>,
+        sequence: u32,
+    ) -> Result<(), ZmqError> {{
+        self.next_sequence.insert(topic, sequence.wrapping_add(1));
+        let mut frames = vec![topic.as_str().as_bytes().to_vec(), payload];
+        if topic.is_sequenced() {{
+            frames.push(sequence.to_le_bytes().to_vec());
+        }}
+        self.send_frames(frames).await
+    }}
+
+    /// Send raw frames as one multipart message
+    pub async fn send_frames(&self, frames: Vec<Vec<u8>>) -> Result<(), ZmqError> {{
+        self.sender
+            .send(frames)
+            .await
+            .map_err(|_| ZmqError::Transport("subscriber dropped".to_string()))
+    }}
+}}
+
+#[async_trait]
+impl ZmqFrameSource for ChannelFrameSource {{
+    async fn recv_multipart(&mut self) -> Result<Option<Vec<Vec<u8>>>, ZmqError> {{
+        Ok(self.receiver.recv().await)
+    }}
+}}
+"#
+    )
+    .expect("Failed to write ZMQ test publisher");
+}
+
+fn emit_zeromq_socket(code: &mut String) {
+    writeln!(
+        code,
+        r#"#[cfg(feature = "zmq")]
+#[async_trait]
+impl ZmqFrameSource for zeromq::SubSocket {{
+    async fn recv_multipart(&mut self) -> Result<Option<Vec<Vec<u8>>>, ZmqError> {{
+        use zeromq::SocketRecv;
+        let message = self.recv().await.map_err(|e| ZmqError::Transport(e.to_string()))?;
+        Ok(Some(message.iter().map(|frame| frame.to_vec()).collect()))
+    }}
+}}
+
+#[cfg(feature = "zmq")]
+impl ZmqSubscriber<zeromq::SubSocket> {{
+    /// Connect to a node's ZMQ endpoint (e.g. `tcp://127.0.0.1:28332`) and subscribe to `topics`
+    pub async fn connect(endpoint: &str, topics: &[ZmqTopic]) -> Result<Self, ZmqError> {{
+        use zeromq::Socket;
+        let mut socket = zeromq::SubSocket::new();
+        socket.connect(endpoint).await.map_err(|e| ZmqError::Transport(e.to_string()))?;
+        for topic in topics {{
+            socket.subscribe(topic.as_str()).await.map_err(|e| ZmqError::Transport(e.to_string()))?;
+        }}
+        Ok(Self::new(socket))
+    }}
+}}"#
+    )
+    .expect("Failed to write zeromq socket support");
+}
+
+#[cfg(test)]
+mod tests {
+    use ir::test_utils::{notification, primitive_type};
+    use ir::{FieldKey, VariantDef};
+
+    use super::*;
+
+    fn payload_field(key: &str, protocol_type: &str, required: bool) -> FieldDef {
+        FieldDef {
+            key: FieldKey::Named(key.to_string()),
+            field_type: primitive_type(protocol_type, Some(protocol_type.to_string())),
+            required,
+            description: String::new(),
+            default_value: None,
+            version_added: None,
+            version_removed: None,
+            deprecated: None,
+        }
+    }
+
+    #[test]
+    fn notifications_become_topics_and_structs() {
+        let hashblock = notification(
+            "hashblock",
+            "hashblock",
+            vec![payload_field("blockhash", "blockhash", true)],
+        );
+        let rawtx = notification("rawtx", "rawtx", vec![payload_field("tx", "transaction", true)]);
+
+        let files = ZmqSubscriberGenerator::new(vec![hashblock, rawtx]).generate(&[]);
+        let code = &files[0].1;
+
+        assert_eq!(files[0].0, "zmq.rs");
+        assert!(code.contains("b\"hashblock\" => Some(ZmqTopic::Hashblock),"));
+        assert!(code.contains("pub block_hash: bitcoin::BlockHash,"));
+        assert!(code.contains("pub tx: bitcoin::Transaction,"));
+        assert!(code.contains("fn consensus<T: bitcoin::consensus::Decodable>"));
+        assert!(!code.contains("fn u64_le"));
+    }
+
+    #[test]
+    fn optional_fields_and_label_enums() {
+        let mut label = type_def_enum();
+        label.protocol_type = Some("char".to_string());
+        let sequence = notification(
+            "sequence",
+            "sequence",
+            vec![
+                payload_field("hash", "hash256", true),
+                FieldDef { field_type: label, ..payload_field("label", "char", true) },
+                payload_field("mempool_sequence", "u64le", false),
+            ],
+        );
+
+        let code = &ZmqSubscriberGenerator::new(vec![sequence]).generate(&[])[0].1;
+
+        assert!(code.contains("b'C' => Some(Self::BlockConnected),"));
+        assert!(code.contains("pub mempool_sequence: Option<u64>,"));
+        assert!(code.contains(
+            "let mempool_sequence = if reader.is_empty() { None } else { Some(reader.u64_le()?) };"
+        ));
+        assert!(code.contains("ZmqTopic::Sequence => true,"));
+    }
+
+    fn type_def_enum() -> TypeDef {
+        TypeDef {
+            name: "SequenceLabel".to_string(),
+            kind: TypeKind::Enum,
+            variants: Some(vec![VariantDef {
+                name: "BlockConnected".to_string(),
+                description: "Block connected".to_string(),
+                value: Some("C".to_string()),
+                associated_data: None,
+            }]),
+            ..TypeDef::default()
+        }
+    }
+}
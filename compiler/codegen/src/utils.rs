@@ -3,7 +3,7 @@
 use ir::RpcDef;
 use normalization::UnmappedMethodContext;
 pub use normalization::{SuggestedMapping, UnmappedMethodsError};
-use types::Argument;
+use types::{Argument, Implementation};
 
 /// Validates that every method has a mapping for the given protocol.
 /// Returns an error listing all unmapped methods with suggested keys so the caller
@@ -13,7 +13,7 @@ pub fn validate_method_mappings(
     methods: &[RpcDef],
 ) -> Result<(), UnmappedMethodsError> {
     let method_names: Vec<String> = methods.iter().map(|m| m.name.clone()).collect();
-    normalization::validate_method_mappings(protocol, &method_names)
+    normalization::validate_method_mappings(mapping_key(protocol), &method_names)
 }
 
 /// Strict registry-driven conversion: adapter-specific RPC → canonical → snake_case
@@ -56,10 +56,9 @@ pub fn rpc_method_to_rust_name(rpc_method: &str) -> String {
 pub fn suggest_canonical_key(rpc_method: &str) -> String {
     // Known acronym- or brand-like names that don't follow simple word capitalization.
     // Keep this list small and reserved for truly exceptional cases.
-    match rpc_method {
-        // Bitcoin Core OpenRPC helper method: keep "RPC" fully capitalized.
-        "getopenrpcinfo" => return "GetOpenRpcInfo".to_string(),
-        _ => {}
+    // Bitcoin Core OpenRPC helper method: keep "RPC" fully capitalized.
+    if rpc_method == "getopenrpcinfo" {
+        return "GetOpenRpcInfo".to_string();
     }
 
     let lower = rpc_method.to_lowercase();
@@ -280,7 +279,19 @@ fn segment_lowercase_method_name(s: &str) -> Vec<String> {
 /// Resolves the canonical PascalCase name from an adapter-specific RPC using
 /// normalization presets. If the method is unmapped and [context] is provided,
 /// the error message includes suggested mapping, category, and description.
-pub use normalization::canonical_from_adapter_method;
+pub fn canonical_from_adapter_method(
+    protocol: &str,
+    rpc_method: &str,
+    context: Option<&UnmappedMethodContext<'_>>,
+) -> Result<String, String> {
+    normalization::canonical_from_adapter_method(mapping_key(protocol), rpc_method, context)
+}
+
+/// Normalization key of `protocol`: dialects registered at runtime use their built-in base's
+/// method mappings
+fn mapping_key(protocol: &str) -> &str {
+    protocol.parse::<Implementation>().map(|i| i.builtin_base().as_str()).unwrap_or(protocol)
+}
 
 /// Convert camelCase to snake_case
 fn camel_to_snake_case(input: &str) -> String {
@@ -590,7 +601,7 @@ pub fn sanitize_external_identifier(name: &str) -> String {
 /// Replaces `/` and `-` with `_`, then converts to upper camel case (PascalCase) so
 /// `GetrawaddrmanBucket/position` -> `GetrawaddrmanBucketPosition` and we avoid non_camel_case_types warnings.
 pub fn sanitize_type_name_for_rust(name: &str) -> String {
-    let mut with_underscores = name.replace('/', "_").replace('-', "_");
+    let mut with_underscores = name.replace(['/', '-'], "_");
     with_underscores = with_underscores
         .replace("Lastprocessedblock", "Last_Processed_Block")
         .replace("Pubnonces", "Pub_Nonces");
@@ -1,4 +1,4 @@
-// Generated client trait for Bitcoin Core {{VERSION}}
+// Generated client trait for {{PROTOCOL_DISPLAY}} {{VERSION}}
 
 use async_trait::async_trait;
 use crate::transport::{TransportTrait, TransportError};
@@ -8,9 +8,9 @@ use serde::de::DeserializeOwned;
 
 {{PARAM_STRUCTS}}
 
-#[doc = r#"A versioned client trait for Bitcoin Core {{VERSION}}"#]
+#[doc = r#"A versioned client trait for {{PROTOCOL_DISPLAY}} {{VERSION}}"#]
 #[async_trait]
-pub trait BitcoinClient: Send + Sync + TransportTrait + TransportExt + RpcDispatchExt {
+pub trait {{CLIENT_TRAIT}}: Send + Sync + TransportTrait + TransportExt + RpcDispatchExt {
     type Error;
 
 {{TRAIT_METHOD_SIGNATURES}}
@@ -52,7 +52,7 @@ impl<T: TransportTrait + TransportExt + ?Sized> WalletTransportExt for T {}
 
 // Provide default implementation for any type that implements TransportTrait + TransportExt
 #[async_trait]
-impl<T: TransportTrait + TransportExt + Send + Sync> BitcoinClient for T {
+impl<T: TransportTrait + TransportExt + Send + Sync> {{CLIENT_TRAIT}} for T {
     type Error = TransportError;
 
 {{TRAIT_METHOD_IMPLEMENTATIONS}}
@@ -0,0 +1,234 @@
+//! Runtime version negotiation for {{PROTOCOL_DISPLAY}}
+//!
+//! [`VersionedClient::connect_auto`] asks the node for its version (`getnetworkinfo`) and binds
+//! the transport to the matching version module. The client is itself a transport, so any
+//! version module's client trait can be used on it for typed calls:
+//!
+//! ```ignore
+//! use crate::client_trait::{{FIRST_FEATURE}}::{{CLIENT_TRAIT}};
+//!
+//! let client = VersionedClient::connect_auto(transport).await?;
+//! client.getblockchaininfo().await?;
+//! ```
+//!
+//! Every request is checked against the bound version first: [`VersionedClient::call`] fails
+//! with [`NegotiationError::UnsupportedOnVersion`] instead of the node's "Method not found".
+//! Typed trait methods fail with [`TransportError::Negotiation`], whose source is that error;
+//! [`NegotiationError::from_transport_error`] recovers it.
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::transport::core::TransportExt;
+use crate::transport::{TransportError, TransportTrait};
+
+/// A node version as reported by `getnetworkinfo`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProtocolVersion {
+    /// Major version component (e.g. `30` in `30.2.0`)
+    pub major: u32,
+    /// Minor version component
+    pub minor: u32,
+    /// Patch version component
+    pub patch: u32,
+}
+
+impl ProtocolVersion {
+    /// Create a version from its components
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self { Self { major, minor, patch } }
+
+    /// Parse the `version` (e.g. `300200`) or, failing that, the `subversion` user agent
+    /// (e.g. `/Satoshi:30.2.0/`) of a `getnetworkinfo` response
+    pub fn from_network_info(info: &Value) -> Option<Self> {
+        if let Some(version) = info.get("version").and_then(Value::as_u64).filter(|v| *v > 0) {
+            let version = u32::try_from(version).ok()?;
+            return Some(Self::new(version / 10_000, version / 100 % 100, version % 100));
+        }
+        info.get("subversion").and_then(Value::as_str).and_then(Self::from_subversion)
+    }
+
+    /// Parse a user agent such as `/Satoshi:30.2.0/` or `/Satoshi:29.1.0(knots)/`
+    pub fn from_subversion(subversion: &str) -> Option<Self> {
+        let agent = subversion.trim_matches('/').split('/').next()?;
+        let (_, version) = agent.split_once(':')?;
+        let version: String =
+            version.chars().take_while(|c| c.is_ascii_digit() || *c == '.').collect();
+        let mut parts = version.split('.').map(str::parse::<u32>);
+        let major = parts.next()?.ok()?;
+        let minor = parts.next().unwrap_or(Ok(0)).ok()?;
+        let patch = parts.next().unwrap_or(Ok(0)).ok()?;
+        Some(Self::new(major, minor, patch))
+    }
+}
+
+impl std::fmt::Display for ProtocolVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Errors of a version-negotiated client
+#[derive(Debug, thiserror::Error)]
+pub enum NegotiationError {
+    /// The transport failed or the node returned an error
+    #[error(transparent)]
+    Transport(#[from] TransportError),
+    /// `getnetworkinfo` did not report a parseable version
+    #[error("Could not determine the node version from getnetworkinfo")]
+    UnknownVersion,
+    /// No enabled version feature covers the node's version
+    #[error("Node version {version} is older than every enabled version feature ({{FEATURES}})")]
+    UnsupportedNode {
+        /// The node's version
+        version: ProtocolVersion,
+    },
+    /// The method is not part of the version the client is bound to
+    #[error("{method} is not available on this node{}", since_suffix(.available_since))]
+    UnsupportedOnVersion {
+        /// The requested RPC method
+        method: String,
+        /// The first generated version that has the method, if any
+        available_since: Option<ProtocolVersion>,
+    },
+}
+
+impl NegotiationError {
+    /// The negotiation error a typed trait method failed with, if it was rejected by a
+    /// [`VersionedClient`] rather than the transport or the node
+    pub fn from_transport_error(error: &TransportError) -> Option<&Self> {
+        match error {
+            TransportError::Negotiation(source) => source.downcast_ref(),
+            _ => None,
+        }
+    }
+}
+
+impl From<NegotiationError> for TransportError {
+    fn from(error: NegotiationError) -> Self {
+        match error {
+            NegotiationError::Transport(error) => error,
+            error => TransportError::Negotiation(Box::new(error)),
+        }
+    }
+}
+
+/// RPC methods and the versions (of those generated) that have them, sorted by name
+const METHOD_AVAILABILITY: &[(&str, &[ProtocolVersion])] = &[
+{{METHOD_AVAILABILITY}}];
+
+/// A transport bound to the version module matching the node it talks to
+///
+/// Nodes newer than the newest enabled version bind to that version; a node between two
+/// enabled versions binds to the older one.
+#[derive(Debug)]
+pub enum VersionedClient<T> {
+{{VARIANTS}}}
+
+impl<T: TransportTrait + TransportExt + Send + Sync> VersionedClient<T> {
+    /// Query the node's version and bind `transport` to the matching version module
+    pub async fn connect_auto(transport: T) -> Result<Self, NegotiationError> {
+        let info: Value = transport.call("getnetworkinfo", &[]).await?;
+        let version =
+            ProtocolVersion::from_network_info(&info).ok_or(NegotiationError::UnknownVersion)?;
+        Self::bind(version, transport)
+    }
+
+    /// Bind `transport` to the version module for a node of `version`
+    pub fn bind(version: ProtocolVersion, transport: T) -> Result<Self, NegotiationError> {
+{{BIND}}        Err(NegotiationError::UnsupportedNode { version })
+    }
+
+    /// The node's version
+    pub fn version(&self) -> ProtocolVersion {
+        match self {
+{{VERSION_ARMS}}        }
+    }
+
+    /// Version of the module the client is bound to
+    pub fn module_version(&self) -> ProtocolVersion {
+        match self {
+{{MODULE_ARMS}}        }
+    }
+
+    /// The underlying transport
+    pub fn transport(&self) -> &T {
+        match self {
+{{TRANSPORT_ARMS}}        }
+    }
+
+    /// Whether `method` is part of the bound version module
+    pub fn supports(&self, method: &str) -> bool {
+        availability(method).is_some_and(|versions| versions.contains(&self.module_version()))
+    }
+
+    /// Fail with [`NegotiationError::UnsupportedOnVersion`] unless [`Self::supports`] `method`
+    pub fn ensure_supported(&self, method: &str) -> Result<(), NegotiationError> {
+        if self.supports(method) {
+            return Ok(());
+        }
+        Err(NegotiationError::UnsupportedOnVersion {
+            method: method.to_string(),
+            available_since: available_since(method),
+        })
+    }
+
+    /// Call `method` on the node if the bound version has it
+    pub async fn call<R: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: &[Value],
+    ) -> Result<R, NegotiationError> {
+        self.ensure_supported(method)?;
+        Ok(self.transport().call(method, params).await?)
+    }
+}
+
+/// Forwards to the bound transport after checking each method with
+/// [`VersionedClient::ensure_supported`], so the client traits' typed methods are checked too
+impl<T: TransportTrait + TransportExt + Send + Sync> TransportTrait for VersionedClient<T> {
+    fn send_request<'a>(
+        &'a self,
+        method: &'a str,
+        params: &'a [Value],
+    ) -> std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<Value, TransportError>> + Send + 'a>,
+    > {
+        Box::pin(async move {
+            self.ensure_supported(method)?;
+            self.transport().send_request(method, params).await
+        })
+    }
+
+    fn send_batch<'a>(
+        &'a self,
+        bodies: &'a [Value],
+    ) -> std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<Vec<Value>, TransportError>> + Send + 'a>,
+    > {
+        Box::pin(async move {
+            for method in bodies.iter().filter_map(|body| body.get("method")?.as_str()) {
+                self.ensure_supported(method)?;
+            }
+            self.transport().send_batch(bodies).await
+        })
+    }
+
+    fn url(&self) -> &str { self.transport().url() }
+}
+
+/// Versions (of those generated) that have `method`, oldest first
+fn availability(method: &str) -> Option<&'static [ProtocolVersion]> {
+    METHOD_AVAILABILITY
+        .binary_search_by_key(&method, |(name, _)| *name)
+        .ok()
+        .map(|index| METHOD_AVAILABILITY[index].1)
+}
+
+/// The first generated version that has `method`
+pub fn available_since(method: &str) -> Option<ProtocolVersion> {
+    availability(method)?.first().copied()
+}
+
+fn since_suffix(available_since: &Option<ProtocolVersion>) -> String {
+    available_since.map(|v| format!(" (available since {})", v)).unwrap_or_default()
+}
@@ -340,7 +340,7 @@ mod tests {
         let config = Config::default();
         assert_eq!(config.protocol.protocol_type, "bitcoin_core");
         assert!(config.protocol.version.is_some());
-        let version = config.protocol.version.as_ref().unwrap();
+        let version = config.protocol.version.as_ref().expect("default version");
         assert!(!version.is_empty(), "Version should not be empty");
         assert!(version.starts_with('v'), "Version should start with 'v'");
         assert_eq!(config.protocol.network, Some("regtest".to_string()));
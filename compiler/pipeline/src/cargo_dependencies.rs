@@ -44,10 +44,34 @@ bitcoin = { version = "0.32.8", features = ["rand", "serde"] }
 bitcoin-units = { version = "0.3.0", features = ["serde"] }
 bitreq = { version = "0.3.4", default-features = false, features = ["async-https"] }
 bytes = ">=1.11.1"  # RUSTSEC-2026-0007: Integer overflow in BytesMut::reserve (tokio transitive)
+futures-util = "0.3"
 serde = { version = "1.0", features = ["derive"] }
 serde_json = { version = "1.0.145", features = ["preserve_order"] }
 tempfile = "3.23.0"
 thiserror = "2.0.17"
 tokio = { version = "1.49", features = ["io-util", "macros", "net", "process", "rt", "rt-multi-thread", "sync", "time"] }
 tracing = "0.1.41"
+# ZMQ SUB socket for the notification subscriber (`zmq` feature); 0.4 no longer builds on current rustc
+zeromq = { version = "=0.5.0-pre", default-features = false, features = ["tokio-runtime", "tcp-transport"], optional = true }
+"#;
+
+/// Dependencies only the `https` transport needs, appended to [`GENERATED_CRATE_DEPENDENCIES`].
+/// It verifies the node's own certificate with aws-lc-rs, which (unlike ring, used by
+/// bitreq) handles the P-521 key btcd generates its `rpc.cert` with.
+pub const HTTPS_TRANSPORT_DEPENDENCIES: &str = r#"tokio-rustls = { version = "0.26.4", default-features = false, features = ["aws_lc_rs", "logging", "tls12"] }
+webpki-roots = "1.0"
+"#;
+
+/// The `[dependencies]` section for a generated crate whose RPC transport is `transport_protocol`.
+pub fn generated_crate_dependencies(transport_protocol: &str) -> String {
+    let mut dependencies = GENERATED_CRATE_DEPENDENCIES.to_string();
+    if transport_protocol == "https" {
+        dependencies.push_str(HTTPS_TRANSPORT_DEPENDENCIES);
+    }
+    dependencies
+}
+
+/// Optional features shared by every generated crate (appended to the `[features]` section).
+pub const GENERATED_CRATE_OPTIONAL_FEATURES: &str = r#"serde-deny-unknown-fields = []
+zmq = ["dep:zeromq"]
 "#;
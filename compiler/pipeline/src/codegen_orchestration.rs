@@ -8,7 +8,9 @@ use std::path::Path;
 use std::sync::{Arc, Mutex, OnceLock};
 
 use analysis::{CompilerContext, CompilerPhase, SemanticAnalyzer};
-use codegen::generators::version_specific_response_type::set_external_symbol_recorder;
+use codegen::generators::version_specific_response_type::{
+    deprecatedrpc_features, set_external_symbol_recorder,
+};
 use codegen::generators::versioned_registry::VersionedGeneratorRegistry;
 use codegen::utils::{validate_method_mappings, SuggestedMapping};
 use ir::ProtocolIR;
@@ -25,7 +27,7 @@ use crate::module_generators::test_node::TestNodeModuleGenerator;
 use crate::module_generators::transport::TransportModuleGenerator;
 use crate::module_generators::ModuleGenerator;
 use crate::template_management::copy_templates_to;
-use crate::{feature_aware_cargo, PipelineError};
+use crate::{feature_aware_cargo, feature_aware_mod, PipelineError};
 
 // Bridge from generators back into the pipeline's shared collector (safe, no unsafe)
 static EXTERNAL_COLLECTOR: OnceLock<Mutex<Option<Arc<UsedExternalSymbols>>>> = OnceLock::new();
@@ -76,7 +78,8 @@ fn apply_suggested_mappings(
     implementation: Implementation,
     suggestions: &[SuggestedMapping],
 ) -> Result<(), PipelineError> {
-    let impl_key = implementation.as_str();
+    // Dialects registered at runtime use their built-in base's method mappings
+    let impl_key = implementation.builtin_base().as_str();
     let filename = implementation.protocol_name();
     let paths: Vec<_> = normalization::NORMALIZATION_JSON_DIRS
         .iter()
@@ -150,6 +153,75 @@ fn apply_suggested_mappings(
 /// * `out_dir` - The output directory to write generated code to
 /// * `compiler_ctx` - The compiler context containing the blueprint with semantic analysis results
 pub fn generate_into(out_dir: &Path, compiler_ctx: &CompilerContext) -> Result<(), PipelineError> {
+    generate_crate(out_dir, compiler_ctx, &[])
+}
+
+/// Generate a single crate covering several versions of one implementation
+///
+/// The newest version becomes the crate root, exactly as [`generate_into`] would generate it.
+/// Every version additionally gets a client module behind a `v<major>[_<minor>]` cargo feature; response
+/// types are shared with the root where a method's shape is identical and generated into a
+/// version module only where it differs.
+///
+/// # Arguments
+///
+/// * `out_dir` - The output directory to write generated code to
+/// * `compiler_ctxs` - One analyzed compiler context per version, in any order
+pub fn generate_versions_into(
+    out_dir: &Path,
+    compiler_ctxs: &[CompilerContext],
+) -> Result<(), PipelineError> {
+    let mut ctxs: Vec<&CompilerContext> = compiler_ctxs.iter().collect();
+    ctxs.sort_by(|a, b| a.version.cmp(&b.version));
+    let newest = *ctxs
+        .last()
+        .ok_or_else(|| PipelineError::Message("No versions to generate".to_string()))?;
+
+    for pair in ctxs.windows(2) {
+        if pair[0].implementation != pair[1].implementation {
+            return Err(PipelineError::Message(format!(
+                "Cannot combine {} and {} in one crate",
+                pair[0].implementation, pair[1].implementation
+            )));
+        }
+        let release = |v: &ProtocolVersion| (v.major, v.minor, v.patch);
+        if release(&pair[0].version) == release(&pair[1].version) {
+            return Err(PipelineError::Message(format!(
+                "Versions {} and {} share a feature; pass one build per release",
+                pair[0].version, pair[1].version
+            )));
+        }
+    }
+
+    generate_crate(out_dir, newest, &ctxs)
+}
+
+/// Create the versioned generator registry for a version's analyzed IR
+fn versioned_registry_for(
+    compiler_ctx: &CompilerContext,
+) -> Result<VersionedGeneratorRegistry, PipelineError> {
+    let implementation = compiler_ctx.implementation;
+    VersionedGeneratorRegistry::from_ir(
+        implementation.as_str(),
+        compiler_ctx.version.clone(),
+        &compiler_ctx.ir,
+    )
+    .map_err(|e| {
+        PipelineError::Message(format!(
+            "Failed to initialize versioned generators for {}: {}.",
+            implementation.as_str(),
+            e
+        ))
+    })
+}
+
+/// Generate the crate for `compiler_ctx`, plus feature-gated modules for `versions` (oldest
+/// first; empty for a single-version crate)
+fn generate_crate(
+    out_dir: &Path,
+    compiler_ctx: &CompilerContext,
+    versions: &[&CompilerContext],
+) -> Result<(), PipelineError> {
     // Set up directory structure
     fs::create_dir_all(out_dir)?;
 
@@ -160,9 +232,17 @@ pub fn generate_into(out_dir: &Path, compiler_ctx: &CompilerContext) -> Result<(
     // Extract RPC methods from ProtocolIR
     let rpc_methods: Vec<_> = compiler_ctx.ir.get_rpc_methods().into_iter().cloned().collect();
 
-    // Validate method mappings before generating. If any are missing, write suggestions
-    // into both normalization JSON files and ask the user to re-run.
-    if let Err(e) = validate_method_mappings(implementation.as_str(), &rpc_methods) {
+    // Validate method mappings (of every version) before generating. If any are missing, write
+    // suggestions into both normalization JSON files and ask the user to re-run.
+    let mut all_methods = rpc_methods.clone();
+    for ctx in versions {
+        for method in ctx.ir.get_rpc_methods() {
+            if !all_methods.iter().any(|m| m.name == method.name) {
+                all_methods.push(method.clone());
+            }
+        }
+    }
+    if let Err(e) = validate_method_mappings(implementation.as_str(), &all_methods) {
         let workspace_root =
             find_project_root().map_err(|err| PipelineError::Message(err.to_string()))?;
         apply_suggested_mappings(&workspace_root, implementation, &e.suggestions)?;
@@ -178,18 +258,7 @@ pub fn generate_into(out_dir: &Path, compiler_ctx: &CompilerContext) -> Result<(
     copy_templates_to(out_dir, implementation)?;
 
     // Initialize version-specific generator registry from IR
-    let versioned_registry = VersionedGeneratorRegistry::from_ir(
-        implementation.as_str(),
-        version.clone(),
-        &compiler_ctx.ir,
-    )
-    .map_err(|e| {
-        PipelineError::Message(format!(
-            "Failed to initialize versioned generators for {}: {}.",
-            implementation.as_str(),
-            e
-        ))
-    })?;
+    let versioned_registry = versioned_registry_for(compiler_ctx)?;
 
     let ctx = GenerationContext::builder()
         .implementation(implementation)
@@ -208,6 +277,24 @@ pub fn generate_into(out_dir: &Path, compiler_ctx: &CompilerContext) -> Result<(
     ctx.used_external_symbols.record("bitcoin", "Network");
     set_external_symbol_recorder(pipeline_external_symbol_recorder);
 
+    // Version modules are generated before lib.rs so their external symbols get re-exported
+    let mut version_modules = Vec::with_capacity(versions.len());
+    let mut deprecatedrpc = deprecatedrpc_features(&ctx.rpc_methods);
+    let mut registries = Vec::with_capacity(versions.len());
+    for version_ctx in versions {
+        let methods: Vec<_> = version_ctx.ir.get_rpc_methods().into_iter().cloned().collect();
+        deprecatedrpc.extend(deprecatedrpc_features(&methods));
+        registries.push((versioned_registry_for(version_ctx)?, methods));
+    }
+    // Each version module also converts into the next newer one
+    for (index, (registry, methods)) in registries.iter().enumerate() {
+        let newer = registries.get(index + 1).map(|(newer, methods)| (newer, methods.as_slice()));
+        let module = registry
+            .generate_version_module(methods, &ctx.versioned_registry, &ctx.rpc_methods, newer)
+            .map_err(|e| PipelineError::Message(e.to_string()))?;
+        version_modules.push(module);
+    }
+
     let generators: Vec<Box<dyn ModuleGenerator>> = vec![
         Box::new(TransportModuleGenerator),
         Box::new(ClientTraitModuleGenerator),
@@ -220,8 +307,24 @@ pub fn generate_into(out_dir: &Path, compiler_ctx: &CompilerContext) -> Result<(
     for generator in generators.into_iter() {
         generator.generate_and_write(&ctx)?;
     }
+    feature_aware_mod::generate_version_modules(out_dir, &version_modules)?;
+    feature_aware_mod::generate_negotiation_module(out_dir, implementation, &version_modules)?;
+    let version_features: Vec<String> =
+        version_modules.iter().map(|module| module.feature.clone()).collect();
+    deprecatedrpc.sort();
+    deprecatedrpc.dedup();
 
     let crate_name = implementation.published_crate_name().to_string();
-    feature_aware_cargo::generate_cargo_toml(out_dir, &ctx.rpc_methods, &crate_name, &version)?;
+    let rest_only = ctx.rpc_methods.is_empty() && ctx.has_rest_client();
+    feature_aware_cargo::generate_cargo_toml(
+        out_dir,
+        &ctx.rpc_methods,
+        rest_only,
+        &crate_name,
+        implementation.transport_protocol(),
+        &version,
+        &version_features,
+        &deprecatedrpc,
+    )?;
     Ok(())
 }
@@ -1,6 +1,7 @@
 //! Feature-aware Cargo.toml generation
 //!
-//! Generates Cargo.toml with feature flags based on method categories
+//! Generates Cargo.toml with feature flags based on method categories and, for
+//! multi-version crates, one `v<major>[_<minor>]` feature per protocol version
 
 use std::path::Path;
 
@@ -8,18 +9,36 @@ use ir::RpcDef;
 use semantics::method_categorization::{group_methods_by_category, MethodCategory};
 use types::ProtocolVersion;
 
-use crate::cargo_dependencies::{format_package_section, GENERATED_CRATE_DEPENDENCIES};
+use crate::cargo_dependencies::{
+    format_package_section, generated_crate_dependencies, GENERATED_CRATE_OPTIONAL_FEATURES,
+};
 use crate::PipelineError;
 
 /// Generate Cargo.toml with feature flags
+///
+/// A REST-only crate (`rest_only`, e.g. Esplora) has no RPC methods and therefore no
+/// category features; its REST client is always compiled.
+///
+/// `version_features` lists the version features of a multi-version crate, oldest first;
+/// the newest one is enabled by default. Single-version crates pass an empty slice.
+///
+/// `deprecatedrpc_features` gate response fields the node only returns with a
+/// `-deprecatedrpc=` option; they are opt-in and not part of `full`.
+///
+/// `transport_protocol` adds the dependencies of transports beyond the shared set (e.g. `https`).
+#[allow(clippy::too_many_arguments)]
 pub fn generate_cargo_toml(
     output_dir: &Path,
     methods: &[RpcDef],
+    rest_only: bool,
     crate_name: &str,
+    transport_protocol: &str,
     version: &ProtocolVersion,
+    version_features: &[String],
+    deprecatedrpc_features: &[String],
 ) -> Result<(), PipelineError> {
     // Validate input early
-    if methods.is_empty() {
+    if methods.is_empty() && !rest_only {
         return Err(PipelineError::Message(
 			"No RPC methods provided for code generation. This indicates a problem with the input data or version configuration.".to_string()
 		));
@@ -37,8 +56,8 @@ pub fn generate_cargo_toml(
         "Ethos Developers",
     ));
 
-    cargo_content.push_str(GENERATED_CRATE_DEPENDENCIES);
-    cargo_content.push_str("\n");
+    cargo_content.push_str(&generated_crate_dependencies(transport_protocol));
+    cargo_content.push('\n');
 
     cargo_content.push_str("[features]\n");
 
@@ -46,9 +65,10 @@ pub fn generate_cargo_toml(
     let mut default_categories: Vec<MethodCategory> =
         groups.keys().filter(|&c| c.is_default()).cloned().collect();
     default_categories.sort_by_key(|c| c.display_name());
-    let default_features: Vec<&str> = default_categories.iter().map(|c| c.feature_name()).collect();
+    let mut default_features: Vec<&str> =
+        default_categories.iter().map(|c| c.feature_name()).collect();
 
-    if default_features.is_empty() {
+    if default_features.is_empty() && !rest_only {
         if groups.is_empty() {
             return Err(PipelineError::Message(
 				"No RPC methods found for the specified protocol version. This indicates a problem with the input data or version configuration.".to_string()
@@ -62,7 +82,10 @@ pub fn generate_cargo_toml(
 		)));
     }
 
-    cargo_content.push_str(&format!("default = [\"{}\"]\n", default_features.join("\", \"")));
+    if let Some(newest) = version_features.last() {
+        default_features.push(newest);
+    }
+    cargo_content.push_str(&format!("default = {}\n", feature_list(&default_features)));
 
     // Emit feature flags
     let mut categories: Vec<MethodCategory> = groups.keys().cloned().collect();
@@ -72,14 +95,30 @@ pub fn generate_cargo_toml(
         cargo_content.push_str(&format!("{} = []\n", feature_name));
     }
 
+    if !version_features.is_empty() {
+        cargo_content.push_str("\n# Protocol versions (each adds a version-bound client module)\n");
+        for feature in version_features {
+            cargo_content.push_str(&format!("{} = []\n", feature));
+        }
+    }
+
     cargo_content.push_str("\n# Enable all features\n");
     let mut all_features: Vec<String> =
         groups.keys().map(|c| c.feature_name().to_string()).collect();
     all_features.sort();
-    cargo_content.push_str(&format!("full = [\"{}\"]\n", all_features.join("\", \"")));
+    all_features.extend(version_features.iter().cloned());
+    cargo_content.push_str(&format!("full = {}\n", feature_list(&all_features)));
 
-    // Add serde-deny-unknown-fields feature
-    cargo_content.push_str("serde-deny-unknown-fields = []\n");
+    // Add serde-deny-unknown-fields and zmq features
+    cargo_content.push_str(GENERATED_CRATE_OPTIONAL_FEATURES);
+
+    if !deprecatedrpc_features.is_empty() {
+        cargo_content
+            .push_str("\n# Fields only returned with the node's -deprecatedrpc= options\n");
+        for feature in deprecatedrpc_features {
+            cargo_content.push_str(&format!("{} = []\n", feature));
+        }
+    }
 
     let cargo_path = output_dir
         .parent()
@@ -91,3 +130,9 @@ pub fn generate_cargo_toml(
 
     Ok(())
 }
+
+/// TOML array of feature names (`[]` when there are none).
+fn feature_list<S: AsRef<str>>(features: &[S]) -> String {
+    let quoted: Vec<String> = features.iter().map(|f| format!("\"{}\"", f.as_ref())).collect();
+    format!("[{}]", quoted.join(", "))
+}
@@ -5,6 +5,9 @@
 use std::path::Path;
 
 use codegen::generators::client_trait::MethodTemplate;
+use codegen::generators::versioned_registry::VersionModule;
+use codegen::generators::VersionNegotiationGenerator;
+use codegen::CodeGenerator;
 use ir::RpcDef;
 use semantics::method_categorization::{group_methods_by_category, MethodCategory};
 use types::Implementation;
@@ -145,3 +148,77 @@ pub fn generate_category_modules(
 
     Ok(())
 }
+
+/// Write the version modules of a multi-version crate and gate them behind their features
+///
+/// Each version gets `client_trait/<feature>/`; versions with response shapes that differ
+/// from the crate root also get `types/<feature>.rs`. The declarations are appended to the
+/// `mod.rs` files already written for the crate root.
+pub fn generate_version_modules(
+    output_dir: &Path,
+    modules: &[VersionModule],
+) -> Result<(), PipelineError> {
+    let mut types_decls = String::new();
+    let mut client_decls = String::new();
+
+    for module in modules {
+        let feature = &module.feature;
+        if let Some(response_types) = &module.response_types {
+            let file = (format!("{}.rs", feature), response_types.clone());
+            codegen::write_generated(output_dir.join("types"), &[file])?;
+            types_decls.push_str(&format!("#[cfg(feature = \"{}\")]\n", feature));
+            types_decls.push_str(&format!("pub mod {};\n", feature));
+        }
+
+        codegen::write_generated(
+            output_dir.join("client_trait").join(feature),
+            &module.client_trait,
+        )?;
+        client_decls.push_str(&format!("#[cfg(feature = \"{}\")]\n", feature));
+        client_decls.push_str(&format!("pub mod {};\n", feature));
+    }
+
+    append_declarations(&output_dir.join("types").join("mod.rs"), &types_decls)?;
+    append_declarations(&output_dir.join("client_trait").join("mod.rs"), &client_decls)?;
+
+    Ok(())
+}
+
+/// Write the runtime version negotiation module of a multi-version crate
+///
+/// `negotiation.rs` binds a transport to the version module matching the node, so it is only
+/// compiled when at least one version feature is enabled. Negotiation reads the node version
+/// from `getnetworkinfo`, which only Bitcoin Core (and its derivatives) provide.
+pub fn generate_negotiation_module(
+    output_dir: &Path,
+    implementation: Implementation,
+    modules: &[VersionModule],
+) -> Result<(), PipelineError> {
+    if modules.is_empty() || !implementation.is_bitcoin_core_family() {
+        return Ok(());
+    }
+    let files = VersionNegotiationGenerator::new(implementation, modules).generate(&[]);
+    codegen::write_generated(output_dir, &files)?;
+
+    let features: Vec<String> =
+        modules.iter().map(|m| format!("feature = \"{}\"", m.feature)).collect();
+    let cfg = format!("#[cfg(any({}))]\n", features.join(", "));
+    let mut declarations = String::new();
+    declarations.push_str(&cfg);
+    declarations.push_str("pub mod negotiation;\n");
+    declarations.push_str(&cfg);
+    declarations.push_str("pub use negotiation::{NegotiationError, VersionedClient};\n");
+    append_declarations(&output_dir.join("lib.rs"), &declarations)
+}
+
+/// Append module declarations to an existing mod.rs or lib.rs
+fn append_declarations(mod_rs_path: &Path, declarations: &str) -> Result<(), PipelineError> {
+    if declarations.is_empty() {
+        return Ok(());
+    }
+    let mut content = std::fs::read_to_string(mod_rs_path)?;
+    content.push_str("\n// Protocol versions\n");
+    content.push_str(declarations);
+    std::fs::write(mod_rs_path, trim_trailing_whitespace(&content))?;
+    Ok(())
+}
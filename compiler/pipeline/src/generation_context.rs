@@ -7,9 +7,10 @@ use std::collections::{BTreeMap, BTreeSet};
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
+use adapters::bitcoin_core::zmq::ZMQ_MODULE_NAME;
 use analysis::CompilerDiagnostics;
 use codegen::generators::versioned_registry::VersionedGeneratorRegistry;
-use ir::{ProtocolIR, RpcDef};
+use ir::{MessageDef, ProtocolIR, RpcDef};
 use types::Implementation;
 
 use crate::PipelineError;
@@ -54,6 +55,27 @@ impl GenerationContext {
     pub fn transport_protocol(&self) -> String {
         self.implementation.transport_protocol().to_string()
     }
+
+    /// Whether the IR describes REST endpoints and the crate gets a REST client
+    ///
+    /// The REST transport shares the HTTP transport's error type, so it is only
+    /// generated next to the `http` transport or as the `rest_get` transport's backend.
+    pub fn has_rest_client(&self) -> bool {
+        matches!(self.transport_protocol().as_str(), "http" | "rest_get")
+            && !self.protocol_ir.get_rest_endpoints().is_empty()
+    }
+
+    /// Notifications of the IR's `zmq` module; other modules' notifications (e.g. Electrum
+    /// subscription pushes) arrive over the RPC transport instead
+    pub fn zmq_notifications(&self) -> Vec<MessageDef> {
+        self.protocol_ir
+            .get_module(ZMQ_MODULE_NAME)
+            .map(|module| module.get_notifications().into_iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Whether the IR describes ZMQ notifications and the crate gets a ZMQ subscriber
+    pub fn has_zmq_subscriber(&self) -> bool { !self.zmq_notifications().is_empty() }
 }
 
 #[derive(Default)]
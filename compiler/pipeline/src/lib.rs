@@ -28,7 +28,7 @@ pub type Result<T> = std::result::Result<T, PipelineError>;
 #[derive(Debug, Error)]
 pub enum PipelineError {
     /// Unsupported implementation error.
-    #[error("Unsupported implementation: {0}. Supported: BitcoinCore")]
+    #[error("Unsupported implementation: {0}. Supported: BitcoinCore, Btcd, CoreLightning, Lnd, Electrs, Esplora, BitcoinKnots")]
     UnsupportedImplementation(types::Implementation),
     /// Generic message-based error.
     #[error("{0}")]
@@ -69,4 +69,6 @@ pub mod protocol_compiler;
 pub mod template_management;
 
 // Re-export public API from orchestration module
-pub use orchestration::{compile_from_ir, prepare_output_dir, run_all};
+pub use orchestration::{
+    apply_overlay_file, compile_from_ir, load_implementation_ir, prepare_output_dir, run_all,
+};
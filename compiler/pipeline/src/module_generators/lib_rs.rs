@@ -61,6 +61,8 @@ impl ModuleGenerator for LibRsModuleGenerator {
 
         let node_reexports = format!("pub use node::{{NodeManager, {}}};", node_manager_name);
 
+        let rest_reexport = if ctx.has_rest_client() { "    RestClient,\n" } else { "" };
+
         let lib_content = format!(
             r#"#![forbid(unsafe_code)]
 #![allow(missing_docs)]
@@ -95,7 +97,7 @@ pub use transport::{{
     DefaultTransport,
     TransportError,
     RpcClient,
-}};
+{}}};
 "#,
             ctx.implementation.display_name(),
             ctx.implementation.display_name(),
@@ -105,7 +107,8 @@ pub use transport::{{
             node_reexports,
             bitcoin_reexports,
             clients_dir_name,
-            test_client_name
+            test_client_name,
+            rest_reexport
         );
 
         Ok(vec![("lib.rs".to_string(), lib_content)])
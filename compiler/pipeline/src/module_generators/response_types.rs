@@ -36,11 +36,14 @@ impl ModuleGenerator for ResponseTypesModuleGenerator {
 
         let types_mod_rs = output_dir.join("mod.rs");
         let mut types_content = String::new();
-        for (name, _) in &files {
+        for (name, content) in &files {
             let module_name = name.strip_suffix(".rs").unwrap_or(name);
             if module_name != "mod" {
                 writeln!(types_content, "pub mod {};", module_name)?;
-                writeln!(types_content, "pub use {}::*;", module_name)?;
+                // A glob re-export of a module without public items is an unused import
+                if content.lines().any(|line| line.starts_with("pub ")) {
+                    writeln!(types_content, "pub use {}::*;", module_name)?;
+                }
             }
         }
         writeln!(types_content, "pub use bitcoin::PublicKey;")?;
@@ -5,6 +5,7 @@
 use std::fmt::Write as _;
 use std::path::PathBuf;
 
+use codegen::generators::{RestClientGenerator, ZmqSubscriberGenerator};
 use codegen::{
     write_generated, CodeGenerator, MethodWrapperGenerator, TransportInfrastructureGenerator,
 };
@@ -39,6 +40,22 @@ impl ModuleGenerator for TransportModuleGenerator {
         all_files.extend(core_files);
         all_files.push(("rpc_client.rs".to_string(), rpc_client));
 
+        // Generate REST transport and typed REST client when the IR describes REST endpoints
+        if ctx.has_rest_client() {
+            all_files.extend(TransportInfrastructureGenerator::new("rest").generate(&[]));
+            let endpoints =
+                ctx.protocol_ir.get_rest_endpoints().into_iter().cloned().collect::<Vec<_>>();
+            all_files.extend(
+                RestClientGenerator::new(ctx.implementation.as_str(), endpoints)
+                    .generate(&ctx.rpc_methods),
+            );
+        }
+
+        // Generate the typed ZMQ subscriber when the IR describes ZMQ notifications
+        if ctx.has_zmq_subscriber() {
+            all_files.extend(ZmqSubscriberGenerator::new(ctx.zmq_notifications()).generate(&[]));
+        }
+
         Ok(all_files)
     }
 
@@ -52,8 +69,11 @@ impl ModuleGenerator for TransportModuleGenerator {
         let (method_files, infrastructure_files): (Vec<_>, Vec<_>) =
             files.iter().partition(|(name, _)| {
                 // Method files are categorized files (blockchain.rs, wallet.rs, etc.)
-                // Infrastructure files are core.rs, rpc_client.rs, etc.
-                !name.contains("core") && !name.contains("rpc_client")
+                // Infrastructure files are core.rs, rpc_client.rs, rest.rs, rest_client.rs, zmq.rs
+                !name.contains("core")
+                    && !name.contains("rpc_client")
+                    && !name.starts_with("rest")
+                    && name != "zmq.rs"
             });
 
         // Convert references to owned values
@@ -75,6 +95,15 @@ impl ModuleGenerator for TransportModuleGenerator {
         writeln!(content, "pub use core::{{TransportTrait, DefaultTransport, TransportError}};")?;
         writeln!(content, "pub mod rpc_client;")?;
         writeln!(content, "pub use rpc_client::RpcClient;")?;
+        if ctx.has_rest_client() {
+            writeln!(content, "pub mod rest;")?;
+            writeln!(content, "pub use rest::{{RestFormat, RestTransport}};")?;
+            writeln!(content, "pub mod rest_client;")?;
+            writeln!(content, "pub use rest_client::*;")?;
+        }
+        if ctx.has_zmq_subscriber() {
+            writeln!(content, "pub mod zmq;")?;
+        }
         writeln!(content, "pub mod methods;")?;
         std::fs::write(&mod_rs, content)?;
 
@@ -102,10 +131,13 @@ impl TransportModuleGenerator {
 
         // Generate protocol-specific transport constructor call
         let transport_constructor = match transport_protocol {
-            "http" => "DefaultTransport::new(url, None)",
+            "http" | "https" => "DefaultTransport::new(url, None)",
             "unix" => "DefaultTransport::new(url)",
+            "rest_macaroon" => "DefaultTransport::new(url, None)",
+            "tcp" => "DefaultTransport::new(url)",
+            "rest_get" => "DefaultTransport::new(url)",
             _ => panic!(
-                "Unsupported transport protocol: {}. Supported protocols: http, unix",
+                "Unsupported transport protocol: {}. Supported protocols: http, https, unix, rest_macaroon, tcp, rest_get",
                 transport_protocol
             ),
         };
@@ -7,6 +7,8 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use adapters::bitcoin_core::openrpc::extract_version_ir;
+use analysis::IrValidator;
+use ir::{IrOverlay, ProtocolIR};
 use path::{find_project_root, load_registry};
 use registry::ir_resolver::IrResolver;
 use types::{Implementation, ProtocolVersion};
@@ -72,7 +74,7 @@ pub fn run_all() -> Result<(), PipelineError> {
         })?;
 
         for (implementation, dialect_info) in dialects {
-            // Only process implementations that have working adapters
+            // Skip dialects that are neither built in nor registered
             let implementation = match implementation.parse::<Implementation>() {
                 Ok(impl_name) => impl_name,
                 Err(_) => {
@@ -80,12 +82,6 @@ pub fn run_all() -> Result<(), PipelineError> {
                 }
             };
 
-            // Only process implementations that have working adapters
-            let supported_implementations = [Implementation::BitcoinCore];
-            if !supported_implementations.contains(&implementation) {
-                continue;
-            }
-
             let default_version = dialect_info["default_version"].as_str().ok_or_else(|| {
                 PipelineError::Message(format!(
                     "Invalid registry: no default_version for '{}'",
@@ -139,17 +135,10 @@ pub fn compile_from_ir(
     let src_dir = create_source_directory_with_templates(&crate_root, implementation)?;
 
     // Load ProtocolIR directly from IR file using registry
-    let ir_resolver = IrResolver::new()
-        .map_err(|e| PipelineError::Message(format!("Failed to create IR resolver: {}", e)))?;
-    let ir_path = ir_resolver.resolve_ir_path_for_implementation(&implementation).map_err(|e| {
-        PipelineError::Message(format!("Failed to resolve IR path for {}: {}", implementation, e))
-    })?;
-
-    let mut protocol_ir = ir::ProtocolIR::from_file(&ir_path)
-        .map_err(|e| PipelineError::Message(format!("Failed to load IR file: {}", e)))?;
+    let (mut protocol_ir, ir_path) = load_implementation_ir(implementation)?;
 
     // Filter IR to only RPCs available in the requested version (Bitcoin Core version_added/version_removed)
-    if implementation == Implementation::BitcoinCore {
+    if implementation.is_bitcoin_core_family() {
         let core_version = format!("{}.{}", version.major, version.minor);
         protocol_ir = extract_version_ir(protocol_ir, &core_version);
     }
@@ -169,3 +158,50 @@ pub fn compile_from_ir(
 
     Ok(())
 }
+
+/// Load the IR of `implementation` from the IR file the adapter registry names for it
+///
+/// For a dialect defined as an overlay on a base dialect (e.g. Bitcoin Knots on Bitcoin Core),
+/// the overlay is applied to the base's IR with [`apply_overlay_file`]. Returns the IR and the
+/// path of the IR file it was loaded from.
+pub fn load_implementation_ir(
+    implementation: Implementation,
+) -> Result<(ProtocolIR, PathBuf), PipelineError> {
+    let ir_resolver = IrResolver::new()
+        .map_err(|e| PipelineError::Message(format!("Failed to create IR resolver: {}", e)))?;
+    let ir_path = ir_resolver.resolve_ir_path_for_implementation(&implementation).map_err(|e| {
+        PipelineError::Message(format!("Failed to resolve IR path for {}: {}", implementation, e))
+    })?;
+    let protocol_ir = ProtocolIR::from_file(&ir_path)
+        .map_err(|e| PipelineError::Message(format!("Failed to load IR file: {}", e)))?;
+
+    let overlay = ir_resolver.overlay_for_implementation(&implementation).map_err(|e| {
+        PipelineError::Message(format!("Failed to resolve overlay for {}: {}", implementation, e))
+    })?;
+    match overlay {
+        Some(overlay) => Ok((apply_overlay_file(protocol_ir, &overlay.overlay_file)?, ir_path)),
+        None => Ok((protocol_ir, ir_path)),
+    }
+}
+
+/// Apply the [`IrOverlay`] in `overlay_file` to `protocol_ir` after validating it with
+/// [`IrValidator`]
+pub fn apply_overlay_file(
+    protocol_ir: ProtocolIR,
+    overlay_file: &Path,
+) -> Result<ProtocolIR, PipelineError> {
+    let overlay = IrOverlay::from_file(overlay_file).map_err(|e| {
+        PipelineError::Message(format!(
+            "Failed to load overlay file {}: {}",
+            overlay_file.display(),
+            e
+        ))
+    })?;
+    IrValidator::new().validate_overlay(&protocol_ir, &overlay).map_err(|errors| {
+        PipelineError::Message(format!(
+            "Overlay {} does not apply to the IR:\n  {}",
+            overlay_file.display(),
+            errors.join("\n  ")
+        ))
+    })
+}
@@ -4,13 +4,15 @@
 //! and other project metadata files.
 
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use codegen::format_with_rustfmt;
 use codegen::utils::{protocol_rpc_method_to_rust_name, rpc_method_to_rust_name};
 use types::{Implementation, ProtocolVersion};
 
-use crate::cargo_dependencies::{format_package_section, GENERATED_CRATE_DEPENDENCIES};
+use crate::cargo_dependencies::{
+    format_package_section, generated_crate_dependencies, GENERATED_CRATE_OPTIONAL_FEATURES,
+};
 use crate::PipelineError;
 
 /// Setup project files (Cargo.toml, README, license, .gitignore)
@@ -63,7 +65,7 @@ pub fn write_cargo_toml(
     // Use hardcoded published crate name
     let crate_name = artifact_name.published_crate_name();
     let package_section = format_package_section(
-        &crate_name,
+        crate_name,
         &version,
         &format!("Generated client for {} v{}.", artifact_name.as_str(), protocol_version),
         "Ethos Core Developers",
@@ -72,9 +74,10 @@ pub fn write_cargo_toml(
         r#"{}{}
 
 [features]
-serde-deny-unknown-fields = []
-"#,
-        package_section, GENERATED_CRATE_DEPENDENCIES,
+{}"#,
+        package_section,
+        generated_crate_dependencies(artifact_name.transport_protocol()),
+        GENERATED_CRATE_OPTIONAL_FEATURES,
     );
 
     fs::write(root.join("Cargo.toml"), toml)?;
@@ -104,22 +107,7 @@ pub fn write_readme(
     // Determine protocol-specific content
     let protocol_name = artifact_name.display_name();
     let executable_name = artifact_name.executable_name();
-    let example_method_raw = artifact_name.example_method();
-    let example_method =
-        protocol_rpc_method_to_rust_name(artifact_name.as_str(), example_method_raw)
-            .unwrap_or_else(|_| rpc_method_to_rust_name(example_method_raw));
-    let example_description = artifact_name.example_description();
-    let node_manager_name = artifact_name.node_manager_name();
-    let client_prefix = artifact_name.client_prefix();
-
-    // Generate example code
-    let example_code = generate_example_code(
-        &crate_module_name,
-        node_manager_name,
-        client_prefix,
-        &example_method,
-        example_description,
-    );
+    let example_code = generate_example_code(&crate_module_name, artifact_name);
 
     let protocol_version_short = target_version.short();
     let crate_version = target_version.crate_version();
@@ -186,6 +174,31 @@ This library launches {protocol_name} daemons for local integration testing. For
     Ok(())
 }
 
+/// Record the local IR overlays (`--overlay`) a crate was generated with in its README.md
+///
+/// Adds a section listing the overlay files ahead of the README's "About" section; does nothing
+/// when no overlay was applied.
+pub fn write_applied_overlays(root: &Path, overlays: &[PathBuf]) -> Result<(), PipelineError> {
+    if overlays.is_empty() {
+        return Ok(());
+    }
+    let mut section = String::from(
+        "## Local IR overlays\n\
+         This crate was generated with these corrections applied to the protocol IR:\n",
+    );
+    for overlay in overlays {
+        section.push_str(&format!("- `{}`\n", overlay.display()));
+    }
+    section.push('\n');
+
+    let readme_path = root.join("README.md");
+    let mut readme = fs::read_to_string(&readme_path)?;
+    let at = readme.find("## About").unwrap_or(readme.len());
+    readme.insert_str(at, &section);
+    fs::write(readme_path, readme)?;
+    Ok(())
+}
+
 /// Write the examples/basic.rs file for the generated crate
 ///
 /// # Arguments
@@ -200,24 +213,8 @@ fn write_example_basic(root: &Path, artifact_name: Implementation) -> Result<(),
     let crate_name = artifact_name.published_crate_name();
     let crate_module_name = crate_name.replace('-', "_");
 
-    // Determine protocol-specific content
-    let example_method_raw = artifact_name.example_method();
-    // Convert RPC method name to Rust snake_case function name
-    let example_method =
-        protocol_rpc_method_to_rust_name(artifact_name.as_str(), example_method_raw)
-            .unwrap_or_else(|_| rpc_method_to_rust_name(example_method_raw));
-    let example_description = artifact_name.example_description();
-    let node_manager_name = artifact_name.node_manager_name();
-    let client_prefix = artifact_name.client_prefix();
-
     // Generate the example code
-    let example_code = generate_example_code(
-        &crate_module_name,
-        node_manager_name,
-        client_prefix,
-        &example_method,
-        example_description,
-    );
+    let example_code = generate_example_code(&crate_module_name, artifact_name);
 
     // Create examples directory if it doesn't exist
     let examples_dir = root.join("examples");
@@ -355,21 +352,49 @@ For more information, please see
 /// # Arguments
 ///
 /// * `crate_module_name` - The crate module name (e.g., "ethos_bitcoind")
-/// * `node_manager_name` - The node manager type name (e.g., "BitcoinNodeManager")
-/// * `client_prefix` - The client type name (e.g., "BitcoinClient")
-/// * `example_method` - The example method name in snake_case (e.g., "get_blockchain_info")
-/// * `example_description` - The description for the example (e.g., "Blockchain info")
+/// * `artifact_name` - The implementation the example is for
+///
+/// The example calls the implementation's `example_method` through its client trait on the
+/// node transport, or through the typed `RestClient` for REST-only implementations (whose
+/// `example_method` is an endpoint path, e.g. "blocks/tip/height").
 ///
 /// # Returns
 ///
 /// Returns the example Rust code as a formatted string
-fn generate_example_code(
-    crate_module_name: &str,
-    node_manager_name: &str,
-    client_prefix: &str,
-    example_method: &str,
-    example_description: &str,
-) -> String {
+fn generate_example_code(crate_module_name: &str, artifact_name: Implementation) -> String {
+    let example_method_raw = artifact_name.example_method();
+    let example_description = artifact_name.example_description();
+    let node_manager_name = artifact_name.node_manager_name();
+
+    if artifact_name.transport_protocol() == "rest_get" {
+        let example_method = example_method_raw.trim_matches('/').replace(['/', '-'], "_");
+        return format!(
+            r#"use {crate_module_name}::{{NodeManager, RestClient, {node_manager_name}}};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {{
+    let mut manager = {node_manager_name}::new()?;
+    manager.start().await?;
+    // Waits until the node's HTTP API answers
+    manager.create_transport().await?;
+    let client = RestClient::new(format!("http://127.0.0.1:{{}}", manager.rpc_port()));
+
+    let result = client.{example_method}().await?;
+    println!("{example_description}: {{:?}}", result);
+
+    manager.stop().await?;
+
+    Ok(())
+}}
+"#
+        );
+    }
+
+    // Convert RPC method name to Rust snake_case function name
+    let example_method =
+        protocol_rpc_method_to_rust_name(artifact_name.as_str(), example_method_raw)
+            .unwrap_or_else(|_| rpc_method_to_rust_name(example_method_raw));
+    let client_prefix = artifact_name.client_prefix();
     format!(
         r#"use std::sync::Arc;
 
@@ -98,8 +98,10 @@ mod tests {
             requires_private_keys: false,
             version_added: None,
             version_removed: None,
+            deprecated: None,
             examples: None,
             hidden: None,
+            http: None,
         };
 
         let rpc_def2 = RpcDef {
@@ -112,8 +114,10 @@ mod tests {
             requires_private_keys: false,
             version_added: None,
             version_removed: None,
+            deprecated: None,
             examples: None,
             hidden: None,
+            http: None,
         };
 
         let module1 = ir::ProtocolModule::from_source(
@@ -152,8 +156,10 @@ mod tests {
             requires_private_keys: false,
             version_added: None,
             version_removed: None,
+            deprecated: None,
             examples: None,
             hidden: None,
+            http: None,
         };
 
         let module = ir::ProtocolModule::from_source(
@@ -183,8 +189,10 @@ mod tests {
             requires_private_keys: false,
             version_added: None,
             version_removed: None,
+            deprecated: None,
             examples: None,
             hidden: None,
+            http: None,
         };
 
         let rpc_def2 = RpcDef {
@@ -197,8 +205,10 @@ mod tests {
             requires_private_keys: false,
             version_added: None,
             version_removed: None,
+            deprecated: None,
             examples: None,
             hidden: None,
+            http: None,
         };
 
         let module1 = ir::ProtocolModule::from_source(
@@ -50,19 +50,34 @@ pub fn copy_templates_to(
     implementation: Implementation,
 ) -> Result<(), PipelineError> {
     let project_root = find_project_root().map_err(|e| PipelineError::Message(e.to_string()))?;
-    let src_dir = project_root.join(format!("adapters/templates/{}", implementation));
+    // Dialects registered at runtime use their built-in base's templates, and an overlay
+    // dialect (e.g. Knots) falls back to its base dialect's for templates it does not override
+    let builtin = implementation.builtin_base();
+    let overlay_base = builtin
+        .metadata()
+        .ok()
+        .and_then(|metadata| metadata.base_dialect.as_deref())
+        .and_then(|base| base.parse::<Implementation>().ok());
+    let template_dirs: Vec<PathBuf> = std::iter::once(builtin)
+        .chain(overlay_base)
+        .map(|dialect| project_root.join(format!("adapters/templates/{}", dialect)))
+        .filter(|dir| dir.exists())
+        .collect();
 
     // Error if implementation-specific templates do not exist
-    if !src_dir.exists() {
+    if template_dirs.is_empty() {
         return Err(PipelineError::Io(std::io::Error::new(
             std::io::ErrorKind::NotFound,
             format!("Template directory for implementation '{}' does not exist", implementation),
         )));
     }
-    let template_dir = src_dir;
 
     for filename in TEMPLATE_FILES {
-        let src_path = template_dir.join(filename);
+        let src_path = template_dirs
+            .iter()
+            .map(|dir| dir.join(filename))
+            .find(|path| path.exists())
+            .unwrap_or_else(|| template_dirs[0].join(filename));
         let dst_path = dst_dir.join(filename);
         fs::copy(&src_path, &dst_path)?;
     }
@@ -19,10 +19,12 @@ impl RpcClient {
     /// Wrap an existing transport (no URL+auth dance)
     pub fn from_transport(inner: Arc<dyn TransportTrait>) -> Self { Self { transport: inner } }
 
-    /// Create a new RPC client with the given RPC endpoint URL.
+    /// Create a new RPC client for the given RPC endpoint.
     ///
-    /// The argument must be the full RPC endpoint URL (e.g. `http://127.0.0.1:8332/`),
-    /// not a filesystem path. This constructor is for HTTP/URL-based transport.
+    /// For HTTP transports the argument is the full endpoint URL (e.g. `http://127.0.0.1:8332/`);
+    /// for unix-socket transports it is the socket path (e.g. `~/.lightning/bitcoin/lightning-rpc`);
+    /// for TCP transports it is the `host:port` address (e.g. `127.0.0.1:50001`); for REST GET
+    /// transports it is the API's base URL (e.g. `https://blockstream.info/api`).
     pub fn new(url: &str) -> Self {
         let transport = {{TRANSPORT_CONSTRUCTOR}};
         Self { transport: Arc::new(transport) }
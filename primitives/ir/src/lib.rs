@@ -8,10 +8,12 @@
 //! protocol specifications at different stages of compilation. The IR serves as the bridge between
 //! raw protocol specifications and backend code generation.
 
+pub mod overlay;
 pub mod protocol_ir;
 
 #[cfg(feature = "test-utils")]
 pub mod test_utils;
 
 // Re-export the main ProtocolIR types for convenience
+pub use overlay::{IrOverlay, IrOverride, MethodChange, OverlayError, ResultFieldAddition};
 pub use protocol_ir::*;
@@ -0,0 +1,471 @@
+//! Dialect overlays
+//!
+//! Some dialects are another dialect with a handful of differences: Bitcoin Knots is Bitcoin
+//! Core plus extra RPCs, arguments and result fields. Such a dialect is described by an
+//! [`IrOverlay`] that is applied to the base dialect's IR instead of a full copy of it.
+//!
+//! The same files carry local corrections to an IR ([`IrOverride`]s addressed by path, e.g.
+//! `getblock.result.tx[].fee`) for mistakes that can't wait for an upstream schema fix.
+//!
+//! Applying an overlay is strict: every removal and change must name something the base
+//! has, and additions must not collide with it. All conflicts are collected and reported
+//! together in an [`OverlayError`].
+
+use std::collections::HashSet;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    FieldDef, FieldKey, ParamDef, ProtocolDef, ProtocolIR, ProtocolModule, RpcDef, TypeDef,
+    TypeKind,
+};
+
+/// Module that methods added by an overlay are placed in
+const RPC_MODULE: &str = "rpc";
+
+/// Additions, removals and changes turning a base dialect's IR into another dialect's
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IrOverlay {
+    /// Methods the dialect adds on top of the base
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub add_methods: Vec<RpcDef>,
+    /// Names of base methods the dialect does not serve
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remove_methods: Vec<String>,
+    /// Changes to base methods the dialect keeps
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub change_methods: Vec<MethodChange>,
+    /// Corrections to single methods, params, fields or types
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<IrOverride>,
+}
+
+/// Changes to a single base method
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MethodChange {
+    /// Name of the base method
+    pub method: String,
+    /// Replacement description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Params appended after the base method's
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub add_params: Vec<ParamDef>,
+    /// Names of base params the dialect does not accept
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remove_params: Vec<String>,
+    /// Params replacing the base param of the same name
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub replace_params: Vec<ParamDef>,
+    /// Fields added to the method's result
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub add_result_fields: Vec<ResultFieldAddition>,
+    /// Dotted paths (e.g. `"bip9.statistics"`) of base result fields the dialect does not return
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remove_result_fields: Vec<String>,
+}
+
+/// A field added to a method's result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResultFieldAddition {
+    /// Dotted path of the object the field is added to; empty for the result itself.
+    /// Arrays are stepped through, so a path names fields of their element objects.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub parent: String,
+    /// The added field
+    pub field: FieldDef,
+}
+
+/// A correction to one method, param, result field or type, addressed by path
+///
+/// A path is the method name, optionally followed by `params.<name>` or `result` and dotted
+/// field names; `[]` steps into an array's element type. For example `getblock`,
+/// `getblock.params.verbosity`, `getblock.result.tx[].fee` or `getpeerinfo.result[]`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IrOverride {
+    /// Path of the corrected item
+    pub path: String,
+    /// Protocol primitive (e.g. `"amount"`) the type of a param, field or type becomes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol_type: Option<String>,
+    /// Replacement type of a param, field or type, for corrections beyond a primitive
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub type_def: Option<TypeDef>,
+    /// Whether a param or field is required
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+    /// Replacement description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Version a method, param or field was added in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_added: Option<String>,
+    /// Version a method, param or field was removed in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_removed: Option<String>,
+}
+
+/// Conflicts found while applying an [`IrOverlay`] to its base IR
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverlayError {
+    /// One message per conflicting addition, removal or change
+    pub conflicts: Vec<String>,
+}
+
+impl fmt::Display for OverlayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "overlay does not apply to its base IR: {}", self.conflicts.join("; "))
+    }
+}
+
+impl std::error::Error for OverlayError {}
+
+impl IrOverlay {
+    /// Load an overlay from a JSON file
+    pub fn from_file(path: &std::path::Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Apply the overlay to `base`, returning the dialect's IR
+    ///
+    /// Removals run first, then changes, then additions, then overrides. Added methods are
+    /// inserted into the `rpc` module in name order.
+    pub fn apply(&self, base: &ProtocolIR) -> Result<ProtocolIR, OverlayError> {
+        let mut conflicts = Vec::new();
+        let base_methods: HashSet<&str> =
+            base.get_rpc_methods().into_iter().map(|rpc| rpc.name.as_str()).collect();
+        let removed: HashSet<&str> = self.remove_methods.iter().map(String::as_str).collect();
+
+        for name in &self.remove_methods {
+            if !base_methods.contains(name.as_str()) {
+                conflicts.push(format!("cannot remove method `{}`: not in the base IR", name));
+            }
+        }
+
+        let mut ir = base.clone();
+        for module in ir.modules_mut() {
+            module.definitions_mut().retain(|def| match def {
+                ProtocolDef::RpcMethod(rpc) => !removed.contains(rpc.name.as_str()),
+                _ => true,
+            });
+        }
+
+        for change in &self.change_methods {
+            if removed.contains(change.method.as_str()) {
+                conflicts.push(format!("method `{}` is both removed and changed", change.method));
+                continue;
+            }
+            match rpc_mut(&mut ir, &change.method) {
+                Some(rpc) => change.apply_to(rpc, &mut conflicts),
+                None => conflicts
+                    .push(format!("cannot change method `{}`: not in the base IR", change.method)),
+            }
+        }
+
+        let mut added = HashSet::new();
+        for rpc in &self.add_methods {
+            if !added.insert(rpc.name.as_str()) {
+                conflicts.push(format!("method `{}` is added twice", rpc.name));
+            } else if base_methods.contains(rpc.name.as_str())
+                && !removed.contains(rpc.name.as_str())
+            {
+                conflicts.push(format!(
+                    "cannot add method `{}`: already in the base IR (change it instead)",
+                    rpc.name
+                ));
+            } else {
+                insert_rpc(&mut ir, rpc.clone());
+            }
+        }
+
+        for correction in &self.overrides {
+            let applied =
+                resolve(&mut ir, &correction.path).and_then(|target| correction.apply_to(target));
+            if let Err(reason) = applied {
+                conflicts.push(format!("cannot override `{}`: {}", correction.path, reason));
+            }
+        }
+
+        if conflicts.is_empty() {
+            Ok(ir)
+        } else {
+            Err(OverlayError { conflicts })
+        }
+    }
+}
+
+impl MethodChange {
+    fn apply_to(&self, rpc: &mut RpcDef, conflicts: &mut Vec<String>) {
+        let method = &self.method;
+        if let Some(description) = &self.description {
+            rpc.description = description.clone();
+        }
+
+        for name in &self.remove_params {
+            let before = rpc.params.len();
+            rpc.params.retain(|p| &p.name != name);
+            if rpc.params.len() == before {
+                conflicts
+                    .push(format!("cannot remove param `{}` of `{}`: no such param", name, method));
+            }
+        }
+        for param in &self.replace_params {
+            match rpc.params.iter_mut().find(|p| p.name == param.name) {
+                Some(existing) => *existing = param.clone(),
+                None => conflicts.push(format!(
+                    "cannot replace param `{}` of `{}`: no such param",
+                    param.name, method
+                )),
+            }
+        }
+        for param in &self.add_params {
+            if rpc.params.iter().any(|p| p.name == param.name) {
+                conflicts.push(format!(
+                    "cannot add param `{}` to `{}`: already present",
+                    param.name, method
+                ));
+            } else {
+                rpc.params.push(param.clone());
+            }
+        }
+
+        if self.add_result_fields.is_empty() && self.remove_result_fields.is_empty() {
+            return;
+        }
+        let Some(result) = rpc.result.as_mut() else {
+            conflicts.push(format!("cannot change the result of `{}`: it has none", method));
+            return;
+        };
+
+        for path in &self.remove_result_fields {
+            let (parent, name) = path.rsplit_once('.').unwrap_or(("", path.as_str()));
+            let removed = object_at_mut(result, parent)
+                .and_then(|ty| ty.fields.as_mut())
+                .map(|fields| {
+                    let before = fields.len();
+                    fields.retain(|f| !is_named(&f.key, name));
+                    fields.len() != before
+                })
+                .unwrap_or(false);
+            if !removed {
+                conflicts.push(format!(
+                    "cannot remove result field `{}` of `{}`: no such field",
+                    path, method
+                ));
+            }
+        }
+        for addition in &self.add_result_fields {
+            let Some(parent) = object_at_mut(result, &addition.parent) else {
+                conflicts.push(format!(
+                    "cannot add result field to `{}` of `{}`: no such object",
+                    addition.parent, method
+                ));
+                continue;
+            };
+            let fields = parent.fields.get_or_insert_with(Vec::new);
+            if fields.iter().any(|f| f.key == addition.field.key) {
+                conflicts.push(format!(
+                    "cannot add result field `{}` to `{}`: already present",
+                    addition.field.key.as_ident(),
+                    method
+                ));
+            } else {
+                fields.push(addition.field.clone());
+            }
+        }
+    }
+}
+
+impl IrOverride {
+    fn apply_to(&self, target: Target<'_>) -> Result<(), &'static str> {
+        let retypes = self.protocol_type.is_some() || self.type_def.is_some();
+        if self.protocol_type.is_some() && self.type_def.is_some() {
+            return Err("set either protocol_type or type_def, not both");
+        }
+        let versioned = self.version_added.is_some() || self.version_removed.is_some();
+        match target {
+            Target::Method(rpc) => {
+                if retypes || self.required.is_some() {
+                    return Err("a method has no type or required flag");
+                }
+                override_with(&mut rpc.description, &self.description);
+                override_version(&mut rpc.version_added, &self.version_added);
+                override_version(&mut rpc.version_removed, &self.version_removed);
+            }
+            Target::Param(param) => {
+                self.retype(&mut param.param_type);
+                override_with(&mut param.required, &self.required);
+                override_with(&mut param.description, &self.description);
+                override_version(&mut param.version_added, &self.version_added);
+                override_version(&mut param.version_removed, &self.version_removed);
+            }
+            Target::Field(field) => {
+                self.retype(&mut field.field_type);
+                override_with(&mut field.required, &self.required);
+                override_with(&mut field.description, &self.description);
+                override_version(&mut field.version_added, &self.version_added);
+                override_version(&mut field.version_removed, &self.version_removed);
+            }
+            Target::Type(ty) => {
+                if self.required.is_some() || versioned {
+                    return Err("a result or array element has no required flag or versions");
+                }
+                self.retype(ty);
+                override_with(&mut ty.description, &self.description);
+            }
+        }
+        Ok(())
+    }
+
+    fn retype(&self, ty: &mut TypeDef) {
+        if let Some(type_def) = &self.type_def {
+            *ty = type_def.clone();
+        }
+        if let Some(protocol_type) = &self.protocol_type {
+            ty.name = protocol_type.clone();
+            ty.protocol_type = Some(protocol_type.clone());
+            ty.kind = TypeKind::Primitive;
+            ty.fields = None;
+            ty.variants = None;
+            ty.union_variants = None;
+        }
+    }
+}
+
+/// Item an [`IrOverride`] path resolves to
+enum Target<'a> {
+    Method(&'a mut RpcDef),
+    Param(&'a mut ParamDef),
+    Field(&'a mut FieldDef),
+    Type(&'a mut TypeDef),
+}
+
+impl<'a> Target<'a> {
+    fn into_type(self) -> Option<&'a mut TypeDef> {
+        match self {
+            Target::Method(_) => None,
+            Target::Param(param) => Some(&mut param.param_type),
+            Target::Field(field) => Some(&mut field.field_type),
+            Target::Type(ty) => Some(ty),
+        }
+    }
+}
+
+/// Resolve an [`IrOverride`] path in `ir`
+fn resolve<'a>(ir: &'a mut ProtocolIR, path: &str) -> Result<Target<'a>, &'static str> {
+    let mut segments = path.split('.');
+    let method = segments.next().unwrap_or_default();
+    let rpc = rpc_mut(ir, method).ok_or("no such method")?;
+    let mut target = match segments.next().map(split_element) {
+        None => return Ok(Target::Method(rpc)),
+        Some(("params", false)) => {
+            let (name, element) = segments.next().map(split_element).ok_or("no param named")?;
+            let param = rpc.params.iter_mut().find(|p| p.name == name).ok_or("no such param")?;
+            if element {
+                Target::Type(element_of(&mut param.param_type)?)
+            } else {
+                Target::Param(param)
+            }
+        }
+        Some(("result", element)) => {
+            let result = rpc.result.as_mut().ok_or("the method has no result")?;
+            Target::Type(if element { element_of(result)? } else { result })
+        }
+        Some(_) => return Err("expected `params` or `result` after the method name"),
+    };
+    for (name, element) in segments.map(split_element) {
+        let ty = target.into_type().ok_or("no such field")?;
+        let field = ty
+            .fields
+            .as_mut()
+            .and_then(|fields| fields.iter_mut().find(|f| is_named(&f.key, name)))
+            .ok_or("no such field")?;
+        target = if element {
+            Target::Type(element_of(&mut field.field_type)?)
+        } else {
+            Target::Field(field)
+        };
+    }
+    Ok(target)
+}
+
+/// Split a path segment into its name and whether it ends in `[]`
+fn split_element(segment: &str) -> (&str, bool) {
+    match segment.strip_suffix("[]") {
+        Some(name) => (name, true),
+        None => (segment, false),
+    }
+}
+
+fn element_of(ty: &mut TypeDef) -> Result<&mut TypeDef, &'static str> {
+    let is_array = ty.array_element_type().is_some();
+    match ty.fields.as_deref_mut() {
+        Some([element]) if is_array => Ok(&mut element.field_type),
+        _ => Err("`[]` on a type that is not an array"),
+    }
+}
+
+fn override_with<T: Clone>(value: &mut T, replacement: &Option<T>) {
+    if let Some(replacement) = replacement {
+        *value = replacement.clone();
+    }
+}
+
+fn override_version(value: &mut Option<String>, replacement: &Option<String>) {
+    if replacement.is_some() {
+        value.clone_from(replacement);
+    }
+}
+
+fn is_named(key: &FieldKey, name: &str) -> bool { matches!(key, FieldKey::Named(n) if n == name) }
+
+/// Step into the element type while `ty` is an array
+fn element_mut(mut ty: &mut TypeDef) -> &mut TypeDef {
+    while ty.array_element_type().is_some() {
+        ty = &mut ty.fields.as_mut().expect("array has an element field")[0].field_type;
+    }
+    ty
+}
+
+/// Object type (with or without fields yet) at dotted `path` below `ty`, stepping through
+/// arrays
+fn object_at_mut<'t>(ty: &'t mut TypeDef, path: &str) -> Option<&'t mut TypeDef> {
+    let mut ty = element_mut(ty);
+    for segment in path.split('.').filter(|s| !s.is_empty()) {
+        let field = ty.fields.as_mut()?.iter_mut().find(|f| is_named(&f.key, segment))?;
+        ty = element_mut(&mut field.field_type);
+    }
+    (ty.fields.is_some() || matches!(ty.kind, TypeKind::Object)).then_some(ty)
+}
+
+fn rpc_mut<'a>(ir: &'a mut ProtocolIR, name: &str) -> Option<&'a mut RpcDef> {
+    ir.modules_mut().iter_mut().flat_map(|m| m.definitions_mut().iter_mut()).find_map(|def| {
+        match def {
+            ProtocolDef::RpcMethod(rpc) if rpc.name == name => Some(rpc),
+            _ => None,
+        }
+    })
+}
+
+fn insert_rpc(ir: &mut ProtocolIR, rpc: RpcDef) {
+    let modules = ir.modules_mut();
+    let index = match modules.iter().position(|m| m.name() == RPC_MODULE) {
+        Some(index) => index,
+        None => {
+            modules.push(ProtocolModule::new(
+                RPC_MODULE.to_string(),
+                "RPC methods".to_string(),
+                Vec::new(),
+            ));
+            modules.len() - 1
+        }
+    };
+    let definitions = modules[index].definitions_mut();
+    let at = definitions
+        .iter()
+        .position(|def| matches!(def, ProtocolDef::RpcMethod(r) if r.name > rpc.name))
+        .unwrap_or(definitions.len());
+    definitions.insert(at, ProtocolDef::RpcMethod(rpc));
+}
@@ -37,6 +37,8 @@ pub enum ProtocolDef {
     Type(TypeDef),
     /// Constant definition
     Constant(ConstantDef),
+    /// REST endpoint definition
+    RestEndpoint(RestEndpointDef),
 }
 
 /// RPC method definition
@@ -64,12 +66,64 @@ pub struct RpcDef {
     /// Version when this method was last supported (None if still supported)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub version_removed: Option<String>,
+    /// Deprecation of this method (None if not deprecated)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<Deprecation>,
     /// Example usage strings for the method (preserved from schema)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub examples: Option<Vec<String>>,
     /// Whether this method is hidden from documentation (preserved from schema)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hidden: Option<bool>,
+    /// HTTP route for methods served through a REST gateway (e.g. LND's `google.api.http`
+    /// annotations); `None` for plain JSON-RPC methods
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http: Option<HttpBinding>,
+}
+
+/// HTTP route of an RPC method exposed through a REST gateway
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HttpBinding {
+    /// HTTP verb
+    pub method: HttpMethod,
+    /// Path template (e.g. "/v1/graph/node/{pub_key}")
+    ///
+    /// Each `{field}` segment is filled from the request field with that name; dotted names
+    /// (`{chan_point.output_index}`) select a field of a nested message.
+    pub path: String,
+    /// Request fields sent as the JSON body: `"*"` for all fields not bound in the path,
+    /// a field name for just that field, or `None` to send them as query parameters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+/// HTTP verbs used by REST gateway bindings
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum HttpMethod {
+    /// GET
+    Get,
+    /// POST
+    Post,
+    /// PUT
+    Put,
+    /// PATCH
+    Patch,
+    /// DELETE
+    Delete,
+}
+
+impl HttpMethod {
+    /// The verb as it appears on the wire (e.g. "GET")
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HttpMethod::Get => "GET",
+            HttpMethod::Post => "POST",
+            HttpMethod::Put => "PUT",
+            HttpMethod::Patch => "PATCH",
+            HttpMethod::Delete => "DELETE",
+        }
+    }
 }
 
 /// Network message definition
@@ -85,6 +139,84 @@ pub struct MessageDef {
     pub message_type: MessageType,
     /// Message version
     pub version: Option<String>,
+    /// Topic the message is published under (e.g. ZMQ "hashblock")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+    /// Whether each published message carries a per-topic sequence number
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub sequenced: bool,
+}
+
+/// REST endpoint definition (e.g. Bitcoin Core's `/rest/` interface)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestEndpointDef {
+    /// Endpoint name (e.g. "block", "headers")
+    pub name: String,
+    /// Endpoint description
+    pub description: String,
+    /// Path template without the format extension (e.g. "/rest/block/{blockhash}")
+    ///
+    /// Each `{name}` segment is filled from the path parameter with the same name.
+    pub path: String,
+    /// Path parameters, in template order
+    pub params: Vec<ParamDef>,
+    /// Query string parameters
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub query_params: Vec<ParamDef>,
+    /// Response encodings, selected by the path extension unless `bare_path` is set
+    pub formats: Vec<RestFormat>,
+    /// Whether the single format is served at the bare path instead of being selected by a
+    /// `.{extension}` suffix (e.g. Esplora's `/tx/{txid}/status`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub bare_path: bool,
+    /// JSON response type when the endpoint has a shape of its own
+    pub result: Option<TypeDef>,
+    /// RPC method whose result type the JSON response reuses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shared_result: Option<RestSharedResult>,
+    /// Version when this endpoint was first added/supported
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version_added: Option<String>,
+    /// Version when this endpoint was last supported (None if still supported)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version_removed: Option<String>,
+}
+
+/// Response encodings supported by a REST endpoint
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RestFormat {
+    /// Raw binary serialization
+    Bin,
+    /// Hex-encoded binary serialization
+    Hex,
+    /// JSON document
+    Json,
+    /// Plain-text scalar (e.g. a block hash or height)
+    Text,
+}
+
+/// Reference from a REST JSON response to an RPC method's result type
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RestSharedResult {
+    /// RPC method name (e.g. "getblockheader")
+    pub method: String,
+    /// How the RPC result is wrapped in the REST response
+    #[serde(default)]
+    pub container: RestResultContainer,
+}
+
+/// Container around a shared RPC result type in a REST response
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum RestResultContainer {
+    /// The response is a single RPC result
+    #[default]
+    Single,
+    /// The response is a JSON array of RPC results
+    Array,
+    /// The response is a JSON object mapping keys to RPC results
+    Map,
 }
 
 /// Type definition
@@ -189,6 +321,39 @@ pub struct ParamDef {
     /// Version when this parameter was removed (None = still present)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub version_removed: Option<String>,
+    /// Deprecation of this parameter (None if not deprecated)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<Deprecation>,
+}
+
+/// Deprecation of a method, parameter or field
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Deprecation {
+    /// Version the item was deprecated in (None = unknown)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+    /// What to use instead (e.g. a method or field name), when the schema names one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replacement: Option<String>,
+    /// Deprecated-RPC option that re-enables the item (the `X` of Bitcoin Core's
+    /// `-deprecatedrpc=X`); the node only serves the item when it is set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flag: Option<String>,
+}
+
+impl Deprecation {
+    /// Human-readable note without the version (e.g. "Use `foo` instead"), when the
+    /// deprecation names a replacement or flag
+    pub fn note(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        if let Some(replacement) = &self.replacement {
+            parts.push(format!("Use `{}` instead", replacement));
+        }
+        if let Some(flag) = &self.flag {
+            parts.push(format!("Only served with `-deprecatedrpc={}`", flag));
+        }
+        (!parts.is_empty()).then(|| parts.join(". "))
+    }
 }
 
 /// Identity of a field: either a named key (e.g. JSON key) or an anonymous
@@ -284,6 +449,9 @@ pub struct FieldDef {
     /// Version when this field was removed (None = still present)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub version_removed: Option<String>,
+    /// Deprecation of this field (None if not deprecated)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<Deprecation>,
 }
 
 // Custom deserializer to support both legacy IR field shape (with `key`) and
@@ -308,6 +476,8 @@ impl<'de> Deserialize<'de> for FieldDef {
             version_added: Option<String>,
             #[serde(default)]
             version_removed: Option<String>,
+            #[serde(default)]
+            deprecated: Option<Deprecation>,
         }
 
         #[derive(Deserialize)]
@@ -358,6 +528,7 @@ impl<'de> Deserialize<'de> for FieldDef {
             default_value: helper.default_value,
             version_added: helper.version_added,
             version_removed: helper.version_removed,
+            deprecated: helper.deprecated,
         })
     }
 }
@@ -412,7 +583,7 @@ pub enum TypeKind {
 }
 
 /// Message types
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum MessageType {
     /// Request message
     Request,
@@ -486,6 +657,16 @@ impl ProtocolIR {
             .collect()
     }
 
+    /// Get all REST endpoints across all modules
+    pub fn get_rest_endpoints(&self) -> Vec<&RestEndpointDef> {
+        self.modules.iter().flat_map(|m| m.get_rest_endpoints()).collect()
+    }
+
+    /// Get all notification messages across all modules
+    pub fn get_notifications(&self) -> Vec<&MessageDef> {
+        self.modules.iter().flat_map(|m| m.get_notifications()).collect()
+    }
+
     /// Get all type definitions across all modules
     pub fn get_type_definitions(&self) -> Vec<&TypeDef> {
         self.modules
@@ -566,6 +747,8 @@ impl ProtocolIR {
         for (name, group) in by_module {
             let mut rpc_by_name: BTreeMap<String, RpcDef> = BTreeMap::new();
             let mut types_by_name: BTreeMap<String, TypeDef> = BTreeMap::new();
+            let mut rest_by_name: BTreeMap<String, RestEndpointDef> = BTreeMap::new();
+            let mut messages_by_name: BTreeMap<String, MessageDef> = BTreeMap::new();
             let mut desc = String::new();
 
             for m in group {
@@ -578,14 +761,27 @@ impl ProtocolIR {
                         ProtocolDef::Type(t) => {
                             types_by_name.entry(t.name.clone()).or_insert(t.clone());
                         }
+                        ProtocolDef::RestEndpoint(e) => {
+                            rest_by_name.entry(e.name.clone()).or_insert(e.clone());
+                        }
+                        ProtocolDef::Message(msg) => {
+                            messages_by_name.entry(msg.name.clone()).or_insert(msg.clone());
+                        }
                         _other => { /* keep or bucket as-is if needed */ }
                     }
                 }
             }
 
-            let mut defs = Vec::with_capacity(rpc_by_name.len() + types_by_name.len());
+            let mut defs = Vec::with_capacity(
+                rpc_by_name.len()
+                    + types_by_name.len()
+                    + rest_by_name.len()
+                    + messages_by_name.len(),
+            );
             defs.extend(rpc_by_name.into_values().map(ProtocolDef::RpcMethod));
             defs.extend(types_by_name.into_values().map(ProtocolDef::Type));
+            defs.extend(rest_by_name.into_values().map(ProtocolDef::RestEndpoint));
+            defs.extend(messages_by_name.into_values().map(ProtocolDef::Message));
 
             // Create merged module
             let module = ProtocolModule::from_source(&name, &desc, defs);
@@ -624,6 +820,30 @@ impl ProtocolModule {
             .collect()
     }
 
+    /// Get REST endpoints in this module
+    pub fn get_rest_endpoints(&self) -> Vec<&RestEndpointDef> {
+        self.definitions
+            .iter()
+            .filter_map(|def| match def {
+                ProtocolDef::RestEndpoint(endpoint) => Some(endpoint),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Get notification messages in this module
+    pub fn get_notifications(&self) -> Vec<&MessageDef> {
+        self.definitions
+            .iter()
+            .filter_map(|def| match def {
+                ProtocolDef::Message(message)
+                    if message.message_type == MessageType::Notification =>
+                    Some(message),
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Get the module name
     pub fn name(&self) -> &str { &self.name }
 
@@ -651,3 +871,28 @@ impl RpcDef {
     /// Get the result type name if available
     pub fn result_type_name(&self) -> Option<&str> { self.result.as_ref().map(|r| r.name.as_str()) }
 }
+
+impl RestEndpointDef {
+    /// Names of the `{placeholder}` segments in the path template, in order
+    pub fn path_placeholders(&self) -> Vec<&str> {
+        self.path
+            .split('/')
+            .filter_map(|segment| segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')))
+            .collect()
+    }
+
+    /// Whether the endpoint can return the given format
+    pub fn supports_format(&self, format: RestFormat) -> bool { self.formats.contains(&format) }
+}
+
+impl RestFormat {
+    /// Path extension selecting this format (e.g. "json")
+    pub fn extension(&self) -> &'static str {
+        match self {
+            RestFormat::Bin => "bin",
+            RestFormat::Hex => "hex",
+            RestFormat::Json => "json",
+            RestFormat::Text => "txt",
+        }
+    }
+}
@@ -2,7 +2,10 @@
 //!
 //! Available when the `test-utils` feature is enabled.
 
-use crate::protocol_ir::{ParamDef, ProtocolDef, ProtocolModule, RpcDef, TypeDef, TypeKind};
+use crate::protocol_ir::{
+    FieldDef, MessageDef, MessageType, ParamDef, ProtocolDef, ProtocolModule, RestEndpointDef,
+    RestFormat, RpcDef, TypeDef, TypeKind,
+};
 
 /// Builds a type definition with the given name and kind; other fields are empty/default.
 pub fn type_def(name: &str, kind: TypeKind) -> TypeDef {
@@ -46,6 +49,7 @@ pub fn param(name: &str, param_type: TypeDef, required: bool) -> ParamDef {
         default_value: None,
         version_added: None,
         version_removed: None,
+        deprecated: None,
     }
 }
 
@@ -61,6 +65,41 @@ pub fn rpc(name: &str, params: Vec<ParamDef>, result: Option<TypeDef>, category:
     }
 }
 
+/// Builds a minimal REST endpoint definition with no result type.
+pub fn rest_endpoint(
+    name: &str,
+    path: &str,
+    params: Vec<ParamDef>,
+    formats: Vec<RestFormat>,
+) -> RestEndpointDef {
+    RestEndpointDef {
+        name: name.to_string(),
+        description: String::new(),
+        path: path.to_string(),
+        params,
+        query_params: Vec::new(),
+        formats,
+        bare_path: false,
+        result: None,
+        shared_result: None,
+        version_added: None,
+        version_removed: None,
+    }
+}
+
+/// Builds a sequenced notification message published under `topic`.
+pub fn notification(name: &str, topic: &str, fields: Vec<FieldDef>) -> MessageDef {
+    MessageDef {
+        name: name.to_string(),
+        description: String::new(),
+        fields,
+        message_type: MessageType::Notification,
+        version: None,
+        topic: Some(topic.to_string()),
+        sequenced: true,
+    }
+}
+
 /// Builds a minimal protocol module (name and definitions only; description is empty).
 pub fn minimal_module(name: impl Into<String>, definitions: Vec<ProtocolDef>) -> ProtocolModule {
     ProtocolModule::new(name.into(), String::new(), definitions)
@@ -3,7 +3,10 @@
 //! Tests that IR can be serialized and deserialized without data loss,
 //! and that serialization is deterministic.
 
-use ethos_ir::{AccessLevel, ProtocolDef, ProtocolIR, ProtocolModule, RpcDef, TypeDef, TypeKind};
+use ethos_ir::{
+    AccessLevel, HttpBinding, HttpMethod, ProtocolDef, ProtocolIR, ProtocolModule, RpcDef, TypeDef,
+    TypeKind,
+};
 use tempfile::TempDir;
 
 /// Create a sample ProtocolIR for testing
@@ -31,6 +34,8 @@ fn create_sample_ir() -> ProtocolIR {
         hidden: None,
         version_added: None,
         version_removed: None,
+        deprecated: None,
+        http: None,
     };
 
     let module = ProtocolModule::from_source(
@@ -118,3 +123,26 @@ fn test_directory_creation() {
 
     assert!(nested_path.exists(), "File should be created in nested directory");
 }
+
+#[test]
+fn test_http_binding_roundtrip() {
+    let rpc_def = RpcDef {
+        name: "GetNodeInfo".to_string(),
+        http: Some(HttpBinding {
+            method: HttpMethod::Get,
+            path: "/v1/graph/node/{pub_key}".to_string(),
+            body: None,
+        }),
+        ..RpcDef::default()
+    };
+
+    let json = serde_json::to_value(&rpc_def).expect("serialize");
+    assert_eq!(json["http"]["method"], "GET");
+    assert!(json["http"].get("body").is_none(), "absent body is not serialized");
+
+    let plain = serde_json::to_value(RpcDef::default()).expect("serialize");
+    assert!(plain.get("http").is_none(), "JSON-RPC methods carry no http binding");
+
+    let loaded: RpcDef = serde_json::from_value(json).expect("deserialize");
+    assert_eq!(loaded.http, rpc_def.http);
+}
@@ -0,0 +1,245 @@
+//! Tests for applying dialect overlays to a base IR
+
+use ethos_ir::test_utils::{minimal_module, param, primitive_type, rpc, type_def};
+use ethos_ir::*;
+
+fn field(name: &str, field_type: TypeDef) -> FieldDef {
+    FieldDef {
+        key: FieldKey::Named(name.to_string()),
+        field_type,
+        required: true,
+        description: String::new(),
+        default_value: None,
+        version_added: None,
+        version_removed: None,
+        deprecated: None,
+    }
+}
+
+fn string() -> TypeDef { primitive_type("string", Some("string".to_string())) }
+
+fn base() -> ProtocolIR {
+    let mut peer = type_def("GetPeerInfoElement", TypeKind::Object);
+    peer.fields = Some(vec![field("id", string()), field("addr", string())]);
+    let mut peers = type_def("GetPeerInfo", TypeKind::Array);
+    peers.fields = Some(vec![FieldDef { key: FieldKey::Anonymous(0), ..field("", peer) }]);
+
+    ProtocolIR::new(vec![minimal_module(
+        "rpc",
+        vec![
+            ProtocolDef::RpcMethod(rpc("getblockcount", vec![], None, "blockchain")),
+            ProtocolDef::RpcMethod(rpc("getpeerinfo", vec![], Some(peers), "network")),
+            ProtocolDef::RpcMethod(rpc(
+                "sendrawtransaction",
+                vec![param("hexstring", string(), true), param("maxfeerate", string(), false)],
+                None,
+                "rawtransactions",
+            )),
+        ],
+    )])
+}
+
+fn names(ir: &ProtocolIR) -> Vec<&str> {
+    ir.get_rpc_methods().into_iter().map(|rpc| rpc.name.as_str()).collect()
+}
+
+#[test]
+fn applies_additions_removals_and_changes() {
+    let overlay = IrOverlay {
+        add_methods: vec![rpc("getgeneralinfo", vec![], None, "control")],
+        remove_methods: vec!["getblockcount".to_string()],
+        change_methods: vec![
+            MethodChange {
+                method: "sendrawtransaction".to_string(),
+                add_params: vec![param("ignore_rejects", string(), false)],
+                remove_params: vec!["maxfeerate".to_string()],
+                ..Default::default()
+            },
+            MethodChange {
+                method: "getpeerinfo".to_string(),
+                add_result_fields: vec![ResultFieldAddition {
+                    parent: String::new(),
+                    field: field("misbehavior", string()),
+                }],
+                remove_result_fields: vec!["addr".to_string()],
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+
+    let ir = overlay.apply(&base()).expect("overlay applies");
+    assert_eq!(names(&ir), vec!["getgeneralinfo", "getpeerinfo", "sendrawtransaction"]);
+
+    let methods = ir.get_rpc_methods();
+    let params: Vec<&str> = methods[2].params.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(params, vec!["hexstring", "ignore_rejects"]);
+
+    let peer = methods[1].result.as_ref().and_then(TypeDef::array_element_type).expect("element");
+    let fields: Vec<String> = peer.fields.iter().flatten().map(|f| f.key.as_ident()).collect();
+    assert_eq!(fields, vec!["id", "misbehavior"]);
+}
+
+#[test]
+fn reports_every_conflict() {
+    let overlay = IrOverlay {
+        add_methods: vec![rpc("getpeerinfo", vec![], None, "network")],
+        remove_methods: vec!["getinfo".to_string()],
+        change_methods: vec![
+            MethodChange {
+                method: "sendrawtransaction".to_string(),
+                add_params: vec![param("hexstring", string(), true)],
+                remove_result_fields: vec!["txid".to_string()],
+                ..Default::default()
+            },
+            MethodChange {
+                method: "getpeerinfo".to_string(),
+                add_result_fields: vec![ResultFieldAddition {
+                    parent: "id".to_string(),
+                    field: field("x", string()),
+                }],
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+
+    let err = overlay.apply(&base()).expect_err("overlay conflicts with its base");
+    assert_eq!(
+        err.conflicts,
+        vec![
+            "cannot remove method `getinfo`: not in the base IR",
+            "cannot add param `hexstring` to `sendrawtransaction`: already present",
+            "cannot change the result of `sendrawtransaction`: it has none",
+            "cannot add result field to `id` of `getpeerinfo`: no such object",
+            "cannot add method `getpeerinfo`: already in the base IR (change it instead)",
+        ]
+    );
+}
+
+#[test]
+fn deserializes_sparse_overlays() {
+    let overlay: IrOverlay =
+        serde_json::from_str(r#"{"remove_methods": ["getblockcount"]}"#).expect("parses");
+    assert!(overlay.add_methods.is_empty() && overlay.change_methods.is_empty());
+    assert_eq!(
+        names(&overlay.apply(&base()).expect("applies")),
+        vec!["getpeerinfo", "sendrawtransaction"]
+    );
+}
+
+#[test]
+fn overrides_items_by_path() {
+    let overlay: IrOverlay = serde_json::from_str(
+        r#"{"overrides": [
+            {"path": "getblockcount", "description": "Height of the tip", "version_added": "0.1"},
+            {"path": "sendrawtransaction.params.maxfeerate", "protocol_type": "amount"},
+            {"path": "getpeerinfo.result[].addr", "required": false, "version_removed": "30"},
+            {"path": "getpeerinfo.result[]", "description": "A connected peer"}
+        ]}"#,
+    )
+    .expect("parses");
+
+    let ir = overlay.apply(&base()).expect("overrides apply");
+    let methods = ir.get_rpc_methods();
+    assert_eq!(methods[0].description, "Height of the tip");
+    assert_eq!(methods[0].version_added.as_deref(), Some("0.1"));
+
+    let maxfeerate = &methods[2].params[1];
+    assert_eq!(maxfeerate.param_type.name, "amount");
+    assert_eq!(maxfeerate.param_type.protocol_type.as_deref(), Some("amount"));
+
+    let peer = methods[1].result.as_ref().and_then(TypeDef::array_element_type).expect("element");
+    assert_eq!(peer.description, "A connected peer");
+    let addr = &peer.fields.as_ref().expect("fields")[1];
+    assert!(!addr.required);
+    assert_eq!(addr.version_removed.as_deref(), Some("30"));
+}
+
+#[test]
+fn reports_unresolved_overrides() {
+    let paths = [
+        "getinfo",
+        "getblockcount.params.verbose",
+        "getblockcount.result",
+        "getpeerinfo.result.addr",
+        "getpeerinfo.result[].addr[]",
+        "getpeerinfo.inputs",
+    ];
+    let mut overrides: Vec<IrOverride> = paths
+        .iter()
+        .map(|path| IrOverride { path: path.to_string(), ..Default::default() })
+        .collect();
+    overrides.push(IrOverride {
+        path: "getblockcount".to_string(),
+        required: Some(true),
+        ..Default::default()
+    });
+    let overlay = IrOverlay { overrides, ..Default::default() };
+
+    let err = overlay.apply(&base()).expect_err("overrides conflict with the IR");
+    assert_eq!(
+        err.conflicts,
+        vec![
+            "cannot override `getinfo`: no such method",
+            "cannot override `getblockcount.params.verbose`: no such param",
+            "cannot override `getblockcount.result`: the method has no result",
+            "cannot override `getpeerinfo.result.addr`: no such field",
+            "cannot override `getpeerinfo.result[].addr[]`: `[]` on a type that is not an array",
+            "cannot override `getpeerinfo.inputs`: expected `params` or `result` after the method \
+             name",
+            "cannot override `getblockcount`: a method has no type or required flag",
+        ]
+    );
+}
+
+#[test]
+fn handles_fieldless_objects_and_arrays() {
+    let base = ProtocolIR::new(vec![minimal_module(
+        "rpc",
+        vec![
+            ProtocolDef::RpcMethod(rpc(
+                "getmempoolinfo",
+                vec![],
+                Some(type_def("GetMempoolInfo", TypeKind::Object)),
+                "blockchain",
+            )),
+            ProtocolDef::RpcMethod(rpc(
+                "getrawmempool",
+                vec![],
+                Some(type_def("GetRawMempool", TypeKind::Array)),
+                "blockchain",
+            )),
+        ],
+    )]);
+
+    let overlay = IrOverlay {
+        change_methods: vec![MethodChange {
+            method: "getmempoolinfo".to_string(),
+            add_result_fields: vec![ResultFieldAddition {
+                parent: String::new(),
+                field: field("loaded", string()),
+            }],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let ir = overlay.apply(&base).expect("adds to a fieldless object");
+    let info = ir.get_rpc_methods()[0].result.clone().expect("result");
+    let fields: Vec<String> = info.fields.iter().flatten().map(|f| f.key.as_ident()).collect();
+    assert_eq!(fields, vec!["loaded"]);
+
+    let overlay = IrOverlay {
+        overrides: vec![IrOverride {
+            path: "getrawmempool.result[]".to_string(),
+            description: Some("A txid".to_string()),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let err = overlay.apply(&base).expect_err("fieldless array has no element");
+    assert_eq!(
+        err.conflicts,
+        vec!["cannot override `getrawmempool.result[]`: `[]` on a type that is not an array"]
+    );
+}
@@ -1,6 +1,8 @@
 //! Comprehensive unit tests for the Ethos IR module
 
-use ethos_ir::test_utils::{minimal_module, rpc, type_def};
+use ethos_ir::test_utils::{
+    minimal_module, notification, param, primitive_type, rest_endpoint, rpc, type_def,
+};
 use ethos_ir::*;
 
 #[test]
@@ -199,6 +201,71 @@ fn test_protocol_ir_merge() {
     assert_eq!(single_merged.version(), "0.1.0");
 }
 
+#[test]
+fn test_protocol_ir_merge_keeps_rest_endpoints() {
+    let block = rest_endpoint("block", "/rest/block/{blockhash}", vec![], vec![RestFormat::Json]);
+    let ir1 = ProtocolIR::new(vec![minimal_module(
+        "rest",
+        vec![ProtocolDef::RestEndpoint(block.clone())],
+    )]);
+    let ir2 = ProtocolIR::new(vec![minimal_module("rest", vec![ProtocolDef::RestEndpoint(block)])]);
+
+    let merged = ProtocolIR::merge(vec![ir1, ir2]);
+
+    assert_eq!(merged.get_rest_endpoints().len(), 1);
+    assert!(merged.get_rpc_methods().is_empty());
+}
+
+#[test]
+fn test_protocol_ir_merge_keeps_notifications() {
+    let hashblock = notification("hashblock", "hashblock", vec![]);
+    let ir1 =
+        ProtocolIR::new(vec![minimal_module("zmq", vec![ProtocolDef::Message(hashblock.clone())])]);
+    let ir2 = ProtocolIR::new(vec![minimal_module("zmq", vec![ProtocolDef::Message(hashblock)])]);
+
+    let merged = ProtocolIR::merge(vec![ir1, ir2]);
+
+    let notifications = merged.get_notifications();
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0].topic.as_deref(), Some("hashblock"));
+    assert!(notifications[0].sequenced);
+}
+
+#[test]
+fn test_rest_endpoint_path_placeholders() {
+    let endpoint = rest_endpoint(
+        "getutxos",
+        "/rest/getutxos/{checkmempool}/{outpoints}",
+        vec![
+            param("checkmempool", primitive_type("boolean", None), false),
+            param("outpoints", primitive_type("array", None), true),
+        ],
+        vec![RestFormat::Bin, RestFormat::Hex, RestFormat::Json],
+    );
+
+    assert_eq!(endpoint.path_placeholders(), vec!["checkmempool", "outpoints"]);
+    assert!(endpoint.supports_format(RestFormat::Hex));
+    assert_eq!(RestFormat::Bin.extension(), "bin");
+}
+
+#[test]
+fn test_rest_endpoint_serde_roundtrip() {
+    let mut endpoint =
+        rest_endpoint("headers", "/rest/headers/{blockhash}", vec![], vec![RestFormat::Json]);
+    endpoint.shared_result = Some(RestSharedResult {
+        method: "getblockheader".to_string(),
+        container: RestResultContainer::Array,
+    });
+
+    let json = serde_json::to_value(&endpoint).expect("serialize endpoint");
+    assert_eq!(json["formats"], serde_json::json!(["json"]));
+    assert_eq!(json["shared_result"]["container"], "array");
+    assert!(json.get("query_params").is_none());
+
+    let back: RestEndpointDef = serde_json::from_value(json).expect("deserialize endpoint");
+    assert_eq!(back.shared_result, endpoint.shared_result);
+}
+
 #[test]
 fn test_protocol_module_new() {
     let definitions = vec![ProtocolDef::RpcMethod(rpc("test", vec![], None, "test"))];
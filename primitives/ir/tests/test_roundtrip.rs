@@ -23,6 +23,7 @@ fn test_field_key_roundtrip() {
                 default_value: None,
                 version_added: None,
                 version_removed: None,
+                deprecated: None,
             },
             FieldDef {
                 key: FieldKey::Anonymous(1),
@@ -32,6 +33,7 @@ fn test_field_key_roundtrip() {
                 default_value: None,
                 version_added: None,
                 version_removed: None,
+                deprecated: None,
             },
         ]),
         variants: None,
@@ -66,6 +68,7 @@ fn array_element_type_helper_supports_anonymous_and_named_field_0() {
             default_value: None,
             version_added: None,
             version_removed: None,
+            deprecated: None,
         }]),
         variants: None,
         union_variants: None,
@@ -92,6 +95,7 @@ fn array_element_type_helper_supports_anonymous_and_named_field_0() {
             default_value: None,
             version_added: None,
             version_removed: None,
+            deprecated: None,
         }]),
         variants: None,
         union_variants: None,
@@ -123,6 +127,7 @@ fn array_element_type_helper_supports_anonymous_and_named_field_0() {
                 default_value: None,
                 version_added: None,
                 version_removed: None,
+                deprecated: None,
             },
             FieldDef {
                 key: FieldKey::Anonymous(1),
@@ -132,6 +137,7 @@ fn array_element_type_helper_supports_anonymous_and_named_field_0() {
                 default_value: None,
                 version_added: None,
                 version_removed: None,
+                deprecated: None,
             },
         ]),
         variants: None,
@@ -13,10 +13,24 @@
 
 use serde_json::{self, Value};
 
-/// Embed normalization from workspace for Bitcoin Core.
+/// Embed normalization from workspace for Bitcoin Core and the other `bitcoin` dialects (btcd,
+/// Bitcoin Knots).
 const BITCOIN_NORMALIZATION_JSON: &str =
     include_str!("../../../resources/adapters/normalization/bitcoin.json");
 
+/// Embed normalization from workspace for the `lightning` dialects (Core Lightning).
+const LIGHTNING_NORMALIZATION_JSON: &str =
+    include_str!("../../../resources/adapters/normalization/lightning.json");
+
+/// Embed normalization from workspace for the `electrum` dialects (electrs).
+const ELECTRUM_NORMALIZATION_JSON: &str =
+    include_str!("../../../resources/adapters/normalization/electrum.json");
+
+/// Embed normalization from workspace for the `esplora` dialects (Esplora's REST API has no
+/// RPC methods, so its mappings are empty).
+const ESPLORA_NORMALIZATION_JSON: &str =
+    include_str!("../../../resources/adapters/normalization/esplora.json");
+
 /// Relative dirs (from workspace root) for the two copies of each normalization JSON file.
 /// Used for error messages and by the pipeline when writing suggested mappings.
 pub const NORMALIZATION_JSON_DIRS: [&str; 2] =
@@ -69,35 +83,57 @@ pub fn canonical_from_adapter_method(
     rpc_method: &str,
     context: Option<&UnmappedMethodContext<'_>>,
 ) -> Result<String, String> {
-    let (preset_json_str, impl_key) = match protocol {
-        "bitcoin_core" => (BITCOIN_NORMALIZATION_JSON, "bitcoin_core"),
-        other => return Err(format!("Unsupported protocol '{}'. Supported: bitcoin_core", other)),
+    // Overlay dialects map only the methods they add and fall back to their base's mappings
+    let (preset_json_str, impl_key, base_key) = match protocol {
+        "bitcoin_core" => (BITCOIN_NORMALIZATION_JSON, "bitcoin_core", None),
+        "btcd" => (BITCOIN_NORMALIZATION_JSON, "btcd", None),
+        "bitcoin_knots" => (BITCOIN_NORMALIZATION_JSON, "bitcoin_knots", Some("bitcoin_core")),
+        "core_lightning" => (LIGHTNING_NORMALIZATION_JSON, "core_lightning", None),
+        "lnd" => (LIGHTNING_NORMALIZATION_JSON, "lnd", None),
+        "electrs" => (ELECTRUM_NORMALIZATION_JSON, "electrs", None),
+        "esplora" => (ESPLORA_NORMALIZATION_JSON, "esplora", None),
+        other =>
+            return Err(format!(
+                "Unsupported protocol '{}'. Supported: bitcoin_core, btcd, bitcoin_knots, core_lightning, lnd, electrs, esplora",
+                other
+            )),
     };
 
     let preset: Value = serde_json::from_str(preset_json_str)
         .map_err(|e| format!("Failed to parse normalization preset for {}: {}", protocol, e))?;
 
-    let mappings = preset
-        .get("method_mappings")
-        .and_then(|mm| mm.get(impl_key))
-        .and_then(|v| v.as_object())
-        .ok_or_else(|| {
-            format!(
-                "Normalization preset for '{}' missing method_mappings. File must define method_mappings.{}",
-                protocol, impl_key
-            )
-        })?;
+    let mappings_for = |key: &str| {
+        preset.get("method_mappings").and_then(|mm| mm.get(key)).and_then(|v| v.as_object()).ok_or_else(
+            || {
+                format!(
+                    "Normalization preset for '{}' missing method_mappings. File must define method_mappings.{}",
+                    protocol, key
+                )
+            },
+        )
+    };
+    let mut mapping_sets = vec![mappings_for(impl_key)?];
+    if let Some(base_key) = base_key {
+        mapping_sets.push(mappings_for(base_key)?);
+    }
 
     // Build reverse map: adapter-specific -> canonical (PascalCase)
-    for (canonical, adapter_specific_val) in mappings.iter() {
-        if let Some(adapter_specific) = adapter_specific_val.as_str() {
-            if adapter_specific == rpc_method {
-                return Ok(canonical.to_string());
+    for mappings in mapping_sets {
+        for (canonical, adapter_specific_val) in mappings.iter() {
+            if let Some(adapter_specific) = adapter_specific_val.as_str() {
+                if adapter_specific == rpc_method {
+                    return Ok(canonical.to_string());
+                }
             }
         }
     }
 
-    let filename = if protocol == "bitcoin_core" { "bitcoin" } else { protocol };
+    let filename = match protocol {
+        "bitcoin_core" | "btcd" | "bitcoin_knots" => "bitcoin",
+        "core_lightning" | "lnd" => "lightning",
+        "electrs" => "electrum",
+        other => other,
+    };
     let suggested = suggest_canonical_key(rpc_method);
     let path_list: String = NORMALIZATION_JSON_DIRS
         .iter()
@@ -144,10 +180,9 @@ pub fn canonical_from_adapter_method(
 pub fn suggest_canonical_key(rpc_method: &str) -> String {
     // Known acronym- or brand-like names that don't follow simple word capitalization.
     // Keep this list small and reserved for truly exceptional cases.
-    match rpc_method {
-        // Bitcoin Core OpenRPC helper method: keep "RPC" fully capitalized.
-        "getopenrpcinfo" => return "GetOpenRpcInfo".to_string(),
-        _ => {}
+    // Bitcoin Core OpenRPC helper method: keep "RPC" fully capitalized.
+    if rpc_method == "getopenrpcinfo" {
+        return "GetOpenRpcInfo".to_string();
     }
 
     let lower = rpc_method.to_lowercase();
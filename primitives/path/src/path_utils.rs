@@ -7,8 +7,6 @@
 
 use std::path::{Path, PathBuf};
 
-use types::ProtocolVersion;
-
 /// Find the workspace root by looking for the root Cargo.toml
 ///
 /// This function walks up the directory tree from the current directory
@@ -156,53 +154,8 @@ pub fn load_registry() -> Result<serde_json::Value, Box<dyn std::error::Error>>
 pub fn parse_version_components(version: &str) -> (u32, u32, u32) {
     let version_clean = version.trim_start_matches('v');
     let parts: Vec<&str> = version_clean.split('.').collect();
-    let major: u32 = parts.get(0).and_then(|s| s.parse().ok()).unwrap_or(0);
+    let major: u32 = parts.first().and_then(|s| s.parse().ok()).unwrap_or(0);
     let minor: u32 = parts.get(1).and_then(|s| s.parse().ok()).unwrap_or(0);
     let patch: u32 = parts.get(2).and_then(|s| s.parse().ok()).unwrap_or(0);
     (major, minor, patch)
 }
-
-/// Format version string for filename (e.g., "30.2" -> "30_2_0", "30.2.1" -> "30_2_1")
-///
-/// Replaces dots with underscores to create filesystem-safe version strings.
-/// Normalizes 2-part versions (e.g., "30.2") to 3-part versions with patch 0 (e.g., "30.2.0").
-/// Uses `ProtocolVersion::as_filename_version()` for consistency across the codebase.
-pub fn format_version_for_filename(version: &str) -> String {
-    // Parse version string to ProtocolVersion for consistent formatting
-    ProtocolVersion::from_string(version)
-        .map(|v| {
-            // If the original version string only has 2 parts, normalize to 3 parts with patch 0
-            let parts: Vec<&str> = v.version_string.split('.').collect();
-            if parts.len() == 2 {
-                // Normalize 2-part version to 3-part: "30.2" -> "30.2.0" -> "30_2_0"
-                format!("{}_{}_{}", v.major, v.minor, v.patch)
-            } else {
-                // Use the standard filename version for 3-part versions
-                v.as_filename_version()
-            }
-        })
-        .unwrap_or_else(|_| {
-            // Fallback: if parsing fails, use parsed components
-            let (major, minor, patch) = parse_version_components(version);
-            format!("{}_{}_{}", major, minor, patch)
-        })
-}
-
-/// Generate version-specific IR filename
-///
-/// # Arguments
-///
-/// * `version` - Version string (e.g., "30.2", "30.2.0", "30.2.1")
-/// * `protocol` - Protocol name (e.g., "bitcoin")
-///
-/// # Examples
-///
-/// ```
-/// use ethos_path::version_ir_filename;
-/// assert_eq!(version_ir_filename("30.2", "bitcoin"), "v30_2_0_bitcoin.ir.json");
-/// assert_eq!(version_ir_filename("30.2.0", "bitcoin"), "v30_2_0_bitcoin.ir.json");
-/// assert_eq!(version_ir_filename("30.2.1", "bitcoin"), "v30_2_1_bitcoin.ir.json");
-/// ```
-pub fn version_ir_filename(version: &str, protocol: &str) -> String {
-    format!("v{}_{}.ir.json", format_version_for_filename(version), protocol)
-}
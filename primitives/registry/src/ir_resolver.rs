@@ -8,6 +8,7 @@ use std::path::PathBuf;
 use path::find_project_root;
 use serde_json::Value;
 use types::implementation::{Implementation, Protocol};
+use types::ImplementationMetadata;
 
 /// Errors that can occur during IR file resolution
 #[derive(Debug, thiserror::Error)]
@@ -29,6 +30,16 @@ pub enum IrResolverError {
 /// Result alias for IR resolver operations
 pub type IrResolverResult<T> = std::result::Result<T, IrResolverError>;
 
+/// A dialect whose IR is another dialect's plus an overlay (`base_dialect` and `overlay_file`
+/// in the registry)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DialectOverlay {
+    /// Dialect whose IR the overlay applies to
+    pub base: Implementation,
+    /// Path of the [`ir::IrOverlay`] JSON file
+    pub overlay_file: PathBuf,
+}
+
 /// IR file resolver that uses the adapter registry to locate IR files
 pub struct IrResolver {
     registry: Value,
@@ -67,18 +78,85 @@ impl IrResolver {
         Ok(self.project_root.join(ir_file))
     }
 
+    /// Registry entry of an implementation's dialect
+    fn dialect(&self, implementation: &Implementation) -> &Value {
+        &self.registry["adapters"][implementation.protocol_name().as_str()]["dialects"]
+            [implementation.as_str()]
+    }
+
     /// Resolve the IR file path for a given implementation
+    ///
+    /// A dialect-level `ir_file` (e.g. btcd's btcjson-derived IR) takes precedence over
+    /// the protocol's shared IR file. For an overlay dialect this is its base's IR file; see
+    /// [`Self::overlay_for_implementation`].
     pub fn resolve_ir_path_for_implementation(
         &self,
         implementation: &Implementation,
     ) -> IrResolverResult<PathBuf> {
+        if let Some(overlay) = self.overlay_for_implementation(implementation)? {
+            return self.resolve_ir_path_for_implementation(&overlay.base);
+        }
         let protocol_name = implementation.protocol_name();
+        if let Some(ir_file) = self.dialect(implementation)["ir_file"].as_str() {
+            return Ok(self.project_root.join(ir_file));
+        }
         let protocol = protocol_name
             .parse::<Protocol>()
             .map_err(|e| IrResolverError::Registry(format!("Invalid protocol name: {}", e)))?;
         self.resolve_ir_path(&protocol)
     }
 
+    /// The overlay an implementation's IR is defined by, if its dialect names a `base_dialect`
+    ///
+    /// The base must be a dialect of the same protocol and must not itself be an overlay.
+    pub fn overlay_for_implementation(
+        &self,
+        implementation: &Implementation,
+    ) -> IrResolverResult<Option<DialectOverlay>> {
+        let dialect = self.dialect(implementation);
+        let Some(base) = dialect["base_dialect"].as_str() else {
+            return Ok(None);
+        };
+        let base = base.parse::<Implementation>().map_err(|e| {
+            IrResolverError::Registry(format!(
+                "Invalid base_dialect of '{}': {}",
+                implementation, e
+            ))
+        })?;
+        if base.protocol_name() != implementation.protocol_name()
+            || !self.dialect(&base)["base_dialect"].is_null()
+        {
+            return Err(IrResolverError::Registry(format!(
+                "base_dialect of '{}' must be a non-overlay {} dialect, not '{}'",
+                implementation,
+                implementation.protocol_name(),
+                base
+            )));
+        }
+        let overlay_file = dialect["overlay_file"].as_str().ok_or_else(|| {
+            IrResolverError::Registry(format!(
+                "Dialect '{}' has a base_dialect but no overlay_file",
+                implementation
+            ))
+        })?;
+        Ok(Some(DialectOverlay { base, overlay_file: self.project_root.join(overlay_file) }))
+    }
+
+    /// Register the registry's dialects that are not built in, so they parse as
+    /// [`Implementation`]s
+    ///
+    /// Such a dialect declares its `metadata` like the built-ins do and names a built-in
+    /// `base_dialect` whose generators it uses; see [`Implementation::register`]. Returns the
+    /// registered implementations.
+    pub fn register_dialects(&self) -> IrResolverResult<Vec<Implementation>> {
+        ImplementationMetadata::from_registry(&self.registry)
+            .map_err(IrResolverError::Registry)?
+            .into_iter()
+            .filter(|metadata| !Implementation::BUILTIN.iter().any(|b| b.as_str() == metadata.name))
+            .map(|metadata| Implementation::register(metadata).map_err(IrResolverError::Registry))
+            .collect()
+    }
+
     /// Returns the default version string for this implementation, as defined in the registry.
     ///
     /// This reads `registry["adapters"][protocol]["dialects"][implementation]["default_version"]`
@@ -174,6 +252,88 @@ mod tests {
             .resolve_ir_path_for_implementation(&Implementation::BitcoinCore)
             .expect("Failed to resolve Bitcoin Core IR path");
         assert!(bitcoin_core_path.to_string_lossy().contains("bitcoin.ir.json"));
+
+        // Dialect-level ir_file overrides the protocol's
+        let btcd_path = resolver
+            .resolve_ir_path_for_implementation(&Implementation::Btcd)
+            .expect("Failed to resolve btcd IR path");
+        assert!(btcd_path.to_string_lossy().contains("btcd.ir.json"));
+
+        let cln_path = resolver
+            .resolve_ir_path_for_implementation(&Implementation::CoreLightning)
+            .expect("Failed to resolve Core Lightning IR path");
+        assert!(cln_path.to_string_lossy().contains("lightning.ir.json"));
+
+        let lnd_path = resolver
+            .resolve_ir_path_for_implementation(&Implementation::Lnd)
+            .expect("Failed to resolve LND IR path");
+        assert!(lnd_path.to_string_lossy().contains("lnd.ir.json"));
+
+        let electrs_path = resolver
+            .resolve_ir_path_for_implementation(&Implementation::Electrs)
+            .expect("Failed to resolve electrs IR path");
+        assert!(electrs_path.to_string_lossy().contains("electrum.ir.json"));
+
+        // Overlay dialects resolve to their base's IR
+        let knots_path = resolver
+            .resolve_ir_path_for_implementation(&Implementation::BitcoinKnots)
+            .expect("Failed to resolve Bitcoin Knots IR path");
+        assert_eq!(knots_path, bitcoin_core_path);
+
+        let esplora_path = resolver
+            .resolve_ir_path_for_implementation(&Implementation::Esplora)
+            .expect("Failed to resolve Esplora IR path");
+        assert!(esplora_path.to_string_lossy().contains("esplora.ir.json"));
+    }
+
+    #[test]
+    fn test_overlay_for_implementation() {
+        let resolver = IrResolver::new().expect("Failed to create resolver");
+
+        let overlay = resolver
+            .overlay_for_implementation(&Implementation::BitcoinKnots)
+            .expect("Failed to read Bitcoin Knots overlay")
+            .expect("Bitcoin Knots is an overlay dialect");
+        assert_eq!(overlay.base, Implementation::BitcoinCore);
+        assert!(overlay.overlay_file.exists());
+
+        assert_eq!(
+            resolver
+                .overlay_for_implementation(&Implementation::BitcoinCore)
+                .expect("Failed to read Bitcoin Core dialect"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_register_dialects() {
+        let mut resolver = IrResolver::new().expect("Failed to create resolver");
+        assert!(resolver.register_dialects().expect("Failed to register dialects").is_empty());
+
+        let dialects = &mut resolver.registry["adapters"]["bitcoin"]["dialects"];
+        let mut vendor = dialects["bitcoin_knots"].clone();
+        vendor["name"] = "Vendor Bitcoin".into();
+        vendor["implementation"] = "bitcoin_vendor".into();
+        vendor["metadata"]["client_prefix"] = "VendorBitcoinClient".into();
+        dialects["bitcoin_vendor"] = vendor;
+
+        let registered = resolver.register_dialects().expect("Failed to register dialects");
+        let vendor = "bitcoin_vendor".parse::<Implementation>().expect("vendor is registered");
+        assert_eq!(registered, vec![vendor]);
+        assert_eq!(vendor.display_name(), "Vendor Bitcoin");
+        assert_eq!(vendor.client_prefix(), "VendorBitcoinClient");
+        assert_eq!(vendor.builtin_base(), Implementation::BitcoinCore);
+
+        let overlay = resolver
+            .overlay_for_implementation(&vendor)
+            .expect("Failed to read vendor overlay")
+            .expect("vendor is an overlay dialect");
+        assert_eq!(overlay.base, Implementation::BitcoinCore);
+        assert!(resolver
+            .resolve_ir_path_for_implementation(&vendor)
+            .expect("Failed to resolve vendor IR path")
+            .to_string_lossy()
+            .contains("bitcoin.ir.json"));
     }
 
     #[test]
@@ -182,5 +342,8 @@ mod tests {
         let protocols = resolver.list_available_protocols().expect("Failed to list protocols");
 
         assert!(protocols.contains(&Protocol::Bitcoin));
+        assert!(protocols.contains(&Protocol::Lightning));
+        assert!(protocols.contains(&Protocol::Electrum));
+        assert!(protocols.contains(&Protocol::Esplora));
     }
 }
@@ -89,6 +89,8 @@ pub fn map_parameter_type_to_rust(param_type: &str, param_name: &str) -> String
                 "Vec<bitcoin::Address<bitcoin::address::NetworkUnchecked>>".to_owned(),
             // sendall: list of { address, amount? } (struct emitted in generated params)
             "recipients" => "Vec<SendallRecipient>".to_owned(),
+            // REST getutxos: outpoints rendered as <txid>-<n> path segments
+            "outpoints" => "Vec<bitcoin::OutPoint>".to_owned(),
             _ => "Vec<serde_json::Value>".to_owned(),
         };
     }
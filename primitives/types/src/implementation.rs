@@ -1,16 +1,23 @@
 //! Type-safe implementation names for Bitcoin protocol implementations.
 //!
 //! This module provides the `Implementation` enum that provides
-//! compile-time validation.
+//! compile-time validation for the built-in implementations. Their metadata lives in
+//! the dialects' `metadata` entries in `resources/adapters/registry.json`; dialects that are
+//! not built in can be registered at runtime from the same entries.
 //!
 //! To add a new protocol later: add a variant to `Protocol`, a dialect in
-//! `resources/adapters/registry.json`, and an adapter (plus `Implementation` variant and metadata).
+//! `resources/adapters/registry.json`, and an adapter (plus an `Implementation` variant).
 
 use std::fmt;
 use std::str::FromStr;
+use std::sync::OnceLock;
 
 use serde::{Deserialize, Serialize};
 
+use crate::implementation_metadata::{
+    builtin, register, registered_dialect, ImplementationMetadata, RegisteredDialect,
+};
+
 /// Protocol names for Bitcoin protocol implementations.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 pub enum Protocol {
@@ -18,6 +25,15 @@ pub enum Protocol {
     #[default]
     #[serde(rename = "bitcoin")]
     Bitcoin,
+    /// Lightning Network protocol
+    #[serde(rename = "lightning")]
+    Lightning,
+    /// Electrum server protocol
+    #[serde(rename = "electrum")]
+    Electrum,
+    /// Esplora HTTP API
+    #[serde(rename = "esplora")]
+    Esplora,
 }
 
 impl Protocol {
@@ -25,6 +41,9 @@ impl Protocol {
     pub fn as_str(&self) -> &'static str {
         match self {
             Protocol::Bitcoin => "bitcoin",
+            Protocol::Lightning => "lightning",
+            Protocol::Electrum => "electrum",
+            Protocol::Esplora => "esplora",
         }
     }
 }
@@ -35,6 +54,9 @@ impl FromStr for Protocol {
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         match s {
             "bitcoin" => Ok(Protocol::Bitcoin),
+            "lightning" => Ok(Protocol::Lightning),
+            "electrum" => Ok(Protocol::Electrum),
+            "esplora" => Ok(Protocol::Esplora),
             _ => Err(format!("Unknown protocol name: {}", s)),
         }
     }
@@ -50,150 +72,171 @@ pub enum Implementation {
     /// Bitcoin Core implementation
     #[default]
     BitcoinCore,
+    /// btcd (btcsuite) implementation
+    Btcd,
+    /// Core Lightning (lightningd) implementation
+    CoreLightning,
+    /// LND (Lightning Network Daemon) implementation
+    Lnd,
+    /// electrs (Electrum Rust Server) implementation
+    Electrs,
+    /// Esplora (Blockstream's electrs fork) HTTP API implementation
+    Esplora,
+    /// Bitcoin Knots implementation (Bitcoin Core IR plus an overlay)
+    BitcoinKnots,
+    /// A dialect registered at runtime from the adapter registry (see
+    /// [`Implementation::register`])
+    #[serde(skip)]
+    Registered(&'static RegisteredDialect),
 }
 
-/// Metadata for an implementation variant.
-struct ImplementationMetadata {
-    as_str: &'static str,
-    protocol_name: &'static str,
-    display_name: &'static str,
-    crate_name: &'static str,
-    client_dir_name: &'static str,
-    transport_protocol: &'static str,
-    executable_name: &'static str,
-    test_client_prefix: &'static str,
-    node_manager_name: &'static str,
-    client_prefix: &'static str,
-    example_method: &'static str,
-    example_description: &'static str,
-}
-
-impl ImplementationMetadata {
-    const fn new(
-        as_str: &'static str,
-        protocol_name: &'static str,
-        display_name: &'static str,
-        crate_name: &'static str,
-        client_dir_name: &'static str,
-        transport_protocol: &'static str,
-        executable_name: &'static str,
-        test_client_prefix: &'static str,
-        node_manager_name: &'static str,
-        client_prefix: &'static str,
-        example_method: &'static str,
-        example_description: &'static str,
-    ) -> Self {
-        Self {
-            as_str,
-            protocol_name,
-            display_name,
-            crate_name,
-            client_dir_name,
-            transport_protocol,
-            executable_name,
-            test_client_prefix,
-            node_manager_name,
-            client_prefix,
-            example_method,
-            example_description,
+impl Implementation {
+    /// The built-in implementations
+    pub const BUILTIN: [Implementation; 7] = [
+        Implementation::BitcoinCore,
+        Implementation::Btcd,
+        Implementation::CoreLightning,
+        Implementation::Lnd,
+        Implementation::Electrs,
+        Implementation::Esplora,
+        Implementation::BitcoinKnots,
+    ];
+
+    /// Register a dialect that is not built in, from its registry metadata.
+    ///
+    /// The dialect must name a built-in `base_dialect` of the same protocol: its generators,
+    /// method mappings and templates are used for the registered one. Registering a name
+    /// again returns the implementation registered first.
+    ///
+    /// Registered dialects are leaked to hand out `'static` names and metadata, so
+    /// registration is meant to run once per process.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name is a built-in implementation, or if the metadata names an
+    /// unknown protocol, type adapter or base dialect.
+    pub fn register(metadata: ImplementationMetadata) -> Result<Implementation, String> {
+        if Implementation::BUILTIN.iter().any(|b| b.as_str() == metadata.name) {
+            return Err(format!("'{}' is a built-in implementation", metadata.name));
         }
+        metadata.protocol.parse::<Protocol>()?;
+        type_adapter(&metadata.type_adapter)?;
+        let base_name = metadata.base_dialect.as_deref().ok_or_else(|| {
+            format!("Dialect '{}' needs a built-in base_dialect to generate with", metadata.name)
+        })?;
+        let base = Implementation::BUILTIN
+            .into_iter()
+            .find(|b| b.as_str() == base_name)
+            .filter(|b| b.protocol_name() == metadata.protocol)
+            .ok_or_else(|| {
+                format!(
+                    "base_dialect of '{}' must be a built-in {} implementation, not '{}'",
+                    metadata.name, metadata.protocol, base_name
+                )
+            })?;
+        Ok(Implementation::Registered(register(metadata, base)))
     }
-}
-
-const IMPLEMENTATION_METADATA: [ImplementationMetadata; 1] = [ImplementationMetadata::new(
-    "bitcoin_core",
-    "bitcoin",
-    "Bitcoin Core",
-    "bitcoin-core",
-    "bitcoin_core_client",
-    "http",
-    "bitcoind",
-    "BitcoinTestClient",
-    "BitcoinNodeManager",
-    "BitcoinClient",
-    "getblockchaininfo",
-    "Blockchain info",
-)];
 
-impl Implementation {
     /// Get the metadata for this implementation.
-    fn metadata(&self) -> &'static ImplementationMetadata {
-        let index = match self {
-            Implementation::BitcoinCore => 0,
-        };
-        &IMPLEMENTATION_METADATA[index]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the registry cannot be read or declares no valid metadata for a
+    /// built-in implementation. The accessors below fall back to empty values in that case,
+    /// so callers taking an implementation from users should check this first.
+    pub fn metadata(&self) -> Result<&'static ImplementationMetadata, String> {
+        match self {
+            Implementation::Registered(dialect) => Ok(dialect.metadata()),
+            _ => builtin()?
+                .iter()
+                .find(|m| m.name == self.as_str())
+                .ok_or_else(|| format!("The registry declares no metadata for {}", self.as_str())),
+        }
+    }
+
+    /// Metadata for the accessors, empty when [`Self::metadata`] fails
+    fn metadata_or_empty(&self) -> &'static ImplementationMetadata {
+        static EMPTY: OnceLock<ImplementationMetadata> = OnceLock::new();
+        self.metadata().unwrap_or_else(|_| EMPTY.get_or_init(Default::default))
     }
 
     /// Get the string representation of the implementation name.
-    pub fn as_str(&self) -> &'static str { self.metadata().as_str }
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Implementation::BitcoinCore => "bitcoin_core",
+            Implementation::Btcd => "btcd",
+            Implementation::CoreLightning => "core_lightning",
+            Implementation::Lnd => "lnd",
+            Implementation::Electrs => "electrs",
+            Implementation::Esplora => "esplora",
+            Implementation::BitcoinKnots => "bitcoin_knots",
+            Implementation::Registered(dialect) => dialect.metadata().name.as_str(),
+        }
+    }
+
+    /// Get the built-in implementation whose generators, method mappings and templates this
+    /// one uses: itself for built-ins, the `base_dialect` for registered dialects.
+    pub fn builtin_base(&self) -> Implementation {
+        match self {
+            Implementation::Registered(dialect) => dialect.base(),
+            _ => *self,
+        }
+    }
 
     /// Get the protocol name that this implementation supports.
-    pub fn protocol_name(&self) -> String { self.metadata().protocol_name.to_string() }
+    pub fn protocol_name(&self) -> String { self.metadata_or_empty().protocol.clone() }
 
     /// Get the human-readable display name for the implementation.
-    pub fn display_name(&self) -> &'static str { self.metadata().display_name }
+    pub fn display_name(&self) -> &'static str { &self.metadata_or_empty().display_name }
 
     /// Get the crate name for the implementation (with hyphens).
-    pub fn crate_name(&self) -> &'static str { self.metadata().crate_name }
+    pub fn crate_name(&self) -> &'static str { &self.metadata_or_empty().crate_name }
 
     /// Get the client directory name for the implementation.
-    pub fn client_dir_name(&self) -> &'static str { self.metadata().client_dir_name }
+    pub fn client_dir_name(&self) -> &'static str { &self.metadata_or_empty().client_dir_name }
 
     /// Get the transport protocol for the implementation.
-    pub fn transport_protocol(&self) -> &'static str { self.metadata().transport_protocol }
+    pub fn transport_protocol(&self) -> &'static str { &self.metadata_or_empty().node.transport }
 
     /// Get the executable name for the implementation.
-    pub fn executable_name(&self) -> &'static str { self.metadata().executable_name }
+    pub fn executable_name(&self) -> &'static str { &self.metadata_or_empty().node.executable }
 
     /// Get the test client class name prefix.
-    pub fn test_client_prefix(&self) -> &'static str { self.metadata().test_client_prefix }
+    pub fn test_client_prefix(&self) -> &'static str {
+        &self.metadata_or_empty().test_client_prefix
+    }
 
     /// Get the node manager name for the implementation.
-    pub fn node_manager_name(&self) -> &'static str { self.metadata().node_manager_name }
+    pub fn node_manager_name(&self) -> &'static str { &self.metadata_or_empty().node_manager_name }
 
     /// Get the client class name prefix.
-    pub fn client_prefix(&self) -> &'static str { self.metadata().client_prefix }
+    pub fn client_prefix(&self) -> &'static str { &self.metadata_or_empty().client_prefix }
 
     /// Get the example method name for documentation.
-    pub fn example_method(&self) -> &'static str { self.metadata().example_method }
+    pub fn example_method(&self) -> &'static str { &self.metadata_or_empty().example_method }
 
     /// Get the example method description for documentation.
-    pub fn example_description(&self) -> &'static str { self.metadata().example_description }
+    pub fn example_description(&self) -> &'static str {
+        &self.metadata_or_empty().example_description
+    }
+
+    /// Whether this implementation serves Bitcoin Core's RPC interface: Core itself or a
+    /// derivative such as Knots, whose IR carries Core's `version_added`/`version_removed`.
+    pub fn is_bitcoin_core_family(&self) -> bool {
+        matches!(self.builtin_base(), Implementation::BitcoinCore | Implementation::BitcoinKnots)
+    }
 
     /// Get the published crate name for this implementation (e.g., "ethos-bitcoind").
     pub fn published_crate_name(&self) -> &'static str {
-        match self {
-            Implementation::BitcoinCore => "ethos-bitcoind",
-        }
+        &self.metadata_or_empty().published_crate_name
     }
 
     /// Get node metadata for this implementation
     pub fn node_metadata(&self) -> crate::node_metadata::NodeMetadata {
-        match self {
-            Implementation::BitcoinCore => crate::node_metadata::NodeMetadata {
-                executable: "bitcoind".to_string(),
-                transport: "http".to_string(),
-                requires_auth: true,
-                cli_args: crate::node_metadata::CliArgs::new()
-                    .add_value_arg("chain", "-chain={}")
-                    .add_value_arg("datadir", "-datadir={}")
-                    .add_value_arg("rpc_port", "-rpcport={}")
-                    .add_value_arg("rpc_bind", "-rpcbind=127.0.0.1:{}")
-                    .add_value_arg("rpc_user", "-rpcuser={}")
-                    .add_value_arg("rpc_password", "-rpcpassword={}")
-                    .add_static_arg("-listen=0")
-                    .add_static_arg("-rpcallowip=127.0.0.1")
-                    .add_static_arg("-fallbackfee=0.0002")
-                    .add_static_arg("-server=1")
-                    .add_static_arg("-prune=1"),
-                readiness_method: "getnetworkinfo".to_string(),
-                initialization_error_codes: vec![-28, -4],
-                socket_path_pattern: None,
-            },
-        }
+        self.metadata_or_empty().node.clone()
     }
 
-    /// Create a type adapter for this implementation.
+    /// Create the type adapter named by this implementation's metadata.
     ///
     /// # Returns
     ///
@@ -201,12 +244,19 @@ impl Implementation {
     ///
     /// # Errors
     ///
-    /// Returns an error if the implementation doesn't have a type adapter yet.
-    /// Currently supported: BitcoinCore
+    /// Returns an error if the metadata cannot be read or names an unknown type adapter.
     pub fn create_type_adapter(&self) -> Result<Box<dyn crate::type_adapter::TypeAdapter>, String> {
-        match self {
-            Implementation::BitcoinCore => Ok(Box::new(crate::adapters::BitcoinCoreAdapter)),
-        }
+        type_adapter(&self.metadata()?.type_adapter)
+    }
+}
+
+/// Type adapter named by implementation metadata
+fn type_adapter(name: &str) -> Result<Box<dyn crate::type_adapter::TypeAdapter>, String> {
+    match name {
+        // btcd, the CLN schema adapter, the LND proto adapter, the Electrum adapter and the
+        // OpenAPI adapter emit the same type vocabulary as Bitcoin Core
+        "bitcoin_core" => Ok(Box::new(crate::adapters::BitcoinCoreAdapter)),
+        other => Err(format!("Unknown type adapter: {}", other)),
     }
 }
 
@@ -216,7 +266,15 @@ impl FromStr for Implementation {
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         match s {
             "bitcoin_core" => Ok(Implementation::BitcoinCore),
-            _ => Err(format!("Unknown implementation name: {}", s)),
+            "btcd" => Ok(Implementation::Btcd),
+            "core_lightning" => Ok(Implementation::CoreLightning),
+            "lnd" => Ok(Implementation::Lnd),
+            "electrs" => Ok(Implementation::Electrs),
+            "esplora" => Ok(Implementation::Esplora),
+            "bitcoin_knots" => Ok(Implementation::BitcoinKnots),
+            _ => registered_dialect(s)
+                .map(Implementation::Registered)
+                .ok_or_else(|| format!("Unknown implementation name: {}", s)),
         }
     }
 }
@@ -244,16 +302,44 @@ mod tests {
     #[test]
     fn test_protocol_as_str() {
         assert_eq!(Protocol::Bitcoin.as_str(), "bitcoin");
+        assert_eq!(Protocol::Lightning.as_str(), "lightning");
+        assert_eq!("lightning".parse::<Protocol>(), Ok(Protocol::Lightning));
+        assert_eq!("electrum".parse::<Protocol>(), Ok(Protocol::Electrum));
+        assert_eq!("esplora".parse::<Protocol>(), Ok(Protocol::Esplora));
     }
 
     #[test]
     fn test_implementation_as_str() {
         assert_eq!(Implementation::BitcoinCore.as_str(), "bitcoin_core");
+        assert_eq!(Implementation::Btcd.as_str(), "btcd");
+        assert_eq!("btcd".parse::<Implementation>(), Ok(Implementation::Btcd));
+        assert_eq!("bitcoin_knots".parse::<Implementation>(), Ok(Implementation::BitcoinKnots));
     }
 
     #[test]
     fn test_implementation_protocol_name() {
         assert_eq!(Implementation::BitcoinCore.protocol_name(), "bitcoin".to_string());
+        assert_eq!(Implementation::Btcd.protocol_name(), "bitcoin".to_string());
+        assert_eq!(Implementation::CoreLightning.protocol_name(), "lightning".to_string());
+        assert_eq!(Implementation::Lnd.protocol_name(), "lightning".to_string());
+        assert_eq!(Implementation::Electrs.protocol_name(), "electrum".to_string());
+        assert_eq!(Implementation::Esplora.protocol_name(), "esplora".to_string());
+        assert_eq!(Implementation::BitcoinKnots.protocol_name(), "bitcoin".to_string());
+    }
+
+    #[test]
+    fn test_implementation_bitcoin_core_family() {
+        assert!(Implementation::BitcoinCore.is_bitcoin_core_family());
+        assert!(Implementation::BitcoinKnots.is_bitcoin_core_family());
+        assert!(!Implementation::Btcd.is_bitcoin_core_family());
+    }
+
+    #[test]
+    fn test_implementation_metadata() {
+        for implementation in Implementation::BUILTIN {
+            let metadata = implementation.metadata().expect("registry declares built-in metadata");
+            assert_eq!(metadata.name, implementation.as_str());
+        }
     }
 
     #[test]
@@ -274,11 +360,17 @@ mod tests {
     #[test]
     fn test_implementation_transport_protocol() {
         assert_eq!(Implementation::BitcoinCore.transport_protocol(), "http");
+        assert_eq!(Implementation::Btcd.transport_protocol(), "https");
+        assert_eq!(Implementation::CoreLightning.transport_protocol(), "unix");
+        assert_eq!(Implementation::Lnd.transport_protocol(), "rest_macaroon");
+        assert_eq!(Implementation::Electrs.transport_protocol(), "tcp");
+        assert_eq!(Implementation::Esplora.transport_protocol(), "rest_get");
     }
 
     #[test]
     fn test_implementation_executable_name() {
         assert_eq!(Implementation::BitcoinCore.executable_name(), "bitcoind");
+        assert_eq!(Implementation::Btcd.executable_name(), "btcd");
     }
 
     #[test]
@@ -289,6 +381,7 @@ mod tests {
     #[test]
     fn test_implementation_node_manager_name() {
         assert_eq!(Implementation::BitcoinCore.node_manager_name(), "BitcoinNodeManager");
+        assert_eq!(Implementation::Btcd.node_manager_name(), "BtcdNodeManager");
     }
 
     #[test]
@@ -320,11 +413,100 @@ mod tests {
             Some(&"-chain={}".to_string())
         );
         assert!(bitcoin_core_meta.cli_args.static_args.contains(&"-listen=0".to_string()));
+
+        let btcd_meta = Implementation::Btcd.node_metadata();
+        assert_eq!(btcd_meta.executable, "btcd");
+        assert_eq!(btcd_meta.transport, "https");
+        assert_eq!(
+            btcd_meta.cli_args.value_args.get("rpc_password"),
+            Some(&"--rpcpass={}".to_string())
+        );
+        assert!(!btcd_meta.cli_args.static_args.contains(&"--notls".to_string()));
+        assert_eq!(btcd_meta.tls_cert_path_pattern.as_deref(), Some("{datadir}/rpc.cert"));
+
+        let cln_meta = Implementation::CoreLightning.node_metadata();
+        assert_eq!(cln_meta.executable, "lightningd");
+        assert_eq!(cln_meta.transport, "unix");
+        assert_eq!(
+            cln_meta.socket_path_pattern.as_deref(),
+            Some("{datadir}/{chain}/lightning-rpc")
+        );
+
+        let lnd_meta = Implementation::Lnd.node_metadata();
+        assert_eq!(lnd_meta.transport, "rest_macaroon");
+        assert_eq!(lnd_meta.readiness_method, "GetInfo");
+        assert!(lnd_meta.socket_path_pattern.is_none());
+        assert_eq!(
+            lnd_meta.macaroon_path_pattern.as_deref(),
+            Some("{datadir}/data/chain/bitcoin/{chain}/admin.macaroon")
+        );
+
+        let electrs_meta = Implementation::Electrs.node_metadata();
+        assert_eq!(electrs_meta.transport, "tcp");
+        assert_eq!(electrs_meta.readiness_method, "server.ping");
+        assert_eq!(
+            electrs_meta.cli_args.value_args.get("rpc_port"),
+            Some(&"--electrum-rpc-addr=127.0.0.1:{}".to_string())
+        );
+
+        let esplora_meta = Implementation::Esplora.node_metadata();
+        assert_eq!(esplora_meta.executable, "electrs");
+        assert_eq!(esplora_meta.transport, "rest_get");
+        assert_eq!(esplora_meta.readiness_method, "blocks/tip/height");
+    }
+
+    #[test]
+    fn test_implementation_register() {
+        let mut metadata =
+            Implementation::BitcoinCore.metadata().expect("built-in metadata").clone();
+        metadata.name = "bitcoin_fork".to_string();
+        metadata.display_name = "Bitcoin Fork".to_string();
+        metadata.base_dialect = Some("bitcoin_core".to_string());
+        metadata.published_crate_name = "ethos-bitcoind-fork".to_string();
+
+        let fork = Implementation::register(metadata.clone()).expect("registers");
+        assert_eq!(fork.as_str(), "bitcoin_fork");
+        assert_eq!("bitcoin_fork".parse::<Implementation>(), Ok(fork));
+        assert_eq!(fork.builtin_base(), Implementation::BitcoinCore);
+        assert!(fork.is_bitcoin_core_family());
+        assert_eq!(fork.display_name(), "Bitcoin Fork");
+        assert_eq!(fork.published_crate_name(), "ethos-bitcoind-fork");
+        assert_eq!(fork.executable_name(), "bitcoind");
+        assert!(fork.create_type_adapter().is_ok());
+
+        let mut builtin = metadata.clone();
+        builtin.name = "bitcoin_core".to_string();
+        assert!(Implementation::register(builtin).is_err());
+
+        let mut no_base = metadata.clone();
+        no_base.name = "bitcoin_fork_without_base".to_string();
+        no_base.base_dialect = None;
+        assert!(Implementation::register(no_base).is_err());
+
+        let mut unknown_adapter = metadata.clone();
+        unknown_adapter.name = "bitcoin_fork_with_unknown_adapter".to_string();
+        unknown_adapter.type_adapter = "nonexistent".to_string();
+        assert!(Implementation::register(unknown_adapter).is_err());
+
+        let mut other_protocol = metadata;
+        other_protocol.name = "lightning_fork".to_string();
+        other_protocol.protocol = "lightning".to_string();
+        assert!(Implementation::register(other_protocol).is_err());
+        assert!("lightning_fork".parse::<Implementation>().is_err());
     }
 
     #[test]
     fn test_implementation_create_type_adapter() {
         let bitcoin_core_adapter = Implementation::BitcoinCore.create_type_adapter();
         assert!(bitcoin_core_adapter.is_ok());
+        assert!(Implementation::Btcd.create_type_adapter().is_ok());
+        for implementation in Implementation::BUILTIN {
+            let metadata = implementation.metadata().expect("built-in metadata");
+            assert_eq!(metadata.type_adapter, "bitcoin_core");
+        }
+        assert_eq!(
+            type_adapter("nonexistent").err(),
+            Some("Unknown type adapter: nonexistent".to_string())
+        );
     }
 }
@@ -0,0 +1,193 @@
+//! Implementation metadata read from the adapter registry.
+//!
+//! Every dialect in `resources/adapters/registry.json` may carry a `metadata` entry describing
+//! how its generated crate, clients and test nodes are named and run. The built-in
+//! implementations' metadata is read from the workspace registry at runtime, falling back to
+//! the copy compiled in; dialects that are not built in can be registered at runtime (see
+//! [`crate::Implementation::register`]) without recompiling.
+
+use std::hash::{Hash, Hasher};
+use std::sync::{Mutex, OnceLock};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::node_metadata::NodeMetadata;
+use crate::Implementation;
+
+/// The adapter registry as of the build, read when the workspace's copy cannot be (e.g.
+/// outside the workspace)
+const EMBEDDED_REGISTRY_JSON: &str = include_str!("../../../resources/adapters/registry.json");
+
+/// Metadata of an implementation, as declared by its dialect's `metadata` registry entry.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ImplementationMetadata {
+    /// Dialect key (e.g. "bitcoin_core"); taken from the registry key
+    #[serde(skip)]
+    pub name: String,
+    /// Protocol the dialect belongs to (e.g. "bitcoin"); taken from the adapter key
+    #[serde(skip)]
+    pub protocol: String,
+    /// Human-readable name; taken from the dialect's `name`
+    #[serde(skip)]
+    pub display_name: String,
+    /// Dialect whose IR and generators this one builds on; taken from the dialect's
+    /// `base_dialect`
+    #[serde(skip)]
+    pub base_dialect: Option<String>,
+    /// Crate name (with hyphens)
+    pub crate_name: String,
+    /// Client directory name
+    pub client_dir_name: String,
+    /// Published crate name (e.g. "ethos-bitcoind")
+    pub published_crate_name: String,
+    /// Client class name prefix
+    pub client_prefix: String,
+    /// Test client class name prefix
+    pub test_client_prefix: String,
+    /// Node manager name
+    pub node_manager_name: String,
+    /// Example method name for documentation
+    pub example_method: String,
+    /// Example method description for documentation
+    pub example_description: String,
+    /// Type adapter used for code generation (e.g. "bitcoin_core")
+    pub type_adapter: String,
+    /// How to spawn and manage a node of the implementation
+    pub node: NodeMetadata,
+}
+
+impl ImplementationMetadata {
+    /// Read the metadata of every dialect in `registry` that declares a `metadata` entry
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a dialect's `metadata` entry is malformed.
+    pub fn from_registry(registry: &Value) -> Result<Vec<Self>, String> {
+        let mut all = Vec::new();
+        let Some(adapters) = registry["adapters"].as_object() else {
+            return Ok(all);
+        };
+        for (protocol, adapter) in adapters {
+            let Some(dialects) = adapter["dialects"].as_object() else {
+                continue;
+            };
+            for (name, dialect) in dialects {
+                let Some(metadata) = dialect.get("metadata") else {
+                    continue;
+                };
+                let mut metadata = Self::deserialize(metadata)
+                    .map_err(|e| format!("Invalid metadata for dialect '{}': {}", name, e))?;
+                metadata.name = name.clone();
+                metadata.protocol = protocol.clone();
+                metadata.display_name = dialect["name"].as_str().unwrap_or(name).to_string();
+                metadata.base_dialect = dialect["base_dialect"].as_str().map(str::to_string);
+                all.push(metadata);
+            }
+        }
+        Ok(all)
+    }
+}
+
+/// A dialect registered at runtime: its metadata and the built-in implementation it builds on
+///
+/// Only [`Implementation::register`] creates one, after checking the metadata, so a
+/// registered [`Implementation`] always has both.
+#[derive(Debug)]
+pub struct RegisteredDialect {
+    metadata: ImplementationMetadata,
+    base: Implementation,
+}
+
+impl RegisteredDialect {
+    /// The dialect's metadata
+    pub fn metadata(&self) -> &ImplementationMetadata { &self.metadata }
+
+    /// The built-in implementation whose generators, method mappings and templates it uses
+    pub fn base(&self) -> Implementation { self.base }
+}
+
+impl PartialEq for RegisteredDialect {
+    fn eq(&self, other: &Self) -> bool { self.metadata.name == other.metadata.name }
+}
+
+impl Eq for RegisteredDialect {}
+
+impl Hash for RegisteredDialect {
+    fn hash<H: Hasher>(&self, state: &mut H) { self.metadata.name.hash(state) }
+}
+
+/// Metadata of the built-in implementations
+///
+/// Read once from the workspace registry ([`path::load_registry`]), or from the embedded copy
+/// when that cannot be read.
+///
+/// # Errors
+///
+/// Returns an error if the registry's metadata entries are malformed.
+pub(crate) fn builtin() -> Result<&'static [ImplementationMetadata], String> {
+    static BUILTIN: OnceLock<Result<Vec<ImplementationMetadata>, String>> = OnceLock::new();
+    BUILTIN
+        .get_or_init(|| {
+            let registry = match path::load_registry() {
+                Ok(registry) => registry,
+                Err(_) => serde_json::from_str(EMBEDDED_REGISTRY_JSON)
+                    .map_err(|e| format!("Failed to parse the embedded registry.json: {}", e))?,
+            };
+            let mut all = ImplementationMetadata::from_registry(&registry)?;
+            all.retain(|m| Implementation::BUILTIN.iter().any(|b| b.as_str() == m.name));
+            Ok(all)
+        })
+        .as_deref()
+        .map_err(Clone::clone)
+}
+
+/// Dialects registered at runtime
+fn registered() -> &'static Mutex<Vec<&'static RegisteredDialect>> {
+    static REGISTERED: OnceLock<Mutex<Vec<&'static RegisteredDialect>>> = OnceLock::new();
+    REGISTERED.get_or_init(Default::default)
+}
+
+/// The runtime-registered dialect `name`
+pub(crate) fn registered_dialect(name: &str) -> Option<&'static RegisteredDialect> {
+    registered()
+        .lock()
+        .expect("registered dialects lock")
+        .iter()
+        .find(|d| d.metadata.name == name)
+        .copied()
+}
+
+/// Register `metadata` on top of the built-in `base`, returning the stored dialect;
+/// registering a name twice keeps the first
+///
+/// The dialect is leaked to hand out `'static` references, so registration is meant to run
+/// once per process (e.g. when a command line names a dialect that is not built in).
+pub(crate) fn register(
+    metadata: ImplementationMetadata,
+    base: Implementation,
+) -> &'static RegisteredDialect {
+    let mut registered = registered().lock().expect("registered dialects lock");
+    if let Some(existing) = registered.iter().find(|d| d.metadata.name == metadata.name) {
+        return existing;
+    }
+    let dialect: &'static RegisteredDialect =
+        Box::leak(Box::new(RegisteredDialect { metadata, base }));
+    registered.push(dialect);
+    dialect
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedded_registry_declares_builtin_metadata() {
+        let registry: Value =
+            serde_json::from_str(EMBEDDED_REGISTRY_JSON).expect("embedded registry parses");
+        let all = ImplementationMetadata::from_registry(&registry).expect("metadata parses");
+        for implementation in Implementation::BUILTIN {
+            assert!(all.iter().any(|m| m.name == implementation.as_str()));
+        }
+    }
+}
@@ -18,6 +18,12 @@ use serde::{Deserialize, Serialize};
 /// Bitcoin protocol implementations (e.g. Bitcoin Core)
 /// and the `Protocol` enum that groups them by protocol family.
 pub mod implementation;
+/// Implementation metadata read from the adapter registry.
+///
+/// This module provides the metadata (crate and client names, node executable, transport,
+/// CLI argument templates and example method) that each dialect declares in
+/// `resources/adapters/registry.json`.
+pub mod implementation_metadata;
 /// Node metadata for implementation-specific node management.
 ///
 /// This module provides metadata structures for spawning and managing protocol
@@ -29,8 +35,10 @@ pub mod node_metadata;
 pub mod version;
 /// Re-export the `Implementation` enum for convenience.
 pub use implementation::{Implementation, Protocol};
-/// Re-export the `ProtocolVersion` type for convenience.
-pub use version::{ProtocolVersion, VersionError};
+/// Re-export the implementation metadata for convenience.
+pub use implementation_metadata::ImplementationMetadata;
+/// Re-export the version types for convenience.
+pub use version::{ProtocolVersion, VersionError, VersionOp, VersionRange};
 
 /// Protocol-specific type adapters.
 ///
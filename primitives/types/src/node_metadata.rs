@@ -8,11 +8,11 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 
 /// Metadata for node management configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct NodeMetadata {
     /// Binary executable name (e.g., "bitcoind")
     pub executable: String,
-    /// Transport protocol ("http" or "unix")
+    /// Transport protocol ("http", "https", "unix", "rest_macaroon", "tcp" or "rest_get")
     pub transport: String,
     /// Whether authentication is required
     pub requires_auth: bool,
@@ -24,6 +24,10 @@ pub struct NodeMetadata {
     pub initialization_error_codes: Vec<i32>,
     /// Socket path pattern (for Unix socket transports)
     pub socket_path_pattern: Option<String>,
+    /// Macaroon file pattern (for macaroon-authenticated REST transports)
+    pub macaroon_path_pattern: Option<String>,
+    /// Certificate file the client trusts (for https transports with a self-signed certificate)
+    pub tls_cert_path_pattern: Option<String>,
 }
 
 /// CLI argument configuration
@@ -14,6 +14,13 @@ use thiserror::Error;
 /// - `30.99.0` or `v30.99.0` (Bitcoin Core SemVer - 'v' prefix optional)
 /// - `25.09` or `v25.09` (CalVer - 'v' prefix optional)
 /// - `0.1.0` or `v0.1.0` (ProtocolVersion format - 'v' prefix optional)
+/// - `30` or `v30` (major only, meaning `30.0.0`)
+/// - `30.0rc1` or `v30.0-rc1` (release candidate)
+/// - `29.1.knots20250903` or `30.99.0-705399b1d57a-dirty` (Knots-style or build suffix)
+///
+/// Versions order by major, minor and patch, then release candidates before their release,
+/// then the plain release before suffixed builds of it (suffixes compare as strings, so
+/// Knots' dated suffixes order by date).
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ProtocolVersion {
     /// Original version string as provided by the version metadata or configuration.
@@ -26,6 +33,12 @@ pub struct ProtocolVersion {
     pub patch: u32,
     /// Protocol name for module naming (e.g., "bitcoin_core").
     pub protocol: Option<String>,
+    /// Release-candidate number (e.g., `1` in `v30.0rc1`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub release_candidate: Option<u32>,
+    /// Suffix after the version numbers (e.g., `knots20250903` in `v29.1.knots20250903`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suffix: Option<String>,
 }
 
 impl PartialOrd for ProtocolVersion {
@@ -34,7 +47,15 @@ impl PartialOrd for ProtocolVersion {
 
 impl Ord for ProtocolVersion {
     fn cmp(&self, other: &Self) -> Ordering {
-        (self.major, self.minor, self.patch).cmp(&(other.major, other.minor, other.patch))
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (self.release_candidate, other.release_candidate) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            })
+            .then_with(|| self.suffix.cmp(&other.suffix))
     }
 }
 
@@ -50,7 +71,8 @@ pub enum VersionError {
 }
 
 impl ProtocolVersion {
-    /// Parse a `ProtocolVersion` from a string in the `[v]MAJOR.MINOR[.PATCH]` format.
+    /// Parse a `ProtocolVersion` from a string in the `[v]MAJOR[.MINOR[.PATCH]][rcN][SUFFIX]`
+    /// format.
     pub fn from_string(s: &str) -> std::result::Result<Self, VersionError> {
         Self::from_string_with_protocol(s, None)
     }
@@ -63,23 +85,32 @@ impl ProtocolVersion {
         // Expected formats (v prefix is optional):
         // 30.99.0 or v30.99.0
         // 0.1.0 or v0.1.0
-        let re = Regex::new(r"^(?:v)?(\d+)\.(\d+)(?:\.(\d+))?$")
-            .map_err(|e: regex::Error| VersionError::Parse(e.to_string()))?;
+        // 30 or v30
+        // 30.0rc1 or v30.0-rc1
+        // 29.1.knots20250903 or 30.99.0-705399b1d57a-dirty
+        let re = Regex::new(
+            r"^(?:v)?(\d+)(?:\.(\d+))?(?:\.(\d+))?(?:[-.]?rc(\d+))?(?:[-.+]([0-9A-Za-z][0-9A-Za-z.+-]*))?$",
+        )
+        .map_err(|e: regex::Error| VersionError::Parse(e.to_string()))?;
         let caps = re.captures(s).ok_or_else(|| VersionError::InvalidFormat(s.to_string()))?;
+        let number = |index: usize| -> std::result::Result<Option<u32>, VersionError> {
+            caps.get(index)
+                .map(|m| m.as_str().parse())
+                .transpose()
+                .map_err(|e: std::num::ParseIntError| VersionError::Parse(e.to_string()))
+        };
 
         // Store original version string without normalization
         let version_string = s.to_string();
 
         Ok(Self {
             version_string,
-            major: caps[1]
-                .parse()
-                .map_err(|e: std::num::ParseIntError| VersionError::Parse(e.to_string()))?,
-            minor: caps[2]
-                .parse()
-                .map_err(|e: std::num::ParseIntError| VersionError::Parse(e.to_string()))?,
-            patch: caps.get(3).map(|m| m.as_str().parse().unwrap_or(0)).unwrap_or(0),
+            major: number(1)?.unwrap_or(0),
+            minor: number(2)?.unwrap_or(0),
+            patch: number(3)?.unwrap_or(0),
             protocol,
+            release_candidate: number(4)?,
+            suffix: caps.get(5).map(|m| m.as_str().to_string()),
         })
     }
 
@@ -98,9 +129,15 @@ impl ProtocolVersion {
     /// Render as a short `vMAJOR.MINOR` string.
     pub fn short(&self) -> String { format!("v{}.{}", self.major, self.minor) }
 
-    /// Render as a crate-compatible version string: `MAJOR.MINOR.PATCH`.
+    /// Whether this is a release candidate (e.g., `v30.0rc1`).
+    pub fn is_release_candidate(&self) -> bool { self.release_candidate.is_some() }
+
+    /// Render as a crate-compatible version string: `MAJOR.MINOR.PATCH[-rc.N]`.
     pub fn crate_version(&self) -> String {
-        format!("{}.{}.{}", self.major, self.minor, self.patch)
+        match self.release_candidate {
+            Some(rc) => format!("{}.{}.{}-rc.{}", self.major, self.minor, self.patch, rc),
+            None => format!("{}.{}.{}", self.major, self.minor, self.patch),
+        }
     }
 
     /// Render as a module name segment used in generated code.
@@ -215,17 +252,170 @@ impl std::str::FromStr for ProtocolVersion {
 
 impl Default for ProtocolVersion {
     fn default() -> Self {
-        Self { version_string: "0.0.0".to_string(), major: 0, minor: 0, patch: 0, protocol: None }
+        Self {
+            version_string: "0.0.0".to_string(),
+            major: 0,
+            minor: 0,
+            patch: 0,
+            protocol: None,
+            release_candidate: None,
+            suffix: None,
+        }
+    }
+}
+
+/// Format version string for filename (e.g., "30.2" -> "30_2_0", "30.2.1" -> "30_2_1")
+///
+/// Replaces dots with underscores to create filesystem-safe version strings.
+/// Normalizes 2-part versions (e.g., "30.2") to 3-part versions with patch 0 (e.g., "30.2.0").
+/// Uses `ProtocolVersion::as_filename_version()` for consistency across the codebase.
+pub fn format_version_for_filename(version: &str) -> String {
+    // Parse version string to ProtocolVersion for consistent formatting
+    ProtocolVersion::from_string(version)
+        .map(|v| {
+            // If the original version string only has 2 parts, normalize to 3 parts with patch 0
+            let parts: Vec<&str> = v.version_string.split('.').collect();
+            if parts.len() == 2 {
+                // Normalize 2-part version to 3-part: "30.2" -> "30.2.0" -> "30_2_0"
+                format!("{}_{}_{}", v.major, v.minor, v.patch)
+            } else {
+                // Use the standard filename version for 3-part versions
+                v.as_filename_version()
+            }
+        })
+        .unwrap_or_else(|_| {
+            // Fallback: if parsing fails, use parsed components
+            let (major, minor, patch) = path::parse_version_components(version);
+            format!("{}_{}_{}", major, minor, patch)
+        })
+}
+
+/// Generate version-specific IR filename
+///
+/// # Arguments
+///
+/// * `version` - Version string (e.g., "30.2", "30.2.0", "30.2.1")
+/// * `protocol` - Protocol name (e.g., "bitcoin")
+///
+/// # Examples
+///
+/// ```
+/// use types::version::version_ir_filename;
+/// assert_eq!(version_ir_filename("30.2", "bitcoin"), "v30_2_0_bitcoin.ir.json");
+/// assert_eq!(version_ir_filename("30.2.0", "bitcoin"), "v30_2_0_bitcoin.ir.json");
+/// assert_eq!(version_ir_filename("30.2.1", "bitcoin"), "v30_2_1_bitcoin.ir.json");
+/// ```
+pub fn version_ir_filename(version: &str, protocol: &str) -> String {
+    format!("v{}_{}.ir.json", format_version_for_filename(version), protocol)
+}
+
+/// Comparison operator of a [`VersionRange`] bound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionOp {
+    /// `=` (also a bare version)
+    Exact,
+    /// `>`
+    Greater,
+    /// `>=`
+    GreaterEq,
+    /// `<`
+    Less,
+    /// `<=`
+    LessEq,
+}
+
+impl VersionOp {
+    fn symbol(self) -> &'static str {
+        match self {
+            VersionOp::Exact => "=",
+            VersionOp::Greater => ">",
+            VersionOp::GreaterEq => ">=",
+            VersionOp::Less => "<",
+            VersionOp::LessEq => "<=",
+        }
     }
 }
 
+/// A set of versions given by comma-separated bounds, e.g. `>=v28.1, <v30`.
+///
+/// A version is in the range when it satisfies every bound; an empty range contains every
+/// version. Bounds compare with [`ProtocolVersion`]'s ordering, so `<v30` still contains
+/// `v30.0rc1`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VersionRange {
+    bounds: Vec<(VersionOp, ProtocolVersion)>,
+}
+
+impl VersionRange {
+    /// Parse a range such as `>=v28.1, <v30` or `=v29.0`.
+    pub fn parse(s: &str) -> std::result::Result<Self, VersionError> {
+        let mut bounds = Vec::new();
+        for bound in s.split(',').map(str::trim).filter(|b| !b.is_empty()) {
+            let (op, version) = [
+                (">=", VersionOp::GreaterEq),
+                ("<=", VersionOp::LessEq),
+                (">", VersionOp::Greater),
+                ("<", VersionOp::Less),
+                ("=", VersionOp::Exact),
+            ]
+            .iter()
+            .find_map(|(symbol, op)| bound.strip_prefix(symbol).map(|rest| (*op, rest)))
+            .unwrap_or((VersionOp::Exact, bound));
+            bounds.push((op, ProtocolVersion::from_string(version.trim())?));
+        }
+        Ok(Self { bounds })
+    }
+
+    /// The versions from `added` (inclusive) up to `removed` (exclusive); a missing side is
+    /// unbounded.
+    pub fn between(added: Option<ProtocolVersion>, removed: Option<ProtocolVersion>) -> Self {
+        let bounds = added
+            .map(|v| (VersionOp::GreaterEq, v))
+            .into_iter()
+            .chain(removed.map(|v| (VersionOp::Less, v)))
+            .collect();
+        Self { bounds }
+    }
+
+    /// Whether `version` satisfies every bound.
+    pub fn contains(&self, version: &ProtocolVersion) -> bool {
+        self.bounds.iter().all(|(op, bound)| {
+            let ordering = version.cmp(bound);
+            match op {
+                VersionOp::Exact => ordering == Ordering::Equal,
+                VersionOp::Greater => ordering == Ordering::Greater,
+                VersionOp::GreaterEq => ordering != Ordering::Less,
+                VersionOp::Less => ordering == Ordering::Less,
+                VersionOp::LessEq => ordering != Ordering::Greater,
+            }
+        })
+    }
+
+    /// The range's bounds in the order they were given.
+    pub fn bounds(&self) -> &[(VersionOp, ProtocolVersion)] { &self.bounds }
+}
+
+impl std::fmt::Display for VersionRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let bounds: Vec<String> =
+            self.bounds.iter().map(|(op, v)| format!("{}{}", op.symbol(), v)).collect();
+        f.write_str(&bounds.join(", "))
+    }
+}
+
+impl std::str::FromStr for VersionRange {
+    type Err = VersionError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> { Self::parse(s) }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_from_string() {
-        let version = ProtocolVersion::from_string("v1.2.3").unwrap();
+        let version = ProtocolVersion::from_string("v1.2.3").expect("valid version");
         assert_eq!(version.version_string, "v1.2.3");
         assert_eq!(version.major, 1);
         assert_eq!(version.minor, 2);
@@ -237,21 +427,23 @@ mod tests {
     fn test_from_string_with_protocol() {
         let with_patch =
             ProtocolVersion::from_string_with_protocol("v30.1.2", Some("bitcoin_core".to_string()))
-                .unwrap();
+                .expect("valid version");
         assert_eq!(with_patch.version_string, "v30.1.2");
         assert_eq!(with_patch.major, 30);
         assert_eq!(with_patch.minor, 1);
         assert_eq!(with_patch.patch, 2);
         assert_eq!(with_patch.protocol.as_deref(), Some("bitcoin_core"));
 
-        let without_patch = ProtocolVersion::from_string_with_protocol("v25.09", None).unwrap();
+        let without_patch =
+            ProtocolVersion::from_string_with_protocol("v25.09", None).expect("valid version");
         assert_eq!(without_patch.version_string, "v25.09");
         assert_eq!(without_patch.major, 25);
         assert_eq!(without_patch.minor, 9);
         assert_eq!(without_patch.patch, 0);
         assert_eq!(without_patch.protocol, None);
 
-        let error = ProtocolVersion::from_string_with_protocol("invalid", None).unwrap_err();
+        let error = ProtocolVersion::from_string_with_protocol("invalid", None)
+            .expect_err("expected version error");
         match error {
             VersionError::InvalidFormat(s) => assert_eq!(s, "invalid"),
             _ => panic!("unexpected error variant"),
@@ -266,6 +458,8 @@ mod tests {
             minor: 2,
             patch: 3,
             protocol: None,
+            release_candidate: None,
+            suffix: None,
         };
         assert_eq!(version.as_str(), "v1.2.3");
     }
@@ -278,6 +472,8 @@ mod tests {
             minor: 3,
             patch: 4,
             protocol: None,
+            release_candidate: None,
+            suffix: None,
         };
         assert_eq!(version.major(), 2);
     }
@@ -290,6 +486,8 @@ mod tests {
             minor: 4,
             patch: 5,
             protocol: None,
+            release_candidate: None,
+            suffix: None,
         };
         assert_eq!(version.minor(), 4);
     }
@@ -302,6 +500,8 @@ mod tests {
             minor: 5,
             patch: 6,
             protocol: None,
+            release_candidate: None,
+            suffix: None,
         };
         assert_eq!(version.as_doc_version(), "4.5");
     }
@@ -314,6 +514,8 @@ mod tests {
             minor: 6,
             patch: 7,
             protocol: None,
+            release_candidate: None,
+            suffix: None,
         };
         assert_eq!(version.short(), "v5.6");
     }
@@ -326,6 +528,8 @@ mod tests {
             minor: 7,
             patch: 8,
             protocol: None,
+            release_candidate: None,
+            suffix: None,
         };
         assert_eq!(version.crate_version(), "6.7.8");
     }
@@ -338,8 +542,10 @@ mod tests {
             minor: 2,
             patch: 3,
             protocol: None,
+            release_candidate: None,
+            suffix: None,
         };
-        let error = without_protocol.as_module_name().unwrap_err();
+        let error = without_protocol.as_module_name().expect_err("expected version error");
         match error {
             VersionError::InvalidFormat(message) => {
                 assert!(message.contains("Protocol name is required for module naming"));
@@ -353,8 +559,10 @@ mod tests {
             minor: 2,
             patch: 3,
             protocol: Some("bitcoin_core".to_string()),
+            release_candidate: None,
+            suffix: None,
         };
-        let module_name = with_protocol.as_module_name().unwrap();
+        let module_name = with_protocol.as_module_name().expect("valid version");
         assert_eq!(module_name, "bitcoin_core_v1_2_3");
     }
 
@@ -366,6 +574,8 @@ mod tests {
             minor: 1,
             patch: 0,
             protocol: None,
+            release_candidate: None,
+            suffix: None,
         };
         assert_eq!(version.identifier(), "30-1-0");
     }
@@ -378,6 +588,8 @@ mod tests {
             minor: 2,
             patch: 0,
             protocol: None,
+            release_candidate: None,
+            suffix: None,
         };
         assert_eq!(version1.as_filename_version(), "30_2");
 
@@ -387,6 +599,8 @@ mod tests {
             minor: 2,
             patch: 1,
             protocol: None,
+            release_candidate: None,
+            suffix: None,
         };
         assert_eq!(version2.as_filename_version(), "30_2_1");
 
@@ -396,6 +610,8 @@ mod tests {
             minor: 9,
             patch: 0,
             protocol: None,
+            release_candidate: None,
+            suffix: None,
         };
         assert_eq!(version3.as_filename_version(), "25_09");
 
@@ -405,6 +621,8 @@ mod tests {
             minor: 2,
             patch: 0,
             protocol: None,
+            release_candidate: None,
+            suffix: None,
         };
         assert_eq!(version4.as_filename_version(), "30_2");
     }
@@ -417,6 +635,8 @@ mod tests {
             minor: 0,
             patch: 0,
             protocol: None,
+            release_candidate: None,
+            suffix: None,
         };
         let different_major_target = ProtocolVersion {
             version_string: "v25.0.0".to_string(),
@@ -424,6 +644,8 @@ mod tests {
             minor: 0,
             patch: 0,
             protocol: None,
+            release_candidate: None,
+            suffix: None,
         };
         assert!(!different_major_self.matches_target(&different_major_target));
 
@@ -433,6 +655,8 @@ mod tests {
             minor: 0,
             patch: 0,
             protocol: None,
+            release_candidate: None,
+            suffix: None,
         };
         let target_major_only = ProtocolVersion {
             version_string: "25".to_string(),
@@ -440,6 +664,8 @@ mod tests {
             minor: 0,
             patch: 0,
             protocol: None,
+            release_candidate: None,
+            suffix: None,
         };
         assert!(self_for_major_only.matches_target(&target_major_only));
 
@@ -449,6 +675,8 @@ mod tests {
             minor: 1,
             patch: 0,
             protocol: None,
+            release_candidate: None,
+            suffix: None,
         };
         let target_different_minor = ProtocolVersion {
             version_string: "v25.2.0".to_string(),
@@ -456,6 +684,8 @@ mod tests {
             minor: 2,
             patch: 0,
             protocol: None,
+            release_candidate: None,
+            suffix: None,
         };
         assert!(!self_different_minor.matches_target(&target_different_minor));
 
@@ -465,6 +695,8 @@ mod tests {
             minor: 0,
             patch: 1,
             protocol: None,
+            release_candidate: None,
+            suffix: None,
         };
         let target_minor_only = ProtocolVersion {
             version_string: "v25.0".to_string(),
@@ -472,6 +704,8 @@ mod tests {
             minor: 0,
             patch: 0,
             protocol: None,
+            release_candidate: None,
+            suffix: None,
         };
         assert!(self_target_minor_only_self.matches_target(&target_minor_only));
 
@@ -481,6 +715,8 @@ mod tests {
             minor: 0,
             patch: 0,
             protocol: None,
+            release_candidate: None,
+            suffix: None,
         };
         let target_with_patch = ProtocolVersion {
             version_string: "v25.0.2".to_string(),
@@ -488,6 +724,8 @@ mod tests {
             minor: 0,
             patch: 2,
             protocol: None,
+            release_candidate: None,
+            suffix: None,
         };
         assert!(self_minor_only.matches_target(&target_with_patch));
 
@@ -497,6 +735,8 @@ mod tests {
             minor: 0,
             patch: 3,
             protocol: None,
+            release_candidate: None,
+            suffix: None,
         };
         let target_exact_true = ProtocolVersion {
             version_string: "v25.0.3".to_string(),
@@ -504,6 +744,8 @@ mod tests {
             minor: 0,
             patch: 3,
             protocol: None,
+            release_candidate: None,
+            suffix: None,
         };
         assert!(self_exact_true.matches_target(&target_exact_true));
 
@@ -513,6 +755,8 @@ mod tests {
             minor: 0,
             patch: 3,
             protocol: None,
+            release_candidate: None,
+            suffix: None,
         };
         let target_exact_false = ProtocolVersion {
             version_string: "v25.0.4".to_string(),
@@ -520,7 +764,76 @@ mod tests {
             minor: 0,
             patch: 4,
             protocol: None,
+            release_candidate: None,
+            suffix: None,
         };
         assert!(!self_exact_false.matches_target(&target_exact_false));
     }
+
+    #[test]
+    fn test_pre_release_and_suffixes() {
+        let rc = ProtocolVersion::from_string("v30.0rc2").expect("valid version");
+        assert_eq!((rc.major, rc.minor, rc.patch), (30, 0, 0));
+        assert_eq!(rc.release_candidate, Some(2));
+        assert!(rc.is_release_candidate());
+        assert_eq!(rc.crate_version(), "30.0.0-rc.2");
+        let dashed = ProtocolVersion::from_string("28.0-rc1").expect("valid version");
+        assert_eq!(dashed.release_candidate, Some(1));
+
+        let knots = ProtocolVersion::from_string("v29.1.knots20250903").expect("valid version");
+        assert_eq!((knots.major, knots.minor, knots.patch), (29, 1, 0));
+        assert_eq!(knots.suffix.as_deref(), Some("knots20250903"));
+
+        let dirty =
+            ProtocolVersion::from_string("30.99.0-705399b1d57a-dirty").expect("valid version");
+        assert_eq!(dirty.patch, 0);
+        assert_eq!(dirty.suffix.as_deref(), Some("705399b1d57a-dirty"));
+
+        let major_only = ProtocolVersion::from_string("v30").expect("valid version");
+        assert_eq!((major_only.major, major_only.minor, major_only.patch), (30, 0, 0));
+    }
+
+    #[test]
+    fn test_ordering() {
+        let ordered: Vec<ProtocolVersion> = [
+            "28.1",
+            "29.0rc1",
+            "29.0rc2",
+            "29.0",
+            "29.0.1",
+            "29.1",
+            "29.1.knots20250305",
+            "29.1.knots20250903",
+            "30.99.0-705399b1d57a-dirty",
+            "31",
+        ]
+        .iter()
+        .map(|v| ProtocolVersion::from_string(v).expect("valid version"))
+        .collect();
+        for pair in ordered.windows(2) {
+            assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn test_version_range() {
+        let range = VersionRange::parse(">=v28.1, <v30").expect("valid range");
+        let contains = |v: &str| range.contains(&ProtocolVersion::from_string(v).expect("valid"));
+        assert!(!contains("28.0"));
+        assert!(contains("28.1"));
+        assert!(contains("29.1.knots20250903"));
+        assert!(contains("30.0rc1"));
+        assert!(!contains("30.0"));
+        assert_eq!(range.to_string(), ">=v28.1, <v30");
+
+        let exact: VersionRange = "v29.0".parse().expect("valid range");
+        assert_eq!(exact.bounds()[0].0, VersionOp::Exact);
+        assert!(VersionRange::default().contains(&ProtocolVersion::default()));
+        assert!(VersionRange::parse(">=banana").is_err());
+
+        let added = ProtocolVersion::from_string("29.1").ok();
+        let between = VersionRange::between(added, None);
+        assert!(!between.contains(&ProtocolVersion::from_string("29.0").expect("valid")));
+        assert!(between.contains(&ProtocolVersion::from_string("29.1.0").expect("valid")));
+    }
 }
@@ -21,6 +21,8 @@ fn create_test_rpc_def(results: Vec<MethodResult>) -> RpcDef {
         hidden: None,
         version_added: None,
         version_removed: None,
+        deprecated: None,
+        http: None,
     }
 }
 
@@ -51,6 +53,7 @@ fn convert_method_result_to_type_def(result: &MethodResult) -> ir::TypeDef {
                     default_value: None,
                     version_added: None,
                     version_removed: None,
+                    deprecated: None,
                 })
                 .collect(),
         )